@@ -0,0 +1,39 @@
+//! SM2标量乘法与签名、验签的单次耗时
+//!
+//! `cargo bench --bench sm2_scalar`，加上`--features window-5`或`window-6`比较不同的窗口宽度。
+//! 基点乘法对应签名与密钥生成，任意点乘法对应加密与解密，双标量乘法对应验签。
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use yarism::sm2::hazmat::{self, ScalarBytesBe};
+use yarism::sm2::{Crypto, HexKey, KeyPair, PrivateKey, PublicKey};
+
+const PRK: &str = "6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e";
+const PUK: &str = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+
+fn main() {
+    let k = ScalarBytesBe(hex::decode("59276e27d506861a16680f3ad9c02dccef3cc1fa3cdbe4ce6d54b80deac1bc21").unwrap().try_into().unwrap());
+    let (x, y) = hazmat::scalar_base_multiply(ScalarBytesBe::from(&PrivateKey::decode(PRK)));
+    report("k·G", || hazmat::scalar_base_multiply(k));
+    report("k·P", || hazmat::scalar_multiply(&x, &y, k));
+    report("s·G + t·P", || hazmat::double_scalar_multiply(k, &x, &y, k));
+
+    let crypto = Crypto::default();
+    let signer = crypto.signer(KeyPair::new(PrivateKey::decode(PRK), PublicKey::decode(PUK)));
+    let verifier = crypto.verifier(PublicKey::decode(PUK));
+    let signature = signer.sign_bytes(b"bench");
+    report("sign", || signer.sign_bytes(b"bench"));
+    report("verify", || verifier.verify_bytes(b"bench", &signature));
+}
+
+/// 运行至少1秒，输出单次耗时
+fn report<T>(name: &str, mut f: impl FnMut() -> T) {
+    black_box(f());
+    let (start, mut runs) = (Instant::now(), 0u32);
+    while runs < 3 || start.elapsed() < Duration::from_secs(1) {
+        black_box(f());
+        runs += 1;
+    }
+    println!("{:<16} {:>10.2?}", name, start.elapsed() / runs);
+}
@@ -0,0 +1,101 @@
+//! SM2公钥、签名与密文的格式转换，对接第三方系统时最常用的几种：
+//!
+//! * key：pem ↔ hex（04 ‖ x ‖ y）↔ compressed（02/03 ‖ x）
+//! * sig：der ↔ raw（r ‖ s）
+//! * cipher：c1c3c2 ↔ c1c2c3 ↔ der（GM/T 0009 SM2Cipher）
+//!
+//! 除PEM外输入输出均为16进制；输入省略或为`-`时从标准输入读取。
+//!
+//! cargo run --example convert -- key pem hex public.pem
+//! cargo run --example convert -- cipher c1c2c3 c1c3c2 04...
+//! cargo run --example convert -- sig der raw 3045...
+
+use std::io::Read;
+use std::process::ExitCode;
+use std::{env, fs, io};
+
+use yarism::sm2::{Ciphertext, Mode, PublicKey, PublicKeyFormat, Signature};
+
+const USAGE: &str = "\
+usage: convert <kind> <from> <to> [input | -]
+  key     pem | hex | compressed
+  sig     der | raw
+  cipher  c1c3c2 | c1c2c3 | der";
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let [kind, from, to, rest @ ..] = &args[..] else {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    };
+    let input = match rest.first().map(String::as_str) {
+        None | Some("-") => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text).map(|_| text).map_err(|e| e.to_string())
+        }
+        // 文件存在时读取文件，否则视为直接给出的文本
+        Some(arg) => Ok(fs::read_to_string(arg).unwrap_or_else(|_| arg.to_string())),
+    };
+    let result = input.and_then(|input| match kind.as_str() {
+        "key" => convert_key(input.trim(), from, to),
+        "sig" => convert_signature(input.trim(), from, to),
+        "cipher" => convert_cipher(input.trim(), from, to),
+        _ => Err(USAGE.to_string()),
+    });
+    match result {
+        Ok(output) => {
+            println!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn convert_key(input: &str, from: &str, to: &str) -> Result<String, String> {
+    let key = match from {
+        "pem" => PublicKey::from_spki_pem(input),
+        "hex" => PublicKey::from_text(input, PublicKeyFormat::HexUncompressed),
+        "compressed" => PublicKey::from_text(input, PublicKeyFormat::HexCompressed),
+        _ => return Err(format!("Unknown key format {:?}.", from)),
+    }.map_err(|e| e.to_string())?;
+    match to {
+        "pem" => Ok(key.to_spki_pem().trim_end().to_string()),
+        "hex" => Ok(key.to_text(PublicKeyFormat::HexUncompressed)),
+        "compressed" => Ok(key.to_text(PublicKeyFormat::HexCompressed)),
+        _ => Err(format!("Unknown key format {:?}.", to)),
+    }
+}
+
+fn convert_signature(input: &str, from: &str, to: &str) -> Result<String, String> {
+    let data = hex::decode(input).map_err(|e| e.to_string())?;
+    let signature = match from {
+        "der" => Signature::from_der(&data),
+        "raw" => Signature::from_bytes(&data),
+        _ => return Err(format!("Unknown signature format {:?}.", from)),
+    }.map_err(|e| e.to_string())?;
+    match to {
+        "der" => Ok(hex::encode(signature.to_der())),
+        "raw" => Ok(hex::encode(signature.to_bytes())),
+        _ => Err(format!("Unknown signature format {:?}.", to)),
+    }
+}
+
+fn convert_cipher(input: &str, from: &str, to: &str) -> Result<String, String> {
+    let data = hex::decode(input).map_err(|e| e.to_string())?;
+    let mode = |name: &str| match name {
+        "c1c3c2" | "der" => Ok(Mode::C1C3C2),
+        "c1c2c3" => Ok(Mode::C1C2C3),
+        _ => Err(format!("Unknown cipher format {:?}.", name)),
+    };
+    let ciphertext = match from {
+        "der" => Ciphertext::from_der(&data, mode(to)?),
+        _ => Ciphertext::from_bytes(&data, mode(from)?),
+    }.map_err(|e| e.to_string())?;
+    match to {
+        "der" => Ok(hex::encode(ciphertext.to_der())),
+        _ => Ok(hex::encode(ciphertext.convert_mode(mode(to)?).to_bytes())),
+    }
+}
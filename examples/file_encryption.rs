@@ -0,0 +1,47 @@
+//! 数字信封方式加密文件：SM4-CBC加密文件内容，SM2加密SM4秘钥。
+//!
+//! cargo run --example file_encryption [path]
+
+use std::{env, fs};
+
+use yarism::sm2::simple;
+use yarism::sm4::{self, CryptoFactory, Mode};
+
+fn main() {
+    let path = env::args().nth(1);
+    let plain = match &path {
+        Some(path) => fs::read(path).expect("The input file can not be read."),
+        None => "Hello World, 哈喽，世界\n".repeat(64).into_bytes(),
+    };
+
+    // 接收方秘钥对
+    let (prk, puk) = simple::generate_keypair();
+
+    // 加密：一次一密的SM4秘钥由接收方公钥加密后与密文一同保存
+    let key = sm4::generate_key();
+    let iv = sm4::generate_iv();
+    let body = CryptoFactory::new(Mode::CBC { key: key.clone(), iv: iv.clone() }).encrypt_bytes(&plain);
+    let wrapped = simple::encrypt(&puk, &hex::decode(&key).unwrap()).unwrap();
+
+    let mut envelope = Vec::new();
+    envelope.extend_from_slice(&(wrapped.len() as u16).to_be_bytes());
+    envelope.extend_from_slice(&wrapped);
+    envelope.extend_from_slice(&hex::decode(&iv).unwrap());
+    envelope.extend_from_slice(&body);
+
+    let out = env::temp_dir().join("yarism-example.enc");
+    fs::write(&out, &envelope).expect("The output file can not be written.");
+    println!("{} bytes -> {} ({} bytes)", plain.len(), out.display(), envelope.len());
+
+    // 解密
+    let envelope = fs::read(&out).unwrap();
+    let n = u16::from_be_bytes([envelope[0], envelope[1]]) as usize;
+    let (wrapped, rest) = envelope[2..].split_at(n);
+    let (iv, body) = rest.split_at(16);
+    let key = hex::encode(simple::decrypt(&prk, wrapped).unwrap());
+    let text = CryptoFactory::new(Mode::CBC { key, iv: hex::encode(iv) }).decrypt_bytes(body);
+
+    assert_eq!(text, plain);
+    println!("decrypted {} bytes", text.len());
+    fs::remove_file(out).ok();
+}
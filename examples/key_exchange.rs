@@ -0,0 +1,78 @@
+//! 两个进程之间建立会话秘钥：发起方生成会话秘钥，以响应方公钥加密并用自己的私钥签名后发送，
+//! 响应方验签、解密后以会话秘钥加密确认消息返回。
+//!
+//! 这里演示秘钥传输方式，GB/T 32918.3的秘钥交换协议见`yarism::sm2::exchange`；进程间通过标准输入输出逐行交换16进制报文。
+//!
+//! cargo run --example key_exchange
+
+use std::env;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+
+use yarism::sm2::simple;
+use yarism::sm4::{self, CryptoFactory, Mode};
+
+const ACK: &[u8] = b"session established";
+
+fn main() {
+    if env::args().nth(1).as_deref() == Some("--responder") {
+        responder();
+    } else {
+        initiator();
+    }
+}
+
+fn initiator() {
+    let (prk, puk) = simple::generate_keypair();
+
+    let mut child = Command::new(env::current_exe().unwrap())
+        .arg("--responder")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("The responder process can not be started.");
+    let mut tx = child.stdin.take().unwrap();
+    let mut rx = BufReader::new(child.stdout.take().unwrap()).lines();
+
+    // 交换公钥。实际场景中公钥应来自可信的证书
+    writeln!(tx, "{}", puk).unwrap();
+    let peer = rx.next().unwrap().unwrap();
+
+    let key = sm4::generate_key();
+    let wrapped = simple::encrypt(&peer, &hex::decode(&key).unwrap()).unwrap();
+    let signature = simple::sign(&prk, &wrapped).unwrap();
+    writeln!(tx, "{} {}", hex::encode(&wrapped), hex::encode(&signature)).unwrap();
+
+    let line = rx.next().unwrap().unwrap();
+    let (iv, cipher) = line.split_once(' ').unwrap();
+    let ack = CryptoFactory::new(Mode::CBC { key: key.clone(), iv: iv.to_string() })
+        .decrypt_bytes(&hex::decode(cipher).unwrap());
+    assert_eq!(ack, ACK);
+
+    drop(tx);
+    child.wait().unwrap();
+    println!("initiator: session key {} confirmed by pid {}", key, child.id());
+}
+
+fn responder() {
+    let (prk, puk) = simple::generate_keypair();
+    let stdin = std::io::stdin();
+    let mut rx = stdin.lock().lines();
+    let mut tx = std::io::stdout();
+
+    let peer = rx.next().unwrap().unwrap();
+    writeln!(tx, "{}", puk).unwrap();
+
+    let line = rx.next().unwrap().unwrap();
+    let (wrapped, signature) = line.split_once(' ').unwrap();
+    let wrapped = hex::decode(wrapped).unwrap();
+    if !simple::verify(&peer, &wrapped, &hex::decode(signature).unwrap()) {
+        panic!("The key transport message is not signed by the initiator.")
+    }
+    let key = hex::encode(simple::decrypt(&prk, &wrapped).unwrap());
+
+    let iv = sm4::generate_iv();
+    let ack = CryptoFactory::new(Mode::CBC { key, iv: iv.clone() }).encrypt_bytes(ACK);
+    writeln!(tx, "{} {}", iv, hex::encode(ack)).unwrap();
+    tx.flush().unwrap();
+}
@@ -0,0 +1,62 @@
+//! 对JSON接口报文签名：服务端对报文体签名并将签名放在`X-Signature`头中，客户端验签。
+//!
+//! cargo run --example signed_json
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use yarism::sm2::simple;
+use yarism::sm3;
+
+struct Response {
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl Response {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str())
+    }
+}
+
+/// 服务端：签名覆盖报文体的原始字节，而不是解析后的JSON，避免字段顺序、空白带来的歧义
+fn serve(prk: &str, order: u32) -> Response {
+    let body = format!(r#"{{"order":{},"amount":"100.00","currency":"CNY"}}"#, order);
+    let signature = simple::sign(prk, body.as_bytes()).unwrap();
+    Response {
+        headers: vec![
+            ("Content-Type".to_string(), "application/json".to_string()),
+            ("X-Digest".to_string(), format!("SM3={}", sm3::digest(&body))),
+            ("X-Signature".to_string(), STANDARD.encode(signature)),
+        ],
+        body,
+    }
+}
+
+/// 客户端：验证摘要与签名
+fn accept(puk: &str, resp: &Response) -> bool {
+    let digest = format!("SM3={}", sm3::digest(&resp.body));
+    if resp.header("x-digest") != Some(digest.as_str()) {
+        return false;
+    }
+    let signature = match resp.header("x-signature").map(|e| STANDARD.decode(e)) {
+        Some(Ok(signature)) => signature,
+        _ => return false,
+    };
+    simple::verify(puk, resp.body.as_bytes(), &signature)
+}
+
+fn main() {
+    let (prk, puk) = simple::generate_keypair();
+
+    let resp = serve(&prk, 42);
+    for (k, v) in &resp.headers {
+        println!("{}: {}", k, v);
+    }
+    println!("\n{}\n", resp.body);
+    assert!(accept(&puk, &resp));
+
+    let mut tampered = resp;
+    tampered.body = tampered.body.replace("100.00", "999.00");
+    assert!(!accept(&puk, &tampered));
+    println!("tampered body rejected");
+}
@@ -0,0 +1,37 @@
+//! SM4加密文本并输出16进制密文，分组模式、秘钥与初始化向量由命令行给出，省略时随机生成。
+//!
+//! cargo run --example sm4_hex -- [ecb|cbc|cfb|ofb|ctr][-prefixed] [key] [iv] [text]
+//!
+//! CI以`--no-default-features --features "sm4 no-fmt"`构建本示例的release版本，检查其中不含SM4的panic消息。
+
+use std::{env, process};
+
+use yarism::sm4::{self, CryptoFactory, IvPolicy, Mode};
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let arg = |i: usize, default: fn() -> String| args.get(i).cloned().unwrap_or_else(default);
+    let name = args.first().map(String::as_str).unwrap_or("cbc");
+    let (key, iv, text) = (arg(1, sm4::generate_key), arg(2, sm4::generate_iv), arg(3, || "Hello World, 哈喽，世界".to_string()));
+
+    let mode = match name.trim_end_matches("-prefixed") {
+        "ecb" => Mode::ECB { key },
+        "cbc" => Mode::CBC { key, iv },
+        "cfb" => Mode::CFB { key, iv },
+        "ofb" => Mode::OFB { key, iv },
+        "ctr" => Mode::CTR { key, iv },
+        _ => {
+            eprintln!("unknown mode: {}", name);
+            process::exit(2);
+        }
+    };
+    let policy = match name.ends_with("-prefixed") {
+        true => IvPolicy::Prefixed,
+        false => IvPolicy::Separate,
+    };
+
+    let crypto = CryptoFactory::with_max_len(CryptoFactory::with_iv_policy(mode, policy), 1 << 20);
+    let cipher = crypto.encrypt(text.clone());
+    println!("{}", cipher);
+    assert_eq!(crypto.decrypt(cipher), text);
+}
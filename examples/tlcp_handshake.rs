@@ -0,0 +1,103 @@
+//! 模拟TLCP（GB/T 38636）双证书握手的秘钥协商流程，报文在内存中传递。
+//!
+//! 服务端持有签名证书与加密证书：ServerKeyExchange由签名私钥签名，预主秘钥由客户端以加密证书公钥加密。
+//! 主秘钥、工作秘钥的派生以SM3简化代替标准中的PRF，仅用于演示各组件的组合方式。
+//!
+//! cargo run --example tlcp_handshake
+
+use rand::RngCore;
+use yarism::cert::{self, Certificate, Subject, Validity};
+use yarism::sm2::{simple, HexKey, KeyPair, PrivateKey, PublicKey};
+use yarism::sm3;
+use yarism::sm4::{CryptoFactory, Mode};
+
+struct Server {
+    sign_prk: String,
+    enc_prk: String,
+    sign_cert: Certificate,
+    enc_cert: Certificate,
+}
+
+impl Server {
+    fn new() -> Self {
+        let (sign_prk, sign_cert) = issue("sign.example.com");
+        let (enc_prk, enc_cert) = issue("enc.example.com");
+        Server { sign_prk, enc_prk, sign_cert, enc_cert }
+    }
+}
+
+fn issue(name: &str) -> (String, Certificate) {
+    let (prk, puk) = simple::generate_keypair();
+    let keypair = KeyPair::new(PrivateKey::decode(&prk), PublicKey::decode(&puk));
+    let cert = cert::generate_self_signed(&keypair, &Subject::new(name).dns(name), &Validity::days(1));
+    (prk, cert)
+}
+
+fn random<const N: usize>() -> [u8; N] {
+    let mut out = [0u8; N];
+    rand::thread_rng().fill_bytes(&mut out);
+    out
+}
+
+/// 简化的秘钥派生：SM3(secret ‖ label ‖ seed)
+fn derive(secret: &[u8], label: &str, seed: &[&[u8]]) -> [u8; 32] {
+    let mut data = [secret, label.as_bytes()].concat();
+    for e in seed {
+        data.extend_from_slice(e);
+    }
+    sm3::hash(&data)
+}
+
+fn main() {
+    let server = Server::new();
+    let mut transcript = Vec::new();
+
+    // ClientHello
+    let client_random = random::<32>();
+    transcript.extend_from_slice(&client_random);
+
+    // ServerHello, Certificate, ServerKeyExchange
+    let server_random = random::<32>();
+    let certs = [server.sign_cert.to_der(), server.enc_cert.to_der()];
+    let params = [&client_random[..], &server_random, certs[1]].concat();
+    let ske = simple::sign(&server.sign_prk, &params).unwrap();
+    transcript.extend_from_slice(&server_random);
+    certs.iter().for_each(|e| transcript.extend_from_slice(e));
+    transcript.extend_from_slice(&ske);
+
+    // 客户端：校验证书与ServerKeyExchange签名，生成并加密预主秘钥
+    let sign_cert = Certificate::from_der(certs[0]).unwrap();
+    let enc_cert = Certificate::from_der(certs[1]).unwrap();
+    assert!(sign_cert.verify_signature(sign_cert.public_key()));
+    assert!(enc_cert.verify_signature(enc_cert.public_key()));
+    let params = [&client_random[..], &server_random, enc_cert.to_der()].concat();
+    assert!(simple::verify(&sign_cert.public_key().encode(), &params, &ske));
+
+    let mut pre_master = random::<48>();
+    pre_master[..2].copy_from_slice(&[0x01, 0x01]);
+    let cke = simple::encrypt(&enc_cert.public_key().encode(), &pre_master).unwrap();
+    transcript.extend_from_slice(&cke);
+
+    // 服务端：以加密私钥解密预主秘钥
+    let server_pre_master = simple::decrypt(&server.enc_prk, &cke).unwrap();
+    assert_eq!(server_pre_master, pre_master);
+
+    // 双方派生主秘钥、工作秘钥并交换Finished
+    let session = |pre_master: &[u8]| {
+        let master = derive(pre_master, "master secret", &[&client_random, &server_random]);
+        let block = derive(&master, "key expansion", &[&server_random, &client_random]);
+        (master, hex::encode(&block[..16]), hex::encode(&block[16..]))
+    };
+    let (client_master, client_key, client_iv) = session(&pre_master);
+    let (server_master, server_key, server_iv) = session(&server_pre_master);
+
+    let hash = sm3::hash(&transcript);
+    let client_finished = derive(&client_master, "client finished", &[&hash]);
+    let server_finished = derive(&server_master, "client finished", &[&hash]);
+    assert_eq!(client_finished, server_finished);
+
+    // 应用数据
+    let record = CryptoFactory::new(Mode::CBC { key: client_key, iv: client_iv }).encrypt_bytes(b"GET / HTTP/1.1");
+    let plain = CryptoFactory::new(Mode::CBC { key: server_key, iv: server_iv }).decrypt_bytes(&record);
+    println!("server received: {}", String::from_utf8(plain).unwrap());
+}
@@ -0,0 +1,420 @@
+use std::net::IpAddr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use std::fmt::{Display, Formatter};
+
+use crate::clock::Clock;
+use crate::codec::{pem_decode, pem_encode};
+use crate::env::EnvClock;
+use crate::sm2::ecc::{Crypto, Signature};
+use crate::sm2::error::VerifyError;
+use crate::sm2::{KeyPair, PublicKey};
+use crate::error_code::{Code, ErrorCode};
+
+pub(crate) mod oid;
+mod name;
+mod x509;
+
+pub use name::{Attribute, DistinguishedName};
+
+pub(crate) use x509::{encodable, read_time, write_generalized_time, write_time};
+
+/// 生成SM3WithSM2签名的自签名证书，可用于搭建TLCP测试服务，无需借助OpenSSL及定制配置
+pub fn generate_self_signed(keypair: &KeyPair, subject: &Subject, validity: &Validity) -> Certificate {
+    Certificate::from_der(&x509::self_signed(keypair, subject, validity)).unwrap()
+}
+
+/// 文档验签：解析PEM证书，以系统时间检查有效期，再以证书公钥及默认用户ID验证DER编码的签名，成功时返回证书
+///
+/// 不验证证书链，调用方需另行确认证书的签发者可信。
+pub fn verify_with_certificate(cert_pem: &str, msg: &[u8], signature: &[u8]) -> Result<Certificate, CertError> {
+    verify_with_certificate_with_clock(cert_pem, msg, signature, &EnvClock)
+}
+
+/// 同[`verify_with_certificate`]，以`clock`的当前时间检查有效期
+pub fn verify_with_certificate_with_clock(cert_pem: &str, msg: &[u8], signature: &[u8], clock: &dyn Clock) -> Result<Certificate, CertError> {
+    let cert = Certificate::from_pem(cert_pem)?;
+    cert.check_validity(clock)?;
+    let signature = Signature::from_der(signature).map_err(|e| CertError::BadSignature(VerifyError::Malformed(e)))?;
+    Crypto::default().verifier(cert.public_key.clone()).check_bytes(msg, &signature).map_err(CertError::BadSignature)?;
+    Ok(cert)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CertError {
+    /// 证书不是合法的DER/PEM编码
+    Malformed,
+    /// 签名算法或公钥算法不是SM3WithSM2/SM2
+    UnsupportedAlgorithm,
+    /// 公钥不是合法的非压缩格式SM2公钥
+    InvalidPublicKey,
+    /// 证书自该时间戳起才生效
+    NotYetValid(i64),
+    /// 证书已于该时间戳过期
+    Expired(i64),
+    /// 证书公钥验证签名失败
+    BadSignature(VerifyError),
+    /// 有效期超出证书时间可表示的范围（0000年至9999年）
+    InvalidValidity,
+}
+
+impl Display for CertError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CertError::Malformed => write!(f, "The certificate data is malformed."),
+            CertError::UnsupportedAlgorithm => write!(f, "The certificate algorithm is not SM2/SM3WithSM2."),
+            CertError::InvalidPublicKey => write!(f, "The certificate public key is invalid."),
+            CertError::NotYetValid(t) => write!(f, "The certificate is not valid before {}.", t),
+            CertError::Expired(t) => write!(f, "The certificate expired at {}.", t),
+            CertError::BadSignature(e) => write!(f, "{}", e),
+            CertError::InvalidValidity => write!(f, "The certificate validity must be within the years 0000 to 9999."),
+        }
+    }
+}
+
+impl std::error::Error for CertError {}
+
+impl ErrorCode for CertError {
+    fn code(&self) -> Code {
+        match self {
+            CertError::Malformed | CertError::InvalidValidity => Code::INVALID_DATA,
+            CertError::UnsupportedAlgorithm => Code::NOT_SUPPORTED,
+            CertError::InvalidPublicKey => Code::INVALID_KEY,
+            CertError::NotYetValid(_) | CertError::Expired(_) => Code::VERIFY_FAILED,
+            CertError::BadSignature(e) => e.code(),
+        }
+    }
+}
+
+/// 证书主体：可分辨名称（DN）以及主体备用名称（SAN）
+#[derive(Clone, Debug)]
+pub struct Subject {
+    name: DistinguishedName,
+    alt_names: Vec<AltName>,
+}
+
+impl Subject {
+    /// 仅含通用名称（CN）的主体
+    pub fn new(common_name: &str) -> Self {
+        Self::from_name(DistinguishedName::new().common_name(common_name))
+    }
+
+    pub fn from_name(name: DistinguishedName) -> Self {
+        Subject { name, alt_names: vec![] }
+    }
+
+    pub fn dns(mut self, name: &str) -> Self {
+        self.alt_names.push(AltName::Dns(name.to_string()));
+        self
+    }
+
+    pub fn ip(mut self, ip: IpAddr) -> Self {
+        self.alt_names.push(AltName::Ip(ip));
+        self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AltName {
+    Dns(String),
+    Ip(IpAddr),
+}
+
+/// 证书有效期，UNIX时间戳（秒）
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Validity {
+    not_before: i64,
+    not_after: i64,
+}
+
+impl Validity {
+    /// 起止时间须在0000年至9999年之间，否则panic
+    pub fn new(not_before: SystemTime, not_after: SystemTime) -> Self {
+        if not_after < not_before {
+            panic!("The end of validity must not be earlier than the start.")
+        }
+        match Self::checked(timestamp(not_before), Some(timestamp(not_after))) {
+            Ok(validity) => validity,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// 从当前时间开始，有效期为`days`天，截止时间超过9999年时panic
+    pub fn days(days: u32) -> Self {
+        Self::days_with_clock(days, &EnvClock)
+    }
+
+    /// 从`clock`的当前时间开始，有效期为`days`天，截止时间超过9999年时panic
+    pub fn days_with_clock(days: u32, clock: &dyn Clock) -> Self {
+        match Self::try_days_with_clock(days, clock) {
+            Ok(validity) => validity,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// 同[`Validity::days`]，截止时间无法编码时返回[`CertError::InvalidValidity`]
+    pub fn try_days(days: u32) -> Result<Self, CertError> {
+        Self::try_days_with_clock(days, &EnvClock)
+    }
+
+    /// 同[`Validity::days_with_clock`]，截止时间无法编码时返回[`CertError::InvalidValidity`]
+    pub fn try_days_with_clock(days: u32, clock: &dyn Clock) -> Result<Self, CertError> {
+        let now = clock.now();
+        Self::checked(now, now.checked_add(i64::from(days) * 86400))
+    }
+
+    fn checked(not_before: i64, not_after: Option<i64>) -> Result<Self, CertError> {
+        match not_after {
+            Some(not_after) if x509::encodable(not_before) && x509::encodable(not_after) => Ok(Validity { not_before, not_after }),
+            _ => Err(CertError::InvalidValidity),
+        }
+    }
+
+    pub fn not_before(&self) -> i64 {
+        self.not_before
+    }
+
+    pub fn not_after(&self) -> i64 {
+        self.not_after
+    }
+
+    /// `time`是否在有效期内，起止时间均包含在内
+    pub fn contains(&self, time: i64) -> bool {
+        self.not_before <= time && time <= self.not_after
+    }
+}
+
+/// X.509证书
+#[derive(Clone, Debug)]
+pub struct Certificate {
+    der: Vec<u8>,
+    tbs: Vec<u8>,
+    serial: Vec<u8>,
+    issuer: Vec<u8>,
+    subject: Vec<u8>,
+    validity: Validity,
+    public_key: PublicKey,
+    signature: Vec<u8>,
+}
+
+impl Certificate {
+    pub fn from_der(der: &[u8]) -> Result<Self, CertError> {
+        let parts = x509::parse(der)?;
+        Ok(Certificate {
+            der: der.to_vec(),
+            tbs: parts.tbs,
+            serial: parts.serial,
+            issuer: parts.issuer,
+            subject: parts.subject,
+            validity: parts.validity,
+            public_key: parts.public_key,
+            signature: parts.signature,
+        })
+    }
+
+    pub fn from_pem(pem: &str) -> Result<Self, CertError> {
+        Self::from_der(&pem_decode("CERTIFICATE", pem).ok_or(CertError::Malformed)?)
+    }
+
+    pub fn to_der(&self) -> &[u8] {
+        &self.der
+    }
+
+    pub fn to_pem(&self) -> String {
+        pem_encode("CERTIFICATE", &self.der)
+    }
+
+    /// 序列号，DER编码的INTEGER
+    pub fn serial_der(&self) -> &[u8] {
+        &self.serial
+    }
+
+    /// 签发者名称，DER编码的Name
+    pub fn issuer_der(&self) -> &[u8] {
+        &self.issuer
+    }
+
+    /// 主体名称，DER编码的Name
+    pub fn subject_der(&self) -> &[u8] {
+        &self.subject
+    }
+
+    /// 签发者名称
+    pub fn issuer(&self) -> Result<DistinguishedName, CertError> {
+        DistinguishedName::from_der(&self.issuer)
+    }
+
+    /// 主体名称
+    pub fn subject(&self) -> Result<DistinguishedName, CertError> {
+        DistinguishedName::from_der(&self.subject)
+    }
+
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    pub fn validity(&self) -> &Validity {
+        &self.validity
+    }
+
+    /// 以`clock`的当前时间检查证书是否在有效期内
+    pub fn check_validity(&self, clock: &dyn Clock) -> Result<(), CertError> {
+        let now = clock.now();
+        if now < self.validity.not_before {
+            Err(CertError::NotYetValid(self.validity.not_before))
+        } else if now > self.validity.not_after {
+            Err(CertError::Expired(self.validity.not_after))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// 使用签发者公钥验证证书签名，自签名证书传入自身公钥
+    pub fn verify_signature(&self, issuer: &PublicKey) -> bool {
+        match Signature::try_decode(&self.signature) {
+            Some(signature) => Crypto::default().verifier(issuer.clone()).verify_bytes(&self.tbs, &signature),
+            None => false,
+        }
+    }
+}
+
+fn timestamp(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_secs() as i64,
+        Err(e) => -(e.duration().as_secs() as i64),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use crate::clock::FixedClock;
+    use crate::sm2::ecc::{Crypto, Signature};
+    use crate::sm2::{HexKey, PrivateKey, PublicKey};
+
+    use super::*;
+
+    #[test]
+    fn self_signed() {
+        let prk = "6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e";
+        let puk = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+        let keypair = KeyPair::new(PrivateKey::decode(prk), PublicKey::decode(puk));
+
+        let subject = Subject::new("localhost")
+            .dns("localhost")
+            .ip(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)));
+        let cert = generate_self_signed(&keypair, &subject, &Validity::days(30));
+
+        let (tbs, signature) = yasna::parse_der(cert.to_der(), |reader| {
+            reader.read_sequence(|reader| {
+                let tbs = reader.next().read_der()?;
+                reader.next().read_sequence(|reader| reader.next().read_oid())?;
+                let signature = reader.next().read_bitvec_bytes()?.0;
+                Ok((tbs, signature))
+            })
+        }).unwrap();
+
+        let verifier = Crypto::default().verifier(PublicKey::decode(puk));
+        assert!(verifier.verify_bytes(&tbs, &Signature::try_decode(&signature).unwrap()));
+        assert!(cert.to_pem().starts_with("-----BEGIN CERTIFICATE-----\n"));
+    }
+
+    #[test]
+    fn parse() {
+        let prk = "6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e";
+        let puk = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+        let keypair = KeyPair::new(PrivateKey::decode(prk), PublicKey::decode(puk));
+
+        let validity = Validity::days(365);
+        let cert = generate_self_signed(&keypair, &Subject::new("seal"), &validity);
+        let cert = Certificate::from_pem(&cert.to_pem()).unwrap();
+
+        assert_eq!(cert.public_key().encode(), puk);
+        assert_eq!(cert.validity(), &validity);
+        assert!(cert.verify_signature(cert.public_key()));
+        assert_eq!(cert.subject().unwrap(), DistinguishedName::new().common_name("seal"));
+        assert!(cert.issuer().unwrap().canonical_eq(&cert.subject().unwrap()));
+
+        let mut der = cert.to_der().to_vec();
+        let len = der.len();
+        der[len - 1] ^= 0x01;
+        assert!(!Certificate::from_der(&der).unwrap().verify_signature(cert.public_key()));
+        assert_eq!(Certificate::from_der(&der[..100]).unwrap_err(), CertError::Malformed);
+    }
+
+    #[test]
+    fn validity_range() {
+        // 9999-12-31T23:59:59Z
+        const MAX: i64 = 253402300799;
+        let prk = "6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e";
+        let puk = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+        let keypair = KeyPair::new(PrivateKey::decode(prk), PublicKey::decode(puk));
+
+        let clock = FixedClock::new(MAX - 10 * 86400);
+        let validity = Validity::try_days_with_clock(10, &clock).unwrap();
+        assert_eq!(validity.not_after(), MAX);
+        let cert = generate_self_signed(&keypair, &Subject::new("max"), &validity);
+        assert_eq!(Certificate::from_der(cert.to_der()).unwrap().validity(), &validity);
+
+        clock.advance(1);
+        assert_eq!(Validity::try_days_with_clock(10, &clock), Err(CertError::InvalidValidity));
+        assert_eq!(Validity::try_days_with_clock(u32::MAX, &FixedClock::new(0)), Err(CertError::InvalidValidity));
+        assert_eq!(Validity::try_days_with_clock(1, &FixedClock::new(i64::MAX)), Err(CertError::InvalidValidity));
+
+        // 1950年以前使用GeneralizedTime
+        let validity = Validity::try_days_with_clock(1, &FixedClock::new(-1000000000)).unwrap();
+        let cert = generate_self_signed(&keypair, &Subject::new("min"), &validity);
+        assert_eq!(Certificate::from_der(cert.to_der()).unwrap().validity(), &validity);
+    }
+
+    #[test]
+    #[should_panic(expected = "The certificate validity must be within the years 0000 to 9999.")]
+    fn days_overflow() {
+        Validity::days(u32::MAX);
+    }
+
+    #[test]
+    fn check_validity() {
+        let prk = "6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e";
+        let puk = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+        let keypair = KeyPair::new(PrivateKey::decode(prk), PublicKey::decode(puk));
+
+        let clock = FixedClock::new(1_700_000_000);
+        let validity = Validity::days_with_clock(1, &clock);
+        assert_eq!(validity.not_before(), 1_700_000_000);
+        let cert = generate_self_signed(&keypair, &Subject::new("clock"), &validity);
+
+        assert_eq!(cert.check_validity(&clock), Ok(()));
+        clock.advance(86400);
+        assert_eq!(cert.check_validity(&clock), Ok(()));
+        clock.advance(1);
+        assert_eq!(cert.check_validity(&clock), Err(CertError::Expired(1_700_086_400)));
+        clock.set(1_699_999_999);
+        assert_eq!(cert.check_validity(&clock), Err(CertError::NotYetValid(1_700_000_000)));
+        assert!(!validity.contains(1_699_999_999));
+    }
+
+    #[test]
+    fn verify_with_certificate() {
+        let prk = "6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e";
+        let puk = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+        let keypair = KeyPair::new(PrivateKey::decode(prk), PublicKey::decode(puk));
+
+        let clock = FixedClock::new(1_700_000_000);
+        let pem = generate_self_signed(&keypair, &Subject::new("document"), &Validity::days_with_clock(1, &clock)).to_pem();
+        let signature = Crypto::default().signer(keypair).sign_bytes(b"document").to_der();
+
+        let cert = super::verify_with_certificate_with_clock(&pem, b"document", &signature, &clock).unwrap();
+        assert_eq!(cert.public_key().encode(), puk);
+        assert_eq!(super::verify_with_certificate_with_clock(&pem, b"tampered", &signature, &clock).unwrap_err(), CertError::BadSignature(VerifyError::Mismatch));
+        assert!(matches!(super::verify_with_certificate_with_clock(&pem, b"document", &signature[1..], &clock), Err(CertError::BadSignature(VerifyError::Malformed(_)))));
+        assert_eq!(super::verify_with_certificate_with_clock(&pem[1..], b"document", &signature, &clock).unwrap_err(), CertError::Malformed);
+        clock.advance(86401);
+        assert_eq!(super::verify_with_certificate_with_clock(&pem, b"document", &signature, &clock).unwrap_err(), CertError::Expired(1_700_086_400));
+        // 系统时间早已超出有效期
+        assert_eq!(super::verify_with_certificate(&pem, b"document", &signature).unwrap_err(), CertError::Expired(1_700_086_400));
+        assert_eq!(CertError::BadSignature(VerifyError::Mismatch).code(), Code::VERIFY_FAILED);
+    }
+}
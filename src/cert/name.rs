@@ -0,0 +1,247 @@
+use yasna::tags::{TAG_BMPSTRING, TAG_IA5STRING, TAG_PRINTABLESTRING, TAG_TELETEXSTRING, TAG_UTF8STRING};
+use yasna::{ASN1Error, ASN1ErrorKind, BERReader, DERWriter};
+
+use crate::cert::oid;
+use crate::cert::CertError;
+
+/// 名称属性类型
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Attribute {
+    /// C，两位国家代码
+    Country,
+    /// O
+    Organization,
+    /// OU
+    OrganizationalUnit,
+    /// CN
+    CommonName,
+    /// 其他属性，保存其OID
+    Other(Vec<u64>),
+}
+
+impl Attribute {
+    fn oid(&self) -> &[u64] {
+        match self {
+            Attribute::Country => oid::COUNTRY,
+            Attribute::Organization => oid::ORGANIZATION,
+            Attribute::OrganizationalUnit => oid::ORGANIZATIONAL_UNIT,
+            Attribute::CommonName => oid::COMMON_NAME,
+            Attribute::Other(id) => id,
+        }
+    }
+
+    fn of(id: &[u64]) -> Self {
+        [Attribute::Country, Attribute::Organization, Attribute::OrganizationalUnit, Attribute::CommonName]
+            .into_iter()
+            .find(|e| e.oid() == id)
+            .unwrap_or_else(|| Attribute::Other(id.to_vec()))
+    }
+}
+
+/// 可分辨名称（DN），按添加顺序编码为X.501 Name
+///
+/// 编码时属性值能以PrintableString表示的使用PrintableString，否则使用UTF8String；
+/// 解析时兼容早期国内CA以GB 2312/GBK字节填充的字符串类型。
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DistinguishedName {
+    attributes: Vec<(Attribute, String)>,
+}
+
+impl DistinguishedName {
+    pub fn new() -> Self {
+        DistinguishedName::default()
+    }
+
+    /// 国家代码，如"CN"
+    pub fn country(self, country: &str) -> Self {
+        if country.len() != 2 || !country.bytes().all(|b| b.is_ascii_alphabetic()) {
+            panic!("The country must be a two-letter code.")
+        }
+        self.push(Attribute::Country, country)
+    }
+
+    pub fn organization(self, organization: &str) -> Self {
+        self.push(Attribute::Organization, organization)
+    }
+
+    pub fn organizational_unit(self, unit: &str) -> Self {
+        self.push(Attribute::OrganizationalUnit, unit)
+    }
+
+    pub fn common_name(self, common_name: &str) -> Self {
+        self.push(Attribute::CommonName, common_name)
+    }
+
+    fn push(mut self, attribute: Attribute, value: &str) -> Self {
+        self.attributes.push((attribute, value.to_string()));
+        self
+    }
+
+    /// 指定属性的第一个值
+    pub fn get(&self, attribute: &Attribute) -> Option<&str> {
+        self.attributes.iter().find(|(a, _)| a == attribute).map(|(_, v)| v.as_str())
+    }
+
+    pub fn attributes(&self) -> &[(Attribute, String)] {
+        &self.attributes
+    }
+
+    /// DER编码的Name
+    pub fn to_der(&self) -> Vec<u8> {
+        yasna::construct_der(|writer| self.write(writer))
+    }
+
+    /// 解析BER/DER编码的Name
+    pub fn from_der(der: &[u8]) -> Result<Self, CertError> {
+        yasna::parse_ber(der, Self::read).map_err(|_| CertError::Malformed)
+    }
+
+    /// 按RFC 5280 7.1比较：逐个属性比较，忽略首尾空白、连续空白及大小写
+    pub fn canonical_eq(&self, other: &DistinguishedName) -> bool {
+        self.attributes.len() == other.attributes.len()
+            && self.attributes.iter().zip(other.attributes.iter()).all(|((a, x), (b, y))| {
+                a == b && canonicalize(x) == canonicalize(y)
+            })
+    }
+
+    /// Name ::= SEQUENCE OF SET OF SEQUENCE { type OBJECT IDENTIFIER, value ANY }
+    pub(crate) fn write(&self, writer: DERWriter) {
+        writer.write_sequence(|writer| {
+            for (attribute, value) in &self.attributes {
+                writer.next().write_set(|writer| {
+                    writer.next().write_sequence(|writer| {
+                        writer.next().write_oid(&oid::of(attribute.oid()));
+                        if is_printable(value) {
+                            writer.next().write_printable_string(value);
+                        } else {
+                            writer.next().write_utf8_string(value);
+                        }
+                    })
+                })
+            }
+        })
+    }
+
+    pub(crate) fn read(reader: BERReader) -> Result<Self, ASN1Error> {
+        let mut attributes = vec![];
+        reader.read_sequence_of(|reader| {
+            reader.read_set_of(|reader| {
+                reader.read_sequence(|reader| {
+                    let id = reader.next().read_oid()?;
+                    let value = read_string(reader.next())?;
+                    attributes.push((Attribute::of(id.components()), value));
+                    Ok(())
+                })
+            })
+        })?;
+        Ok(DistinguishedName { attributes })
+    }
+}
+
+/// DirectoryString ::= CHOICE { teletexString, printableString, universalString, utf8String, bmpString }
+///
+/// 非UTF-8的字节串按GB 18030（GB 2312、GBK的超集）解码
+fn read_string(reader: BERReader) -> Result<String, ASN1Error> {
+    let value = reader.read_tagged_der()?;
+    let bytes = value.value();
+    let tag = value.tag();
+    if tag == TAG_BMPSTRING {
+        if bytes.len() % 2 != 0 {
+            return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+        }
+        let units: Vec<u16> = bytes.chunks(2).map(|e| u16::from_be_bytes([e[0], e[1]])).collect();
+        return String::from_utf16(&units).map_err(|_| ASN1Error::new(ASN1ErrorKind::Invalid));
+    }
+    if ![TAG_UTF8STRING, TAG_PRINTABLESTRING, TAG_TELETEXSTRING, TAG_IA5STRING].contains(&tag) {
+        return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(s) => Ok(s.to_string()),
+        Err(_) => {
+            let (s, _, malformed) = encoding_rs::GB18030.decode(bytes);
+            if malformed {
+                return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+            }
+            Ok(s.into_owned())
+        }
+    }
+}
+
+/// PrintableString字符集：字母、数字、空格及 '()+,-./:=
+fn is_printable(value: &str) -> bool {
+    value.bytes().all(|b| {
+        b.is_ascii_alphanumeric() || b" '()+,-./:=".contains(&b)
+    })
+}
+
+fn canonicalize(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn main() {
+        let name = DistinguishedName::new()
+            .country("CN")
+            .organization("Example Co., Ltd.")
+            .organizational_unit("研发中心")
+            .common_name("localhost");
+
+        let der = name.to_der();
+        // C使用PrintableString，中文OU使用UTF8String
+        assert_eq!(&der[..13], hex::decode("3054310b300906035504061302").unwrap());
+        assert!(der.windows(2).any(|w| w == [0x0C, 12]));
+
+        let parsed = DistinguishedName::from_der(&der).unwrap();
+        assert_eq!(parsed, name);
+        assert_eq!(parsed.get(&Attribute::OrganizationalUnit), Some("研发中心"));
+        assert_eq!(parsed.get(&Attribute::CommonName), Some("localhost"));
+
+        let other = DistinguishedName::new()
+            .country("cn")
+            .organization("  example   CO., ltd. ")
+            .organizational_unit("研发中心")
+            .common_name("LOCALHOST");
+        assert!(name.canonical_eq(&other));
+        assert!(!name.canonical_eq(&other.clone().common_name("extra")));
+        assert!(!name.canonical_eq(&DistinguishedName::new().common_name("localhost")));
+    }
+
+    #[test]
+    fn gb2312() {
+        // 早期证书以GB 2312字节填充PrintableString："中国"
+        let der = yasna::construct_der(|writer| {
+            writer.write_sequence(|writer| {
+                writer.next().write_set(|writer| {
+                    writer.next().write_sequence(|writer| {
+                        writer.next().write_oid(&oid::of(oid::ORGANIZATION));
+                        writer.next().write_tagged_implicit(TAG_PRINTABLESTRING, |writer| {
+                            writer.write_bytes(&[0xD6, 0xD0, 0xB9, 0xFA])
+                        });
+                    })
+                })
+            })
+        });
+        let name = DistinguishedName::from_der(&der).unwrap();
+        assert_eq!(name.get(&Attribute::Organization), Some("中国"));
+
+        // BMPString
+        let der = yasna::construct_der(|writer| {
+            writer.write_sequence(|writer| {
+                writer.next().write_set(|writer| {
+                    writer.next().write_sequence(|writer| {
+                        writer.next().write_oid(&oid::of(&[2, 5, 4, 7]));
+                        writer.next().write_bmp_string("北京");
+                    })
+                })
+            })
+        });
+        let name = DistinguishedName::from_der(&der).unwrap();
+        assert_eq!(name.attributes(), &[(Attribute::Other(vec![2, 5, 4, 7]), "北京".to_string())]);
+        assert_eq!(DistinguishedName::from_der(&der[..der.len() - 1]).unwrap_err(), CertError::Malformed);
+    }
+}
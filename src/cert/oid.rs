@@ -0,0 +1,40 @@
+use yasna::models::ObjectIdentifier;
+
+/// id-ecPublicKey
+pub(crate) const EC_PUBLIC_KEY: &[u64] = &[1, 2, 840, 10045, 2, 1];
+/// SM2椭圆曲线
+pub(crate) const SM2: &[u64] = &[1, 2, 156, 10197, 1, 301];
+/// SM2-1椭圆曲线数字签名算法
+pub(crate) const SM2_SIGN: &[u64] = &[1, 2, 156, 10197, 1, 301, 1];
+/// SM3密码杂凑算法
+pub(crate) const SM3: &[u64] = &[1, 2, 156, 10197, 1, 401];
+/// 基于SM2算法和SM3算法的签名
+pub(crate) const SM3_WITH_SM2: &[u64] = &[1, 2, 156, 10197, 1, 501];
+
+/// GM/T 0010 数据类型
+pub(crate) const GM_DATA: &[u64] = &[1, 2, 156, 10197, 6, 1, 4, 2, 1];
+/// GM/T 0010 签名数据类型
+pub(crate) const GM_SIGNED_DATA: &[u64] = &[1, 2, 156, 10197, 6, 1, 4, 2, 2];
+
+/// PKCS#9 签名属性
+pub(crate) const CONTENT_TYPE: &[u64] = &[1, 2, 840, 113549, 1, 9, 3];
+pub(crate) const MESSAGE_DIGEST: &[u64] = &[1, 2, 840, 113549, 1, 9, 4];
+pub(crate) const SIGNING_TIME: &[u64] = &[1, 2, 840, 113549, 1, 9, 5];
+
+/// RFC 3161 时间戳信息内容类型 id-ct-TSTInfo
+pub(crate) const TST_INFO: &[u64] = &[1, 2, 840, 113549, 1, 9, 16, 1, 4];
+
+/// X.520 名称属性
+pub(crate) const COMMON_NAME: &[u64] = &[2, 5, 4, 3];
+pub(crate) const COUNTRY: &[u64] = &[2, 5, 4, 6];
+pub(crate) const ORGANIZATION: &[u64] = &[2, 5, 4, 10];
+pub(crate) const ORGANIZATIONAL_UNIT: &[u64] = &[2, 5, 4, 11];
+
+/// X.509 v3 extensions
+pub(crate) const SUBJECT_ALT_NAME: &[u64] = &[2, 5, 29, 17];
+pub(crate) const BASIC_CONSTRAINTS: &[u64] = &[2, 5, 29, 19];
+
+#[inline(always)]
+pub(crate) fn of(components: &[u64]) -> ObjectIdentifier {
+    ObjectIdentifier::from_slice(components)
+}
@@ -0,0 +1,234 @@
+use std::net::IpAddr;
+
+use num_bigint::BigUint;
+use time::OffsetDateTime;
+use yasna::models::{GeneralizedTime, UTCTime};
+use yasna::tags::{TAG_GENERALIZEDTIME, TAG_UTCTIME};
+use yasna::{ASN1Error, ASN1ErrorKind, BERReader, DERWriter, Tag};
+
+use crate::cert::oid;
+use crate::env;
+use crate::cert::{AltName, CertError, Subject, Validity};
+use crate::sm2::ecc::Crypto;
+use crate::sm2::key::{KeyPair, PublicKey};
+
+/// 证书中与签名验证相关的部分
+pub(crate) struct Parts {
+    pub(crate) tbs: Vec<u8>,
+    pub(crate) serial: Vec<u8>,
+    pub(crate) issuer: Vec<u8>,
+    pub(crate) subject: Vec<u8>,
+    pub(crate) validity: Validity,
+    pub(crate) public_key: PublicKey,
+    pub(crate) signature: Vec<u8>,
+}
+
+/// Certificate  ::=  SEQUENCE  {
+///      tbsCertificate       TBSCertificate,
+///      signatureAlgorithm   AlgorithmIdentifier,
+///      signatureValue       BIT STRING  }
+pub(crate) fn self_signed(keypair: &KeyPair, subject: &Subject, validity: &Validity) -> Vec<u8> {
+    let tbs = tbs_certificate(keypair.puk(), subject, validity);
+    let signature = Crypto::default().signer(keypair.clone()).sign_bytes(&tbs).encode();
+
+    yasna::construct_der(|writer| {
+        writer.write_sequence(|writer| {
+            writer.next().write_der(&tbs);
+            write_algorithm(writer.next());
+            writer.next().write_bitvec_bytes(&signature, signature.len() * 8);
+        })
+    })
+}
+
+/// TBSCertificate  ::=  SEQUENCE  {
+///      version         \[0]  EXPLICIT Version DEFAULT v1,
+///      serialNumber         CertificateSerialNumber,
+///      signature            AlgorithmIdentifier,
+///      issuer               Name,
+///      validity             Validity,
+///      subject              Name,
+///      subjectPublicKeyInfo SubjectPublicKeyInfo,
+///      extensions      \[3]  EXPLICIT Extensions OPTIONAL }
+fn tbs_certificate(puk: &PublicKey, subject: &Subject, validity: &Validity) -> Vec<u8> {
+    // 序列号为正整数，最高位清零
+    let serial = {
+        let mut bytes: [u8; 16] = env::random();
+        bytes[0] &= 0x7F;
+        BigUint::from_bytes_be(&bytes)
+    };
+
+    yasna::construct_der(|writer| {
+        writer.write_sequence(|writer| {
+            writer.next().write_tagged(Tag::context(0), |writer| writer.write_u8(2));
+            writer.next().write_biguint(&serial);
+            write_algorithm(writer.next());
+            subject.name.write(writer.next());
+            writer.next().write_sequence(|writer| {
+                write_time(writer.next(), validity.not_before);
+                write_time(writer.next(), validity.not_after);
+            });
+            subject.name.write(writer.next());
+            write_public_key_info(writer.next(), puk);
+            writer.next().write_tagged(Tag::context(3), |writer| {
+                writer.write_sequence(|writer| {
+                    // 自签名证书同时作为信任锚，与`openssl req -x509`一致标记为CA
+                    let constraints = yasna::construct_der(|writer| {
+                        writer.write_sequence(|writer| writer.next().write_bool(true))
+                    });
+                    write_extension(writer.next(), oid::BASIC_CONSTRAINTS, true, &constraints);
+
+                    if !subject.alt_names.is_empty() {
+                        let names = yasna::construct_der(|writer| {
+                            writer.write_sequence(|writer| {
+                                subject.alt_names.iter().for_each(|name| write_alt_name(writer.next(), name));
+                            })
+                        });
+                        write_extension(writer.next(), oid::SUBJECT_ALT_NAME, false, &names);
+                    }
+                })
+            });
+        })
+    })
+}
+
+/// AlgorithmIdentifier ::= SEQUENCE { algorithm OBJECT IDENTIFIER }
+fn write_algorithm(writer: DERWriter) {
+    writer.write_sequence(|writer| writer.next().write_oid(&oid::of(oid::SM3_WITH_SM2)))
+}
+
+/// 时间戳能否编码为UTCTime或GeneralizedTime，即在0000年至9999年之间
+pub(crate) fn encodable(timestamp: i64) -> bool {
+    OffsetDateTime::from_unix_timestamp(timestamp).is_ok_and(|datetime| (0..10000).contains(&datetime.year()))
+}
+
+/// 1950年至2049年使用UTCTime，其余使用GeneralizedTime (RFC 5280 4.1.2.5)，时间戳须满足[`encodable`]
+pub(crate) fn write_time(writer: DERWriter, timestamp: i64) {
+    let datetime = datetime_of(timestamp);
+    if (1950..2050).contains(&datetime.year()) {
+        writer.write_utctime(&UTCTime::from_datetime(datetime))
+    } else {
+        writer.write_generalized_time(&GeneralizedTime::from_datetime(datetime))
+    }
+}
+
+/// 始终使用GeneralizedTime，用于电子印章、时间戳令牌等规定了该类型的格式，时间戳须满足[`encodable`]
+pub(crate) fn write_generalized_time(writer: DERWriter, timestamp: i64) {
+    writer.write_generalized_time(&GeneralizedTime::from_datetime(datetime_of(timestamp)))
+}
+
+fn datetime_of(timestamp: i64) -> OffsetDateTime {
+    OffsetDateTime::from_unix_timestamp(timestamp).expect("The timestamp is out of range.")
+}
+
+/// SubjectPublicKeyInfo ::= SEQUENCE {
+///      algorithm         AlgorithmIdentifier { id-ecPublicKey, sm2 },
+///      subjectPublicKey  BIT STRING }
+fn write_public_key_info(writer: DERWriter, puk: &PublicKey) {
+    let key = puk.to_bytes();
+    writer.write_sequence(|writer| {
+        writer.next().write_sequence(|writer| {
+            writer.next().write_oid(&oid::of(oid::EC_PUBLIC_KEY));
+            writer.next().write_oid(&oid::of(oid::SM2));
+        });
+        writer.next().write_bitvec_bytes(&key, key.len() * 8);
+    })
+}
+
+/// Extension ::= SEQUENCE { extnID OBJECT IDENTIFIER, critical BOOLEAN DEFAULT FALSE, extnValue OCTET STRING }
+fn write_extension(writer: DERWriter, id: &[u64], critical: bool, value: &[u8]) {
+    writer.write_sequence(|writer| {
+        writer.next().write_oid(&oid::of(id));
+        if critical {
+            writer.next().write_bool(true);
+        }
+        writer.next().write_bytes(value);
+    })
+}
+
+/// GeneralName ::= CHOICE { dNSName \[2] IA5String, iPAddress \[7] OCTET STRING, ... }
+fn write_alt_name(writer: DERWriter, name: &AltName) {
+    match name {
+        AltName::Dns(dns) => {
+            writer.write_tagged_implicit(Tag::context(2), |writer| writer.write_ia5_string(dns))
+        }
+        AltName::Ip(IpAddr::V4(ip)) => {
+            writer.write_tagged_implicit(Tag::context(7), |writer| writer.write_bytes(&ip.octets()))
+        }
+        AltName::Ip(IpAddr::V6(ip)) => {
+            writer.write_tagged_implicit(Tag::context(7), |writer| writer.write_bytes(&ip.octets()))
+        }
+    }
+}
+
+/// 解析SM3WithSM2签名、SM2公钥的X.509证书
+pub(crate) fn parse(der: &[u8]) -> Result<Parts, CertError> {
+    let (tbs, algorithm, signature) = yasna::parse_der(der, |reader| {
+        reader.read_sequence(|reader| {
+            let tbs = reader.next().read_der()?;
+            let algorithm = reader.next().read_sequence(|reader| {
+                let algorithm = reader.next().read_oid()?;
+                reader.read_optional(|reader| reader.read_null())?;
+                Ok(algorithm)
+            })?;
+            let signature = reader.next().read_bitvec_bytes()?.0;
+            Ok((tbs, algorithm, signature))
+        })
+    }).map_err(|_| CertError::Malformed)?;
+
+    if algorithm != oid::of(oid::SM3_WITH_SM2) {
+        return Err(CertError::UnsupportedAlgorithm);
+    }
+
+    let (serial, issuer, validity, subject, key) = yasna::parse_der(&tbs, |reader| {
+        reader.read_sequence(|reader| {
+            reader.read_optional(|reader| reader.read_tagged(Tag::context(0), |reader| reader.read_u8()))?;
+            let serial = reader.next().read_der()?;
+            // signature
+            reader.next().read_der()?;
+            let issuer = reader.next().read_der()?;
+            let validity = reader.next().read_sequence(|reader| {
+                let not_before = read_time(reader.next())?;
+                let not_after = read_time(reader.next())?;
+                Ok(Validity { not_before, not_after })
+            })?;
+            let subject = reader.next().read_der()?;
+            let key = reader.next().read_sequence(|reader| {
+                let algorithm = reader.next().read_sequence(|reader| {
+                    let algorithm = reader.next().read_oid()?;
+                    let curve = reader.read_optional(|reader| reader.read_oid())?;
+                    Ok((algorithm, curve))
+                })?;
+                let key = reader.next().read_bitvec_bytes()?.0;
+                Ok((algorithm, key))
+            })?;
+            // issuerUniqueID, subjectUniqueID, extensions
+            for _ in 0..3 {
+                reader.read_optional(|reader| reader.read_tagged_der())?;
+            }
+            Ok((serial, issuer, validity, subject, key))
+        })
+    }).map_err(|_| CertError::Malformed)?;
+
+    let ((algorithm, curve), key) = key;
+    if algorithm != oid::of(oid::EC_PUBLIC_KEY) || curve != Some(oid::of(oid::SM2)) {
+        return Err(CertError::UnsupportedAlgorithm);
+    }
+    let public_key = PublicKey::try_from_bytes(&key).ok_or(CertError::InvalidPublicKey)?;
+
+    Ok(Parts { tbs, serial, issuer, subject, validity, public_key, signature })
+}
+
+/// Time ::= CHOICE { utcTime UTCTime, generalTime GeneralizedTime }
+pub(crate) fn read_time(reader: BERReader) -> Result<i64, ASN1Error> {
+    let value = reader.read_tagged_der()?;
+    let datetime = if value.tag() == TAG_UTCTIME {
+        UTCTime::parse(value.value()).map(|t| *t.datetime())
+    } else if value.tag() == TAG_GENERALIZEDTIME {
+        GeneralizedTime::parse(value.value()).map(|t| *t.datetime())
+    } else {
+        None
+    };
+    datetime
+        .map(|t| t.unix_timestamp())
+        .ok_or_else(|| ASN1Error::new(ASN1ErrorKind::Invalid))
+}
@@ -0,0 +1,69 @@
+//! 时间来源
+//!
+//! 证书有效期、时间戳与令牌过期的检查经由[`Clock`]取得当前时间。默认使用系统时钟；
+//! 没有实时时钟的嵌入式设备可接入自己的时间来源（如GNSS或上级服务器下发的时间），测试中可使用[`FixedClock`]。
+
+use std::cell::Cell;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub trait Clock {
+    /// 当前UNIX时间戳（秒）
+    fn now(&self) -> i64;
+}
+
+/// 操作系统的系统时钟
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> i64 {
+        match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(d) => d.as_secs() as i64,
+            Err(e) => -(e.duration().as_secs() as i64),
+        }
+    }
+}
+
+/// 固定的时间，仅在调用[`FixedClock::set`]或[`FixedClock::advance`]时改变
+#[derive(Debug, Clone, Default)]
+pub struct FixedClock(Cell<i64>);
+
+impl FixedClock {
+    pub fn new(now: i64) -> Self {
+        FixedClock(Cell::new(now))
+    }
+
+    pub fn set(&self, now: i64) {
+        self.0.set(now)
+    }
+
+    /// 向后拨动`seconds`秒，负数则向前
+    pub fn advance(&self, seconds: i64) {
+        self.0.set(self.0.get() + seconds)
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> i64 {
+        self.0.get()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn main() {
+        assert!(SystemClock.now() > 1_700_000_000);
+
+        let clock = FixedClock::new(1_700_000_000);
+        assert_eq!(clock.now(), 1_700_000_000);
+        clock.advance(60);
+        assert_eq!(clock.now(), 1_700_000_060);
+        clock.set(0);
+        let clock: &dyn Clock = &clock;
+        assert_eq!(clock.now(), 0);
+    }
+}
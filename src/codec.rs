@@ -0,0 +1,426 @@
+use std::fmt::{Display, Formatter};
+use std::sync::RwLock;
+
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+
+use crate::error_code::{Code, ErrorCode};
+
+const HEX_CHARS: &[u8; 16] = b"0123456789abcdef";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HexError {
+    /// 第`index`个字符不是16进制字符
+    InvalidChar { index: usize, char: char },
+    /// 解码后的字节数与期望不一致
+    LengthMismatch { expected: usize, actual: usize },
+}
+
+impl Display for HexError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HexError::InvalidChar { index, char } => {
+                write!(f, "Invalid hex char {:?} at index {}.", char, index)
+            }
+            HexError::LengthMismatch { expected, actual } => {
+                write!(f, "Expected {} bytes, got {}.", expected, actual)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HexError {}
+
+impl ErrorCode for HexError {
+    fn code(&self) -> Code {
+        match self {
+            HexError::InvalidChar { .. } => Code::INVALID_DATA,
+            HexError::LengthMismatch { .. } => Code::INVALID_LENGTH,
+        }
+    }
+}
+
+/// 加解密接口默认的输入长度上限：1 GiB
+pub const DEFAULT_MAX_LEN: usize = 1 << 30;
+
+/// 输入长度超过上限，在分配输出之前返回
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeLimitError {
+    /// 输入对象，如"SM2 plain data"
+    pub what: &'static str,
+    /// 输入的实际长度
+    pub len: usize,
+    /// 允许的最大长度
+    pub max: usize,
+}
+
+#[cfg_attr(not(any(feature = "sm4", feature = "sm2-encrypt")), allow(dead_code))]
+impl SizeLimitError {
+    /// `len`不超过`max`时返回Ok
+    pub(crate) fn check(what: &'static str, len: usize, max: usize) -> Result<(), SizeLimitError> {
+        match len > max {
+            true => Err(SizeLimitError { what, len, max }),
+            false => Ok(()),
+        }
+    }
+}
+
+impl Display for SizeLimitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The {} is {} bytes, exceeding the limit of {} bytes.", self.what, self.len, self.max)
+    }
+}
+
+impl std::error::Error for SizeLimitError {}
+
+impl ErrorCode for SizeLimitError {
+    fn code(&self) -> Code {
+        Code::INVALID_LENGTH
+    }
+}
+
+/// 定长数据的16进制编码结果，编码在格式化时逐字节写出，不分配中间字符串
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FixedHex<const N: usize>([u8; N]);
+
+impl<const N: usize> FixedHex<N> {
+    /// 将小写16进制字符写入`out`，`out`长度必须为`2N`
+    pub fn write_to(&self, out: &mut [u8]) {
+        if out.len() != N * 2 {
+            panic!("The output length must be {}.", N * 2)
+        }
+        for (i, b) in self.0.iter().enumerate() {
+            out[i * 2] = HEX_CHARS[(b >> 4) as usize];
+            out[i * 2 + 1] = HEX_CHARS[(b & 0x0F) as usize];
+        }
+    }
+}
+
+impl<const N: usize> Display for FixedHex<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut buf = [0u8; 64];
+        for chunk in self.0.chunks(32) {
+            let out = &mut buf[..chunk.len() * 2];
+            for (i, b) in chunk.iter().enumerate() {
+                out[i * 2] = HEX_CHARS[(b >> 4) as usize];
+                out[i * 2 + 1] = HEX_CHARS[(b & 0x0F) as usize];
+            }
+            f.write_str(std::str::from_utf8(out).unwrap())?;
+        }
+        Ok(())
+    }
+}
+
+/// 定长数据的16进制编码，如摘要、秘钥、初始化向量
+pub fn encode_hex_fixed<const N: usize>(data: [u8; N]) -> FixedHex<N> {
+    FixedHex(data)
+}
+
+/// 严格的定长16进制解码，字符串必须恰好编码`N`个字节，大小写均可
+pub fn decode_hex_fixed<const N: usize>(hex: &str) -> Result<[u8; N], HexError> {
+    let bytes = hex.as_bytes();
+    if bytes.len() != N * 2 {
+        return Err(HexError::LengthMismatch { expected: N, actual: bytes.len() / 2 });
+    }
+
+    let mut out = [0u8; N];
+    // 按顺序检查字符，多字节字符总是在首字节处报错，因此`index`必然落在字符边界上
+    for (i, e) in out.iter_mut().enumerate() {
+        *e = (nibble(hex, i * 2)? << 4) | nibble(hex, i * 2 + 1)?;
+    }
+    Ok(out)
+}
+
+fn nibble(hex: &str, index: usize) -> Result<u8, HexError> {
+    hex_value(hex.as_bytes()[index])
+        .ok_or_else(|| HexError::InvalidChar { index, char: hex[index..].chars().next().unwrap_or('?') })
+}
+
+fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// 定长数据与定长的16进制ASCII数组之间的转换，用于SM3摘要、SM4认证标签等，编解码都不分配内存
+///
+/// ```
+/// use yarism::codec::HexArray;
+///
+/// let tag = [0x5a; 16];
+/// let hex: [u8; 32] = tag.to_hex_array();
+/// assert_eq!(&hex[..8], b"5a5a5a5a");
+/// assert_eq!(<[u8; 16]>::from_hex_array(&hex), Ok(tag));
+/// ```
+pub trait HexArray: Sized {
+    /// 16进制编码结果，长度为原数据的两倍
+    type Hex;
+
+    /// 小写16进制ASCII字符
+    fn to_hex_array(&self) -> Self::Hex;
+
+    /// 解析16进制ASCII字符，大小写均可
+    fn from_hex_array(hex: &Self::Hex) -> Result<Self, HexError>;
+}
+
+macro_rules! impl_hex_array {
+    ($($n:literal => $hex:literal),*) => {$(
+        impl HexArray for [u8; $n] {
+            type Hex = [u8; $hex];
+
+            fn to_hex_array(&self) -> [u8; $hex] {
+                let mut out = [0u8; $hex];
+                FixedHex(*self).write_to(&mut out);
+                out
+            }
+
+            fn from_hex_array(hex: &[u8; $hex]) -> Result<Self, HexError> {
+                let mut out = [0u8; $n];
+                for (i, e) in out.iter_mut().enumerate() {
+                    let digit = |index: usize| hex_value(hex[index]).ok_or(HexError::InvalidChar {
+                        index,
+                        // 非ASCII字节不一定构成完整的字符，记为替换字符
+                        char: if hex[index].is_ascii() { hex[index] as char } else { char::REPLACEMENT_CHARACTER },
+                    });
+                    *e = (digit(i * 2)? << 4) | digit(i * 2 + 1)?;
+                }
+                Ok(out)
+            }
+        }
+    )*};
+}
+
+impl_hex_array!(16 => 32, 32 => 64);
+
+/// base64url编码，不带填充，可直接放入HTTP头与JWT
+pub fn encode_base64url(data: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(data)
+}
+
+/// 严格的base64url解码：标准字符集中的`+`、`/`以及填充`=`均视为非法字符
+#[cfg_attr(not(feature = "sm2-verify"), allow(dead_code))]
+pub(crate) fn decode_base64url(what: &'static str, text: &str) -> Result<Vec<u8>, DecodeError> {
+    URL_SAFE_NO_PAD.decode(text).map_err(|e| {
+        let offset = match e {
+            base64::DecodeError::InvalidByte(offset, _) => offset,
+            base64::DecodeError::InvalidLastSymbol(offset, _) => offset,
+            _ => text.len(),
+        };
+        report(DecodeError::new(what, "invalid base64url", text.as_bytes(), offset))
+    })
+}
+
+/// PEM编码，每行64个字符
+#[cfg_attr(not(feature = "sm2-verify"), allow(dead_code))]
+pub(crate) fn pem_encode(label: &str, der: &[u8]) -> String {
+    let body = STANDARD.encode(der);
+    let mut pem = format!("-----BEGIN {}-----\n", label);
+    for line in body.as_bytes().chunks(64) {
+        pem.push_str(std::str::from_utf8(line).unwrap());
+        pem.push('\n');
+    }
+    pem.push_str(&format!("-----END {}-----\n", label));
+    pem
+}
+
+/// PEM解码，忽略标签之外的内容
+#[cfg_attr(not(feature = "sm2-verify"), allow(dead_code))]
+pub(crate) fn pem_decode(label: &str, pem: &str) -> Option<Vec<u8>> {
+    let begin = format!("-----BEGIN {}-----", label);
+    let end = format!("-----END {}-----", label);
+    let start = pem.find(&begin)? + begin.len();
+    let stop = start + pem[start..].find(&end)?;
+    let body: String = pem[start..stop].chars().filter(|c| !c.is_whitespace()).collect();
+    STANDARD.decode(body).ok()
+}
+
+
+/// 解码失败的诊断信息：出错位置、期望与实际长度以及出错位置附近的16进制内容
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError {
+    /// 解码对象，如"SM2 signature"
+    pub what: &'static str,
+    /// 失败原因
+    pub reason: &'static str,
+    /// 出错位置的字节偏移
+    pub offset: usize,
+    /// 期望的长度，与原因无关时为None
+    pub expected: Option<usize>,
+    /// 输入的实际长度
+    pub actual: usize,
+    /// 出错位置前后各8个字节的16进制内容，出错字节以方括号标出
+    pub context: String,
+}
+
+#[cfg_attr(not(feature = "sm2-verify"), allow(dead_code))]
+impl DecodeError {
+    pub(crate) fn new(what: &'static str, reason: &'static str, data: &[u8], offset: usize) -> Self {
+        DecodeError { what, reason, offset, expected: None, actual: data.len(), context: context_of(data, offset) }
+    }
+
+    pub(crate) fn expected(mut self, expected: usize) -> Self {
+        self.expected = Some(expected);
+        self
+    }
+
+    /// 16进制文本解码失败，偏移与上下文均针对文本
+    pub(crate) fn from_hex(what: &'static str, hex: &str, e: HexError) -> Self {
+        match e {
+            HexError::InvalidChar { index, .. } => DecodeError::new(what, "invalid hex char", hex.as_bytes(), index),
+            HexError::LengthMismatch { expected, .. } => {
+                DecodeError::new(what, "unexpected hex length", hex.as_bytes(), hex.len().min(expected * 2))
+                    .expected(expected * 2)
+            }
+        }
+    }
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to decode {}: {} at offset {}", self.what, self.reason, self.offset)?;
+        match self.expected {
+            Some(expected) => write!(f, " (expected {} bytes, got {})", expected, self.actual)?,
+            None => write!(f, " (input is {} bytes)", self.actual)?,
+        }
+        write!(f, ", near `{}`.", self.context)
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl ErrorCode for DecodeError {
+    fn code(&self) -> Code {
+        match self.expected {
+            Some(_) => Code::INVALID_LENGTH,
+            None => Code::INVALID_DATA,
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "sm2-verify"), allow(dead_code))]
+fn context_of(data: &[u8], offset: usize) -> String {
+    let from = offset.saturating_sub(8);
+    let to = (offset + 9).min(data.len());
+    let mut out = String::new();
+    if from > 0 {
+        out.push_str("..");
+    }
+    for (i, b) in data.iter().enumerate().take(to).skip(from) {
+        if i == offset {
+            out.push_str(&format!("[{:02x}]", b));
+        } else {
+            out.push_str(&format!("{:02x}", b));
+        }
+    }
+    if offset >= data.len() {
+        out.push_str("[]");
+    }
+    if to < data.len() {
+        out.push_str("..");
+    }
+    out
+}
+
+static DECODE_LOGGER: RwLock<Option<fn(&DecodeError)>> = RwLock::new(None);
+
+/// 设置解码失败时的日志回调，所有密钥、签名、密文的解码失败都会先交给回调再返回或panic
+pub fn set_decode_logger(logger: Option<fn(&DecodeError)>) {
+    *DECODE_LOGGER.write().unwrap() = logger;
+}
+
+/// 通知日志回调并原样返回错误
+#[cfg_attr(not(feature = "sm2-verify"), allow(dead_code))]
+pub(crate) fn report(e: DecodeError) -> DecodeError {
+    if let Some(logger) = *DECODE_LOGGER.read().unwrap() {
+        logger(&e);
+    }
+    e
+}
+
+/// 读取指定标签的DER TLV，仅支持不超过两个字节的长度，返回(内容起始偏移, 内容长度)
+#[cfg_attr(not(feature = "sm2-verify"), allow(dead_code))]
+pub(crate) fn read_tlv(what: &'static str, data: &[u8], offset: usize, tag: u8) -> Result<(usize, usize), DecodeError> {
+    if offset >= data.len() {
+        return Err(DecodeError::new(what, "unexpected end of input", data, offset).expected(offset + 2));
+    }
+    if data[offset] != tag {
+        return Err(DecodeError::new(what, "unexpected DER tag", data, offset));
+    }
+    let (start, len) = match data.get(offset + 1) {
+        Some(&n) if n < 0x80 => (offset + 2, n as usize),
+        Some(0x81) if offset + 2 < data.len() => (offset + 3, data[offset + 2] as usize),
+        Some(0x82) if offset + 3 < data.len() => (offset + 4, (data[offset + 2] as usize) << 8 | data[offset + 3] as usize),
+        Some(_) => return Err(DecodeError::new(what, "unsupported DER length", data, offset + 1)),
+        None => return Err(DecodeError::new(what, "unexpected end of input", data, offset + 1).expected(offset + 2)),
+    };
+    if start + len > data.len() {
+        return Err(DecodeError::new(what, "DER length exceeds input", data, offset + 1).expected(start + len));
+    }
+    Ok((start, len))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn main() {
+        let data = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef];
+        assert_eq!(encode_hex_fixed(data).to_string(), hex::encode(data));
+        assert_eq!(decode_hex_fixed::<8>("0123456789ABCDEF"), Ok(data));
+
+        let digest = [0x5a; 100];
+        assert_eq!(encode_hex_fixed(digest).to_string(), hex::encode(digest));
+        let mut out = [0u8; 200];
+        encode_hex_fixed(digest).write_to(&mut out);
+        assert_eq!(&out[..], hex::encode(digest).as_bytes());
+
+        let err = decode_hex_fixed::<32>(&"00".repeat(31)).unwrap_err();
+        assert_eq!(err.to_string(), "Expected 32 bytes, got 31.");
+        assert_eq!(decode_hex_fixed::<2>("0g00"), Err(HexError::InvalidChar { index: 1, char: 'g' }));
+        assert!(decode_hex_fixed::<2>("00é").is_err());
+    }
+
+    #[test]
+    fn hex_array() {
+        let digest: [u8; 32] = std::array::from_fn(|i| (i * 9) as u8);
+        let hex = digest.to_hex_array();
+        assert_eq!(&hex[..], hex::encode(digest).as_bytes());
+        assert_eq!(<[u8; 32]>::from_hex_array(&hex), Ok(digest));
+
+        let tag = [0xAB; 16];
+        let mut upper = [b'A'; 32];
+        upper.iter_mut().skip(1).step_by(2).for_each(|c| *c = b'B');
+        assert_eq!(tag.to_hex_array(), [b'a', b'b'].repeat(16)[..]);
+        assert_eq!(<[u8; 16]>::from_hex_array(&upper), Ok(tag));
+
+        upper[5] = b'x';
+        assert_eq!(<[u8; 16]>::from_hex_array(&upper), Err(HexError::InvalidChar { index: 5, char: 'x' }));
+        upper[3] = 0xC3;
+        assert_eq!(<[u8; 16]>::from_hex_array(&upper), Err(HexError::InvalidChar { index: 3, char: '\u{FFFD}' }));
+    }
+
+    #[test]
+    fn diagnostics() {
+        let data: Vec<u8> = (0..32).collect();
+        let e = DecodeError::new("test data", "unexpected byte", &data, 20).expected(16);
+        assert_eq!(e.context, "..0c0d0e0f10111213[14]15161718191a1b1c..");
+        assert_eq!(
+            e.to_string(),
+            "Failed to decode test data: unexpected byte at offset 20 (expected 16 bytes, got 32), near `..0c0d0e0f10111213[14]15161718191a1b1c..`."
+        );
+        assert_eq!(DecodeError::new("test data", "unexpected end of input", &data[..2], 2).context, "0001[]");
+
+        let e = read_tlv("test data", &[0x30, 0x05, 0x02, 0x01, 0x01], 0, 0x30).unwrap_err();
+        assert_eq!((e.offset, e.expected, e.actual), (1, Some(7), 5));
+        assert_eq!(read_tlv("test data", &[0x30, 0x03, 0x02, 0x01, 0x01], 0, 0x30), Ok((2, 3)));
+        assert_eq!(read_tlv("test data", &[0x30, 0x03, 0x02, 0x01, 0x01], 2, 0x04).unwrap_err().reason, "unexpected DER tag");
+
+        let e = DecodeError::from_hex("key", "00zz", decode_hex_fixed::<2>("00zz").unwrap_err());
+        assert_eq!((e.offset, e.context.as_str()), (2, "3030[7a]7a"));
+    }
+}
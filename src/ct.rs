@@ -0,0 +1,86 @@
+//! 常量时间原语：掩码、条件选择、条件交换与字节串比较
+//!
+//! 掩码只取全0或全1两种值。这些函数没有依赖取值的分支或下标，点运算、密钥比较与SM4的标签校验都经由这里，
+//! 审查常量时间性质时只需检查本模块与调用处是否把秘密数据用作分支条件。
+
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+use subtle::ConstantTimeEq;
+
+/// 可按位选择的无符号整数
+pub(crate) trait Word: Copy + BitAnd<Output = Self> + BitOr<Output = Self> + BitXor<Output = Self> + Not<Output = Self> {}
+
+impl Word for u8 {}
+impl Word for u32 {}
+impl Word for u64 {}
+
+/// x非0时返回全1，为0时返回0
+#[inline(always)]
+pub(crate) fn ct_mask(x: u32) -> u32 {
+    // x非0时 x | -x 的最高位为1
+    0u32.wrapping_sub((x | x.wrapping_neg()) >> 31)
+}
+
+/// a = b时返回全1，否则返回0
+#[inline(always)]
+pub(crate) fn ct_eq_mask(a: u32, b: u32) -> u32 {
+    !ct_mask(a ^ b)
+}
+
+/// mask为全1时取b，为0时取a
+#[inline(always)]
+pub(crate) fn ct_select<T: Word, const N: usize>(a: &[T; N], b: &[T; N], mask: T) -> [T; N] {
+    std::array::from_fn(|i| a[i] ^ (mask & (a[i] ^ b[i])))
+}
+
+/// mask为全1时交换a与b，为0时不变
+#[inline(always)]
+pub(crate) fn ct_swap<T: Word, const N: usize>(a: &mut [T; N], b: &mut [T; N], mask: T) {
+    for i in 0..N {
+        let d = mask & (a[i] ^ b[i]);
+        a[i] = a[i] ^ d;
+        b[i] = b[i] ^ d;
+    }
+}
+
+/// 字节串是否相同，由`subtle`比较；长度不同时直接返回false，长度相同时比较时间只依赖长度
+pub(crate) fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks() {
+        for (x, expected) in [(0, 0), (1, u32::MAX), (15, u32::MAX), (1 << 31, u32::MAX), (u32::MAX, u32::MAX)] {
+            assert_eq!(ct_mask(x), expected);
+        }
+        assert_eq!(ct_eq_mask(7, 7), u32::MAX);
+        assert_eq!(ct_eq_mask(7, 7 | 1 << 31), 0);
+        assert_eq!(ct_eq_mask(0, 1), 0);
+    }
+
+    #[test]
+    fn select_and_swap() {
+        let (a, b) = ([1u64, 2, 3], [4u64, 5, 6]);
+        assert_eq!(ct_select(&a, &b, 0), a);
+        assert_eq!(ct_select(&a, &b, u64::MAX), b);
+
+        let (mut x, mut y) = ([1u32, 2], [3u32, 4]);
+        ct_swap(&mut x, &mut y, 0);
+        assert_eq!((x, y), ([1, 2], [3, 4]));
+        ct_swap(&mut x, &mut y, u32::MAX);
+        assert_eq!((x, y), ([3, 4], [1, 2]));
+    }
+
+    #[test]
+    fn eq() {
+        assert!(ct_eq(b"", b""));
+        assert!(ct_eq(b"tag", b"tag"));
+        assert!(!ct_eq(b"tag", b"tah"));
+        assert!(!ct_eq(b"tag", b"ta"));
+    }
+}
@@ -0,0 +1,45 @@
+//! 字节串与字的相互转换
+//!
+//! SM3、SM4均按大端序将字节串划分为32位字。转换统一经由移位完成，不依赖宿主机字节序，
+//! 在s390x、PowerPC等大端平台上与x86_64、AArch64得到相同结果。
+
+/// 字节串按大端序转换为N个字，`bytes`长度必须为4N
+#[inline(always)]
+pub(crate) fn load_be<const N: usize>(bytes: &[u8]) -> [u32; N] {
+    debug_assert_eq!(bytes.len(), N * 4);
+    let mut words = [0u32; N];
+    for (word, b) in words.iter_mut().zip(bytes.chunks_exact(4)) {
+        *word = (b[0] as u32) << 24 | (b[1] as u32) << 16 | (b[2] as u32) << 8 | b[3] as u32;
+    }
+    words
+}
+
+/// 字按大端序写入字节串，`out`长度必须为字数的4倍
+#[inline(always)]
+pub(crate) fn store_be(words: &[u32], out: &mut [u8]) {
+    debug_assert_eq!(out.len(), words.len() * 4);
+    for (word, b) in words.iter().zip(out.chunks_exact_mut(4)) {
+        b.copy_from_slice(&[(word >> 24) as u8, (word >> 16) as u8, (word >> 8) as u8, *word as u8]);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn main() {
+        let bytes = [0x01, 0x02, 0x03, 0x04, 0xf0, 0xe0, 0xd0, 0xc0];
+        let words: [u32; 2] = load_be(&bytes);
+        assert_eq!(words, [0x01020304, 0xf0e0d0c0]);
+
+        let mut out = [0u8; 8];
+        store_be(&words, &mut out);
+        assert_eq!(out, bytes);
+
+        // 与宿主机字节序无关
+        assert_eq!(words[0], u32::from_be_bytes([0x01, 0x02, 0x03, 0x04]));
+        assert_eq!(words[0].to_ne_bytes(), if cfg!(target_endian = "big") { [1, 2, 3, 4] } else { [4, 3, 2, 1] });
+    }
+}
@@ -0,0 +1,160 @@
+// 启用部分功能时，随机数或时间来源可能未被使用
+#![cfg_attr(not(all(feature = "sm2-sign", feature = "sm4")), allow(dead_code))]
+
+use std::cell::RefCell;
+
+use rand::{CryptoRng, RngCore};
+
+use crate::clock::{Clock, SystemClock};
+
+/// 运行环境：随机数与当前时间的来源
+///
+/// 密钥生成、签名、加密、证书与签名时间等均经由此处取得随机数和时间，默认使用操作系统提供的来源，
+/// 测试中可替换为确定性的实现，使结果可复现。
+pub(crate) trait Env {
+    /// 以随机字节填充`out`
+    fn fill_bytes(&mut self, out: &mut [u8]);
+    /// 当前UNIX时间戳（秒）
+    fn now(&self) -> i64;
+}
+
+/// 操作系统随机源与系统时钟
+struct Os;
+
+impl Env for Os {
+    fn fill_bytes(&mut self, out: &mut [u8]) {
+        rand::thread_rng().fill_bytes(out)
+    }
+
+    fn now(&self) -> i64 {
+        SystemClock.now()
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Box<dyn Env>> = RefCell::new(Box::new(Os));
+}
+
+pub(crate) fn fill_bytes(out: &mut [u8]) {
+    CURRENT.with(|env| env.borrow_mut().fill_bytes(out))
+}
+
+pub(crate) fn random<const N: usize>() -> [u8; N] {
+    let mut out = [0u8; N];
+    fill_bytes(&mut out);
+    out
+}
+
+pub(crate) fn now() -> i64 {
+    CURRENT.with(|env| env.borrow().now())
+}
+
+/// 以当前运行环境为来源的随机数生成器，供未指定随机数生成器的接口使用
+pub(crate) struct EnvRng;
+
+impl RngCore for EnvRng {
+    fn next_u32(&mut self) -> u32 {
+        u32::from_be_bytes(random())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        u64::from_be_bytes(random())
+    }
+
+    fn fill_bytes(&mut self, out: &mut [u8]) {
+        fill_bytes(out)
+    }
+
+    fn try_fill_bytes(&mut self, out: &mut [u8]) -> Result<(), rand::Error> {
+        fill_bytes(out);
+        Ok(())
+    }
+}
+
+impl CryptoRng for EnvRng {}
+
+/// 以当前运行环境为来源的时钟，供未指定时钟的接口使用
+pub(crate) struct EnvClock;
+
+impl Clock for EnvClock {
+    fn now(&self) -> i64 {
+        now()
+    }
+}
+
+/// 在当前线程中以`env`替换运行环境执行`f`，结束后恢复
+#[cfg(test)]
+pub(crate) fn with_env<R>(env: impl Env + 'static, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<Box<dyn Env>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take().unwrap();
+            CURRENT.with(|env| *env.borrow_mut() = previous);
+        }
+    }
+
+    let previous = CURRENT.with(|current| current.replace(Box::new(env)));
+    let _restore = Restore(Some(previous));
+    f()
+}
+
+/// 由种子确定的随机数与固定时钟
+#[cfg(test)]
+pub(crate) struct Deterministic {
+    rng: rand::rngs::StdRng,
+    now: i64,
+}
+
+#[cfg(test)]
+impl Deterministic {
+    pub(crate) fn new(seed: u64, now: i64) -> Self {
+        use rand::SeedableRng;
+        Deterministic { rng: rand::rngs::StdRng::seed_from_u64(seed), now }
+    }
+}
+
+#[cfg(test)]
+impl Env for Deterministic {
+    fn fill_bytes(&mut self, out: &mut [u8]) {
+        self.rng.fill_bytes(out)
+    }
+
+    fn now(&self) -> i64 {
+        self.now
+    }
+}
+
+
+#[cfg(all(test, feature = "sm2-sign", feature = "sm2-encrypt", feature = "sm4"))]
+mod tests {
+    use crate::sm2::{simple, Crypto, HexKey, KeyPair, PrivateKey, PublicKey};
+    use crate::{cert, sm4};
+
+    use super::*;
+
+    #[test]
+    fn main() {
+        let run = || {
+            let (prk, puk) = simple::generate_keypair();
+            let cipher = simple::encrypt(&puk, b"env").unwrap();
+            let keypair = KeyPair::new(PrivateKey::decode(&prk), PublicKey::decode(&puk));
+            let signature = Crypto::default().signer(keypair.clone()).sign_bytes(b"env").encode();
+            let validity = cert::Validity::days(1);
+            let certificate = cert::generate_self_signed(&keypair, &cert::Subject::new("env"), &validity);
+            (prk, cipher, signature, sm4::generate_key(), validity.not_before(), certificate.to_der().to_vec())
+        };
+
+        let first = with_env(Deterministic::new(7, 1_700_000_000), run);
+        let second = with_env(Deterministic::new(7, 1_700_000_000), run);
+        assert_eq!(first, second);
+        assert_eq!(first.4, 1_700_000_000);
+
+        let other = with_env(Deterministic::new(8, 1_700_000_000), run);
+        assert_ne!(first.0, other.0);
+
+        // 恢复为操作系统来源
+        assert_ne!(random::<16>(), random::<16>());
+        assert!(now() > 1_700_000_000);
+    }
+}
@@ -0,0 +1,249 @@
+//! 数字信封：SM4-GCM加密数据，SM2加密一次性的数据密钥
+//!
+//! 信封格式为 C (113字节，SM2 C1C3C2加密的16字节数据密钥) ∥ nonce (12字节) ∥ GCM密文 ∥ 认证标签 (16字节)。
+//! 遥测等场景下向同一接收方发送大量小消息时，每个信封一次SM2加密的开销远大于数据本身，
+//! [`Sealer`]可按[`CachePolicy`]在一段时间、一定消息数内复用同一接收方的数据密钥及其SM2密文。
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::clock::{Clock, SystemClock};
+use crate::env;
+use crate::error_code::{Code, ErrorCode};
+use crate::sm2::ecc::Crypto;
+use crate::sm2::{PrivateKey, PublicKey};
+use crate::sm4::{GcmError, Nonce12, Sm4Gcm};
+
+/// SM2加密16字节数据密钥后的长度：C1 (65) ∥ C3 (32) ∥ C2 (16)
+const WRAPPED_LEN: usize = 65 + 32 + 16;
+const HEADER_LEN: usize = WRAPPED_LEN + 12;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvelopeError {
+    /// 信封短于SM2密文、nonce与认证标签之和
+    TooShort,
+    /// 数据密钥的SM2密文无法解密
+    BadWrappedKey,
+    /// 认证标签校验失败
+    Gcm(GcmError),
+}
+
+impl Display for EnvelopeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnvelopeError::TooShort => write!(f, "The envelope is too short."),
+            EnvelopeError::BadWrappedKey => write!(f, "The wrapped data key can not be decrypted."),
+            EnvelopeError::Gcm(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for EnvelopeError {}
+
+impl ErrorCode for EnvelopeError {
+    fn code(&self) -> Code {
+        match self {
+            EnvelopeError::TooShort => Code::INVALID_CIPHERTEXT,
+            EnvelopeError::BadWrappedKey => Code::INVALID_DATA,
+            EnvelopeError::Gcm(e) => e.code(),
+        }
+    }
+}
+
+/// 每个信封使用新的数据密钥加密
+pub fn seal(recipient: &PublicKey, aad: &[u8], plain: &[u8]) -> Vec<u8> {
+    let key: [u8; 16] = env::random();
+    let wrapped = Crypto::default().encryptor(recipient.clone()).encrypt_bytes(&key);
+    assemble(&key, &wrapped, &Nonce12::random(), aad, plain)
+}
+
+/// 解密[`seal`]或[`Sealer::seal`]生成的信封
+pub fn open(private_key: &PrivateKey, aad: &[u8], envelope: &[u8]) -> Result<Vec<u8>, EnvelopeError> {
+    if envelope.len() < HEADER_LEN + 16 {
+        return Err(EnvelopeError::TooShort);
+    }
+    let (wrapped, rest) = envelope.split_at(WRAPPED_LEN);
+    let (nonce, cipher) = rest.split_at(12);
+    let key: [u8; 16] = Crypto::default().decryptor(private_key.clone()).try_decrypt_bytes(wrapped)
+        .ok()
+        .and_then(|key| key.try_into().ok())
+        .ok_or(EnvelopeError::BadWrappedKey)?;
+    Sm4Gcm::new(&key).decrypt(&Nonce12::from_slice(nonce).unwrap(), aad, cipher).map_err(EnvelopeError::Gcm)
+}
+
+fn assemble(key: &[u8; 16], wrapped: &[u8], nonce: &Nonce12, aad: &[u8], plain: &[u8]) -> Vec<u8> {
+    let mut envelope = Vec::with_capacity(HEADER_LEN + plain.len() + 16);
+    envelope.extend_from_slice(wrapped);
+    envelope.extend_from_slice(nonce.as_bytes());
+    envelope.extend_from_slice(&Sm4Gcm::new(key).encrypt(nonce, aad, plain));
+    envelope
+}
+
+/// 数据密钥的复用策略，任一上限达到后为该接收方生成新的数据密钥
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachePolicy {
+    /// 最多缓存的接收方个数，超出时淘汰最久未使用的
+    pub capacity: usize,
+    /// 数据密钥的最长使用时间（秒）
+    pub ttl: i64,
+    /// 单个数据密钥最多加密的消息数，不超过2^32
+    pub max_messages: u64,
+}
+
+impl Default for CachePolicy {
+    /// 256个接收方，每个数据密钥最多使用5分钟、65536条消息
+    fn default() -> Self {
+        CachePolicy { capacity: 256, ttl: 300, max_messages: 1 << 16 }
+    }
+}
+
+struct CachedKey {
+    key: [u8; 16],
+    wrapped: Vec<u8>,
+    created: i64,
+    messages: u64,
+    last_used: u64,
+}
+
+/// 按接收方公钥缓存数据密钥的信封加密器
+///
+/// 复用同一数据密钥时nonce由消息计数生成，保证同一密钥下不重复。
+/// 缓存的数据密钥泄露会暴露其有效期内发往该接收方的全部消息，策略应按此权衡。
+pub struct Sealer {
+    policy: CachePolicy,
+    cache: HashMap<Vec<u8>, CachedKey>,
+    tick: u64,
+}
+
+impl Sealer {
+    pub fn new(policy: CachePolicy) -> Self {
+        Sealer { policy, cache: HashMap::new(), tick: 0 }
+    }
+
+    /// 同[`Sealer::seal_with_clock`]，使用系统时钟
+    pub fn seal(&mut self, recipient: &PublicKey, aad: &[u8], plain: &[u8]) -> Vec<u8> {
+        self.seal_with_clock(recipient, aad, plain, &SystemClock)
+    }
+
+    /// 使用缓存的数据密钥加密，缓存中没有可用密钥时生成新的数据密钥并做一次SM2加密
+    pub fn seal_with_clock(&mut self, recipient: &PublicKey, aad: &[u8], plain: &[u8], clock: &dyn Clock) -> Vec<u8> {
+        let now = clock.now();
+        let id = recipient.to_bytes();
+        let max_messages = self.policy.max_messages.min(1 << 32);
+        let expired = |entry: &CachedKey| now - entry.created >= self.policy.ttl || entry.messages >= max_messages;
+        if self.cache.get(&id).map_or(true, expired) {
+            if !self.cache.contains_key(&id) && self.cache.len() >= self.policy.capacity {
+                self.evict();
+            }
+            let key: [u8; 16] = env::random();
+            let wrapped = Crypto::default().encryptor(recipient.clone()).encrypt_bytes(&key);
+            self.cache.insert(id.clone(), CachedKey { key, wrapped, created: now, messages: 0, last_used: 0 });
+        }
+
+        self.tick += 1;
+        let entry = self.cache.get_mut(&id).unwrap();
+        let nonce = Nonce12::from_counter(entry.messages);
+        entry.messages += 1;
+        entry.last_used = self.tick;
+        assemble(&entry.key, &entry.wrapped, &nonce, aad, plain)
+    }
+
+    /// 丢弃全部缓存的数据密钥，如接收方轮换密钥后
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    /// 当前缓存的接收方个数
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    fn evict(&mut self) {
+        if let Some(id) = self.cache.iter().min_by_key(|(_, entry)| entry.last_used).map(|(id, _)| id.clone()) {
+            self.cache.remove(&id);
+        }
+    }
+}
+
+impl Default for Sealer {
+    fn default() -> Self {
+        Sealer::new(CachePolicy::default())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::clock::FixedClock;
+    use crate::sm2::key::HexKey;
+
+    use super::*;
+
+    const PRK: &str = "6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e";
+    const PUK: &str = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+
+    fn wrapped(envelope: &[u8]) -> &[u8] {
+        &envelope[..WRAPPED_LEN]
+    }
+
+    #[test]
+    fn round_trip() {
+        let (prk, puk) = (PrivateKey::decode(PRK), PublicKey::decode(PUK));
+        let envelope = seal(&puk, b"aad", b"telemetry");
+        assert_eq!(envelope.len(), HEADER_LEN + 9 + 16);
+        assert_eq!(open(&prk, b"aad", &envelope).unwrap(), b"telemetry");
+        assert_eq!(open(&prk, b"other", &envelope), Err(EnvelopeError::Gcm(GcmError::TagMismatch)));
+        assert_eq!(open(&prk, b"aad", &envelope[..HEADER_LEN + 15]), Err(EnvelopeError::TooShort));
+
+        let mut tampered = envelope.clone();
+        tampered[80] ^= 1;
+        assert_eq!(open(&prk, b"aad", &tampered), Err(EnvelopeError::BadWrappedKey));
+        assert_eq!(EnvelopeError::BadWrappedKey.code(), Code::INVALID_DATA);
+    }
+
+    #[test]
+    fn cached_keys() {
+        let (prk, puk) = (PrivateKey::decode(PRK), PublicKey::decode(PUK));
+        let clock = FixedClock::new(1_700_000_000);
+        let mut sealer = Sealer::new(CachePolicy { capacity: 2, ttl: 60, max_messages: 3 });
+
+        // 同一数据密钥下nonce为0、1、2
+        let envelopes: Vec<Vec<u8>> = (0..4u8).map(|i| sealer.seal_with_clock(&puk, b"", &[i], &clock)).collect();
+        for (i, envelope) in envelopes.iter().enumerate() {
+            assert_eq!(open(&prk, b"", envelope).unwrap(), [i as u8]);
+        }
+        assert_eq!(wrapped(&envelopes[0]), wrapped(&envelopes[2]));
+        assert_eq!(envelopes[1][WRAPPED_LEN..HEADER_LEN], *Nonce12::from_counter(1).as_bytes());
+        // 第4条消息超出消息数上限
+        assert_ne!(wrapped(&envelopes[2]), wrapped(&envelopes[3]));
+
+        // 超过有效期
+        clock.advance(60);
+        let renewed = sealer.seal_with_clock(&puk, b"", b"", &clock);
+        assert_ne!(wrapped(&renewed), wrapped(&envelopes[3]));
+        assert_eq!(open(&prk, b"", &renewed).unwrap(), b"");
+    }
+
+    #[test]
+    fn eviction() {
+        let clock = FixedClock::new(0);
+        let keys: Vec<PublicKey> = (0..3).map(|_| crate::sm2::generate_keypair().1).map(|k| PublicKey::decode(&k)).collect();
+        let mut sealer = Sealer::new(CachePolicy { capacity: 2, ..CachePolicy::default() });
+
+        let first = sealer.seal_with_clock(&keys[0], b"", b"", &clock);
+        sealer.seal_with_clock(&keys[1], b"", b"", &clock);
+        // 访问keys[0]后keys[1]成为最久未使用的
+        assert_eq!(wrapped(&sealer.seal_with_clock(&keys[0], b"", b"", &clock)), wrapped(&first));
+        sealer.seal_with_clock(&keys[2], b"", b"", &clock);
+        assert_eq!(sealer.len(), 2);
+        assert_eq!(wrapped(&sealer.seal_with_clock(&keys[0], b"", b"", &clock)), wrapped(&first));
+
+        sealer.clear();
+        assert!(sealer.is_empty());
+        assert_ne!(wrapped(&sealer.seal_with_clock(&keys[0], b"", b"", &clock)), wrapped(&first));
+    }
+}
@@ -0,0 +1,122 @@
+//! GM/T 0016（智能密码钥匙接口，SKF）与GM/T 0018（密码设备应用接口，SDF）的返回码
+//!
+//! 替换厂商中间件时，调用方往往按数值返回码分支处理，[`ErrorCode`]将本库的错误映射到相应的返回码。
+
+/// GM/T 0016 SAR_* 返回码
+pub mod skf {
+    pub const SAR_OK: u32 = 0x00000000;
+    pub const SAR_FAIL: u32 = 0x0A000001;
+    pub const SAR_UNKNOWNERR: u32 = 0x0A000002;
+    pub const SAR_NOTSUPPORTYETERR: u32 = 0x0A000003;
+    pub const SAR_INVALIDPARAMERR: u32 = 0x0A000006;
+    pub const SAR_INDATALENERR: u32 = 0x0A000010;
+    pub const SAR_INDATAERR: u32 = 0x0A000011;
+    pub const SAR_HASHNOTEQUALERR: u32 = 0x0A00001A;
+    pub const SAR_KEYNOTFOUNTERR: u32 = 0x0A00001B;
+    pub const SAR_CERTNOTFOUNTERR: u32 = 0x0A00001C;
+    pub const SAR_DECRYPTPADERR: u32 = 0x0A00001E;
+    pub const SAR_BUFFER_TOO_SMALL: u32 = 0x0A000020;
+}
+
+/// GM/T 0018 SDR_* 返回码
+pub mod sdf {
+    pub const SDR_OK: u32 = 0x00000000;
+    pub const SDR_BASE: u32 = 0x01000000;
+    pub const SDR_UNKNOWERR: u32 = SDR_BASE + 0x01;
+    pub const SDR_NOTSUPPORT: u32 = SDR_BASE + 0x02;
+    pub const SDR_KEYNOTEXIST: u32 = SDR_BASE + 0x08;
+    pub const SDR_ALGNOTSUPPORT: u32 = SDR_BASE + 0x09;
+    pub const SDR_VERIFYERR: u32 = SDR_BASE + 0x0E;
+    pub const SDR_KEYERR: u32 = SDR_BASE + 0x15;
+    pub const SDR_ENCDATAERR: u32 = SDR_BASE + 0x16;
+    pub const SDR_MACERR: u32 = SDR_BASE + 0x19;
+    pub const SDR_NOBUFFER: u32 = SDR_BASE + 0x1C;
+    pub const SDR_INARGERR: u32 = SDR_BASE + 0x1D;
+}
+
+use sdf::*;
+use skf::*;
+
+/// 同一错误在两套接口中的返回码
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Code {
+    pub skf: u32,
+    pub sdf: u32,
+}
+
+impl Code {
+    /// 输入数据格式错误
+    pub const INVALID_DATA: Code = Code { skf: SAR_INDATAERR, sdf: SDR_INARGERR };
+    /// 输入数据长度错误
+    pub const INVALID_LENGTH: Code = Code { skf: SAR_INDATALENERR, sdf: SDR_INARGERR };
+    /// 算法或格式版本不支持
+    pub const NOT_SUPPORTED: Code = Code { skf: SAR_NOTSUPPORTYETERR, sdf: SDR_ALGNOTSUPPORT };
+    /// 密钥不合法
+    pub const INVALID_KEY: Code = Code { skf: SAR_INDATAERR, sdf: SDR_KEYERR };
+    /// 密钥不存在
+    pub const KEY_NOT_FOUND: Code = Code { skf: SAR_KEYNOTFOUNTERR, sdf: SDR_KEYNOTEXIST };
+    /// 证书不存在
+    pub const CERT_NOT_FOUND: Code = Code { skf: SAR_CERTNOTFOUNTERR, sdf: SDR_KEYNOTEXIST };
+    /// 签名验证失败
+    pub const VERIFY_FAILED: Code = Code { skf: SAR_FAIL, sdf: SDR_VERIFYERR };
+    /// 摘要不一致
+    pub const DIGEST_MISMATCH: Code = Code { skf: SAR_HASHNOTEQUALERR, sdf: SDR_VERIFYERR };
+    /// 消息认证码不一致
+    pub const MAC_MISMATCH: Code = Code { skf: SAR_HASHNOTEQUALERR, sdf: SDR_MACERR };
+    /// 密文长度错误
+    pub const INVALID_CIPHERTEXT: Code = Code { skf: SAR_INDATALENERR, sdf: SDR_ENCDATAERR };
+    /// 解密后的填充错误
+    pub const INVALID_PADDING: Code = Code { skf: SAR_DECRYPTPADERR, sdf: SDR_ENCDATAERR };
+    /// 缓冲区不足
+    pub const BUFFER_TOO_SMALL: Code = Code { skf: SAR_BUFFER_TOO_SMALL, sdf: SDR_NOBUFFER };
+    /// 其他错误
+    pub const FAIL: Code = Code { skf: SAR_FAIL, sdf: SDR_UNKNOWERR };
+}
+
+/// 错误对应的GM/T中间件返回码
+pub trait ErrorCode {
+    fn code(&self) -> Code;
+
+    /// GM/T 0016 SAR_* 返回码
+    fn skf_code(&self) -> u32 {
+        self.code().skf
+    }
+
+    /// GM/T 0018 SDR_* 返回码
+    fn sdf_code(&self) -> u32 {
+        self.code().sdf
+    }
+}
+
+#[cfg(all(test, feature = "sm2-sign", feature = "sm4"))]
+mod tests {
+    use super::*;
+    use crate::codec::HexError;
+    use crate::pdf::PdfError;
+    use crate::pkcs7::Pkcs7Error;
+    use crate::sm2::{HexKey, KeyError, PublicKey, VerifyError};
+    use crate::sm4::{CbcError, GcmError};
+
+    #[test]
+    fn constants() {
+        assert_eq!(SAR_INDATAERR, 0x0A000011);
+        assert_eq!(SDR_VERIFYERR, 0x0100000E);
+        assert_eq!(SDR_INARGERR, 0x0100001D);
+    }
+
+    #[test]
+    fn mapping() {
+        let e = HexError::InvalidChar { index: 0, char: 'g' };
+        assert_eq!(e.skf_code(), SAR_INDATAERR);
+        assert_eq!(e.sdf_code(), SDR_INARGERR);
+
+        assert_eq!(PublicKey::try_decode("04").unwrap_err().code(), Code::INVALID_LENGTH);
+        assert_eq!(KeyError::NotOnCurve.sdf_code(), SDR_KEYERR);
+        assert_eq!(VerifyError::Mismatch.sdf_code(), SDR_VERIFYERR);
+
+        assert_eq!(CbcError::InvalidPadding.skf_code(), SAR_DECRYPTPADERR);
+        assert_eq!(GcmError::TagMismatch.sdf_code(), SDR_MACERR);
+        // 包装的错误沿用内层错误的返回码
+        assert_eq!(PdfError::Pkcs7(Pkcs7Error::DigestMismatch).skf_code(), SAR_HASHNOTEQUALERR);
+    }
+}
@@ -0,0 +1,363 @@
+//! 固件升级包的签名清单与流式验证
+//!
+//! 本模块依赖std：SM2验签使用`BigUint`、`Vec`与`Rc`，尚不能用于no_std的bootloader。
+//! 验证器不做I/O，内存占用限于一个分块，可在带分配器的std环境中边接收边校验。
+
+use std::fmt::{Display, Formatter};
+
+use crate::sm2::ecc::{Crypto, Signature};
+use crate::sm2::key::{HexKey, KeyError, PublicKey};
+#[cfg(feature = "sm2-sign")]
+use crate::sm2::key::{KeyPair, PrivateKey};
+use crate::sm3;
+use crate::error_code::{Code, ErrorCode};
+
+const MAGIC: [u8; 4] = *b"YFWM";
+const VERSION: u8 = 0x01;
+
+/// magic(4) ‖ version(1) ‖ chunk size(4) ‖ image length(8) ‖ chunk count(4)
+const HEADER_LEN: usize = 21;
+
+/// 固件升级包签名：按`chunk_size`对固件分块计算SM3摘要，生成清单并使用SM2私钥签名，返回编码后的清单
+#[cfg(feature = "sm2-sign")]
+pub fn sign_image(private_key: &str, public_key: &str, image: &[u8], chunk_size: usize) -> Result<Vec<u8>, FirmwareError> {
+    let mut manifest = Manifest::build(image, chunk_size)?;
+    manifest.sign(private_key, public_key)?;
+    Ok(manifest.encode())
+}
+
+/// 固件升级包验证：验证清单签名，并逐块比对固件的SM3摘要
+pub fn verify_image(public_key: &str, manifest: &[u8], image: &[u8]) -> Result<(), FirmwareError> {
+    let manifest = Manifest::decode(manifest)?;
+    let mut verifier = manifest.verifier(public_key)?;
+    verifier.update(image)?;
+    verifier.finalize()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FirmwareError {
+    /// 清单数据长度不足
+    Truncated,
+    /// 清单标识错误
+    BadMagic,
+    /// 不支持的清单版本
+    UnsupportedVersion(u8),
+    /// 分块大小非法
+    InvalidChunkSize,
+    /// 分块数量与固件长度不一致
+    BadChunkCount(usize),
+    /// 清单签名缺失或验证失败
+    BadSignature,
+    /// 第`index`个分块的摘要不匹配
+    ChunkMismatch(usize),
+    /// 固件长度与清单不一致
+    LengthMismatch { expected: u64, actual: u64 },
+    /// 密钥格式不正确
+    InvalidKey(KeyError),
+}
+
+impl Display for FirmwareError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FirmwareError::Truncated => write!(f, "The manifest data is truncated."),
+            FirmwareError::BadMagic => write!(f, "The manifest magic is invalid."),
+            FirmwareError::UnsupportedVersion(v) => write!(f, "The manifest version {} is not supported.", v),
+            FirmwareError::InvalidChunkSize => write!(f, "The chunk size must be in [1, 2^32 - 1]."),
+            FirmwareError::BadChunkCount(n) => write!(f, "The chunk count {} does not match the image length.", n),
+            FirmwareError::BadSignature => write!(f, "The manifest signature validation failed."),
+            FirmwareError::ChunkMismatch(i) => write!(f, "The hash of chunk {} does not match the manifest.", i),
+            FirmwareError::LengthMismatch { expected, actual } => {
+                write!(f, "The image length must be {}, got {}.", expected, actual)
+            }
+            FirmwareError::InvalidKey(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for FirmwareError {}
+
+impl ErrorCode for FirmwareError {
+    fn code(&self) -> Code {
+        match self {
+            FirmwareError::Truncated | FirmwareError::BadChunkCount(_) => Code::INVALID_LENGTH,
+            FirmwareError::LengthMismatch { .. } => Code::INVALID_LENGTH,
+            FirmwareError::BadMagic | FirmwareError::InvalidChunkSize => Code::INVALID_DATA,
+            FirmwareError::UnsupportedVersion(_) => Code::NOT_SUPPORTED,
+            FirmwareError::BadSignature => Code::VERIFY_FAILED,
+            FirmwareError::ChunkMismatch(_) => Code::DIGEST_MISMATCH,
+            FirmwareError::InvalidKey(e) => e.code(),
+        }
+    }
+}
+
+/// 固件清单
+///
+/// 编码格式（大端序）：
+/// ```text
+/// magic "YFWM"(4) ‖ version(1) ‖ chunk size(4) ‖ image length(8) ‖ chunk count(4) ‖
+/// SM3(chunk 0) ‖ ... ‖ SM3(chunk n-1) ‖ signature length(2) ‖ signature(DER)
+/// ```
+/// 签名覆盖签名长度字段之前的全部数据。
+#[derive(Debug, Clone)]
+pub struct Manifest {
+    chunk_size: u32,
+    image_len: u64,
+    hashes: Vec<[u8; 32]>,
+    signature: Vec<u8>,
+}
+
+impl Manifest {
+    /// 构造未签名的清单，最后一个分块可以不足`chunk_size`，`chunk_size`须在\[1, 2^32 - 1]内
+    pub fn build(image: &[u8], chunk_size: usize) -> Result<Self, FirmwareError> {
+        if chunk_size == 0 || chunk_size > u32::MAX as usize {
+            return Err(FirmwareError::InvalidChunkSize);
+        }
+        Ok(Manifest {
+            chunk_size: chunk_size as u32,
+            image_len: image.len() as u64,
+            hashes: image.chunks(chunk_size).map(sm3::hash).collect(),
+            signature: vec![],
+        })
+    }
+
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size as usize
+    }
+
+    pub fn image_len(&self) -> u64 {
+        self.image_len
+    }
+
+    pub fn hashes(&self) -> &[[u8; 32]] {
+        &self.hashes
+    }
+
+    /// 使用SM2私钥对清单签名，密钥格式不正确时返回[`FirmwareError::InvalidKey`]
+    #[cfg(feature = "sm2-sign")]
+    pub fn sign(&mut self, private_key: &str, public_key: &str) -> Result<(), FirmwareError> {
+        let private_key = PrivateKey::try_decode(private_key).map_err(FirmwareError::InvalidKey)?;
+        let public_key = PublicKey::try_decode(public_key).map_err(FirmwareError::InvalidKey)?;
+        let signer = Crypto::default().signer(KeyPair::new(private_key, public_key));
+        self.signature = signer.sign_bytes(&self.body()).encode();
+        Ok(())
+    }
+
+    /// 验证清单签名，成功后返回用于流式校验固件的验证器
+    pub fn verifier(&self, public_key: &str) -> Result<ManifestVerifier, FirmwareError> {
+        let public_key = PublicKey::try_decode(public_key).map_err(FirmwareError::InvalidKey)?;
+        let signature = Signature::try_decode(&self.signature).ok_or(FirmwareError::BadSignature)?;
+        let verifier = Crypto::default().verifier(public_key);
+        if !verifier.verify_bytes(&self.body(), &signature) {
+            return Err(FirmwareError::BadSignature);
+        }
+        Ok(ManifestVerifier {
+            manifest: self.clone(),
+            buffer: Vec::with_capacity(self.chunk_size as usize),
+            index: 0,
+            received: 0,
+        })
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = self.body();
+        out.extend_from_slice(&(self.signature.len() as u16).to_be_bytes());
+        out.extend_from_slice(&self.signature);
+        out
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self, FirmwareError> {
+        if data.len() < HEADER_LEN {
+            return Err(FirmwareError::Truncated);
+        }
+        if data[..4] != MAGIC {
+            return Err(FirmwareError::BadMagic);
+        }
+        if data[4] != VERSION {
+            return Err(FirmwareError::UnsupportedVersion(data[4]));
+        }
+
+        let chunk_size = u32::from_be_bytes(data[5..9].try_into().unwrap());
+        let image_len = u64::from_be_bytes(data[9..17].try_into().unwrap());
+        let count = u32::from_be_bytes(data[17..21].try_into().unwrap()) as usize;
+        if chunk_size == 0 {
+            return Err(FirmwareError::InvalidChunkSize);
+        }
+        if image_len / chunk_size as u64 + u64::from(image_len % chunk_size as u64 != 0) != count as u64 {
+            return Err(FirmwareError::BadChunkCount(count));
+        }
+
+        // 32位平台上分块数量乘以32可能溢出，溢出时数据必然不足
+        let offset = count.checked_mul(32).and_then(|n| n.checked_add(HEADER_LEN)).ok_or(FirmwareError::Truncated)?;
+        if data.len() < offset.saturating_add(2) {
+            return Err(FirmwareError::Truncated);
+        }
+        let hashes = data[HEADER_LEN..offset]
+            .chunks(32)
+            .map(|e| e.try_into().unwrap())
+            .collect();
+
+        let len = u16::from_be_bytes([data[offset], data[offset + 1]]) as usize;
+        if data.len() != offset + 2 + len {
+            return Err(FirmwareError::Truncated);
+        }
+
+        Ok(Manifest { chunk_size, image_len, hashes, signature: data[offset + 2..].to_vec() })
+    }
+
+    /// 参与签名的清单数据
+    fn body(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(HEADER_LEN + self.hashes.len() * 32);
+        out.extend_from_slice(&MAGIC);
+        out.push(VERSION);
+        out.extend_from_slice(&self.chunk_size.to_be_bytes());
+        out.extend_from_slice(&self.image_len.to_be_bytes());
+        out.extend_from_slice(&(self.hashes.len() as u32).to_be_bytes());
+        self.hashes.iter().for_each(|h| out.extend_from_slice(h));
+        out
+    }
+}
+
+/// 固件流式验证器
+///
+/// 固件数据可以按任意长度分段传入，验证器最多缓存一个分块，分块凑满后立即校验摘要，
+/// 因此可以边接收边写入Flash，在第一个被篡改的分块处中止升级。
+pub struct ManifestVerifier {
+    manifest: Manifest,
+    buffer: Vec<u8>,
+    index: usize,
+    received: u64,
+}
+
+impl ManifestVerifier {
+    pub fn update(&mut self, data: &[u8]) -> Result<(), FirmwareError> {
+        let chunk_size = self.manifest.chunk_size as usize;
+        let mut data = data;
+        while !data.is_empty() {
+            let n = (chunk_size - self.buffer.len()).min(data.len());
+            self.buffer.extend_from_slice(&data[..n]);
+            self.received += n as u64;
+            data = &data[n..];
+
+            if self.received > self.manifest.image_len {
+                return Err(FirmwareError::LengthMismatch {
+                    expected: self.manifest.image_len,
+                    actual: self.received,
+                });
+            }
+            if self.buffer.len() == chunk_size {
+                self.check()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 校验剩余的分块以及固件总长度
+    pub fn finalize(mut self) -> Result<(), FirmwareError> {
+        if self.received != self.manifest.image_len {
+            return Err(FirmwareError::LengthMismatch {
+                expected: self.manifest.image_len,
+                actual: self.received,
+            });
+        }
+        if !self.buffer.is_empty() {
+            self.check()?;
+        }
+        Ok(())
+    }
+
+    fn check(&mut self) -> Result<(), FirmwareError> {
+        let hash = sm3::hash(&self.buffer);
+        if self.manifest.hashes.get(self.index) != Some(&hash) {
+            return Err(FirmwareError::ChunkMismatch(self.index));
+        }
+        self.buffer.clear();
+        self.index += 1;
+        Ok(())
+    }
+}
+
+
+#[cfg(all(test, feature = "sm2-sign", feature = "sm2-encrypt"))]
+mod tests {
+    use super::*;
+
+    const PRK: &str = "6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e";
+    const PUK: &str = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+
+    fn image() -> Vec<u8> {
+        (0..10000u32).map(|i| (i * 7 + 3) as u8).collect()
+    }
+
+    #[test]
+    fn sign_verify() {
+        let image = image();
+        let manifest = sign_image(PRK, PUK, &image, 1024).unwrap();
+        assert_eq!(verify_image(PUK, &manifest, &image), Ok(()));
+
+        let decoded = Manifest::decode(&manifest).unwrap();
+        assert_eq!(decoded.hashes().len(), 10);
+        assert_eq!(decoded.image_len(), 10000);
+        assert_eq!(decoded.encode(), manifest);
+    }
+
+    #[test]
+    fn streaming() {
+        let image = image();
+        let manifest = Manifest::decode(&sign_image(PRK, PUK, &image, 512).unwrap()).unwrap();
+        let mut verifier = manifest.verifier(PUK).unwrap();
+        for piece in image.chunks(333) {
+            verifier.update(piece).unwrap();
+        }
+        assert_eq!(verifier.finalize(), Ok(()));
+    }
+
+    #[test]
+    fn tampered() {
+        let image = image();
+        let manifest = sign_image(PRK, PUK, &image, 1024).unwrap();
+
+        let mut bad = image.clone();
+        bad[5000] ^= 0x01;
+        assert_eq!(verify_image(PUK, &manifest, &bad), Err(FirmwareError::ChunkMismatch(4)));
+
+        assert_eq!(
+            verify_image(PUK, &manifest, &image[..9000]),
+            Err(FirmwareError::LengthMismatch { expected: 10000, actual: 9000 })
+        );
+
+        let mut bad = manifest.clone();
+        bad[HEADER_LEN] ^= 0x01;
+        assert_eq!(verify_image(PUK, &bad, &image), Err(FirmwareError::BadSignature));
+
+        assert_eq!(Manifest::decode(&manifest[..10]).unwrap_err(), FirmwareError::Truncated);
+    }
+
+    #[test]
+    fn invalid_key() {
+        let image = image();
+        assert!(matches!(sign_image(&PRK[2..], PUK, &image, 1024), Err(FirmwareError::InvalidKey(_))));
+        assert!(matches!(sign_image(PRK, "zz", &image, 1024), Err(FirmwareError::InvalidKey(_))));
+
+        let manifest = sign_image(PRK, PUK, &image, 1024).unwrap();
+        assert!(matches!(verify_image(&PUK[2..], &manifest, &image), Err(FirmwareError::InvalidKey(_))));
+    }
+
+    #[test]
+    fn invalid_chunk_size() {
+        let image = image();
+        assert_eq!(sign_image(PRK, PUK, &image, 0), Err(FirmwareError::InvalidChunkSize));
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(Manifest::build(&image, u32::MAX as usize + 1).unwrap_err(), FirmwareError::InvalidChunkSize);
+    }
+
+    #[test]
+    fn huge_chunk_count() {
+        // 1字节分块、2^32-1个分块，清单中没有对应的摘要
+        let mut header = MAGIC.to_vec();
+        header.push(VERSION);
+        header.extend_from_slice(&1u32.to_be_bytes());
+        header.extend_from_slice(&u64::from(u32::MAX).to_be_bytes());
+        header.extend_from_slice(&u32::MAX.to_be_bytes());
+        assert_eq!(Manifest::decode(&header).unwrap_err(), FirmwareError::Truncated);
+    }
+}
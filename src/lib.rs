@@ -1,8 +1,58 @@
+/// 前置条件不满足时panic；启用`no-fmt`时不带消息，消息字符串与格式化代码不进入二进制。
+/// 测试构建保留消息，以便`should_panic`核对
+#[cfg(any(feature = "sm3", feature = "sm4"))]
+macro_rules! fail {
+    ($message:literal $(, $arg:expr)*) => {{
+        #[cfg(any(test, not(feature = "no-fmt")))]
+        panic!($message $(, $arg)*);
+        #[cfg(all(not(test), feature = "no-fmt"))]
+        {
+            $(let _ = &$arg;)*
+            panic!();
+        }
+    }};
+}
+
+#[cfg(feature = "sm2-verify")]
 pub mod sm2;
+#[cfg(feature = "sm3")]
 pub mod sm3;
+#[cfg(feature = "sm4")]
 pub mod sm4;
-
-#[cfg(test)]
+pub mod clock;
+pub mod codec;
+pub mod error_code;
+pub mod prelude;
+#[cfg(feature = "sm2-verify")]
+pub mod firmware;
+#[cfg(feature = "sm2-verify")]
+pub mod signed;
+#[cfg(feature = "sm2-sign")]
+pub mod cert;
+#[cfg(feature = "sm2-sign")]
+pub mod seal;
+#[cfg(feature = "sm2-sign")]
+pub mod pkcs7;
+#[cfg(feature = "sm2-sign")]
+pub mod pdf;
+#[cfg(feature = "sm2-sign")]
+pub mod tsp;
+#[cfg(feature = "sm3")]
+pub mod token;
+#[cfg(all(feature = "sm2-verify", feature = "sm4"))]
+pub mod suite;
+#[cfg(all(feature = "sm2-encrypt", feature = "sm4"))]
+pub mod envelope;
+mod env;
+#[cfg(any(feature = "sm3", feature = "sm4"))]
+mod endian;
+#[cfg(any(feature = "sm3", feature = "sm4"))]
+#[cfg_attr(not(feature = "sm2-verify"), allow(dead_code))]
+mod ct;
+#[cfg(all(test, feature = "sm2-verify", feature = "sm4"))]
+mod wycheproof;
+
+#[cfg(all(test, feature = "sm2-sign", feature = "sm2-encrypt", feature = "sm4"))]
 mod tests {
     use crate::{sm2, sm3, sm4};
 
@@ -38,7 +88,10 @@ mod tests {
         let s = sm2::sign(prk, puk, text);
         let f = sm2::verify(puk, text, &s);
 
-        assert_eq!(f, true);
+        assert!(f);
+        assert!(!sm2::verify(&puk[2..], text, &s));
+        assert!(!sm2::verify(puk, text, "zz"));
+        assert!(!sm2::verify(puk, text, &s[2..]));
     }
 
     #[test]
@@ -136,4 +189,27 @@ mod tests {
         let text = crypto.decrypt(cipher);
         assert_eq!(plain, text);
     }
-}
\ No newline at end of file
+
+    /// 在仅有16KiB栈的线程中运行SM3、SM4的栈受限接口，栈溢出会使测试进程异常退出
+    #[test]
+    fn bounded_stack() {
+        let worker = std::thread::Builder::new().stack_size(16 * 1024).spawn(|| {
+            let mut hasher = sm3::Hasher::new();
+            for _ in 0..64 {
+                hasher.update(&[0x61; 97]);
+            }
+            let digest = hasher.finalize();
+
+            let key = [0x01; 16];
+            let mut block = [0u8; 16];
+            block.copy_from_slice(&digest[..16]);
+            sm4::encrypt_block(&key, &mut block);
+            sm4::decrypt_block(&key, &mut block);
+            (digest, block)
+        }).unwrap();
+
+        let (digest, block) = worker.join().unwrap();
+        assert_eq!(digest, sm3::hash(&[0x61; 97 * 64]));
+        assert_eq!(block, digest[..16]);
+    }
+}
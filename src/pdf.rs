@@ -0,0 +1,233 @@
+use std::fmt::{Display, Formatter};
+
+use crate::cert::Certificate;
+use crate::pkcs7::{Pkcs7Error, SignedData};
+use crate::sm2::KeyPair;
+use crate::sm3;
+use crate::error_code::{Code, ErrorCode};
+
+/// 对已预留签名域的PDF文档签名：计算ByteRange覆盖部分的SM3摘要，
+/// 生成GM/T 0010签名数据并以十六进制写入/Contents占位符
+pub fn sign(document: &mut [u8], keypair: &KeyPair, cert: &Certificate) -> Result<(), PdfError> {
+    let range = ByteRange::find(document).ok_or(PdfError::ByteRangeNotFound)?;
+    let digest = range.digest(document)?;
+    let signed_data = SignedData::sign_digest(keypair, cert, &digest).encode();
+    range.fill_contents(document, &signed_data)
+}
+
+/// 验证PDF文档最后一个签名域，要求ByteRange覆盖签名域以外的全部内容，成功时返回签名者证书
+pub fn verify(document: &[u8]) -> Result<Certificate, PdfError> {
+    let range = ByteRange::find(document).ok_or(PdfError::ByteRangeNotFound)?;
+    if !range.covers(document) {
+        return Err(PdfError::InvalidByteRange);
+    }
+    let signed_data = SignedData::decode(&range.contents(document)?)?;
+    Ok(signed_data.verify_digest(&range.digest(document)?)?.clone())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PdfError {
+    /// 文档中没有/ByteRange
+    ByteRangeNotFound,
+    /// ByteRange越界，或两段之间不是/Contents的十六进制字符串
+    InvalidByteRange,
+    /// /Contents占位符长度不足
+    ContentsTooSmall { required: usize, available: usize },
+    /// /Contents不是合法的十六进制字符串
+    MalformedContents,
+    Pkcs7(Pkcs7Error),
+}
+
+impl Display for PdfError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PdfError::ByteRangeNotFound => write!(f, "The /ByteRange entry is not found."),
+            PdfError::InvalidByteRange => write!(f, "The /ByteRange entry is invalid."),
+            PdfError::ContentsTooSmall { required, available } => {
+                write!(f, "The /Contents placeholder needs {} bytes, got {}.", required, available)
+            }
+            PdfError::MalformedContents => write!(f, "The /Contents entry is not a valid hex string."),
+            PdfError::Pkcs7(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for PdfError {}
+
+impl ErrorCode for PdfError {
+    fn code(&self) -> Code {
+        match self {
+            PdfError::ByteRangeNotFound | PdfError::InvalidByteRange | PdfError::MalformedContents => Code::INVALID_DATA,
+            PdfError::ContentsTooSmall { .. } => Code::BUFFER_TOO_SMALL,
+            PdfError::Pkcs7(e) => e.code(),
+        }
+    }
+}
+
+impl From<Pkcs7Error> for PdfError {
+    fn from(e: Pkcs7Error) -> Self {
+        PdfError::Pkcs7(e)
+    }
+}
+
+/// PDF签名域的`/ByteRange [offset1 length1 offset2 length2]`
+///
+/// 两段之间的空隙即`/Contents <...>`，签名覆盖空隙以外的全部字节。
+/// OFD等其他格式同样可以用ByteRange描述待签名的片段。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange(pub [usize; 4]);
+
+impl ByteRange {
+    /// 查找文档中最后一个/ByteRange，即最近一次增量更新的签名域
+    pub fn find(document: &[u8]) -> Option<Self> {
+        const KEY: &[u8] = b"/ByteRange";
+        let start = document.windows(KEY.len()).rposition(|w| w == KEY)? + KEY.len();
+        let mut rest = skip_whitespace(&document[start..]);
+        if rest.first() != Some(&b'[') {
+            return None;
+        }
+        rest = &rest[1..];
+
+        let mut range = [0; 4];
+        for e in range.iter_mut() {
+            rest = skip_whitespace(rest);
+            let len = rest.iter().take_while(|c| c.is_ascii_digit()).count();
+            *e = std::str::from_utf8(&rest[..len]).ok()?.parse().ok()?;
+            rest = &rest[len..];
+        }
+        if skip_whitespace(rest).first() != Some(&b']') {
+            return None;
+        }
+        Some(ByteRange(range))
+    }
+
+    /// ByteRange是否从文档开头覆盖到文档结尾
+    pub fn covers(&self, document: &[u8]) -> bool {
+        let [offset1, _, offset2, length2] = self.0;
+        offset1 == 0 && offset2.checked_add(length2) == Some(document.len())
+    }
+
+    /// 计算两段数据拼接后的SM3摘要
+    pub fn digest(&self, document: &[u8]) -> Result<[u8; 32], PdfError> {
+        let (first, second) = self.segments(document)?;
+        Ok(sm3::hash(&[first, second].concat()))
+    }
+
+    /// 解码/Contents中的签名数据，去除占位符末尾补齐的0
+    pub fn contents(&self, document: &[u8]) -> Result<Vec<u8>, PdfError> {
+        let hex = self.placeholder(document)?;
+        let hex: Vec<u8> = hex.iter().copied().filter(|c| !c.is_ascii_whitespace()).collect();
+        let bytes = hex::decode(hex).map_err(|_| PdfError::MalformedContents)?;
+        let len = der_len(&bytes).ok_or(PdfError::MalformedContents)?;
+        Ok(bytes[..len].to_vec())
+    }
+
+    /// 将签名数据以十六进制写入/Contents占位符，不足部分补0
+    pub fn fill_contents(&self, document: &mut [u8], signed_data: &[u8]) -> Result<(), PdfError> {
+        let [offset1, length1, _, _] = self.0;
+        let available = self.placeholder(document)?.len();
+        let encoded = hex::encode_upper(signed_data);
+        if encoded.len() > available {
+            return Err(PdfError::ContentsTooSmall { required: encoded.len() / 2, available: available / 2 });
+        }
+
+        let start = offset1 + length1 + 1;
+        document[start..start + available].fill(b'0');
+        document[start..start + encoded.len()].copy_from_slice(encoded.as_bytes());
+        Ok(())
+    }
+
+    fn segments<'a>(&self, document: &'a [u8]) -> Result<(&'a [u8], &'a [u8]), PdfError> {
+        let [offset1, length1, offset2, length2] = self.0;
+        let end1 = offset1.checked_add(length1).ok_or(PdfError::InvalidByteRange)?;
+        let end2 = offset2.checked_add(length2).ok_or(PdfError::InvalidByteRange)?;
+        if end1 > offset2 || end2 > document.len() {
+            return Err(PdfError::InvalidByteRange);
+        }
+        Ok((&document[offset1..end1], &document[offset2..end2]))
+    }
+
+    /// 两段之间`<`与`>`之内的十六进制字符
+    fn placeholder<'a>(&self, document: &'a [u8]) -> Result<&'a [u8], PdfError> {
+        let [offset1, length1, offset2, _] = self.0;
+        self.segments(document)?;
+        let gap = &document[offset1 + length1..offset2];
+        if gap.len() < 2 || gap[0] != b'<' || gap[gap.len() - 1] != b'>' {
+            return Err(PdfError::InvalidByteRange);
+        }
+        Ok(&gap[1..gap.len() - 1])
+    }
+}
+
+fn skip_whitespace(data: &[u8]) -> &[u8] {
+    let n = data.iter().take_while(|c| c.is_ascii_whitespace()).count();
+    &data[n..]
+}
+
+/// DER编码的总长度（标签、长度与内容）
+fn der_len(data: &[u8]) -> Option<usize> {
+    let first = *data.get(1)? as usize;
+    let (header, len) = if first < 0x80 {
+        (2, first)
+    } else {
+        let n = first & 0x7F;
+        if n == 0 || n > 4 {
+            return None;
+        }
+        let len = data.get(2..2 + n)?.iter().fold(0, |acc, b| (acc << 8) | *b as usize);
+        (2 + n, len)
+    };
+    let total = header + len;
+    if total > data.len() {
+        return None;
+    }
+    Some(total)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::cert::{generate_self_signed, Subject, Validity};
+    use crate::sm2::{HexKey, PrivateKey, PublicKey};
+
+    use super::*;
+
+    const PRK: &str = "6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e";
+    const PUK: &str = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+
+    /// 构造带签名域占位符的最小PDF片段
+    fn document() -> Vec<u8> {
+        let head = b"%PDF-1.7\n1 0 obj\n<</Type/Sig/Filter/Adobe.PPKLite/SubFilter/adbe.pkcs7.detached/Contents ".to_vec();
+        let placeholder = format!("<{}>", "0".repeat(8192));
+        let tail = b"/ByteRange [0 0000000000 0000000000 0000000000]>>\nendobj\n%%EOF\n".to_vec();
+
+        let offset2 = head.len() + placeholder.len();
+        let length2 = tail.len();
+        let range = format!("[0 {:010} {:010} {:010}]", head.len(), offset2, length2);
+        let tail = String::from_utf8(tail).unwrap().replace("[0 0000000000 0000000000 0000000000]", &range);
+
+        [head, placeholder.into_bytes(), tail.into_bytes()].concat()
+    }
+
+    #[test]
+    fn main() {
+        let keypair = KeyPair::new(PrivateKey::decode(PRK), PublicKey::decode(PUK));
+        let cert = generate_self_signed(&keypair, &Subject::new("signer"), &Validity::days(365));
+
+        let mut document = document();
+        let range = ByteRange::find(&document).unwrap();
+        assert!(range.covers(&document));
+
+        sign(&mut document, &keypair, &cert).unwrap();
+        assert_eq!(verify(&document).unwrap().to_der(), cert.to_der());
+        assert_eq!(range.digest(&document).unwrap(), range.digest(&self::document()).unwrap());
+
+        let mut tampered = document.clone();
+        tampered[3] = b'X';
+        assert_eq!(verify(&tampered).unwrap_err(), PdfError::Pkcs7(Pkcs7Error::DigestMismatch));
+
+        let mut appended = document.clone();
+        appended.extend_from_slice(b"\n");
+        assert_eq!(verify(&appended).unwrap_err(), PdfError::InvalidByteRange);
+    }
+}
@@ -0,0 +1,468 @@
+use std::fmt::{Display, Formatter};
+
+use yasna::models::{ObjectIdentifier, TaggedDerValue};
+use yasna::tags::TAG_SET;
+use yasna::{DERWriter, PCBit, Tag};
+
+use crate::cert::{oid, read_time, write_time, CertError, Certificate};
+use crate::sm2::ecc::{Crypto, Signature};
+use crate::sm2::KeyPair;
+use crate::{env, sm3};
+use crate::error_code::{Code, ErrorCode};
+
+/// 对数据签名，生成不含原文的GM/T 0010签名数据（DER）
+pub fn sign_detached(keypair: &KeyPair, cert: &Certificate, data: &[u8]) -> Vec<u8> {
+    SignedData::sign(keypair, cert, data, true).encode()
+}
+
+/// 验证不含原文的GM/T 0010签名数据，成功时返回签名者证书
+pub fn verify_detached(signed_data: &[u8], data: &[u8]) -> Result<Certificate, Pkcs7Error> {
+    let signed_data = SignedData::decode(signed_data)?;
+    signed_data.verify_detached(data).cloned()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pkcs7Error {
+    /// 不是合法的DER编码或缺少必要字段
+    Malformed,
+    /// 摘要算法不是SM3或签名算法不是SM2
+    UnsupportedAlgorithm,
+    /// 证书解析失败
+    Certificate(CertError),
+    /// 签名数据中没有与签名者信息匹配的证书
+    SignerNotFound,
+    /// 签名数据既不含原文，也没有消息摘要属性
+    MissingContent,
+    /// 原文摘要与消息摘要属性不一致
+    DigestMismatch,
+    /// 签名验证失败
+    BadSignature,
+}
+
+impl Display for Pkcs7Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pkcs7Error::Malformed => write!(f, "The signed data is malformed."),
+            Pkcs7Error::UnsupportedAlgorithm => write!(f, "The digest or signature algorithm is not SM3/SM2."),
+            Pkcs7Error::Certificate(e) => write!(f, "{}", e),
+            Pkcs7Error::SignerNotFound => write!(f, "The signer certificate is not found."),
+            Pkcs7Error::MissingContent => write!(f, "The signed content is missing."),
+            Pkcs7Error::DigestMismatch => write!(f, "The content digest does not match the signed attributes."),
+            Pkcs7Error::BadSignature => write!(f, "The signature validation failed."),
+        }
+    }
+}
+
+impl std::error::Error for Pkcs7Error {}
+
+impl ErrorCode for Pkcs7Error {
+    fn code(&self) -> Code {
+        match self {
+            Pkcs7Error::Malformed | Pkcs7Error::MissingContent => Code::INVALID_DATA,
+            Pkcs7Error::UnsupportedAlgorithm => Code::NOT_SUPPORTED,
+            Pkcs7Error::Certificate(e) => e.code(),
+            Pkcs7Error::SignerNotFound => Code::CERT_NOT_FOUND,
+            Pkcs7Error::DigestMismatch => Code::DIGEST_MISMATCH,
+            Pkcs7Error::BadSignature => Code::VERIFY_FAILED,
+        }
+    }
+}
+
+impl From<CertError> for Pkcs7Error {
+    fn from(e: CertError) -> Self {
+        Pkcs7Error::Certificate(e)
+    }
+}
+
+/// GM/T 0010 签名数据，摘要算法为SM3，签名算法为SM2，仅支持单个签名者
+///
+/// 签名者信息总是包含签名属性（内容类型、签名时间、消息摘要），
+/// 因此对于ByteRange这类只能得到摘要的场景，也可以直接对摘要签名和验证。
+#[derive(Debug, Clone)]
+pub struct SignedData {
+    content_type: ObjectIdentifier,
+    content: Option<Vec<u8>>,
+    certificates: Vec<Certificate>,
+    signer: SignerInfo,
+}
+
+#[derive(Debug, Clone)]
+struct SignerInfo {
+    issuer: Vec<u8>,
+    serial: Vec<u8>,
+    /// 签名属性SET OF中的各个元素（DER），保持原始顺序
+    attributes: Option<Vec<Vec<u8>>>,
+    message_digest: Option<Vec<u8>>,
+    signing_time: Option<i64>,
+    signature: Vec<u8>,
+}
+
+impl SignedData {
+    /// 对数据签名，`detached`为true时签名数据中不包含原文
+    pub fn sign(keypair: &KeyPair, cert: &Certificate, data: &[u8], detached: bool) -> Self {
+        let content = if detached { None } else { Some(data) };
+        Self::sign_content(keypair, cert, oid::GM_DATA, content, &sm3::hash(data))
+    }
+
+    /// 对预先计算的SM3摘要签名，生成不含原文的签名数据
+    pub fn sign_digest(keypair: &KeyPair, cert: &Certificate, digest: &[u8; 32]) -> Self {
+        Self::sign_content(keypair, cert, oid::GM_DATA, None, digest)
+    }
+
+    pub(crate) fn sign_content(
+        keypair: &KeyPair,
+        cert: &Certificate,
+        content_type: &[u64],
+        content: Option<&[u8]>,
+        digest: &[u8; 32],
+    ) -> Self {
+        let signing_time = env::now();
+        let attributes = vec![
+            write_attribute(oid::CONTENT_TYPE, |writer| writer.write_oid(&oid::of(content_type))),
+            write_attribute(oid::SIGNING_TIME, |writer| write_time(writer, signing_time)),
+            write_attribute(oid::MESSAGE_DIGEST, |writer| writer.write_bytes(digest)),
+        ];
+        let signer = Crypto::default().signer(keypair.clone());
+        let signature = signer.sign_bytes(&attribute_set(&attributes)).encode();
+
+        SignedData {
+            content_type: oid::of(content_type),
+            content: content.map(|e| e.to_vec()),
+            certificates: vec![cert.clone()],
+            signer: SignerInfo {
+                issuer: cert.issuer_der().to_vec(),
+                serial: cert.serial_der().to_vec(),
+                attributes: Some(attributes),
+                message_digest: Some(digest.to_vec()),
+                signing_time: Some(signing_time),
+                signature,
+            },
+        }
+    }
+
+    /// 签名数据中包含的原文
+    pub fn content(&self) -> Option<&[u8]> {
+        self.content.as_deref()
+    }
+
+    pub fn certificates(&self) -> &[Certificate] {
+        &self.certificates
+    }
+
+    /// 与签名者信息中签发者和序列号匹配的证书
+    pub fn signer_certificate(&self) -> Option<&Certificate> {
+        let signer = &self.signer;
+        self.certificates
+            .iter()
+            .find(|c| c.issuer_der() == signer.issuer && c.serial_der() == signer.serial)
+    }
+
+    pub(crate) fn content_type(&self) -> &ObjectIdentifier {
+        &self.content_type
+    }
+
+    /// 签名时间属性，UNIX时间戳（秒）
+    pub fn signing_time(&self) -> Option<i64> {
+        self.signer.signing_time
+    }
+
+    /// 验证包含原文的签名数据，成功时返回签名者证书
+    pub fn verify(&self) -> Result<&Certificate, Pkcs7Error> {
+        let content = self.content.as_deref().ok_or(Pkcs7Error::MissingContent)?;
+        self.verify_signer(Some(content), &sm3::hash(content))
+    }
+
+    /// 使用指定证书验证包含原文的签名数据，适用于签名数据中不携带证书的情况
+    pub fn verify_with_certificate(&self, cert: &Certificate) -> Result<(), Pkcs7Error> {
+        let content = self.content.as_deref().ok_or(Pkcs7Error::MissingContent)?;
+        self.verify_with(cert, Some(content), &sm3::hash(content))
+    }
+
+    /// 使用外部原文验证签名数据，成功时返回签名者证书
+    pub fn verify_detached(&self, data: &[u8]) -> Result<&Certificate, Pkcs7Error> {
+        self.verify_signer(Some(data), &sm3::hash(data))
+    }
+
+    /// 使用原文的SM3摘要验证签名数据，要求签名者信息包含消息摘要属性
+    pub fn verify_digest(&self, digest: &[u8; 32]) -> Result<&Certificate, Pkcs7Error> {
+        self.verify_signer(None, digest)
+    }
+
+    /// ContentInfo ::= SEQUENCE { contentType OBJECT IDENTIFIER, content \[0] EXPLICIT SignedData }
+    pub fn encode(&self) -> Vec<u8> {
+        yasna::construct_der(|writer| {
+            writer.write_sequence(|writer| {
+                writer.next().write_oid(&oid::of(oid::GM_SIGNED_DATA));
+                writer.next().write_tagged(Tag::context(0), |writer| self.write_signed_data(writer));
+            })
+        })
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self, Pkcs7Error> {
+        let (content_type, content, certificates, signers) = yasna::parse_der(data, |reader| {
+            reader.read_sequence(|reader| {
+                let content_type = reader.next().read_oid()?;
+                if content_type != oid::of(oid::GM_SIGNED_DATA) {
+                    return Err(yasna::ASN1Error::new(yasna::ASN1ErrorKind::Invalid));
+                }
+                reader.next().read_tagged(Tag::context(0), |reader| {
+                    reader.read_sequence(|reader| {
+                        reader.next().read_u8()?;
+                        let mut digest_algorithms = vec![];
+                        reader.next().read_set_of(|reader| {
+                            digest_algorithms.push(read_algorithm(reader)?);
+                            Ok(())
+                        })?;
+                        let (content_type, content) = reader.next().read_sequence(|reader| {
+                            let content_type = reader.next().read_oid()?;
+                            let content = reader.read_optional(|reader| {
+                                reader.read_tagged(Tag::context(0), |reader| reader.read_bytes())
+                            })?;
+                            Ok((content_type, content))
+                        })?;
+                        let mut certificates = vec![];
+                        reader.read_optional(|reader| {
+                            reader.read_tagged_implicit(Tag::context(0), |reader| {
+                                reader.read_set_of(|reader| {
+                                    certificates.push(reader.read_der()?);
+                                    Ok(())
+                                })
+                            })
+                        })?;
+                        reader.read_optional(|reader| {
+                            reader.read_tagged_implicit(Tag::context(1), |reader| {
+                                reader.read_set_of(|reader| reader.read_der().map(|_| ()))
+                            })
+                        })?;
+                        let mut signers = vec![];
+                        reader.next().read_set_of(|reader| {
+                            signers.push(read_signer_info(reader)?);
+                            Ok(())
+                        })?;
+                        if digest_algorithms.iter().any(|a| a != &oid::of(oid::SM3)) {
+                            return Err(yasna::ASN1Error::new(yasna::ASN1ErrorKind::Invalid));
+                        }
+                        Ok((content_type, content, certificates, signers))
+                    })
+                })
+            })
+        }).map_err(|_| Pkcs7Error::Malformed)?;
+
+        if signers.len() != 1 {
+            return Err(Pkcs7Error::Malformed);
+        }
+        let (digest_algorithm, signature_algorithm, signer) = signers.into_iter().next().unwrap();
+        if digest_algorithm != oid::of(oid::SM3)
+            || (signature_algorithm != oid::of(oid::SM2_SIGN) && signature_algorithm != oid::of(oid::SM3_WITH_SM2))
+        {
+            return Err(Pkcs7Error::UnsupportedAlgorithm);
+        }
+
+        Ok(SignedData {
+            content_type,
+            content,
+            certificates: certificates.iter().map(|c| Certificate::from_der(c)).collect::<Result<_, _>>()?,
+            signer,
+        })
+    }
+
+    /// SignedData ::= SEQUENCE {
+    ///      version           INTEGER,
+    ///      digestAlgorithms  SET OF AlgorithmIdentifier,
+    ///      contentInfo       SEQUENCE { contentType OBJECT IDENTIFIER, content \[0] EXPLICIT OCTET STRING OPTIONAL },
+    ///      certificates  \[0] IMPLICIT SET OF Certificate OPTIONAL,
+    ///      signerInfos       SET OF SignerInfo }
+    fn write_signed_data(&self, writer: DERWriter) {
+        writer.write_sequence(|writer| {
+            writer.next().write_u8(1);
+            writer.next().write_set(|writer| write_algorithm(writer.next(), oid::SM3));
+            writer.next().write_sequence(|writer| {
+                writer.next().write_oid(&self.content_type);
+                if let Some(content) = &self.content {
+                    writer.next().write_tagged(Tag::context(0), |writer| writer.write_bytes(content));
+                }
+            });
+            writer.next().write_tagged_implicit(Tag::context(0), |writer| {
+                writer.write_set_of(|writer| {
+                    self.certificates.iter().for_each(|c| writer.next().write_der(c.to_der()));
+                })
+            });
+            writer.next().write_set(|writer| self.write_signer_info(writer.next()));
+        })
+    }
+
+    /// SignerInfo ::= SEQUENCE {
+    ///      version                    INTEGER,
+    ///      issuerAndSerialNumber      SEQUENCE { issuer Name, serialNumber INTEGER },
+    ///      digestAlgorithm            AlgorithmIdentifier,
+    ///      authenticatedAttributes \[0] IMPLICIT SET OF Attribute OPTIONAL,
+    ///      digestEncryptionAlgorithm  AlgorithmIdentifier,
+    ///      encryptedDigest            OCTET STRING }
+    fn write_signer_info(&self, writer: DERWriter) {
+        let signer = &self.signer;
+        writer.write_sequence(|writer| {
+            writer.next().write_u8(1);
+            writer.next().write_sequence(|writer| {
+                writer.next().write_der(&signer.issuer);
+                writer.next().write_der(&signer.serial);
+            });
+            write_algorithm(writer.next(), oid::SM3);
+            if let Some(attributes) = &signer.attributes {
+                writer.next().write_tagged_der(&TaggedDerValue::from_tag_pc_and_bytes(
+                    Tag::context(0),
+                    PCBit::Constructed,
+                    attributes.concat(),
+                ));
+            }
+            write_algorithm(writer.next(), oid::SM2_SIGN);
+            writer.next().write_bytes(&signer.signature);
+        })
+    }
+
+    fn verify_signer(&self, data: Option<&[u8]>, digest: &[u8; 32]) -> Result<&Certificate, Pkcs7Error> {
+        let cert = self.signer_certificate().ok_or(Pkcs7Error::SignerNotFound)?;
+        self.verify_with(cert, data, digest)?;
+        Ok(cert)
+    }
+
+    fn verify_with(&self, cert: &Certificate, data: Option<&[u8]>, digest: &[u8; 32]) -> Result<(), Pkcs7Error> {
+        let signer = &self.signer;
+        let message = match &signer.attributes {
+            Some(attributes) => {
+                if signer.message_digest.as_deref() != Some(&digest[..]) {
+                    return Err(Pkcs7Error::DigestMismatch);
+                }
+                attribute_set(attributes)
+            }
+            None => data.ok_or(Pkcs7Error::MissingContent)?.to_vec(),
+        };
+
+        let signature = Signature::try_decode(&signer.signature).ok_or(Pkcs7Error::BadSignature)?;
+        let verifier = Crypto::default().verifier(cert.public_key().clone());
+        if !verifier.verify_bytes(&message, &signature) {
+            return Err(Pkcs7Error::BadSignature);
+        }
+        Ok(())
+    }
+}
+
+/// 签名原文为签名属性以SET OF标签编码的DER
+fn attribute_set(attributes: &[Vec<u8>]) -> Vec<u8> {
+    yasna::construct_der(|writer| {
+        writer.write_tagged_der(&TaggedDerValue::from_tag_pc_and_bytes(TAG_SET, PCBit::Constructed, attributes.concat()))
+    })
+}
+
+/// Attribute ::= SEQUENCE { attrType OBJECT IDENTIFIER, attrValues SET OF AttributeValue }
+fn write_attribute<F: FnOnce(DERWriter)>(id: &[u64], value: F) -> Vec<u8> {
+    yasna::construct_der(|writer| {
+        writer.write_sequence(|writer| {
+            writer.next().write_oid(&oid::of(id));
+            writer.next().write_set(|writer| value(writer.next()));
+        })
+    })
+}
+
+fn write_algorithm(writer: DERWriter, id: &[u64]) {
+    writer.write_sequence(|writer| writer.next().write_oid(&oid::of(id)))
+}
+
+fn read_algorithm(reader: yasna::BERReader) -> yasna::ASN1Result<ObjectIdentifier> {
+    reader.read_sequence(|reader| {
+        let algorithm = reader.next().read_oid()?;
+        reader.read_optional(|reader| reader.read_null())?;
+        Ok(algorithm)
+    })
+}
+
+/// 返回摘要算法、签名算法以及签名者信息
+fn read_signer_info(reader: yasna::BERReader) -> yasna::ASN1Result<(ObjectIdentifier, ObjectIdentifier, SignerInfo)> {
+    reader.read_sequence(|reader| {
+        reader.next().read_u8()?;
+        let (issuer, serial) = reader.next().read_sequence(|reader| {
+            let issuer = reader.next().read_der()?;
+            let serial = reader.next().read_der()?;
+            Ok((issuer, serial))
+        })?;
+        let digest_algorithm = read_algorithm(reader.next())?;
+
+        let mut attributes = None;
+        let mut message_digest = None;
+        let mut signing_time = None;
+        reader.read_optional(|reader| {
+            reader.read_tagged_implicit(Tag::context(0), |reader| {
+                let mut list = vec![];
+                reader.read_set_of(|reader| {
+                    let attribute = reader.read_der()?;
+                    let (id, value) = yasna::parse_der(&attribute, |reader| {
+                        reader.read_sequence(|reader| {
+                            let id = reader.next().read_oid()?;
+                            let mut values = vec![];
+                            reader.next().read_set_of(|reader| {
+                                values.push(reader.read_der()?);
+                                Ok(())
+                            })?;
+                            Ok((id, values))
+                        })
+                    })?;
+                    if let Some(value) = value.first() {
+                        if id == oid::of(oid::MESSAGE_DIGEST) {
+                            message_digest = Some(yasna::parse_der(value, |reader| reader.read_bytes())?);
+                        } else if id == oid::of(oid::SIGNING_TIME) {
+                            signing_time = Some(yasna::parse_der(value, read_time)?);
+                        }
+                    }
+                    list.push(attribute);
+                    Ok(())
+                })?;
+                attributes = Some(list);
+                Ok(())
+            })
+        })?;
+
+        let signature_algorithm = read_algorithm(reader.next())?;
+        let signature = reader.next().read_bytes()?;
+        reader.read_optional(|reader| {
+            reader.read_tagged_implicit(Tag::context(1), |reader| {
+                reader.read_set_of(|reader| reader.read_der().map(|_| ()))
+            })
+        })?;
+
+        let signer = SignerInfo { issuer, serial, attributes, message_digest, signing_time, signature };
+        Ok((digest_algorithm, signature_algorithm, signer))
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::cert::{generate_self_signed, Subject, Validity};
+    use crate::sm2::{HexKey, PrivateKey, PublicKey};
+
+    use super::*;
+
+    const PRK: &str = "6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e";
+    const PUK: &str = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+
+    #[test]
+    fn main() {
+        let keypair = KeyPair::new(PrivateKey::decode(PRK), PublicKey::decode(PUK));
+        let cert = generate_self_signed(&keypair, &Subject::new("signer"), &Validity::days(365));
+
+        let data = b"contract";
+        let signed = sign_detached(&keypair, &cert, data);
+        assert_eq!(verify_detached(&signed, data).unwrap().to_der(), cert.to_der());
+        assert_eq!(verify_detached(&signed, b"tampered").unwrap_err(), Pkcs7Error::DigestMismatch);
+
+        let decoded = SignedData::decode(&signed).unwrap();
+        assert!(decoded.signing_time().is_some());
+        assert_eq!(decoded.verify_digest(&sm3::hash(data)).unwrap().to_der(), cert.to_der());
+        assert_eq!(decoded.verify().unwrap_err(), Pkcs7Error::MissingContent);
+        assert_eq!(decoded.encode(), signed);
+
+        let attached = SignedData::sign(&keypair, &cert, data, false).encode();
+        let decoded = SignedData::decode(&attached).unwrap();
+        assert_eq!(decoded.content(), Some(&data[..]));
+        assert!(decoded.verify().is_ok());
+    }
+}
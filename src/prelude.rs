@@ -0,0 +1,58 @@
+//! 常用高层类型的统一入口
+//!
+//! `use yarism::prelude::*`导入当前版本的预导入集合[`v1`]。内部模块调整时这里导出的名称保持不变，
+//! 不兼容的变化只会出现在新的版本模块中。
+
+pub use self::v1::*;
+
+/// 第一版预导入集合，随启用的特性导出相应算法的类型
+pub mod v1 {
+    pub use crate::codec::HexArray;
+    pub use crate::error_code::ErrorCode;
+
+    /// SM2签名、验签与加解密的配置入口，即[`crate::sm2::Crypto`]
+    #[cfg(feature = "sm2-verify")]
+    pub use crate::sm2::Crypto as Sm2;
+    #[cfg(feature = "sm2-verify")]
+    pub use crate::sm2::{HexKey, KeyError, PublicKey, Signature, Verifier, VerifyError};
+    #[cfg(feature = "sm2-sign")]
+    pub use crate::sm2::Signer;
+    #[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+    pub use crate::sm2::{KeyPair, PrivateKey};
+
+    /// 流式SM3杂凑，即[`crate::sm3::Hasher`]
+    #[cfg(feature = "sm3")]
+    pub use crate::sm3::Hasher as Sm3;
+    /// HMAC-SM3，即[`crate::sm3::Hmac`]
+    #[cfg(feature = "sm3")]
+    pub use crate::sm3::Hmac as HmacSm3;
+
+    #[cfg(feature = "sm4")]
+    pub use crate::sm4::{GcmError, Iv16, Nonce12, Sm4Gcm, Sm4Key};
+}
+
+#[cfg(all(test, feature = "sm2-sign", feature = "sm4"))]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn prelude() {
+        let keypair = KeyPair::new(
+            PrivateKey::decode("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e"),
+            PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e"),
+        );
+        let sm2 = Sm2::default();
+        let signature = sm2.signer(keypair.clone()).sign_bytes(b"prelude");
+        assert_eq!(sm2.verifier(keypair.puk().clone()).check_bytes(b"prelude", &signature), Ok(()));
+
+        let mut sm3 = Sm3::new();
+        sm3.update(b"abc");
+        assert_eq!(hex::encode(sm3.finalize()), "66c7f0f462eeedd9d1f2d46bdc10e4e24167c4875cf2f7a2297da02b8f4ba8e0");
+
+        let gcm = Sm4Gcm::new(&[0x42; 16]);
+        let nonce = Nonce12::new([7; 12]);
+        let cipher = gcm.encrypt(&nonce, b"aad", b"prelude");
+        assert_eq!(gcm.decrypt(&nonce, b"aad", &cipher), Ok(b"prelude".to_vec()));
+        assert_eq!(gcm.decrypt(&nonce, b"", &cipher).unwrap_err().code(), crate::error_code::Code::MAC_MISMATCH);
+    }
+}
@@ -0,0 +1,631 @@
+use std::fmt::{Display, Formatter};
+
+use yasna::{ASN1Error, ASN1ErrorKind, ASN1Result, BERReader, DERWriter, Tag};
+
+use crate::cert::{encodable, oid, write_generalized_time, CertError, Certificate};
+use crate::sm2::ecc::{Crypto, Signature};
+use crate::sm2::KeyPair;
+use crate::{env, sm3};
+use crate::error_code::{Code, ErrorCode};
+
+/// GB/T 38540-2020 电子印章数据格式版本
+const VERSION: u32 = 4;
+/// 印章头标识
+const HEADER_ID: &str = "ES";
+/// 证书列表类型：数字证书
+const CERT_LIST_TYPE: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SealError {
+    /// 不是合法的DER编码或缺少必要字段
+    Malformed,
+    /// 不支持的数据格式版本
+    UnsupportedVersion(u32),
+    /// 签名算法不是SM3WithSM2
+    UnsupportedAlgorithm,
+    /// 证书解析失败
+    Certificate(CertError),
+    /// 制章人签名验证失败
+    BadSealSignature,
+    /// 签章人签名验证失败
+    BadSignature,
+    /// 签章人证书不在印章的证书列表中
+    UnauthorizedSigner,
+    /// 签章时间不在印章有效期内
+    Expired,
+    /// 原文摘要不匹配
+    DigestMismatch,
+    /// 编码为IA5String的字段含非ASCII字符，参数为字段名
+    NotAscii(&'static str),
+    /// 编码为GeneralizedTime的时间不在0000年至9999年内，参数为字段名
+    InvalidTime(&'static str),
+}
+
+impl Display for SealError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SealError::Malformed => write!(f, "The seal data is malformed."),
+            SealError::UnsupportedVersion(v) => write!(f, "The seal version {} is not supported.", v),
+            SealError::UnsupportedAlgorithm => write!(f, "The signature algorithm is not SM3WithSM2."),
+            SealError::Certificate(e) => write!(f, "{}", e),
+            SealError::BadSealSignature => write!(f, "The seal maker signature validation failed."),
+            SealError::BadSignature => write!(f, "The seal signature validation failed."),
+            SealError::UnauthorizedSigner => write!(f, "The signer certificate is not authorized by the seal."),
+            SealError::Expired => write!(f, "The signing time is outside the seal validity."),
+            SealError::DigestMismatch => write!(f, "The data digest does not match the signature."),
+            SealError::NotAscii(field) => write!(f, "The {} must be ASCII to be encoded as IA5String.", field),
+            SealError::InvalidTime(field) => write!(f, "The {} must be within the years 0000 to 9999.", field),
+        }
+    }
+}
+
+impl std::error::Error for SealError {}
+
+impl ErrorCode for SealError {
+    fn code(&self) -> Code {
+        match self {
+            SealError::Malformed | SealError::NotAscii(_) | SealError::InvalidTime(_) => Code::INVALID_DATA,
+            SealError::UnsupportedVersion(_) | SealError::UnsupportedAlgorithm => Code::NOT_SUPPORTED,
+            SealError::Certificate(e) => e.code(),
+            SealError::BadSealSignature | SealError::BadSignature | SealError::Expired => Code::VERIFY_FAILED,
+            SealError::UnauthorizedSigner => Code::CERT_NOT_FOUND,
+            SealError::DigestMismatch => Code::DIGEST_MISMATCH,
+        }
+    }
+}
+
+impl From<CertError> for SealError {
+    fn from(e: CertError) -> Self {
+        SealError::Certificate(e)
+    }
+}
+
+/// 印章信息 SES_SealInfo
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SealInfo {
+    /// 厂商标识
+    pub vid: String,
+    /// 电子印章标识
+    pub es_id: String,
+    pub property: Property,
+    pub picture: Picture,
+}
+
+/// 印章属性 SES_ESPropertyInfo，时间均为UNIX时间戳（秒）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Property {
+    /// 印章类型：1 单位印章，2 个人印章
+    pub seal_type: u32,
+    pub name: String,
+    /// 签章人证书（DER）列表，为空时任何签章人都不被授权
+    pub cert_list: Vec<Vec<u8>>,
+    pub create_date: i64,
+    pub valid_start: i64,
+    pub valid_end: i64,
+}
+
+/// 印章图像 SES_ESPictureInfo，宽高单位为毫米
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Picture {
+    /// 图像类型，如"PNG"、"GIF"、"OFD"
+    pub kind: String,
+    pub data: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// 电子印章 SESeal
+///
+/// ```text
+/// SESeal ::= SEQUENCE {
+///      eSealInfo   SES_SealInfo,
+///      cert        OCTET STRING,          -- 制章人证书
+///      signAlgID   OBJECT IDENTIFIER,
+///      signedValue BIT STRING }           -- 对前三项组成的SEQUENCE签名
+/// ```
+#[derive(Debug, Clone)]
+pub struct Seal {
+    info: SealInfo,
+    info_der: Vec<u8>,
+    cert: Certificate,
+    signature: Vec<u8>,
+}
+
+impl Seal {
+    /// 制章人使用SM2私钥制作电子印章，`cert`为制章人证书
+    ///
+    /// 厂商标识、印章标识与图像类型编码为IA5String，含非ASCII字符时返回[`SealError::NotAscii`]；
+    /// 制作日期与有效期编码为GeneralizedTime，不在0000年至9999年内时返回[`SealError::InvalidTime`]。
+    pub fn make(info: SealInfo, keypair: &KeyPair, cert: &Certificate) -> Result<Self, SealError> {
+        check_ascii("vid", &info.vid)?;
+        check_ascii("es_id", &info.es_id)?;
+        check_ascii("picture kind", &info.picture.kind)?;
+        check_time("create date", info.property.create_date)?;
+        check_time("valid start", info.property.valid_start)?;
+        check_time("valid end", info.property.valid_end)?;
+        let info_der = yasna::construct_der(|writer| write_info(writer, &info));
+        let mut seal = Seal { info, info_der, cert: cert.clone(), signature: vec![] };
+        let signer = Crypto::default().signer(keypair.clone());
+        seal.signature = signer.sign_bytes(&seal.to_sign()).encode();
+        Ok(seal)
+    }
+
+    pub fn info(&self) -> &SealInfo {
+        &self.info
+    }
+
+    pub fn cert(&self) -> &Certificate {
+        &self.cert
+    }
+
+    /// 使用制章人证书验证印章签名
+    pub fn verify(&self) -> Result<(), SealError> {
+        let signature = Signature::try_decode(&self.signature).ok_or(SealError::BadSealSignature)?;
+        let verifier = Crypto::default().verifier(self.cert.public_key().clone());
+        if !verifier.verify_bytes(&self.to_sign(), &signature) {
+            return Err(SealError::BadSealSignature);
+        }
+        Ok(())
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        yasna::construct_der(|writer| {
+            writer.write_sequence(|writer| {
+                writer.next().write_der(&self.info_der);
+                writer.next().write_bytes(self.cert.to_der());
+                writer.next().write_oid(&oid::of(oid::SM3_WITH_SM2));
+                writer.next().write_bitvec_bytes(&self.signature, self.signature.len() * 8);
+            })
+        })
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self, SealError> {
+        let (info_der, cert, algorithm, signature) = yasna::parse_der(data, |reader| {
+            reader.read_sequence(|reader| {
+                let info_der = reader.next().read_der()?;
+                let cert = reader.next().read_bytes()?;
+                let algorithm = reader.next().read_oid()?;
+                let signature = reader.next().read_bitvec_bytes()?.0;
+                Ok((info_der, cert, algorithm, signature))
+            })
+        }).map_err(|_| SealError::Malformed)?;
+
+        if algorithm != oid::of(oid::SM3_WITH_SM2) {
+            return Err(SealError::UnsupportedAlgorithm);
+        }
+        let (version, info) = yasna::parse_der(&info_der, read_info).map_err(|_| SealError::Malformed)?;
+        if version != VERSION {
+            return Err(SealError::UnsupportedVersion(version));
+        }
+
+        Ok(Seal { info, info_der, cert: Certificate::from_der(&cert)?, signature })
+    }
+
+    /// 制章人签名原文
+    fn to_sign(&self) -> Vec<u8> {
+        yasna::construct_der(|writer| {
+            writer.write_sequence(|writer| {
+                writer.next().write_der(&self.info_der);
+                writer.next().write_bytes(self.cert.to_der());
+                writer.next().write_oid(&oid::of(oid::SM3_WITH_SM2));
+            })
+        })
+    }
+}
+
+/// 电子签章 SES_Signature
+///
+/// ```text
+/// SES_Signature ::= SEQUENCE {
+///      toSign          TBS_Sign,
+///      cert            OCTET STRING,      -- 签章人证书
+///      signatureAlgID  OBJECT IDENTIFIER,
+///      signature       BIT STRING,        -- 对前三项组成的SEQUENCE签名
+///      timeStamp   [0] BIT STRING OPTIONAL }
+///
+/// TBS_Sign ::= SEQUENCE {
+///      version         INTEGER,
+///      eseal           SESeal,
+///      timeInfo        GeneralizedTime,
+///      dataHash        BIT STRING,        -- SM3(原文)
+///      propertyInfo    IA5String,
+///      extDatas        ExtensionDatas OPTIONAL }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SealSignature {
+    seal: Seal,
+    time: i64,
+    data_hash: [u8; 32],
+    property_info: String,
+    to_sign_der: Vec<u8>,
+    cert: Certificate,
+    signature: Vec<u8>,
+    timestamp: Option<Vec<u8>>,
+}
+
+impl SealSignature {
+    /// 签章人使用电子印章对原文签章，`property_info`为原文属性（如文件名、页码与位置）
+    ///
+    /// `property_info`按标准编码为IA5String，含中文文件名等非ASCII字符时返回[`SealError::NotAscii`]，
+    /// 可由调用方先行转义（如百分号编码）。
+    pub fn sign(seal: &Seal, data: &[u8], property_info: &str, keypair: &KeyPair, cert: &Certificate) -> Result<Self, SealError> {
+        check_ascii("property info", property_info)?;
+        let time = env::now();
+        check_time("signing time", time)?;
+        let data_hash = sm3::hash(data);
+        let seal_der = seal.encode();
+        let to_sign_der = yasna::construct_der(|writer| {
+            writer.write_sequence(|writer| {
+                writer.next().write_u32(VERSION);
+                writer.next().write_der(&seal_der);
+                write_generalized_time(writer.next(), time);
+                writer.next().write_bitvec_bytes(&data_hash, 256);
+                writer.next().write_ia5_string(property_info);
+            })
+        });
+
+        let mut signature = SealSignature {
+            seal: seal.clone(),
+            time,
+            data_hash,
+            property_info: property_info.to_string(),
+            to_sign_der,
+            cert: cert.clone(),
+            signature: vec![],
+            timestamp: None,
+        };
+        let signer = Crypto::default().signer(keypair.clone());
+        signature.signature = signer.sign_bytes(&signature.to_sign()).encode();
+        Ok(signature)
+    }
+
+    pub fn seal(&self) -> &Seal {
+        &self.seal
+    }
+
+    pub fn cert(&self) -> &Certificate {
+        &self.cert
+    }
+
+    /// 签章时间，UNIX时间戳（秒）
+    pub fn time(&self) -> i64 {
+        self.time
+    }
+
+    pub fn data_hash(&self) -> &[u8; 32] {
+        &self.data_hash
+    }
+
+    pub fn property_info(&self) -> &str {
+        &self.property_info
+    }
+
+    /// 签章人签名值（DER），时间戳即对该值申请
+    pub fn signature(&self) -> &[u8] {
+        &self.signature
+    }
+
+    pub fn timestamp(&self) -> Option<&[u8]> {
+        self.timestamp.as_deref()
+    }
+
+    /// 设置对签名值申请的时间戳，时间戳不参与签名
+    pub fn set_timestamp(&mut self, timestamp: Vec<u8>) {
+        self.timestamp = Some(timestamp);
+    }
+
+    /// 验证电子签章：印章签名、签章人授权、印章有效期、原文摘要以及签章人签名
+    ///
+    /// 签章人证书须在印章的证书列表中，列表为空时返回[`SealError::UnauthorizedSigner`]。
+    pub fn verify(&self, data: &[u8]) -> Result<(), SealError> {
+        self.seal.verify()?;
+
+        let property = &self.seal.info.property;
+        if !property.cert_list.iter().any(|c| c == self.cert.to_der()) {
+            return Err(SealError::UnauthorizedSigner);
+        }
+        if self.time < property.valid_start || self.time > property.valid_end {
+            return Err(SealError::Expired);
+        }
+        if sm3::hash(data) != self.data_hash {
+            return Err(SealError::DigestMismatch);
+        }
+
+        let signature = Signature::try_decode(&self.signature).ok_or(SealError::BadSignature)?;
+        let verifier = Crypto::default().verifier(self.cert.public_key().clone());
+        if !verifier.verify_bytes(&self.to_sign(), &signature) {
+            return Err(SealError::BadSignature);
+        }
+        Ok(())
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        yasna::construct_der(|writer| {
+            writer.write_sequence(|writer| {
+                writer.next().write_der(&self.to_sign_der);
+                writer.next().write_bytes(self.cert.to_der());
+                writer.next().write_oid(&oid::of(oid::SM3_WITH_SM2));
+                writer.next().write_bitvec_bytes(&self.signature, self.signature.len() * 8);
+                if let Some(timestamp) = &self.timestamp {
+                    writer.next().write_tagged(Tag::context(0), |writer| {
+                        writer.write_bitvec_bytes(timestamp, timestamp.len() * 8)
+                    });
+                }
+            })
+        })
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self, SealError> {
+        let (to_sign_der, cert, algorithm, signature, timestamp) = yasna::parse_der(data, |reader| {
+            reader.read_sequence(|reader| {
+                let to_sign_der = reader.next().read_der()?;
+                let cert = reader.next().read_bytes()?;
+                let algorithm = reader.next().read_oid()?;
+                let signature = reader.next().read_bitvec_bytes()?.0;
+                let timestamp = reader.read_optional(|reader| {
+                    reader.read_tagged(Tag::context(0), |reader| Ok(reader.read_bitvec_bytes()?.0))
+                })?;
+                Ok((to_sign_der, cert, algorithm, signature, timestamp))
+            })
+        }).map_err(|_| SealError::Malformed)?;
+
+        if algorithm != oid::of(oid::SM3_WITH_SM2) {
+            return Err(SealError::UnsupportedAlgorithm);
+        }
+
+        let (version, seal, time, data_hash, property_info) = yasna::parse_der(&to_sign_der, |reader| {
+            reader.read_sequence(|reader| {
+                let version = reader.next().read_u32()?;
+                let seal = reader.next().read_der()?;
+                let time = read_time(reader.next())?;
+                let data_hash = reader.next().read_bitvec_bytes()?.0;
+                let property_info = reader.next().read_ia5_string()?;
+                reader.read_optional(|reader| reader.read_der())?;
+                Ok((version, seal, time, data_hash, property_info))
+            })
+        }).map_err(|_| SealError::Malformed)?;
+
+        if version != VERSION {
+            return Err(SealError::UnsupportedVersion(version));
+        }
+
+        Ok(SealSignature {
+            seal: Seal::decode(&seal)?,
+            time,
+            data_hash: data_hash.try_into().map_err(|_| SealError::Malformed)?,
+            property_info,
+            to_sign_der,
+            cert: Certificate::from_der(&cert)?,
+            signature,
+            timestamp,
+        })
+    }
+
+    /// 签章人签名原文
+    fn to_sign(&self) -> Vec<u8> {
+        yasna::construct_der(|writer| {
+            writer.write_sequence(|writer| {
+                writer.next().write_der(&self.to_sign_der);
+                writer.next().write_bytes(self.cert.to_der());
+                writer.next().write_oid(&oid::of(oid::SM3_WITH_SM2));
+            })
+        })
+    }
+}
+
+/// SES_SealInfo ::= SEQUENCE {
+///      header    SES_Header,              -- SEQUENCE { ID IA5String, version INTEGER, Vid IA5String }
+///      esID      IA5String,
+///      property  SES_ESPropertyInfo,
+///      picture   SES_ESPictureInfo,
+///      extDatas  ExtensionDatas OPTIONAL }
+fn write_info(writer: DERWriter, info: &SealInfo) {
+    writer.write_sequence(|writer| {
+        writer.next().write_sequence(|writer| {
+            writer.next().write_ia5_string(HEADER_ID);
+            writer.next().write_u32(VERSION);
+            writer.next().write_ia5_string(&info.vid);
+        });
+        writer.next().write_ia5_string(&info.es_id);
+
+        let property = &info.property;
+        writer.next().write_sequence(|writer| {
+            writer.next().write_u32(property.seal_type);
+            writer.next().write_utf8_string(&property.name);
+            writer.next().write_u32(CERT_LIST_TYPE);
+            writer.next().write_sequence_of(|writer| {
+                property.cert_list.iter().for_each(|cert| writer.next().write_bytes(cert));
+            });
+            write_generalized_time(writer.next(), property.create_date);
+            write_generalized_time(writer.next(), property.valid_start);
+            write_generalized_time(writer.next(), property.valid_end);
+        });
+
+        let picture = &info.picture;
+        writer.next().write_sequence(|writer| {
+            writer.next().write_ia5_string(&picture.kind);
+            writer.next().write_bytes(&picture.data);
+            writer.next().write_u32(picture.width);
+            writer.next().write_u32(picture.height);
+        });
+    })
+}
+
+/// 返回印章头中的版本号以及印章信息
+fn read_info(reader: BERReader) -> ASN1Result<(u32, SealInfo)> {
+    reader.read_sequence(|reader| {
+        let (id, version, vid) = reader.next().read_sequence(|reader| {
+            let id = reader.next().read_ia5_string()?;
+            let version = reader.next().read_u32()?;
+            let vid = reader.next().read_ia5_string()?;
+            Ok((id, version, vid))
+        })?;
+        if id != HEADER_ID {
+            return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+        }
+        let es_id = reader.next().read_ia5_string()?;
+
+        let property = reader.next().read_sequence(|reader| {
+            let seal_type = reader.next().read_u32()?;
+            let name = reader.next().read_utf8string()?;
+            if reader.next().read_u32()? != CERT_LIST_TYPE {
+                return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+            }
+            let mut cert_list = vec![];
+            reader.next().read_sequence_of(|reader| {
+                cert_list.push(reader.read_bytes()?);
+                Ok(())
+            })?;
+            let create_date = read_time(reader.next())?;
+            let valid_start = read_time(reader.next())?;
+            let valid_end = read_time(reader.next())?;
+            Ok(Property { seal_type, name, cert_list, create_date, valid_start, valid_end })
+        })?;
+
+        let picture = reader.next().read_sequence(|reader| {
+            let kind = reader.next().read_ia5_string()?;
+            let data = reader.next().read_bytes()?;
+            let width = reader.next().read_u32()?;
+            let height = reader.next().read_u32()?;
+            Ok(Picture { kind, data, width, height })
+        })?;
+
+        reader.read_optional(|reader| reader.read_der())?;
+        Ok((version, SealInfo { vid, es_id, property, picture }))
+    })
+}
+
+/// yasna写入IA5String时断言内容为ASCII，在编码前检查以返回错误而不是panic
+fn check_ascii(field: &'static str, value: &str) -> Result<(), SealError> {
+    match value.is_ascii() {
+        true => Ok(()),
+        false => Err(SealError::NotAscii(field)),
+    }
+}
+
+/// GeneralizedTime只能表示0000年至9999年，在编码前检查以返回错误而不是panic
+fn check_time(field: &'static str, timestamp: i64) -> Result<(), SealError> {
+    match encodable(timestamp) {
+        true => Ok(()),
+        false => Err(SealError::InvalidTime(field)),
+    }
+}
+
+fn read_time(reader: BERReader) -> ASN1Result<i64> {
+    Ok(reader.read_generalized_time()?.datetime().unix_timestamp())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::cert::{generate_self_signed, Subject, Validity};
+    use crate::sm2::{HexKey, PrivateKey, PublicKey};
+
+    use super::*;
+
+    const PRK: &str = "6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e";
+    const PUK: &str = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+
+    fn fixture(valid_end: i64) -> (KeyPair, Certificate, SealInfo) {
+        let keypair = KeyPair::new(PrivateKey::decode(PRK), PublicKey::decode(PUK));
+        let cert = generate_self_signed(&keypair, &Subject::new("seal"), &Validity::days(365));
+        let info = SealInfo {
+            vid: "yarism".to_string(),
+            es_id: "11010000000001".to_string(),
+            property: Property {
+                seal_type: 1,
+                name: "测试专用章".to_string(),
+                cert_list: vec![cert.to_der().to_vec()],
+                create_date: 1700000000,
+                valid_start: 1700000000,
+                valid_end,
+            },
+            picture: Picture { kind: "PNG".to_string(), data: vec![0x89, 0x50, 0x4E, 0x47], width: 40, height: 40 },
+        };
+        (keypair, cert, info)
+    }
+
+    #[test]
+    fn sign_verify() {
+        let (keypair, cert, info) = fixture(4102444800);
+        let seal = Seal::make(info.clone(), &keypair, &cert).unwrap();
+        assert_eq!(seal.verify(), Ok(()));
+
+        let seal = Seal::decode(&seal.encode()).unwrap();
+        assert_eq!(seal.info(), &info);
+        assert_eq!(seal.verify(), Ok(()));
+
+        let data = b"OFD document content";
+        let mut signature = SealSignature::sign(&seal, data, "/Doc_0/Document.xml", &keypair, &cert).unwrap();
+        assert_eq!(signature.verify(data), Ok(()));
+
+        signature.set_timestamp(vec![0x30, 0x00]);
+        let decoded = SealSignature::decode(&signature.encode()).unwrap();
+        assert_eq!(decoded.property_info(), "/Doc_0/Document.xml");
+        assert_eq!(decoded.timestamp(), Some(&[0x30, 0x00][..]));
+        assert_eq!(decoded.encode(), signature.encode());
+        assert_eq!(decoded.verify(data), Ok(()));
+        assert_eq!(decoded.verify(b"tampered"), Err(SealError::DigestMismatch));
+    }
+
+    #[test]
+    fn invalid() {
+        let (keypair, cert, info) = fixture(1700000001);
+        let seal = Seal::make(info, &keypair, &cert).unwrap();
+        let signature = SealSignature::sign(&seal, b"data", "", &keypair, &cert).unwrap();
+        assert_eq!(signature.verify(b"data"), Err(SealError::Expired));
+
+        let mut der = seal.encode();
+        let len = der.len();
+        der[len - 1] ^= 0x01;
+        assert_eq!(Seal::decode(&der).unwrap().verify(), Err(SealError::BadSealSignature));
+        assert_eq!(Seal::decode(&der[..len - 1]).unwrap_err(), SealError::Malformed);
+
+        let (keypair, cert, mut info) = fixture(4102444800);
+        info.property.cert_list = vec![vec![0x30, 0x00]];
+        let seal = Seal::make(info, &keypair, &cert).unwrap();
+        let signature = SealSignature::sign(&seal, b"data", "", &keypair, &cert).unwrap();
+        assert_eq!(signature.verify(b"data"), Err(SealError::UnauthorizedSigner));
+
+        // 证书列表为空时不授权任何签章人
+        let (keypair, cert, mut info) = fixture(4102444800);
+        info.property.cert_list = vec![];
+        let seal = Seal::make(info, &keypair, &cert).unwrap();
+        let signature = SealSignature::sign(&seal, b"data", "", &keypair, &cert).unwrap();
+        assert_eq!(signature.verify(b"data"), Err(SealError::UnauthorizedSigner));
+    }
+
+    #[test]
+    fn non_ascii() {
+        let (keypair, cert, info) = fixture(4102444800);
+        let seal = Seal::make(info.clone(), &keypair, &cert).unwrap();
+        let e = SealSignature::sign(&seal, b"data", "合同.ofd", &keypair, &cert).unwrap_err();
+        assert_eq!(e, SealError::NotAscii("property info"));
+
+        let mut vendor = info.clone();
+        vendor.vid = "厂商".to_string();
+        assert_eq!(Seal::make(vendor, &keypair, &cert).unwrap_err(), SealError::NotAscii("vid"));
+        let mut picture = info;
+        picture.picture.kind = "图片".to_string();
+        assert_eq!(Seal::make(picture, &keypair, &cert).unwrap_err(), SealError::NotAscii("picture kind"));
+    }
+
+    #[test]
+    fn time_range() {
+        // 9999-12-31T23:59:59Z仍可编码，1950年之前同样使用GeneralizedTime
+        let (keypair, cert, mut info) = fixture(253402300799);
+        info.property.valid_start = -1_000_000_000;
+        let seal = Seal::make(info.clone(), &keypair, &cert).unwrap();
+        assert_eq!(Seal::decode(&seal.encode()).unwrap().info(), &info);
+
+        let (_, _, info) = fixture(253402300800);
+        let e = Seal::make(info, &keypair, &cert).unwrap_err();
+        assert_eq!((e.clone(), e.code()), (SealError::InvalidTime("valid end"), Code::INVALID_DATA));
+        let (_, _, mut info) = fixture(4102444800);
+        info.property.create_date = i64::MAX;
+        assert_eq!(Seal::make(info, &keypair, &cert).unwrap_err(), SealError::InvalidTime("create date"));
+        let (_, _, mut info) = fixture(4102444800);
+        info.property.valid_start = -62167219201;
+        assert_eq!(Seal::make(info, &keypair, &cert).unwrap_err(), SealError::InvalidTime("valid start"));
+    }
+}
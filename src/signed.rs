@@ -0,0 +1,384 @@
+//! 轻量的带签名者信息的SM2签名消息
+//!
+//! 在裸签名之外携带签名时间、签名者标识与可选的原文，编码唯一确定，
+//! 适用于系统间交换签名但不需要完整GM/T 0010 CMS（见[`crate::pkcs7`]）的场合。
+
+use std::fmt::{Display, Formatter};
+
+#[cfg(feature = "sm2-sign")]
+use crate::cert::Certificate;
+use crate::sm2::ecc::{Crypto, Signature};
+#[cfg(feature = "sm2-sign")]
+use crate::sm2::key::KeyPair;
+use crate::sm2::key::PublicKey;
+use crate::error_code::{Code, ErrorCode};
+use crate::sm3;
+#[cfg(feature = "sm2-sign")]
+use crate::env;
+
+const MAGIC: [u8; 4] = *b"YSGM";
+const VERSION: u8 = 0x01;
+
+const KEY_ID: u8 = 0x01;
+const ISSUER_SERIAL: u8 = 0x02;
+
+const DETACHED: u8 = 0x00;
+const ATTACHED: u8 = 0x01;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignedMessageError {
+    /// 数据长度不足或有多余数据
+    Truncated,
+    /// 消息标识错误
+    BadMagic,
+    /// 不支持的消息版本
+    UnsupportedVersion(u8),
+    /// 签名者标识或原文标志的取值非法
+    Malformed,
+    /// 签名者标识与给定的公钥或证书不符
+    SignerMismatch,
+    /// 原文摘要不匹配
+    DigestMismatch,
+    /// 签名验证失败
+    BadSignature,
+}
+
+impl Display for SignedMessageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignedMessageError::Truncated => write!(f, "The signed message length is invalid."),
+            SignedMessageError::BadMagic => write!(f, "The signed message magic is invalid."),
+            SignedMessageError::UnsupportedVersion(v) => write!(f, "The signed message version {} is not supported.", v),
+            SignedMessageError::Malformed => write!(f, "The signed message is malformed."),
+            SignedMessageError::SignerMismatch => write!(f, "The signer does not match the given key or certificate."),
+            SignedMessageError::DigestMismatch => write!(f, "The content digest does not match the signature."),
+            SignedMessageError::BadSignature => write!(f, "The message signature validation failed."),
+        }
+    }
+}
+
+impl std::error::Error for SignedMessageError {}
+
+impl ErrorCode for SignedMessageError {
+    fn code(&self) -> Code {
+        match self {
+            SignedMessageError::Truncated => Code::INVALID_LENGTH,
+            SignedMessageError::BadMagic | SignedMessageError::Malformed => Code::INVALID_DATA,
+            SignedMessageError::UnsupportedVersion(_) => Code::NOT_SUPPORTED,
+            SignedMessageError::SignerMismatch => Code::KEY_NOT_FOUND,
+            SignedMessageError::DigestMismatch => Code::DIGEST_MISMATCH,
+            SignedMessageError::BadSignature => Code::VERIFY_FAILED,
+        }
+    }
+}
+
+/// 签名者标识
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignerId {
+    /// 公钥标识，见[`SignerId::key_id`]
+    KeyId([u8; 20]),
+    /// 签名证书的颁发者（DER编码的Name）与序列号（DER编码的INTEGER）
+    IssuerSerial { issuer: Vec<u8>, serial: Vec<u8> },
+}
+
+impl SignerId {
+    /// 以SM3(04 ∥ x ∥ y)的前20字节作为公钥标识
+    pub fn key_id(key: &PublicKey) -> Self {
+        let mut id = [0u8; 20];
+        id.copy_from_slice(&sm3::hash(&key.to_bytes())[..20]);
+        SignerId::KeyId(id)
+    }
+
+    /// 以证书的颁发者与序列号标识签名者
+    #[cfg(feature = "sm2-sign")]
+    pub fn from_certificate(cert: &Certificate) -> Self {
+        SignerId::IssuerSerial { issuer: cert.issuer_der().to_vec(), serial: cert.serial_der().to_vec() }
+    }
+
+    /// 是否标识该证书：颁发者与序列号相同，或公钥标识与证书公钥一致
+    #[cfg(feature = "sm2-sign")]
+    pub fn matches_certificate(&self, cert: &Certificate) -> bool {
+        match self {
+            SignerId::KeyId(_) => *self == SignerId::key_id(cert.public_key()),
+            SignerId::IssuerSerial { issuer, serial } => issuer == cert.issuer_der() && serial == cert.serial_der(),
+        }
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        match self {
+            SignerId::KeyId(id) => {
+                out.push(KEY_ID);
+                out.extend_from_slice(id);
+            }
+            SignerId::IssuerSerial { issuer, serial } => {
+                out.push(ISSUER_SERIAL);
+                for part in [issuer, serial] {
+                    out.extend_from_slice(&(part.len() as u16).to_be_bytes());
+                    out.extend_from_slice(part);
+                }
+            }
+        }
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, SignedMessageError> {
+        match reader.byte()? {
+            KEY_ID => Ok(SignerId::KeyId(reader.take(20)?.try_into().unwrap())),
+            ISSUER_SERIAL => {
+                let issuer = reader.prefixed()?.to_vec();
+                let serial = reader.prefixed()?.to_vec();
+                Ok(SignerId::IssuerSerial { issuer, serial })
+            }
+            _ => Err(SignedMessageError::Malformed),
+        }
+    }
+}
+
+/// 签名消息
+///
+/// 编码格式（大端序）：
+/// ```text
+/// magic "YSGM"(4) ‖ version(1) ‖ signing time(8) ‖ signer id ‖ SM3(content)(32) ‖
+/// content flag(1) ‖ [content length(4) ‖ content] ‖ signature r ‖ s(64)
+/// ```
+/// signer id为 0x01 ‖ key id(20) 或 0x02 ‖ issuer length(2) ‖ issuer ‖ serial length(2) ‖ serial；
+/// content flag为0时原文不随消息传递。签名覆盖content flag之前的全部数据，
+/// 解码时拒绝非法取值与多余数据，因此同一消息只有一种编码。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedMessage {
+    signing_time: i64,
+    signer: SignerId,
+    digest: [u8; 32],
+    content: Option<Vec<u8>>,
+    signature: [u8; 64],
+}
+
+impl SignedMessage {
+    /// 对原文签名，签名时间取当前时间，`detached`为true时消息中不含原文
+    #[cfg(feature = "sm2-sign")]
+    pub fn sign(keypair: &KeyPair, signer: SignerId, content: &[u8], detached: bool) -> Self {
+        let mut message = SignedMessage {
+            signing_time: env::now(),
+            signer,
+            digest: sm3::hash(content),
+            content: (!detached).then(|| content.to_vec()),
+            signature: [0u8; 64],
+        };
+        message.signature = Crypto::default().signer(keypair.clone()).sign_bytes(&message.to_sign()).to_bytes();
+        message
+    }
+
+    /// 签名时间，UNIX时间戳（秒）
+    pub fn signing_time(&self) -> i64 {
+        self.signing_time
+    }
+
+    pub fn signer(&self) -> &SignerId {
+        &self.signer
+    }
+
+    /// 原文的SM3摘要
+    pub fn digest(&self) -> &[u8; 32] {
+        &self.digest
+    }
+
+    /// 随消息传递的原文，分离式签名时为None
+    pub fn content(&self) -> Option<&[u8]> {
+        self.content.as_deref()
+    }
+
+    /// 原始格式 r ‖ s 的签名
+    pub fn signature(&self) -> &[u8; 64] {
+        &self.signature
+    }
+
+    /// 使用签名者公钥验证，签名者标识为公钥标识时须与该公钥一致；消息须携带原文
+    pub fn verify(&self, key: &PublicKey) -> Result<(), SignedMessageError> {
+        let content = self.content.as_deref().ok_or(SignedMessageError::DigestMismatch)?;
+        self.verify_detached(key, content)
+    }
+
+    /// 使用签名者公钥验证消息及外部传入的原文
+    pub fn verify_detached(&self, key: &PublicKey, content: &[u8]) -> Result<(), SignedMessageError> {
+        if matches!(self.signer, SignerId::KeyId(_)) && self.signer != SignerId::key_id(key) {
+            return Err(SignedMessageError::SignerMismatch);
+        }
+        self.verify_signature(key)?;
+        if sm3::hash(content) != self.digest {
+            return Err(SignedMessageError::DigestMismatch);
+        }
+        Ok(())
+    }
+
+    /// 使用签名证书验证，签名者标识须指向该证书；证书本身的有效性由调用方检查
+    #[cfg(feature = "sm2-sign")]
+    pub fn verify_with_certificate(&self, cert: &Certificate) -> Result<(), SignedMessageError> {
+        if !self.signer.matches_certificate(cert) {
+            return Err(SignedMessageError::SignerMismatch);
+        }
+        self.verify(cert.public_key())
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = self.to_sign();
+        match &self.content {
+            Some(content) => {
+                out.push(ATTACHED);
+                out.extend_from_slice(&(content.len() as u32).to_be_bytes());
+                out.extend_from_slice(content);
+            }
+            None => out.push(DETACHED),
+        }
+        out.extend_from_slice(&self.signature);
+        out
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self, SignedMessageError> {
+        let mut reader = Reader(data);
+        if reader.take(4)? != MAGIC {
+            return Err(SignedMessageError::BadMagic);
+        }
+        let version = reader.byte()?;
+        if version != VERSION {
+            return Err(SignedMessageError::UnsupportedVersion(version));
+        }
+        let signing_time = i64::from_be_bytes(reader.take(8)?.try_into().unwrap());
+        let signer = SignerId::read(&mut reader)?;
+        let digest = reader.take(32)?.try_into().unwrap();
+        let content = match reader.byte()? {
+            DETACHED => None,
+            ATTACHED => {
+                let len = u32::from_be_bytes(reader.take(4)?.try_into().unwrap()) as usize;
+                Some(reader.take(len)?.to_vec())
+            }
+            _ => return Err(SignedMessageError::Malformed),
+        };
+        let signature = reader.take(64)?.try_into().unwrap();
+        if !reader.0.is_empty() {
+            return Err(SignedMessageError::Truncated);
+        }
+        Ok(SignedMessage { signing_time, signer, digest, content, signature })
+    }
+
+    fn verify_signature(&self, key: &PublicKey) -> Result<(), SignedMessageError> {
+        let signature = Signature::from_bytes(&self.signature).map_err(|_| SignedMessageError::BadSignature)?;
+        match Crypto::default().verifier(key.clone()).verify_bytes(&self.to_sign(), &signature) {
+            true => Ok(()),
+            false => Err(SignedMessageError::BadSignature),
+        }
+    }
+
+    /// 参与签名的数据
+    fn to_sign(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(4 + 1 + 8 + 21 + 32);
+        out.extend_from_slice(&MAGIC);
+        out.push(VERSION);
+        out.extend_from_slice(&self.signing_time.to_be_bytes());
+        self.signer.write(&mut out);
+        out.extend_from_slice(&self.digest);
+        out
+    }
+}
+
+/// 按顺序读取编码数据
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], SignedMessageError> {
+        if self.0.len() < len {
+            return Err(SignedMessageError::Truncated);
+        }
+        let (head, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    fn byte(&mut self) -> Result<u8, SignedMessageError> {
+        Ok(self.take(1)?[0])
+    }
+
+    /// 2字节长度前缀的数据
+    fn prefixed(&mut self) -> Result<&'a [u8], SignedMessageError> {
+        let len = u16::from_be_bytes(self.take(2)?.try_into().unwrap()) as usize;
+        self.take(len)
+    }
+}
+
+
+#[cfg(all(test, feature = "sm2-sign"))]
+mod tests {
+    use crate::cert::{generate_self_signed, Subject, Validity};
+    use crate::sm2::key::{HexKey, PrivateKey};
+
+    use super::*;
+
+    const PRK: &str = "6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e";
+    const PUK: &str = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+
+    fn keypair() -> KeyPair {
+        KeyPair::new(PrivateKey::decode(PRK), PublicKey::decode(PUK))
+    }
+
+    #[test]
+    fn key_id() {
+        let puk = PublicKey::decode(PUK);
+        let message = SignedMessage::sign(&keypair(), SignerId::key_id(&puk), b"payload", false);
+        assert_eq!(message.content(), Some(&b"payload"[..]));
+        assert!(message.signing_time() > 1_700_000_000);
+
+        let encoded = message.encode();
+        assert_eq!(encoded.len(), 4 + 1 + 8 + 21 + 32 + 1 + 4 + 7 + 64);
+        let decoded = SignedMessage::decode(&encoded).unwrap();
+        assert_eq!(decoded, message);
+        assert_eq!(decoded.verify(&puk), Ok(()));
+
+        let other = PublicKey::decode(&crate::sm2::generate_keypair().1);
+        assert_eq!(decoded.verify(&other), Err(SignedMessageError::SignerMismatch));
+        assert_eq!(decoded.verify_detached(&puk, b"tampered"), Err(SignedMessageError::DigestMismatch));
+    }
+
+    #[test]
+    fn certificate() {
+        let keypair = keypair();
+        let cert = generate_self_signed(&keypair, &Subject::new("signer"), &Validity::days(1));
+        let message = SignedMessage::sign(&keypair, SignerId::from_certificate(&cert), b"payload", true);
+        assert_eq!(message.content(), None);
+
+        let decoded = SignedMessage::decode(&message.encode()).unwrap();
+        assert_eq!(decoded.verify_detached(cert.public_key(), b"payload"), Ok(()));
+        assert_eq!(decoded.verify_with_certificate(&cert), Err(SignedMessageError::DigestMismatch));
+        let attached = SignedMessage::sign(&keypair, SignerId::from_certificate(&cert), b"payload", false);
+        assert_eq!(attached.verify_with_certificate(&cert), Ok(()));
+
+        let other = generate_self_signed(&keypair, &Subject::new("other"), &Validity::days(1));
+        assert_eq!(attached.verify_with_certificate(&other), Err(SignedMessageError::SignerMismatch));
+        assert!(SignerId::key_id(cert.public_key()).matches_certificate(&other));
+    }
+
+    #[test]
+    fn decode_errors() {
+        let puk = PublicKey::decode(PUK);
+        let encoded = SignedMessage::sign(&keypair(), SignerId::key_id(&puk), b"payload", false).encode();
+        let decode = |data: &[u8]| SignedMessage::decode(data).unwrap_err();
+
+        assert_eq!(decode(&encoded[..encoded.len() - 1]), SignedMessageError::Truncated);
+        assert_eq!(decode(&[&encoded[..], &[0]].concat()), SignedMessageError::Truncated);
+        assert_eq!(decode(b"YSGX"), SignedMessageError::BadMagic);
+
+        let mut tampered = encoded.clone();
+        tampered[4] = 2;
+        assert_eq!(decode(&tampered), SignedMessageError::UnsupportedVersion(2));
+        tampered = encoded.clone();
+        tampered[13] = 3;
+        assert_eq!(decode(&tampered), SignedMessageError::Malformed);
+        tampered = encoded.clone();
+        tampered[66] = 2;
+        assert_eq!(decode(&tampered), SignedMessageError::Malformed);
+
+        // 签名时间被篡改
+        tampered = encoded.clone();
+        tampered[12] ^= 1;
+        let message = SignedMessage::decode(&tampered).unwrap();
+        assert_eq!(message.verify(&puk), Err(SignedMessageError::BadSignature));
+        assert_eq!(SignedMessageError::BadSignature.code(), Code::VERIFY_FAILED);
+    }
+}
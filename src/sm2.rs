@@ -1,48 +1,109 @@
+#[cfg(feature = "sm2-encrypt")]
 use std::rc::Rc;
-use crate::sm2::ecc::{Crypto, Decryption, Encryption, Signature};
-use crate::sm2::key::{HexKey, KeyGenerator, KeyPair, PrivateKey, PublicKey};
 use crate::sm2::p256::P256Elliptic;
 
-mod key;
-mod ecc;
+pub(crate) mod key;
+pub(crate) mod ecc;
+mod field;
+mod format;
+mod jwk;
 mod p256;
+#[cfg(all(any(feature = "sm2-sign", feature = "sm2-encrypt"), feature = "sm4"))]
+mod pbes2;
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+mod pkcs8;
+mod point;
+#[cfg(feature = "sm2-sign")]
+mod rfc6979;
+#[cfg(feature = "serde")]
+mod serialize;
+pub mod hazmat;
+pub mod backend;
+pub mod simple;
+pub mod signature;
+pub mod ring;
+pub mod curve;
+pub mod error;
+pub mod policy;
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+pub mod exchange;
 
+pub use crate::sm2::ecc::{C1Prefix, Crypto, DEFAULT_USER_ID, Elliptic, EllipticBuilder, Encoding, Mode, Nonce, Signature, Verifier};
+pub use crate::sm2::error::VerifyError;
+#[cfg(feature = "sm2-encrypt")]
+pub use crate::sm2::ecc::{Ciphertext, DecryptError, Decryption, Decryptor, Encryption, Encryptor};
+#[cfg(feature = "sm2-sign")]
+pub use crate::sm2::ecc::Signer;
+pub use crate::sm2::field::Fp256;
+pub use crate::sm2::format::PublicKeyFormat;
+pub use crate::sm2::point::{Point, Scalar};
+pub use crate::sm2::key::{HexKey, KeyError, PublicKey};
+/// SM2推荐曲线参数的大端序字节，可用于常量上下文
+pub use crate::sm2::p256::params::{EC_A, EC_B, EC_GX, EC_GY, EC_N, EC_P};
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+pub use crate::sm2::key::{KeyGenerator, KeyPair, PrivateKey};
+#[cfg(all(any(feature = "sm2-sign", feature = "sm2-encrypt"), feature = "sm4"))]
+pub use crate::sm2::pbes2::{Pbes2, Pbes2Cipher, DEFAULT_ITERATIONS, MAX_ITERATIONS};
 
+
+/// SM2推荐曲线参数
+pub fn curve() -> Elliptic {
+    P256Elliptic::init().ec.clone()
+}
+
+/// 计算用户杂凑值ZA = SM3(ENTLA ∥ IDA ∥ a ∥ b ∥ xG ∥ yG ∥ xA ∥ yA)
+///
+/// 签名时待签名摘要为 e = SM3(ZA ∥ M)，可在主机侧计算后交由密码设备完成标量运算。
+/// 未约定用户ID时使用默认值[`DEFAULT_USER_ID`]。
+pub fn compute_za(id: &[u8], public_key: &PublicKey) -> [u8; 32] {
+    ecc::za(&P256Elliptic::init().ec, id, public_key)
+}
+
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
 pub fn generate_keypair() -> (String, String) {
-    let p256 = P256Elliptic::init();
-    let generator = KeyGenerator::init(Box::new(p256));
+    let generator = KeyGenerator::init(Box::new(P256Elliptic::init()));
     let pair = generator.gen_key_pair();
     (pair.prk().encode(), pair.puk().encode())
 }
 
+#[cfg(feature = "sm2-encrypt")]
 pub fn encrypt(public_key: &str, plain: &str) -> String {
     let crypto = Crypto::default();
     crypto.encryptor(PublicKey::decode(public_key)).execute(plain)
 }
 
+#[cfg(feature = "sm2-encrypt")]
 pub fn decrypt(private_key: &str, cipher: &str) -> String {
     let crypto = Crypto::default();
     crypto.decryptor(PrivateKey::decode(private_key)).execute(cipher)
 }
 
+#[cfg(feature = "sm2-encrypt")]
 pub fn encrypt_c1c2c3(public_key: &str, plain: &str) -> String {
     let crypto = Crypto::c1c2c3(Rc::new(P256Elliptic::init()));
     crypto.encryptor(PublicKey::decode(public_key)).execute(plain)
 }
 
+#[cfg(feature = "sm2-encrypt")]
 pub fn decrypt_c1c2c3(private_key: &str, cipher: &str) -> String {
     let crypto = Crypto::c1c2c3(Rc::new(P256Elliptic::init()));
     crypto.decryptor(PrivateKey::decode(private_key)).execute(cipher)
 }
 
+#[cfg(feature = "sm2-sign")]
 pub fn sign(private_key: &str, public_key: &str, plain: &str) -> String {
     let crypto = Crypto::default();
     let keypair = KeyPair::new(PrivateKey::decode(private_key), PublicKey::decode(public_key));
-    hex::encode(crypto.signer(keypair).sign(&plain).encode())
+    hex::encode(crypto.signer(keypair).sign(plain).encode())
 }
 
+/// 验证16进制编码的DER签名，公钥或签名格式不正确时返回false
 pub fn verify(public_key: &str, plain: &str, signature: &str) -> bool {
-    let crypto = Crypto::default();
-    let s = Signature::decode(hex::decode(signature).unwrap().as_slice());
-    crypto.verifier(PublicKey::decode(public_key)).verify(plain, &s)
+    let (Ok(public_key), Ok(signature)) = (PublicKey::try_decode(public_key), hex::decode(signature)) else {
+        return false;
+    };
+    let Some(signature) = Signature::try_decode(&signature) else {
+        return false;
+    };
+    Crypto::default().verifier(public_key).verify(plain, &signature)
 }
\ No newline at end of file
@@ -0,0 +1,319 @@
+use std::rc::Rc;
+#[cfg(feature = "bench")]
+use std::time::Duration;
+
+use num_bigint::{BigUint, ToBigInt};
+
+use crate::sm2::ecc::{Elliptic, EllipticBuilder};
+use crate::sm2::hazmat::ScalarBytesBe;
+use crate::sm2::key::to_32_bytes;
+use crate::sm2::p256::P256Elliptic;
+use crate::sm2::p256::payload::PayloadHelper;
+#[cfg(feature = "bench")]
+use crate::sm2::p256::point::op_counts;
+use crate::sm2::p256::point::P256AffinePoint;
+
+/// 标量乘法后端
+///
+/// 带有SM2硬件加速引擎（如部分SoC的PKE模块）的平台可实现此特征替换标量乘法，
+/// ZA、KDF、DER编解码等协议逻辑仍由本库完成。坐标与标量均为大端序，标量已模n约简。
+pub trait ScalarMulBackend {
+    /// 基点标量乘法 scalar·G
+    fn scalar_base_multiply(&self, scalar: &ScalarBytesBe) -> ([u8; 32], [u8; 32]);
+    /// 标量乘法 scalar·(x, y)
+    fn scalar_multiply(&self, x: &[u8; 32], y: &[u8; 32], scalar: &ScalarBytesBe) -> ([u8; 32], [u8; 32]);
+}
+
+/// 软件实现，即本库默认使用的P256标量乘法
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Software;
+
+impl ScalarMulBackend for Software {
+    fn scalar_base_multiply(&self, scalar: &ScalarBytesBe) -> ([u8; 32], [u8; 32]) {
+        let p256 = P256Elliptic::init();
+        bytes_of(p256.scalar_base_multiply(scalar.to_biguint()))
+    }
+
+    fn scalar_multiply(&self, x: &[u8; 32], y: &[u8; 32], scalar: &ScalarBytesBe) -> ([u8; 32], [u8; 32]) {
+        let p256 = P256Elliptic::init();
+        bytes_of(p256.scalar_multiply(BigUint::from_bytes_be(x), BigUint::from_bytes_be(y), scalar.to_biguint()))
+    }
+}
+
+/// Montgomery阶梯实现的标量乘法，常量时间且不使用预计算表
+///
+/// 比默认的窗口实现慢一倍左右，逻辑简单、便于审计，可作为窗口代码过大或不便审计时的备选：
+/// `Crypto::default().with_backend(Rc::new(Ladder))`。
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Ladder;
+
+impl ScalarMulBackend for Ladder {
+    fn scalar_base_multiply(&self, scalar: &ScalarBytesBe) -> ([u8; 32], [u8; 32]) {
+        let p256 = P256Elliptic::init();
+        self.scalar_multiply(&to_32_bytes(p256.ec.gx.to_bytes_be()), &to_32_bytes(p256.ec.gy.to_bytes_be()), scalar)
+    }
+
+    fn scalar_multiply(&self, x: &[u8; 32], y: &[u8; 32], scalar: &ScalarBytesBe) -> ([u8; 32], [u8; 32]) {
+        let point = P256AffinePoint::new(
+            PayloadHelper::transform(&BigUint::from_bytes_be(x).to_bigint().unwrap()),
+            PayloadHelper::transform(&BigUint::from_bytes_be(y).to_bigint().unwrap()),
+        );
+        bytes_of(point.multiply_ladder(&scalar.0).to_affine_point().restore())
+    }
+}
+
+/// 一次标量乘法中本库执行的倍点与点加次数
+///
+/// 只统计本库的软件点运算，由硬件等外部后端完成的运算不计入。
+#[cfg(feature = "bench")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OpCounts {
+    pub doublings: u64,
+    pub additions: u64,
+}
+
+/// 一次标量乘法的结果与度量
+#[cfg(feature = "bench")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Measurement {
+    /// 结果点的大端序坐标 (x, y)
+    pub point: ([u8; 32], [u8; 32]),
+    pub counts: OpCounts,
+    /// 耗时，wasm32-unknown-unknown没有单调时钟，为None
+    pub elapsed: Option<Duration>,
+}
+
+/// 带度量的标量乘法门面，用于比较各后端的开销，或检查不同标量下的运算次数是否一致
+///
+/// 需要启用`bench`特性，未启用时倍点与点加不计数。
+///
+/// ```
+/// use std::rc::Rc;
+/// use yarism::sm2::backend::{Instrumented, Ladder};
+/// use yarism::sm2::hazmat::ScalarBytesBe;
+///
+/// let ladder = Instrumented::new(Rc::new(Ladder));
+/// let m = ladder.scalar_base_multiply(&ScalarBytesBe([1; 32]));
+/// assert_eq!((m.counts.doublings, m.counts.additions), (256, 256));
+/// ```
+#[cfg(feature = "bench")]
+pub struct Instrumented {
+    backend: Rc<dyn ScalarMulBackend>,
+}
+
+#[cfg(feature = "bench")]
+impl Instrumented {
+    pub fn new(backend: Rc<dyn ScalarMulBackend>) -> Self {
+        Instrumented { backend }
+    }
+
+    /// 基点标量乘法 scalar·G
+    pub fn scalar_base_multiply(&self, scalar: &ScalarBytesBe) -> Measurement {
+        measure(|| self.backend.scalar_base_multiply(scalar))
+    }
+
+    /// 标量乘法 scalar·(x, y)
+    pub fn scalar_multiply(&self, x: &[u8; 32], y: &[u8; 32], scalar: &ScalarBytesBe) -> Measurement {
+        measure(|| self.backend.scalar_multiply(x, y, scalar))
+    }
+}
+
+#[cfg(feature = "bench")]
+impl Default for Instrumented {
+    fn default() -> Self {
+        Instrumented::new(Rc::new(Software))
+    }
+}
+
+#[cfg(feature = "bench")]
+fn measure(f: impl FnOnce() -> ([u8; 32], [u8; 32])) -> Measurement {
+    let (doublings, additions) = op_counts();
+    let timer = Timer::start();
+    let point = f();
+    let elapsed = timer.elapsed();
+    let (d, a) = op_counts();
+    Measurement { point, counts: OpCounts { doublings: d - doublings, additions: a - additions }, elapsed }
+}
+
+#[cfg(all(feature = "bench", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+struct Timer(std::time::Instant);
+
+#[cfg(all(feature = "bench", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+impl Timer {
+    fn start() -> Self {
+        Timer(std::time::Instant::now())
+    }
+
+    fn elapsed(&self) -> Option<Duration> {
+        Some(self.0.elapsed())
+    }
+}
+
+#[cfg(all(feature = "bench", target_arch = "wasm32", target_os = "unknown"))]
+struct Timer;
+
+#[cfg(all(feature = "bench", target_arch = "wasm32", target_os = "unknown"))]
+impl Timer {
+    fn start() -> Self {
+        Timer
+    }
+
+    fn elapsed(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// SM2推荐曲线上由指定后端完成标量乘法的椭圆曲线，点加仍使用软件实现
+pub(crate) struct Offload {
+    p256: &'static P256Elliptic,
+    backend: Rc<dyn ScalarMulBackend>,
+}
+
+impl Offload {
+    pub(crate) fn new(backend: Rc<dyn ScalarMulBackend>) -> Self {
+        Offload { p256: P256Elliptic::init(), backend }
+    }
+
+    fn reduce(&self, scalar: BigUint) -> ScalarBytesBe {
+        let n = &self.p256.blueprint().n;
+        ScalarBytesBe::from_biguint(&(scalar % n)).unwrap()
+    }
+}
+
+impl EllipticBuilder for Offload {
+    fn blueprint(&self) -> &Elliptic {
+        self.p256.blueprint()
+    }
+
+    fn point_add(&self, x1: BigUint, y1: BigUint, x2: BigUint, y2: BigUint) -> (BigUint, BigUint) {
+        self.p256.point_add(x1, y1, x2, y2)
+    }
+
+    fn scalar_multiply(&self, x: BigUint, y: BigUint, scalar: BigUint) -> (BigUint, BigUint) {
+        let (x, y) = (to_32_bytes(x.to_bytes_be()), to_32_bytes(y.to_bytes_be()));
+        biguint_of(self.backend.scalar_multiply(&x, &y, &self.reduce(scalar)))
+    }
+
+    fn scalar_base_multiply(&self, scalar: BigUint) -> (BigUint, BigUint) {
+        biguint_of(self.backend.scalar_base_multiply(&self.reduce(scalar)))
+    }
+}
+
+fn bytes_of((x, y): (BigUint, BigUint)) -> ([u8; 32], [u8; 32]) {
+    (to_32_bytes(x.to_bytes_be()), to_32_bytes(y.to_bytes_be()))
+}
+
+fn biguint_of((x, y): ([u8; 32], [u8; 32])) -> (BigUint, BigUint) {
+    (BigUint::from_bytes_be(&x), BigUint::from_bytes_be(&y))
+}
+
+
+#[cfg(all(test, feature = "sm2-sign", feature = "sm2-encrypt"))]
+mod tests {
+    use std::cell::Cell;
+
+    use crate::sm2::ecc::Crypto;
+    use crate::sm2::key::{HexKey, KeyPair, PrivateKey, PublicKey};
+
+    use super::*;
+
+    /// 统计调用次数的后端，模拟硬件引擎
+    #[derive(Default)]
+    struct Counting {
+        calls: Cell<usize>,
+    }
+
+    impl ScalarMulBackend for Counting {
+        fn scalar_base_multiply(&self, scalar: &ScalarBytesBe) -> ([u8; 32], [u8; 32]) {
+            self.calls.set(self.calls.get() + 1);
+            Software.scalar_base_multiply(scalar)
+        }
+
+        fn scalar_multiply(&self, x: &[u8; 32], y: &[u8; 32], scalar: &ScalarBytesBe) -> ([u8; 32], [u8; 32]) {
+            self.calls.set(self.calls.get() + 1);
+            Software.scalar_multiply(x, y, scalar)
+        }
+    }
+
+    #[test]
+    fn main() {
+        let prk = PrivateKey::decode("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e");
+        let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+        let data = b"offload";
+
+        let backend = Rc::new(Counting::default());
+        let crypto = Crypto::default().with_backend(backend.clone());
+
+        let signature = crypto.signer(KeyPair::new(prk.clone(), puk.clone())).sign_bytes(data);
+        assert!(Crypto::default().verifier(puk.clone()).verify_bytes(data, &signature));
+        assert!(crypto.verifier(puk.clone()).verify_bytes(data, &signature));
+
+        let cipher = crypto.encryptor(puk).encrypt_bytes(data);
+        assert_eq!(crypto.decryptor(prk).decrypt_bytes(&cipher), data);
+
+        // 签名1次，验签2次，校验加密公钥的阶1次，加密2次，解密1次
+        assert_eq!(backend.calls.get(), 7);
+    }
+
+    #[test]
+    fn ladder() {
+        use num_traits::Num;
+
+        let n = &P256Elliptic::init().ec.n;
+        let (x, y) = bytes_of(P256Elliptic::init().scalar_base_multiply(BigUint::from(7u8)));
+        let scalars = [
+            BigUint::from(0u8),
+            BigUint::from(1u8),
+            BigUint::from(2u8),
+            BigUint::from_str_radix("59276e27d506861a16680f3ad9c02dccef3cc1fa3cdbe4ce6d54b80deac1bc21", 16).unwrap(),
+            BigUint::from_str_radix("1000000000000000000000000000000000000000000000000000000000000000", 16).unwrap(),
+            // 阶梯中出现互为相反数的两点相加
+            n - BigUint::from(2u8),
+            n - BigUint::from(1u8),
+        ];
+        for k in scalars {
+            let scalar = ScalarBytesBe::from_biguint(&k).unwrap();
+            assert_eq!(Ladder.scalar_base_multiply(&scalar), Software.scalar_base_multiply(&scalar), "k = {:x}", k);
+            assert_eq!(Ladder.scalar_multiply(&x, &y, &scalar), Software.scalar_multiply(&x, &y, &scalar), "k = {:x}", k);
+        }
+
+        let prk = PrivateKey::decode("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e");
+        let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+        let crypto = Crypto::default().with_backend(Rc::new(Ladder));
+        let signature = crypto.signer(KeyPair::new(prk.clone(), puk.clone())).sign_bytes(b"ladder");
+        assert!(Crypto::default().verifier(puk.clone()).verify_bytes(b"ladder", &signature));
+        let cipher = Crypto::default().encryptor(puk).encrypt_bytes(b"ladder");
+        assert_eq!(crypto.decryptor(prk).decrypt_bytes(&cipher), b"ladder");
+    }
+    #[test]
+    #[cfg(feature = "bench")]
+    fn instrumented() {
+        use num_traits::Num;
+
+        let n = &P256Elliptic::init().ec.n;
+        let (x, y) = bytes_of(P256Elliptic::init().scalar_base_multiply(BigUint::from(7u8)));
+        let scalars: Vec<ScalarBytesBe> = [
+            BigUint::from(1u8),
+            BigUint::from(2u8),
+            BigUint::from_str_radix("59276e27d506861a16680f3ad9c02dccef3cc1fa3cdbe4ce6d54b80deac1bc21", 16).unwrap(),
+            n - BigUint::from(1u8),
+        ].iter().map(|k| ScalarBytesBe::from_biguint(k).unwrap()).collect();
+
+        let ladder = Instrumented::new(Rc::new(Ladder));
+        let software = Instrumented::default();
+        let base = software.scalar_base_multiply(&scalars[0]).counts;
+        let arbitrary = software.scalar_multiply(&x, &y, &scalars[0]).counts;
+        for scalar in &scalars {
+            let m = ladder.scalar_base_multiply(scalar);
+            assert_eq!(m.counts, OpCounts { doublings: 256, additions: 256 });
+            assert_eq!(m.point, Software.scalar_base_multiply(scalar));
+            assert!(m.elapsed.is_some());
+            assert_eq!(ladder.scalar_multiply(&x, &y, scalar).counts, m.counts);
+
+            // 窗口实现的运算次数同样与标量无关
+            assert_eq!(software.scalar_base_multiply(scalar).counts, base);
+            assert_eq!(software.scalar_multiply(&x, &y, scalar).counts, arbitrary);
+        }
+        assert!(base.additions > 0 && arbitrary.doublings > 0);
+    }
+}
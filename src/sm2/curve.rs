@@ -0,0 +1,316 @@
+//! 自定义参数的素域短Weierstrass曲线 y² = x³ + ax + b
+//!
+//! SM2推荐曲线使用定长limb与预计算表，其他曲线可由[`Weierstrass`]以大整数运算实现[`EllipticBuilder`]，
+//! 交给[`Crypto::c1c3c2`](crate::sm2::Crypto::c1c3c2)等构造函数后即可生成密钥、加解密与签名验签。
+//!
+//! 坐标与标量按32字节编码，p不超过256比特。大整数运算不是常量时间，不适用于需要抵抗计时侧信道的场合。
+
+use std::fmt::{Display, Formatter};
+
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::Zero;
+
+use crate::error_code::{Code, ErrorCode};
+use crate::sm2::ecc::{on_curve, Elliptic, EllipticBuilder};
+use crate::sm2::key::{KeyError, PublicKey};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CurveError {
+    /// p不是大于3的奇数、超过256比特，或`bits`小于p的比特数
+    InvalidField,
+    /// a、b或基点坐标不小于p
+    ParameterOutOfRange,
+    /// 4a³ + 27b² ≡ 0 (mod p)，曲线奇异
+    Singular,
+    /// 基点不在曲线上
+    BaseNotOnCurve,
+    /// n ≤ 2或nG不是无穷远点
+    InvalidOrder,
+}
+
+impl Display for CurveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CurveError::InvalidField => write!(f, "The field prime must be an odd number greater than 3 of at most 256 bits."),
+            CurveError::ParameterOutOfRange => write!(f, "The curve parameters must be less than the field prime."),
+            CurveError::Singular => write!(f, "The curve is singular."),
+            CurveError::BaseNotOnCurve => write!(f, "The base point is not on the curve."),
+            CurveError::InvalidOrder => write!(f, "The order of the base point is invalid."),
+        }
+    }
+}
+
+impl std::error::Error for CurveError {}
+
+impl ErrorCode for CurveError {
+    fn code(&self) -> Code {
+        match self {
+            CurveError::InvalidField => Code::NOT_SUPPORTED,
+            _ => Code::INVALID_DATA,
+        }
+    }
+}
+
+/// 由参数 (p, a, b, G, n) 定义的曲线
+///
+/// ```
+/// # #[cfg(feature = "sm2-encrypt")] {
+/// use std::rc::Rc;
+/// use yarism::sm2::{curve, Crypto, KeyGenerator};
+/// use yarism::sm2::curve::Weierstrass;
+///
+/// let curve = Weierstrass::new(curve()).unwrap();
+/// let keypair = KeyGenerator::init(Box::new(curve.clone())).gen_key_pair();
+/// let crypto = Crypto::c1c3c2(Rc::new(curve));
+/// let cipher = crypto.encryptor(keypair.puk().clone()).encrypt_bytes(b"custom");
+/// assert_eq!(crypto.decryptor(keypair.prk().clone()).decrypt_bytes(&cipher), b"custom");
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Weierstrass {
+    ec: Elliptic,
+}
+
+/// Jacobian坐标 (X, Y, Z)，仿射坐标为 (X/Z², Y/Z³)，Z = 0 表示无穷远点
+#[derive(Clone)]
+struct Jacobian(BigUint, BigUint, BigUint);
+
+impl Weierstrass {
+    /// 校验参数后创建曲线，不检查p与n是否为素数
+    pub fn new(ec: Elliptic) -> Result<Self, CurveError> {
+        let p = &ec.p;
+        if p.bits() > 256 || (ec.bits as u64) < p.bits() || p <= &BigUint::from(3u8) || p.is_even() {
+            return Err(CurveError::InvalidField);
+        }
+        if [&ec.a, &ec.b, &ec.gx, &ec.gy].into_iter().any(|v| v >= p) {
+            return Err(CurveError::ParameterOutOfRange);
+        }
+        let discriminant = (&ec.a * &ec.a % p * &ec.a * 4u8 + &ec.b * &ec.b * 27u8) % p;
+        if discriminant.is_zero() {
+            return Err(CurveError::Singular);
+        }
+        if !on_curve(&ec, &ec.gx, &ec.gy) {
+            return Err(CurveError::BaseNotOnCurve);
+        }
+        let curve = Weierstrass { ec };
+        let base = Jacobian::from_affine(&curve.ec.gx, &curve.ec.gy);
+        if curve.ec.n <= BigUint::from(2u8) || !curve.multiply(&base, &curve.ec.n).2.is_zero() {
+            return Err(CurveError::InvalidOrder);
+        }
+        Ok(curve)
+    }
+
+    /// 由坐标创建本曲线上的公钥，校验方式同[`PublicKey::validate`]
+    pub fn public_key(&self, x: BigUint, y: BigUint) -> Result<PublicKey, KeyError> {
+        let key = PublicKey::new(x, y);
+        key.validate(self)?;
+        Ok(key)
+    }
+
+    fn sub(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        (a + &self.ec.p - b) % &self.ec.p
+    }
+
+    /// [dbl-1998-cmo-2](https://hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html#doubling-dbl-1998-cmo-2)
+    fn double(&self, q: &Jacobian) -> Jacobian {
+        if q.2.is_zero() || q.1.is_zero() {
+            return Jacobian::infinity();
+        }
+        let p = &self.ec.p;
+        let (x, y, z) = (&q.0, &q.1, &q.2);
+        let yy = y * y % p;
+        let zz = z * z % p;
+        let s = x * &yy * 4u8 % p;
+        let m = (x * x * 3u8 + &self.ec.a * &zz % p * &zz) % p;
+        let x3 = self.sub(&(&m * &m % p), &(&s * 2u8 % p));
+        let y3 = self.sub(&(&m * self.sub(&s, &x3) % p), &(&yy * &yy * 8u8 % p));
+        let z3 = y * z * 2u8 % p;
+        Jacobian(x3, y3, z3)
+    }
+
+    /// [add-1998-cmo-2](https://hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html#addition-add-1998-cmo-2)，
+    /// 另行处理无穷远点、P + P 与 P + (-P)
+    fn add(&self, a: &Jacobian, b: &Jacobian) -> Jacobian {
+        if a.2.is_zero() {
+            return b.clone();
+        }
+        if b.2.is_zero() {
+            return a.clone();
+        }
+        let p = &self.ec.p;
+        let (z1z1, z2z2) = (&a.2 * &a.2 % p, &b.2 * &b.2 % p);
+        let (u1, u2) = (&a.0 * &z2z2 % p, &b.0 * &z1z1 % p);
+        let s1 = &a.1 * &b.2 % p * &z2z2 % p;
+        let s2 = &b.1 * &a.2 % p * &z1z1 % p;
+        let (h, r) = (self.sub(&u2, &u1), self.sub(&s2, &s1));
+        if h.is_zero() {
+            return match r.is_zero() {
+                true => self.double(a),
+                false => Jacobian::infinity(),
+            };
+        }
+        let hh = &h * &h % p;
+        let hhh = &h * &hh % p;
+        let v = &u1 * &hh % p;
+        let x3 = self.sub(&self.sub(&(&r * &r % p), &hhh), &(&v * 2u8 % p));
+        let y3 = self.sub(&(&r * self.sub(&v, &x3) % p), &(&s1 * &hhh % p));
+        let z3 = &a.2 * &b.2 % p * &h % p;
+        Jacobian(x3, y3, z3)
+    }
+
+    /// Montgomery阶梯，每一比特各做一次点加与倍点
+    fn multiply(&self, point: &Jacobian, scalar: &BigUint) -> Jacobian {
+        let (mut r0, mut r1) = (Jacobian::infinity(), point.clone());
+        for i in (0..scalar.bits()).rev() {
+            if scalar.bit(i) {
+                r0 = self.add(&r0, &r1);
+                r1 = self.double(&r1);
+            } else {
+                r1 = self.add(&r0, &r1);
+                r0 = self.double(&r0);
+            }
+        }
+        r0
+    }
+
+    /// 无穷远点返回(0, 0)
+    fn to_affine(&self, q: &Jacobian) -> (BigUint, BigUint) {
+        if q.2.is_zero() {
+            return (BigUint::zero(), BigUint::zero());
+        }
+        let p = &self.ec.p;
+        let z = q.2.modpow(&(p - 2u8), p);
+        let zz = &z * &z % p;
+        (&q.0 * &zz % p, &q.1 * &zz % p * &z % p)
+    }
+}
+
+impl Jacobian {
+    fn infinity() -> Self {
+        Jacobian(BigUint::zero(), BigUint::from(1u8), BigUint::zero())
+    }
+
+    /// (0, 0) 视为无穷远点
+    fn from_affine(x: &BigUint, y: &BigUint) -> Self {
+        match x.is_zero() && y.is_zero() {
+            true => Jacobian::infinity(),
+            false => Jacobian(x.clone(), y.clone(), BigUint::from(1u8)),
+        }
+    }
+}
+
+impl EllipticBuilder for Weierstrass {
+    fn blueprint(&self) -> &Elliptic {
+        &self.ec
+    }
+
+    fn point_add(&self, x1: BigUint, y1: BigUint, x2: BigUint, y2: BigUint) -> (BigUint, BigUint) {
+        self.to_affine(&self.add(&Jacobian::from_affine(&x1, &y1), &Jacobian::from_affine(&x2, &y2)))
+    }
+
+    fn scalar_multiply(&self, x: BigUint, y: BigUint, scalar: BigUint) -> (BigUint, BigUint) {
+        let scalar = self.ec.scalar_reduce(scalar);
+        self.to_affine(&self.multiply(&Jacobian::from_affine(&x, &y), &scalar))
+    }
+
+    fn scalar_base_multiply(&self, scalar: BigUint) -> (BigUint, BigUint) {
+        self.scalar_multiply(self.ec.gx.clone(), self.ec.gy.clone(), scalar)
+    }
+}
+
+
+#[cfg(all(test, feature = "sm2-sign", feature = "sm2-encrypt"))]
+mod tests {
+    use std::rc::Rc;
+
+    use num_traits::{Num, One};
+
+    use crate::sm2::ecc::Crypto;
+    use crate::sm2::key::KeyGenerator;
+    use crate::sm2::p256::P256Elliptic;
+
+    use super::*;
+
+    fn hex(value: &str) -> BigUint {
+        BigUint::from_str_radix(value, 16).unwrap()
+    }
+
+    fn secp256k1() -> Elliptic {
+        Elliptic {
+            p: hex("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"),
+            a: BigUint::zero(),
+            b: BigUint::from(7u8),
+            gx: hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"),
+            gy: hex("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"),
+            n: hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"),
+            bits: 256,
+        }
+    }
+
+    /// 与SM2推荐曲线的limb实现对照
+    #[test]
+    fn sm2_parameters() {
+        let p256 = P256Elliptic::init();
+        let curve = Weierstrass::new(p256.ec.clone()).unwrap();
+        let k = hex("59276e27d506861a16680f3ad9c02dccef3cc1fa3cdbe4ce6d54b80deac1bc21");
+        let q = p256.scalar_base_multiply(BigUint::from(7u8));
+        assert_eq!(curve.scalar_base_multiply(k.clone()), p256.scalar_base_multiply(k.clone()));
+        assert_eq!(curve.scalar_multiply(q.0.clone(), q.1.clone(), k.clone()), p256.scalar_multiply(q.0.clone(), q.1.clone(), k));
+        assert_eq!(curve.point_add(q.0.clone(), q.1.clone(), q.0.clone(), q.1.clone()), p256.scalar_base_multiply(BigUint::from(14u8)));
+        let g = curve.scalar_base_multiply(BigUint::one());
+        assert_eq!(curve.point_add(g.0, g.1, q.0, q.1), p256.scalar_base_multiply(BigUint::from(8u8)));
+    }
+
+    #[test]
+    fn secp256k1_vectors() {
+        let curve = Weierstrass::new(secp256k1()).unwrap();
+        let two = curve.scalar_base_multiply(BigUint::from(2u8));
+        assert_eq!(two.0, hex("c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5"));
+        assert_eq!(two.1, hex("1ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a"));
+        let k = hex("59276e27d506861a16680f3ad9c02dccef3cc1fa3cdbe4ce6d54b80deac1bc21");
+        let q = curve.scalar_base_multiply(k);
+        assert_eq!(q.0, hex("bc071931f8c6bddfdb4aa58ac89c28e25dbc72c3cd4f5bb6f251be1e9f3237d7"));
+        assert_eq!(q.1, hex("32456579e63e06db99c669d41676d136ab0381f476023e90572e6edac55330f5"));
+
+        let g = (curve.ec.gx.clone(), curve.ec.gy.clone());
+        let neg = (g.0.clone(), &curve.ec.p - &g.1);
+        assert_eq!(curve.point_add(g.0.clone(), g.1.clone(), neg.0, neg.1), (BigUint::zero(), BigUint::zero()));
+        assert_eq!(curve.point_add(g.0.clone(), g.1.clone(), BigUint::zero(), BigUint::zero()), g);
+    }
+
+    #[test]
+    fn crypto() {
+        let curve = Weierstrass::new(secp256k1()).unwrap();
+        let keypair = KeyGenerator::init(Box::new(curve.clone())).gen_key_pair();
+        let (x, y) = keypair.puk().value();
+        let puk = curve.public_key(x.clone(), y.clone()).unwrap();
+        assert_eq!(curve.public_key(x, &y + 1u8).err(), Some(KeyError::NotOnCurve));
+        assert!(keypair.verify_consistency_with(&curve));
+
+        let crypto = Crypto::c1c3c2(Rc::new(curve));
+        let cipher = crypto.encryptor(puk.clone()).encrypt_bytes(b"secp256k1");
+        assert_eq!(crypto.decryptor(keypair.prk().clone()).decrypt_bytes(&cipher), b"secp256k1");
+        assert!(Crypto::default().decryptor(keypair.prk().clone()).try_decrypt_bytes(&cipher).is_err());
+
+        let signature = crypto.signer(keypair).sign_bytes(b"secp256k1");
+        assert_eq!(crypto.verifier(puk.clone()).check_bytes(b"secp256k1", &signature), Ok(()));
+        assert!(!crypto.verifier(puk).verify_bytes(b"other", &signature));
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        let check = |f: fn(&mut Elliptic)| {
+            let mut ec = secp256k1();
+            f(&mut ec);
+            Weierstrass::new(ec).unwrap_err()
+        };
+        assert_eq!(check(|ec| ec.p = BigUint::from(1u8) << 256), CurveError::InvalidField);
+        assert_eq!(check(|ec| ec.bits = 255), CurveError::InvalidField);
+        assert_eq!(check(|ec| ec.b = ec.p.clone()), CurveError::ParameterOutOfRange);
+        assert_eq!(check(|ec| ec.b = BigUint::zero()), CurveError::Singular);
+        assert_eq!(check(|ec| ec.gy += 1u8), CurveError::BaseNotOnCurve);
+        assert_eq!(check(|ec| ec.n -= 1u8), CurveError::InvalidOrder);
+        assert_eq!(CurveError::Singular.code(), Code::INVALID_DATA);
+    }
+}
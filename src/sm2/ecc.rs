@@ -1,19 +1,49 @@
 use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
-use std::ops::{Add, Mul, Sub};
+use std::ops::{Add, Sub};
 use std::rc::Rc;
 
-use num_bigint::{BigInt, BigUint, ToBigInt};
+use num_bigint::BigUint;
 use num_integer::Integer;
 use num_traits::{One, Zero};
-
-use crate::sm2::key::{KeyPair, PrivateKey, PublicKey, to_32_bytes};
+use rand::{CryptoRng, RngCore};
+use subtle::{Choice, ConstantTimeEq};
+#[cfg(feature = "sm2-encrypt")]
+use base64::Engine;
+#[cfg(feature = "sm2-encrypt")]
+use base64::engine::general_purpose::STANDARD;
+
+#[cfg(feature = "sm2-encrypt")]
+use crate::ct::ct_eq;
+use crate::codec::{decode_base64url, encode_base64url, read_tlv, report, DecodeError, DEFAULT_MAX_LEN};
+#[cfg(feature = "sm2-encrypt")]
+use crate::codec::SizeLimitError;
+use crate::sm2::backend::{Offload, ScalarMulBackend};
+use crate::sm2::error::VerifyError;
+use crate::sm2::key::{PublicKey, to_32_bytes};
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+use crate::sm2::key::KeyError;
+#[cfg(feature = "sm2-encrypt")]
+use crate::sm2::key::PrivateKey;
+#[cfg(feature = "sm2-sign")]
+use crate::sm2::key::KeyPair;
 use crate::sm2::p256::P256Elliptic;
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+use crate::sm2::policy::KeyUsage;
+use crate::sm2::policy::KeyPolicy;
+#[cfg(feature = "sm2-sign")]
+use crate::sm2::p256::params::EC_N;
+#[cfg(feature = "sm2-sign")]
+use crate::sm2::p256::scalar::Scalar;
+#[cfg(feature = "sm2-sign")]
+use crate::sm2::rfc6979::NonceGenerator;
+use crate::env::EnvRng;
 use crate::sm3;
+#[cfg(feature = "sm2-encrypt")]
+use crate::error_code::{Code, ErrorCode};
 
-const UID: [u8; 16] = [
-    0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38,
-];
+/// 未约定用户ID时使用的默认值
+pub const DEFAULT_USER_ID: &[u8] = b"1234567812345678";
 
 pub trait EllipticBuilder {
     fn blueprint(&self) -> &Elliptic;
@@ -24,12 +54,26 @@ pub trait EllipticBuilder {
     fn scalar_multiply(&self, x: BigUint, y: BigUint, scalar: BigUint) -> (BigUint, BigUint);
     /// 基点标量乘法
     fn scalar_base_multiply(&self, scalar: BigUint) -> (BigUint, BigUint);
+    /// 公钥标量乘法，实现可复用公钥中缓存的坐标形式
+    fn scalar_multiply_key(&self, key: &PublicKey, scalar: BigUint) -> (BigUint, BigUint) {
+        let (x, y) = key.value();
+        self.scalar_multiply(x, y, scalar)
+    }
+    /// 验签所需的 sG + tP，结果为无穷远点时返回None；实现可交错两次标量乘法共用倍点运算
+    fn double_scalar_multiply(&self, s: BigUint, key: &PublicKey, t: BigUint) -> Option<(BigUint, BigUint)> {
+        let p1 = self.scalar_base_multiply(s);
+        let p2 = self.scalar_multiply_key(key, t);
+        if p1.0 == p2.0 && p1.1.clone().add(&p2.1).mod_floor(&self.blueprint().p).is_zero() {
+            return None;
+        }
+        Some(self.point_add(p1.0, p1.1, p2.0, p2.1))
+    }
 }
 
 /// 使用SM2椭圆曲线公钥密码算法推荐曲线参数
 ///
 /// y^2 = x^3 + ax + b
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Elliptic {
     pub p: BigUint,
     pub a: BigUint,
@@ -43,6 +87,11 @@ pub struct Elliptic {
 impl Elliptic {
     /// 随机数 【from, to】
     pub fn random(&self, from: BigUint, to: BigUint) -> BigUint {
+        self.random_with(&mut EnvRng, from, to)
+    }
+
+    /// 同[`Elliptic::random`]，随机字节取自`rng`
+    pub fn random_with(&self, rng: &mut (impl CryptoRng + RngCore), from: BigUint, to: BigUint) -> BigUint {
         let temp = match from.clone().cmp(&to) {
             Ordering::Greater => from.clone().sub(&to),
             Ordering::Less => to.clone().sub(&from),
@@ -54,7 +103,8 @@ impl Elliptic {
         }
 
         let k = {
-            let k: Vec<u8> = (0..self.bits / 8 + 8).map(|_| { rand::random::<u8>() }).collect();
+            let mut k = vec![0u8; self.bits / 8 + 8];
+            rng.fill_bytes(&mut k);
             BigUint::from_bytes_be(&k)
         };
 
@@ -64,6 +114,28 @@ impl Elliptic {
         k.mod_floor(&temp).add(&from)
     }
 
+    /// 曲线参数的规范序列化：bits(2字节，大端序) ‖ p ‖ a ‖ b ‖ gx ‖ gy ‖ n，
+    /// 各参数按大端序左补0至`bits / 8`字节（向上取整）
+    pub fn to_canonical_bytes(&self) -> Vec<u8> {
+        let len = (self.bits + 7) / 8;
+        let mut out = Vec::with_capacity(2 + len * 6);
+        out.extend_from_slice(&(self.bits as u16).to_be_bytes());
+        for e in [&self.p, &self.a, &self.b, &self.gx, &self.gy, &self.n] {
+            let bytes = e.to_bytes_be();
+            if bytes.len() > len {
+                panic!("The curve parameter exceeds {} bits.", self.bits)
+            }
+            out.resize(out.len() + len - bytes.len(), 0);
+            out.extend_from_slice(&bytes);
+        }
+        out
+    }
+
+    /// 曲线参数指纹：规范序列化结果的SM3摘要，可用于确认通信双方使用相同的曲线常量
+    pub fn fingerprint(&self) -> [u8; 32] {
+        sm3::hash(&self.to_canonical_bytes())
+    }
+
     pub fn scalar_reduce(&self, scalar: BigUint) -> BigUint {
         // compare scalar and order, n = (scalar mod order) if scalar > order else scalar
         if let Ordering::Greater = scalar.cmp(&self.n) {
@@ -74,126 +146,301 @@ impl Elliptic {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-enum Mode {
+/// 密文分量排列顺序，GM/T 0009-2012之后的标准均为C1C3C2
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Mode {
     C1C2C3,
+    #[default]
     C1C3C2,
 }
 
+/// 密文编码
+///
+/// - Raw：原始字节 04 ‖ C1 ‖ C3 ‖ C2（或C1C2C3）
+/// - Hex：Raw的16进制字符串
+/// - Base64：Raw的Base64字符串
+/// - Der：GM/T 0009 SM2Cipher ::= SEQUENCE { x INTEGER, y INTEGER, hash OCTET STRING, cipherText OCTET STRING }，
+///   分量顺序由结构决定，与Mode无关
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Encoding {
+    Raw,
+    #[default]
+    Hex,
+    Base64,
+    Der,
+}
+
+/// 签名随机数k的来源
+///
+/// - Random：取自系统随机数
+/// - Deterministic：按RFC 6979以HMAC-SM3由私钥和待签名摘要派生，同一消息的签名结果固定
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Nonce {
+    #[default]
+    Random,
+    Deterministic,
+}
+
+/// 解密时对C1首字节0x04的要求
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum C1Prefix {
+    /// C1必须为65字节：04 ‖ x1 ‖ y1
+    #[default]
+    Required,
+    /// 同时接受65字节与省略04前缀的64字节C1，部分.NET、JS实现输出后者
+    Optional,
+}
+
+#[cfg_attr(not(feature = "sm2-encrypt"), allow(dead_code))]
 pub struct Crypto {
     mode: Mode,
+    encoding: Encoding,
+    c1_prefix: C1Prefix,
+    nonce: Nonce,
+    user_id: Vec<u8>,
+    max_len: usize,
+    policy: Option<Rc<dyn KeyPolicy>>,
     builder: Rc<dyn EllipticBuilder>,
 }
 
-impl Crypto {
-    pub fn default() -> Self {
+impl Default for Crypto {
+    fn default() -> Self {
         Self::c1c3c2(Rc::new(P256Elliptic::init()))
     }
+}
+
+impl Crypto {
+    /// 使用SM2推荐曲线以及指定的密文分量顺序
+    pub fn new(mode: Mode) -> Self {
+        Self::with_mode(mode, Rc::new(P256Elliptic::init()))
+    }
 
     pub fn c1c2c3(builder: Rc<dyn EllipticBuilder>) -> Self {
-        Crypto { mode: Mode::C1C2C3, builder }
+        Self::with_mode(Mode::C1C2C3, builder)
     }
 
     pub fn c1c3c2(builder: Rc<dyn EllipticBuilder>) -> Self {
-        Crypto { mode: Mode::C1C3C2, builder }
+        Self::with_mode(Mode::C1C3C2, builder)
+    }
+
+    /// 各构造函数共用，其余设置均取默认值
+    fn with_mode(mode: Mode, builder: Rc<dyn EllipticBuilder>) -> Self {
+        Crypto {
+            mode,
+            encoding: Encoding::default(),
+            c1_prefix: C1Prefix::default(),
+            nonce: Nonce::default(),
+            user_id: DEFAULT_USER_ID.to_vec(),
+            max_len: DEFAULT_MAX_LEN,
+            policy: None,
+            builder,
+        }
+    }
+
+    /// 设置加密结果以及解密输入的密文编码，默认为Hex
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// 使用指定的标量乘法后端，曲线参数为SM2推荐曲线
+    pub fn with_backend(mut self, backend: Rc<dyn ScalarMulBackend>) -> Self {
+        self.builder = Rc::new(Offload::new(backend));
+        self
+    }
+
+    /// 设置签名、验签计算ZA时使用的用户ID，默认为`1234567812345678`
+    pub fn with_user_id(mut self, user_id: &[u8]) -> Self {
+        if user_id.len() >= 8192 {
+            panic!("UID is too large.");
+        }
+        self.user_id = user_id.to_vec();
+        self
+    }
+
+    /// 设置解密时对C1前缀的要求，默认为Required
+    pub fn with_c1_prefix(mut self, c1_prefix: C1Prefix) -> Self {
+        self.c1_prefix = c1_prefix;
+        self
+    }
+
+    /// 设置签名随机数k的来源，默认为Random
+    pub fn with_nonce(mut self, nonce: Nonce) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    /// 设置加密明文与解密所得明文的长度上限，默认为[`DEFAULT_MAX_LEN`]
+    ///
+    /// 超出上限的输入在分配KDF输出与编解码缓冲区之前即被拒绝。
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// 创建签名器、加密器前以`policy`检查密钥，默认不检查
+    pub fn with_policy(mut self, policy: Rc<dyn KeyPolicy>) -> Self {
+        self.policy = Some(policy);
+        self
     }
 
+    /// 公钥未通过[`PublicKey::validate`]时panic
+    #[cfg(feature = "sm2-encrypt")]
     pub fn encryptor(&self, key: PublicKey) -> Encryptor {
-        Encryptor { key, mode: self.mode, builder: self.builder.clone() }
+        match self.try_encryptor(key) {
+            Ok(encryptor) => encryptor,
+            Err(e) => panic!("The public key is invalid. {}", e),
+        }
+    }
+
+    /// 校验公钥并通过密钥策略检查后创建加密器
+    #[cfg(feature = "sm2-encrypt")]
+    pub fn try_encryptor(&self, key: PublicKey) -> Result<Encryptor, KeyError> {
+        key.validate(self.builder.as_ref())?;
+        self.check_policy(&key, KeyUsage::Encrypt)?;
+        Ok(Encryptor { key, mode: self.mode, encoding: self.encoding, max_len: self.max_len, builder: self.builder.clone() })
     }
 
+    #[cfg(feature = "sm2-encrypt")]
     pub fn decryptor(&self, key: PrivateKey) -> Decryptor {
-        Decryptor { key, mode: self.mode, builder: self.builder.clone() }
+        Decryptor { key, mode: self.mode, encoding: self.encoding, c1_prefix: self.c1_prefix, max_len: self.max_len, builder: self.builder.clone() }
     }
 
+    /// 密钥未通过密钥策略检查时panic
+    #[cfg(feature = "sm2-sign")]
     pub fn signer(&self, keypair: KeyPair) -> Signer {
-        let za = self.digest(keypair.puk().clone());
-        Signer { hash: za, keypair, builder: self.builder.clone() }
+        match self.try_signer(keypair) {
+            Ok(signer) => signer,
+            Err(e) => panic!("The key pair is rejected. {}", e),
+        }
+    }
+
+    /// 通过密钥策略检查后创建签名器
+    #[cfg(feature = "sm2-sign")]
+    pub fn try_signer(&self, keypair: KeyPair) -> Result<Signer, KeyError> {
+        self.check_policy(keypair.puk(), KeyUsage::Sign)?;
+        let prefix = self.digest(keypair.puk());
+        Ok(Signer { prefix, keypair, nonce: self.nonce, builder: self.builder.clone() })
     }
 
     pub fn verifier(&self, key: PublicKey) -> Verifier {
-        let za = self.digest(key.clone());
-        Verifier { hash: za, key, builder: self.builder.clone() }
+        let prefix = self.digest(&key);
+        Verifier { prefix, key, builder: self.builder.clone() }
     }
 
-    /// ZA=H256(ENTLA ∥ IDA ∥ a ∥ b ∥ xG ∥ yG ∥xA ∥yA)
-    fn digest(&self, puk: PublicKey) -> Vec<u8> {
-        let ent = {
-            if UID.len() >= 8192 {
-                panic!("UID is too large.");
-            }
-            let r = UID.len() * 8;
-            [((r >> 8) & 0xFF) as u8, (r & 0xFF) as u8].to_vec()
-        };
+    #[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+    fn check_policy(&self, key: &PublicKey, usage: KeyUsage) -> Result<(), KeyError> {
+        match &self.policy {
+            Some(policy) => policy.check(key, usage).map_err(KeyError::PolicyViolation),
+            None => Ok(()),
+        }
+    }
 
-        let id = UID.to_vec();
-        let e = self.builder.blueprint();
-        let (a, b) = (e.a.to_bytes_be(), e.a.to_bytes_be());
-        let (gx, gy) = (e.gx.to_bytes_be(), e.gy.to_bytes_be());
+    /// 已输入ZA的SM3状态，签名、验签时复制后继续输入消息
+    fn digest(&self, puk: &PublicKey) -> sm3::Hasher {
+        let mut hasher = sm3::Hasher::new();
+        hasher.update(&za(self.builder.blueprint(), &self.user_id, puk));
+        hasher
+    }
+}
 
-        let (px, py) = {
-            let key = puk.value();
-            let (x, y) = (key.0.to_bytes_be(), key.1.to_bytes_be());
-            (to_32_bytes(x).to_vec(), to_32_bytes(y).to_vec())
-        };
+/// e = SM3(ZA ∥ M)，prefix为已输入ZA的SM3状态
+fn digest(prefix: &sm3::Hasher, data: &[u8]) -> [u8; 32] {
+    let mut hasher = prefix.clone();
+    hasher.update(data);
+    hasher.finalize()
+}
 
-        sm3::hash([ent, id, a, b, gx, gy, px, py].concat().as_slice()).to_vec()
+/// ZA=H256(ENTLA ∥ IDA ∥ a ∥ b ∥ xG ∥ yG ∥xA ∥yA)
+pub(crate) fn za(e: &Elliptic, id: &[u8], puk: &PublicKey) -> [u8; 32] {
+    let ent = {
+        if id.len() >= 8192 {
+            panic!("UID is too large.");
+        }
+        let r = id.len() * 8;
+        [((r >> 8) & 0xFF) as u8, (r & 0xFF) as u8]
+    };
+
+    let mut hasher = sm3::Hasher::new();
+    hasher.update(&ent).update(id);
+    for v in [&e.a, &e.b, &e.gx, &e.gy] {
+        hasher.update(&to_32_bytes(v.to_bytes_be()));
     }
+    let (x, y) = puk.value();
+    hasher.update(&to_32_bytes(x.to_bytes_be())).update(&to_32_bytes(y.to_bytes_be()));
+    hasher.finalize()
 }
 
+#[cfg(feature = "sm2-encrypt")]
 pub trait Encryption {
+    /// 加密字符串，Hex、Base64编码直接返回对应字符串，Raw、Der编码返回字节的16进制字符串
     fn execute(&self, plain: &str) -> String;
 }
 
+#[cfg(feature = "sm2-encrypt")]
 pub trait Decryption {
+    /// 解密字符串，密文字符串的约定与[`Encryption::execute`]一致
     fn execute(&self, cipher: &str) -> String;
 }
 
+#[cfg(feature = "sm2-encrypt")]
 pub struct Encryptor {
     mode: Mode,
+    encoding: Encoding,
     key: PublicKey,
+    max_len: usize,
     builder: Rc<dyn EllipticBuilder>,
 }
 
-impl Encryption for Encryptor {
-    /// 加密
-    fn execute(&self, plain: &str) -> String {
-        let data = plain.as_bytes();
-        let cipher = loop {
+#[cfg(feature = "sm2-encrypt")]
+impl Encryptor {
+    /// 加密字节串，返回密文原始字节：04 ‖ C1 ‖ C3 ‖ C2 或 04 ‖ C1 ‖ C2 ‖ C3
+    pub fn encrypt_bytes(&self, data: &[u8]) -> Vec<u8> {
+        self.encrypt_bytes_with_rng(data, &mut EnvRng)
+    }
+
+    /// 同[`Encryptor::encrypt_bytes`]，明文超过长度上限时返回错误而不是panic
+    pub fn try_encrypt_bytes(&self, data: &[u8]) -> Result<Vec<u8>, SizeLimitError> {
+        SizeLimitError::check(PLAIN, data.len(), self.max_len)?;
+        Ok(self.encrypt_bytes(data))
+    }
+
+    /// 同[`Encryptor::encrypt_bytes`]，随机数k取自`rng`
+    pub fn encrypt_bytes_with_rng(&self, data: &[u8], rng: &mut (impl CryptoRng + RngCore)) -> Vec<u8> {
+        if let Err(e) = SizeLimitError::check(PLAIN, data.len(), self.max_len) {
+            panic!("{}", e)
+        }
+        loop {
             let k = {
                 let elliptic = self.builder.blueprint();
                 let from = BigUint::one();
-                elliptic.random(from.clone(), elliptic.n.clone().sub(&from.clone()))
+                elliptic.random_with(rng, from.clone(), elliptic.n.clone().sub(&from.clone()))
             };
 
             // C1: [k]G
             let c1 = {
                 let (x1, y1) = self.builder.scalar_base_multiply(k.clone());
-                [vec![0x04], x1.to_bytes_be(), y1.to_bytes_be()].concat()
+                [vec![0x04], to_32_bytes(x1.to_bytes_be()).to_vec(), to_32_bytes(y1.to_bytes_be()).to_vec()].concat()
             };
 
             let (x2, y2) = {
-                let key = self.key.value();
-                let (x, y) = (key.0.clone(), key.1.clone());
-                self.builder.scalar_multiply(x, y, k.clone())
+                let (x2, y2) = self.builder.scalar_multiply_key(&self.key, k.clone());
+                (to_32_bytes(x2.to_bytes_be()).to_vec(), to_32_bytes(y2.to_bytes_be()).to_vec())
             };
 
-            let temp = [x2.to_bytes_be(), y2.to_bytes_be()].concat();
-            let t = kdf(temp, data.len());
+            let temp = [x2.clone(), y2.clone()].concat();
+            let t = kdf(&temp, data.len());
 
-            if is_all_zero(t.clone()) {
+            if !t.is_empty() && is_all_zero(&t) {
                 continue;
             }
 
             // C2: M ^ KDF(x2 ‖ γ2, len(M))
-            let mut c2 = vec![];
-            for i in 0..data.len() {
-                c2.push(data[i] ^ t.clone()[i]);
-            }
+            let c2: Vec<u8> = data.iter().zip(t.iter()).map(|(m, k)| m ^ k).collect();
 
             // C3: hash(x2 ‖ M ‖ γ2)
             let c3 = {
-                let data = [x2.to_bytes_be(), data.to_vec(), y2.to_bytes_be()].concat();
+                let data = [x2, data.to_vec(), y2].concat();
                 sm3::hash(data.as_slice()).to_vec()
             };
 
@@ -201,114 +448,371 @@ impl Encryption for Encryptor {
                 Mode::C1C3C2 => [c1, c3, c2].concat(),
                 Mode::C1C2C3 => [c1, c2, c3].concat()
             };
-        };
+        }
+    }
+}
 
-        hex::encode(cipher)
+#[cfg(feature = "sm2-encrypt")]
+impl Encryptor {
+    /// 加密，返回按Encoding编码的密文
+    pub fn encrypt(&self, plain: &[u8]) -> Vec<u8> {
+        let raw = self.encrypt_bytes(plain);
+        match self.encoding {
+            Encoding::Raw => raw,
+            Encoding::Hex => hex::encode(raw).into_bytes(),
+            Encoding::Base64 => STANDARD.encode(raw).into_bytes(),
+            Encoding::Der => encode_der(&raw, self.mode),
+        }
     }
 }
 
+#[cfg(feature = "sm2-encrypt")]
+impl Encryption for Encryptor {
+    /// 加密
+    fn execute(&self, plain: &str) -> String {
+        let cipher = self.encrypt(plain.as_bytes());
+        match self.encoding {
+            Encoding::Hex | Encoding::Base64 => String::from_utf8(cipher).unwrap(),
+            Encoding::Raw | Encoding::Der => hex::encode(cipher),
+        }
+    }
+}
+
+#[cfg(feature = "sm2-encrypt")]
 pub struct Decryptor {
     mode: Mode,
+    encoding: Encoding,
+    c1_prefix: C1Prefix,
     key: PrivateKey,
+    max_len: usize,
     builder: Rc<dyn EllipticBuilder>,
 }
 
-impl Decryption for Decryptor {
-    /// 解密
-    fn execute(&self, cipher: &str) -> String {
-        let data = {
-            if !cipher.starts_with("04") {
-                panic!("The cipher data is invalid.")
+#[cfg(feature = "sm2-encrypt")]
+impl Decryptor {
+    /// 解密密文原始字节，C1是否必须以0x04开头由[`C1Prefix`]决定
+    pub fn decrypt_bytes(&self, cipher: &[u8]) -> Vec<u8> {
+        match self.try_decrypt_bytes(cipher) {
+            Ok(plain) => plain,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// 同[`Decryptor::decrypt_bytes`]，密文无效或超过长度上限时返回错误而不是panic
+    pub fn try_decrypt_bytes(&self, cipher: &[u8]) -> Result<Vec<u8>, DecryptError> {
+        SizeLimitError::check(CIPHER, cipher.len(), self.max_len.saturating_add(97)).map_err(DecryptError::TooLarge)?;
+        self.decrypt_raw(cipher).map_err(DecryptError::Invalid)
+    }
+
+    fn decrypt_raw(&self, cipher: &[u8]) -> Result<Vec<u8>, &'static str> {
+        match self.c1_prefix {
+            C1Prefix::Required => {
+                if cipher.len() < 97 {
+                    return Err("The cipher data is too short, a 65 bytes C1 and a 32 bytes C3 are required.");
+                }
+                if cipher[0] != 0x04 {
+                    return Err("The cipher data is invalid, C1 must start with 0x04.");
+                }
+                self.decrypt_c1(&cipher[1..])
             }
-            match hex::decode(cipher) {
-                Ok(data) => data[1..].to_vec(),
-                Err(_) => panic!("The cipher data must be composed of hex chars.")
+            C1Prefix::Optional => {
+                if cipher.len() < 96 {
+                    return Err("The cipher data is too short for either a 65 bytes or a 64 bytes C1.");
+                }
+                // 省略前缀的C1也可能恰好以0x04开头，按65字节解析失败时再按64字节解析
+                if cipher[0] == 0x04 && cipher.len() >= 97 {
+                    if let Ok(plain) = self.decrypt_c1(&cipher[1..]) {
+                        return Ok(plain);
+                    }
+                }
+                self.decrypt_c1(cipher)
             }
-        };
+        }
+    }
+
+    /// 解密不含04前缀的密文：x1 ‖ y1 ‖ C3 ‖ C2 或 x1 ‖ y1 ‖ C2 ‖ C3
+    fn decrypt_c1(&self, data: &[u8]) -> Result<Vec<u8>, &'static str> {
         let (c1, c2, c3) = {
             let len = data.len();
             match self.mode {
-                Mode::C1C3C2 => {
-                    (data.clone()[..64].to_vec(), data.clone()[96..].to_vec(), data.clone()[64..96].to_vec())
-                }
-                Mode::C1C2C3 => {
-                    (data.clone()[..64].to_vec(), data.clone()[64..len - 32].to_vec(), data.clone()[len - 32..].to_vec())
-                }
+                Mode::C1C3C2 => (&data[..64], &data[96..], &data[64..96]),
+                Mode::C1C2C3 => (&data[..64], &data[64..len - 32], &data[len - 32..]),
             }
         };
 
-
         let (x2, y2) = {
-            let (x1, y1) = (
-                BigUint::from_bytes_be(&c1.clone()[..32]),
-                BigUint::from_bytes_be(&c1.clone()[32..])
-            );
-            self.builder.scalar_multiply(x1, y1, self.key.value())
+            let (x1, y1) = (BigUint::from_bytes_be(&c1[..32]), BigUint::from_bytes_be(&c1[32..]));
+            // B1：C1须为曲线上的非无穷远点，否则私钥与无效曲线上的点相乘，解密成为泄露私钥的预言机
+            if x1.is_zero() && y1.is_zero() {
+                return Err("The cipher data is invalid, C1 is the point at infinity.");
+            }
+            if !on_curve(self.builder.blueprint(), &x1, &y1) {
+                return Err("The cipher data is invalid, C1 is not on the curve.");
+            }
+            let (x2, y2) = self.builder.scalar_multiply(x1, y1, self.key.value());
+            (to_32_bytes(x2.to_bytes_be()).to_vec(), to_32_bytes(y2.to_bytes_be()).to_vec())
         };
 
-
         let plain = {
-            let temp = [x2.to_bytes_be(), y2.to_bytes_be()].concat();
-            let t = kdf(temp, c2.len());
+            let temp = [x2.clone(), y2.clone()].concat();
+            let t = kdf(&temp, c2.len());
 
-            if is_all_zero(t.clone()) {
-                panic!("The cipher data is invalid.")
+            if !t.is_empty() && is_all_zero(&t) {
+                return Err("The cipher data is invalid.");
             }
 
-            let mut plain = vec![];
-            for i in 0..c2.len() {
-                plain.push(c2.clone()[i] ^ t.clone()[i]);
-            }
-            plain
+            c2.iter().zip(t.iter()).map(|(c, k)| c ^ k).collect::<Vec<u8>>()
         };
 
         let hash = {
-            let temp = [x2.to_bytes_be(), plain.clone(), y2.to_bytes_be()].concat();
-            sm3::hash(&temp).to_vec()
+            let temp = [x2, plain.clone(), y2].concat();
+            sm3::hash(&temp)
         };
 
-        if hash != c3 {
-            panic!("The cipher data hash validation failed.");
+        if !ct_eq(&hash, c3) {
+            return Err("The cipher data hash validation failed.");
         }
 
-        String::from_utf8_lossy(plain.as_slice()).to_string()
+        Ok(plain)
     }
 }
 
+#[cfg(feature = "sm2-encrypt")]
+impl Decryptor {
+    /// 解密按Encoding编码的密文
+    pub fn decrypt(&self, cipher: &[u8]) -> Vec<u8> {
+        const WHAT: &str = "SM2 cipher data";
+        if let Err(e) = SizeLimitError::check(CIPHER, cipher.len(), self.encoded_max_len()) {
+            panic!("{}", e)
+        }
+        let raw = match self.encoding {
+            Encoding::Raw => cipher.to_vec(),
+            Encoding::Hex => match hex::decode(cipher) {
+                Ok(data) => data,
+                Err(e) => {
+                    let offset = match e {
+                        hex::FromHexError::InvalidHexCharacter { index, .. } => index,
+                        _ => cipher.len(),
+                    };
+                    let e = report(DecodeError::new(WHAT, "invalid hex", cipher, offset));
+                    panic!("The cipher data must be composed of hex chars. {}", e)
+                }
+            },
+            Encoding::Base64 => match STANDARD.decode(cipher) {
+                Ok(data) => data,
+                Err(e) => {
+                    let offset = match e {
+                        base64::DecodeError::InvalidByte(offset, _) => offset,
+                        base64::DecodeError::InvalidLastSymbol(offset, _) => offset,
+                        _ => cipher.len(),
+                    };
+                    let e = report(DecodeError::new(WHAT, "invalid base64", cipher, offset));
+                    panic!("The cipher data must be valid base64. {}", e)
+                }
+            },
+            Encoding::Der => match decode_der(cipher, self.mode) {
+                Ok(data) => data,
+                Err(e) => panic!("The cipher data must be valid DER. {}", e)
+            },
+        };
+        self.decrypt_bytes(&raw)
+    }
 
-/// 秘钥派生函数
-#[inline(always)]
-fn kdf(data: Vec<u8>, len: usize) -> Vec<u8> {
-    let mut counter: usize = 0x00000001;
-    let mut result: Vec<u8> = vec![];
-    let k = data.len() + 31 / 32;
-    for i in 0..k {
-        let temp = [data.as_slice(), to_bytes(counter).as_slice()].concat();
-        let hash = sm3::hash(&temp);
-
-        if (i + 1) == k && len % 32 != 0 {
-            result = [result, hash[..(len % 32)].to_vec()].concat();
-        } else {
-            result = [result, hash.to_vec()].concat();
+    /// 明文长度上限对应的编码后密文长度上限，DER按坐标前导零与各层标签、长度字节放宽
+    fn encoded_max_len(&self) -> usize {
+        let raw = self.max_len.saturating_add(97);
+        match self.encoding {
+            Encoding::Raw => raw,
+            Encoding::Hex => raw.saturating_mul(2),
+            Encoding::Base64 => (raw / 3 + 1).saturating_mul(4),
+            Encoding::Der => raw.saturating_add(32),
         }
-        counter += 1;
     }
-    result
 }
 
-#[inline(always)]
-fn is_all_zero(data: Vec<u8>) -> bool {
-    let mut flag = true;
-    for i in 0..data.len() {
-        if data[i] != 0 {
-            flag = false;
-            break;
+#[cfg(feature = "sm2-encrypt")]
+impl Decryption for Decryptor {
+    /// 解密
+    fn execute(&self, cipher: &str) -> String {
+        let plain = match self.encoding {
+            Encoding::Hex | Encoding::Base64 => self.decrypt(cipher.as_bytes()),
+            Encoding::Raw | Encoding::Der => match hex::decode(cipher) {
+                Ok(data) => self.decrypt(&data),
+                Err(_) => panic!("The cipher data must be composed of hex chars.")
+            },
+        };
+        String::from_utf8_lossy(&plain).to_string()
+    }
+}
+
+/// 原始字节密文的各分量，可在不解密的情况下调整分量顺序
+#[cfg(feature = "sm2-encrypt")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ciphertext {
+    mode: Mode,
+    c1: [u8; 65],
+    c2: Vec<u8>,
+    c3: [u8; 32],
+}
+
+#[cfg(feature = "sm2-encrypt")]
+impl Ciphertext {
+    /// 按`mode`解析原始字节密文，C1须以0x04开头
+    pub fn from_bytes(cipher: &[u8], mode: Mode) -> Result<Self, DecodeError> {
+        const WHAT: &str = "SM2 cipher data";
+        if cipher.len() < 97 {
+            return Err(report(DecodeError::new(WHAT, "too short for C1 and C3", cipher, cipher.len()).expected(97)));
+        }
+        if cipher[0] != 0x04 {
+            return Err(report(DecodeError::new(WHAT, "missing 04 prefix", cipher, 0)));
+        }
+        let (c1, rest) = cipher.split_at(65);
+        let (c2, c3) = match mode {
+            Mode::C1C3C2 => (&rest[32..], &rest[..32]),
+            Mode::C1C2C3 => rest.split_at(rest.len() - 32),
+        };
+        Ok(Ciphertext { mode, c1: c1.try_into().unwrap(), c2: c2.to_vec(), c3: c3.try_into().unwrap() })
+    }
+
+    /// 按当前分量顺序输出原始字节密文
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self.mode {
+            Mode::C1C3C2 => [&self.c1[..], &self.c3, &self.c2].concat(),
+            Mode::C1C2C3 => [&self.c1[..], &self.c2, &self.c3].concat(),
+        }
+    }
+
+    /// 原始字节密文的base64url编码（无填充）
+    pub fn to_base64url(&self) -> String {
+        encode_base64url(&self.to_bytes())
+    }
+
+    /// 按`mode`解析base64url编码（无填充）的原始字节密文
+    pub fn from_base64url(cipher: &str, mode: Mode) -> Result<Self, DecodeError> {
+        Self::from_bytes(&decode_base64url("SM2 cipher data", cipher)?, mode)
+    }
+
+    /// GM/T 0009 SM2Cipher DER编码，与分量顺序无关
+    pub fn to_der(&self) -> Vec<u8> {
+        encode_der(&self.to_bytes(), self.mode)
+    }
+
+    /// 解析GM/T 0009 SM2Cipher DER编码，`mode`为此后输出原始字节密文的分量顺序
+    pub fn from_der(der: &[u8], mode: Mode) -> Result<Self, DecodeError> {
+        Self::from_bytes(&decode_der(der, mode)?, mode)
+    }
+
+    /// 调整分量顺序，无需私钥，可用于批量迁移存量密文
+    pub fn convert_mode(self, mode: Mode) -> Self {
+        Ciphertext { mode, ..self }
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// C1：04 ‖ x1 ‖ y1
+    pub fn c1(&self) -> &[u8; 65] {
+        &self.c1
+    }
+
+    /// C2：与明文等长的密文
+    pub fn c2(&self) -> &[u8] {
+        &self.c2
+    }
+
+    /// C3：杂凑值
+    pub fn c3(&self) -> &[u8; 32] {
+        &self.c3
+    }
+}
+
+/// 原始字节密文转为GM/T 0009 SM2Cipher
+#[cfg(feature = "sm2-encrypt")]
+fn encode_der(raw: &[u8], mode: Mode) -> Vec<u8> {
+    let data = &raw[1..];
+    let (c1, c2, c3) = match mode {
+        Mode::C1C3C2 => (&data[..64], &data[96..], &data[64..96]),
+        Mode::C1C2C3 => (&data[..64], &data[64..data.len() - 32], &data[data.len() - 32..]),
+    };
+    yasna::construct_der(|writer| {
+        writer.write_sequence(|writer| {
+            writer.next().write_biguint(&BigUint::from_bytes_be(&c1[..32]));
+            writer.next().write_biguint(&BigUint::from_bytes_be(&c1[32..]));
+            writer.next().write_bytes(c3);
+            writer.next().write_bytes(c2);
+        })
+    })
+}
+
+/// GM/T 0009 SM2Cipher转为原始字节密文
+#[cfg(feature = "sm2-encrypt")]
+fn decode_der(der: &[u8], mode: Mode) -> Result<Vec<u8>, DecodeError> {
+    const WHAT: &str = "SM2Cipher";
+    // SEQUENCE { x INTEGER, y INTEGER, hash OCTET STRING, cipherText OCTET STRING }
+    let (start, len) = read_tlv(WHAT, der, 0, 0x30).map_err(report)?;
+    if start + len != der.len() {
+        return Err(report(DecodeError::new(WHAT, "trailing data after SEQUENCE", der, start + len).expected(start + len)));
+    }
+    let (x, x_len) = read_tlv(WHAT, der, start, 0x02).map_err(report)?;
+    let (y, y_len) = read_tlv(WHAT, der, x + x_len, 0x02).map_err(report)?;
+    let (hash, hash_len) = read_tlv(WHAT, der, y + y_len, 0x04).map_err(report)?;
+    if hash_len != 32 {
+        return Err(report(DecodeError::new(WHAT, "C3 is not 32 bytes", der, hash).expected(32)));
+    }
+    read_tlv(WHAT, der, hash + hash_len, 0x04).map_err(report)?;
+
+    let parsed = yasna::parse_der(der, |reader| {
+        reader.read_sequence(|reader| {
+            let x = reader.next().read_biguint()?;
+            let y = reader.next().read_biguint()?;
+            let c3 = reader.next().read_bytes()?;
+            let c2 = reader.next().read_bytes()?;
+            Ok((x, y, c3, c2))
+        })
+    });
+    let (x, y, c3, c2) = match parsed {
+        Ok((x, y, ..)) if x.bits() > 256 || y.bits() > 256 => {
+            return Err(report(DecodeError::new(WHAT, "coordinate exceeds 256 bits", der, start)));
         }
+        Ok(parsed) => parsed,
+        Err(_) => return Err(report(DecodeError::new(WHAT, "invalid DER", der, start))),
+    };
+    let c1 = [vec![0x04], to_32_bytes(x.to_bytes_be()).to_vec(), to_32_bytes(y.to_bytes_be()).to_vec()].concat();
+    Ok(match mode {
+        Mode::C1C3C2 => [c1, c3, c2].concat(),
+        Mode::C1C2C3 => [c1, c2, c3].concat(),
+    })
+}
+
+
+/// 秘钥派生函数
+///
+/// Z = x2 ∥ y2 恰为一个64字节分组，先压缩一次并保存中间状态，每个计数器只需再压缩填充后的末块，
+/// 长消息加密时的杂凑运算量减半。
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+pub(crate) fn kdf(z: &[u8], len: usize) -> Vec<u8> {
+    let mut prefix = sm3::Hasher::new();
+    prefix.update(z);
+
+    let mut result: Vec<u8> = Vec::with_capacity((len + 31) / 32 * 32);
+    for counter in 1..=(len + 31) / 32 {
+        let mut hasher = prefix.clone();
+        hasher.update(&to_bytes(counter));
+        result.extend_from_slice(&hasher.finalize());
     }
-    flag
+    result.truncate(len);
+    result
+}
+
+#[cfg(feature = "sm2-encrypt")]
+#[inline(always)]
+fn is_all_zero(data: &[u8]) -> bool {
+    data.iter().all(|e| *e == 0)
 }
 
 
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
 #[inline(always)]
 fn to_bytes(x: usize) -> [u8; 4] {
     let mut buf: [u8; 4] = [0; 4];
@@ -328,6 +832,13 @@ pub struct Signature {
     s: BigUint,
 }
 
+/// 比较64字节的 r ‖ s 编码，比较时间不依赖内容
+impl ConstantTimeEq for Signature {
+    fn ct_eq(&self, other: &Signature) -> Choice {
+        self.to_bytes().ct_eq(&other.to_bytes())
+    }
+}
+
 impl Display for Signature {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -343,6 +854,51 @@ impl Signature {
         Signature { r, s }
     }
 
+    pub fn r(&self) -> &BigUint {
+        &self.r
+    }
+
+    pub fn s(&self) -> &BigUint {
+        &self.s
+    }
+
+    /// DER编码：SEQUENCE { r INTEGER, s INTEGER }，与OpenSSL、GmSSL、Java互通
+    pub fn to_der(&self) -> Vec<u8> {
+        self.encode()
+    }
+
+    /// 解析DER编码的签名
+    pub fn from_der(signature: &[u8]) -> Result<Self, DecodeError> {
+        Self::parse(signature)
+    }
+
+    /// 原始格式：r ‖ s，各32字节大端序
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&to_32_bytes(self.r.to_bytes_be()));
+        bytes[32..].copy_from_slice(&to_32_bytes(self.s.to_bytes_be()));
+        bytes
+    }
+
+    /// 解析原始格式 r ‖ s 的签名
+    pub fn from_bytes(signature: &[u8]) -> Result<Self, DecodeError> {
+        if signature.len() != 64 {
+            let e = DecodeError::new("SM2 signature", "unexpected length", signature, signature.len().min(64)).expected(64);
+            return Err(report(e));
+        }
+        Ok(Signature::new(BigUint::from_bytes_be(&signature[..32]), BigUint::from_bytes_be(&signature[32..])))
+    }
+
+    /// 原始格式 r ‖ s 的base64url编码（无填充），与JWS中ECDSA签名的约定一致
+    pub fn to_base64url(&self) -> String {
+        encode_base64url(&self.to_bytes())
+    }
+
+    /// 解析base64url编码（无填充）的原始格式签名
+    pub fn from_base64url(signature: &str) -> Result<Self, DecodeError> {
+        Self::from_bytes(&decode_base64url("SM2 signature", signature)?)
+    }
+
     /// Encodes the signature to DER-encoded ASN.1 data.
     pub(crate) fn encode(&self) -> Vec<u8> {
         let data = yasna::construct_der(|writer| {
@@ -354,60 +910,115 @@ impl Signature {
         data
     }
 
-    /// Decodes the DER-encoded ASN.1 data to Signature.
-    pub(crate) fn decode(signature: &[u8]) -> Self {
+    /// Decodes the DER-encoded ASN.1 data to Signature, returns None if the data is malformed.
+    pub(crate) fn try_decode(signature: &[u8]) -> Option<Self> {
+        Self::parse(signature).ok()
+    }
+
+    /// Decodes the DER-encoded ASN.1 data to Signature, reporting where the data is malformed.
+    pub fn parse(signature: &[u8]) -> Result<Self, DecodeError> {
+        const WHAT: &str = "SM2 signature";
+        // SEQUENCE { r INTEGER, s INTEGER }，先逐个检查TLV以定位出错位置
+        let (start, len) = read_tlv(WHAT, signature, 0, 0x30).map_err(report)?;
+        if start + len != signature.len() {
+            let e = DecodeError::new(WHAT, "trailing data after SEQUENCE", signature, start + len).expected(start + len);
+            return Err(report(e));
+        }
+        let (r, r_len) = read_tlv(WHAT, signature, start, 0x02).map_err(report)?;
+        let (s, s_len) = read_tlv(WHAT, signature, r + r_len, 0x02).map_err(report)?;
+        if s + s_len != signature.len() {
+            return Err(report(DecodeError::new(WHAT, "unexpected element in SEQUENCE", signature, s + s_len)));
+        }
+
         let (r, s) = yasna::parse_der(signature, |reader| {
             reader.read_sequence(|reader| {
                 let r = reader.next().read_biguint()?;
                 let s = reader.next().read_biguint()?;
                 Ok((r, s))
             })
-        }).unwrap();
+        }).map_err(|_| report(DecodeError::new(WHAT, "invalid INTEGER encoding", signature, start)))?;
 
-        Signature::new(r, s)
+        Ok(Signature::new(r, s))
     }
 }
 
+/// 签名者，构造时计算ZA并缓存其SM3中间状态，同一密钥签名多条消息时不再重复计算
+#[cfg(feature = "sm2-sign")]
 pub struct Signer {
-    hash: Vec<u8>,
+    prefix: sm3::Hasher,
     keypair: KeyPair,
+    nonce: Nonce,
     builder: Rc<dyn EllipticBuilder>,
 }
 
+#[cfg(feature = "sm2-sign")]
 impl Signer {
+    /// 使用SM2推荐曲线及默认用户ID的签名者
+    pub fn new(keypair: KeyPair) -> Self {
+        Crypto::default().signer(keypair)
+    }
+
+    /// 使用SM2推荐曲线及指定用户ID的签名者
+    pub fn with_user_id(keypair: KeyPair, user_id: &[u8]) -> Self {
+        Crypto::default().with_user_id(user_id).signer(keypair)
+    }
+
     /// 签名
-    pub(crate) fn sign(&self, plain: &str) -> Signature {
-        let m = [self.hash.clone(), plain.as_bytes().to_vec()].concat();
-        let e = sm3::hash(m.as_slice());
+    pub fn sign(&self, plain: &str) -> Signature {
+        self.sign_bytes(plain.as_bytes())
+    }
+
+    /// 对字节数据签名，随机数k的来源由[`Crypto::with_nonce`]决定
+    pub fn sign_bytes(&self, data: &[u8]) -> Signature {
+        match self.nonce {
+            Nonce::Random => self.sign_bytes_with_rng(data, &mut EnvRng),
+            Nonce::Deterministic => {
+                let e = digest(&self.prefix, data);
+                let n = &self.builder.blueprint().n;
+                let mut nonces = NonceGenerator::new(&self.keypair.prk().value(), &e, n);
+                self.sign_digest(&e, || nonces.next().unwrap())
+            }
+        }
+    }
+
+    /// 同[`Signer::sign_bytes`]，随机数k取自`rng`，不受[`Nonce`]设置影响
+    pub fn sign_bytes_with_rng(&self, data: &[u8], rng: &mut (impl CryptoRng + RngCore)) -> Signature {
+        let e = digest(&self.prefix, data);
+        let elliptic = self.builder.blueprint();
+        let from = BigUint::one();
+        self.sign_digest(&e, || elliptic.random_with(rng, from.clone(), elliptic.n.clone().sub(&from)))
+    }
+
+    /// 对摘要e签名，k ∈ [1, n-1]由`nonce`逐个给出，r或s不合法时取下一个
+    fn sign_digest(&self, e: &[u8; 32], mut nonce: impl FnMut() -> BigUint) -> Signature {
         let elliptic = self.builder.blueprint();
 
         let key = self.keypair.prk();
 
         let (r, s) = loop {
-            let k = {
-                let from = BigUint::one();
-                elliptic.random(from.clone(), elliptic.n.clone().sub(&from.clone()))
-            };
+            let k = nonce();
 
             let r = {
                 let (x, _) = self.builder.scalar_base_multiply(k.clone());
-                BigUint::from_bytes_be(&e).add(&x).mod_floor(&elliptic.n)
+                BigUint::from_bytes_be(e).add(&x).mod_floor(&elliptic.n)
             };
 
             if r == BigUint::zero() || r.clone().add(k.clone()) == elliptic.n {
                 continue;
             }
 
-            let s = {
-                let n = elliptic.n.to_bigint().unwrap();
-                let d = key.value().to_bigint().unwrap();
-                let temp = d.clone().mul(&r.to_bigint().unwrap());
-                // a = k - rd
-                let a = k.to_bigint().unwrap().sub(&temp);
-                let temp = d.clone().add(BigInt::one());
-                // 1 / (1+d)
-                let b = temp.extended_gcd(&n).x.mod_floor(&n);
-                a.mul(b).mod_floor(&n).to_biguint().unwrap()
+            // s = (1 + d)^-1 · (k - rd) mod n，SM2推荐曲线以定长limb运算，不因私钥取值泄露时间信息
+            let s = if elliptic.n.to_bytes_be() == EC_N {
+                let d = Scalar::from_biguint(&key.value());
+                let k = Scalar::from_biguint(&k);
+                let rd = Scalar::from_biguint(&r).multiply(&d);
+                d.add(&Scalar::ONE).invert().multiply(&k.subtract(&rd)).to_biguint()
+            } else {
+                let n = &elliptic.n;
+                let d = key.value();
+                let inv = (&d + 1u8).modpow(&(n - 2u8), n);
+                let rd = (&r * &d).mod_floor(n);
+                (inv * ((k + n - rd).mod_floor(n))).mod_floor(n)
             };
 
             if s == BigUint::zero() {
@@ -422,64 +1033,519 @@ impl Signer {
 }
 
 
+/// 验证者，与[`Signer`]相同地缓存ZA的SM3中间状态
 pub struct Verifier {
-    hash: Vec<u8>,
+    prefix: sm3::Hasher,
     key: PublicKey,
     builder: Rc<dyn EllipticBuilder>,
 }
 
 impl Verifier {
+    /// 使用SM2推荐曲线及默认用户ID的验证者
+    pub fn new(key: PublicKey) -> Self {
+        Crypto::default().verifier(key)
+    }
+
+    /// 使用SM2推荐曲线及指定用户ID的验证者
+    pub fn with_user_id(key: PublicKey, user_id: &[u8]) -> Self {
+        Crypto::default().with_user_id(user_id).verifier(key)
+    }
+
     /// 验签
-    pub(crate) fn verify(&self, plain: &str, signature: &Signature) -> bool {
+    pub fn verify(&self, plain: &str, signature: &Signature) -> bool {
+        self.verify_bytes(plain.as_bytes(), signature)
+    }
+
+    /// 对字节数据验签
+    pub fn verify_bytes(&self, data: &[u8], signature: &Signature) -> bool {
+        self.check_bytes(data, signature).is_ok()
+    }
+
+    /// 对字节数据验签，失败时返回具体未通过的检查项
+    pub fn check_bytes(&self, data: &[u8], signature: &Signature) -> Result<(), VerifyError> {
         let elliptic = self.builder.blueprint();
         let n1 = elliptic.n.clone().sub(BigUint::one());
         let (r, s) = (signature.r.clone(), signature.s.clone());
 
-        if r < BigUint::one() || r > n1.clone() {
-            return false;
+        // r ∈ [1, n-1]
+        if r < BigUint::one() || r > n1 {
+            return Err(VerifyError::ROutOfRange);
         }
 
-        if s < BigUint::one() || s > n1.clone() {
-            return false;
+        // s ∈ [1, n-1]
+        if s < BigUint::one() || s > n1 {
+            return Err(VerifyError::SOutOfRange);
         }
 
-        let e = {
-            let m = [self.hash.clone(), plain.as_bytes().to_vec()].concat();
-            let h = sm3::hash(m.as_slice());
-            BigUint::from_bytes_be(h.as_slice())
-        };
+        // P ∈ E(Fp)
+        let key = self.key.value();
+        if !on_curve(elliptic, &key.0, &key.1) {
+            return Err(VerifyError::InvalidPublicKey);
+        }
 
-        let t = r.clone().add(&s).mod_floor(&elliptic.n);
+        let e = BigUint::from_bytes_be(&digest(&self.prefix, data));
 
+        // t = (r + s) mod n, t ≠ 0
+        let t = r.clone().add(&s).mod_floor(&elliptic.n);
         if BigUint::zero().eq(&t) {
-            return false;
+            return Err(VerifyError::ZeroSum);
         }
 
-        let x = {
-            let key = self.key.value();
-            let p1 = self.builder.scalar_base_multiply(s.clone());
-            let p2 = self.builder.scalar_multiply(key.0, key.1, t);
-            let p3 = self.builder.point_add(p1.0, p1.1, p2.0, p2.1);
-            p3.0
+        // (x1, y1) = sG + tP ≠ O
+        let x = match self.builder.double_scalar_multiply(s, &self.key, t) {
+            Some((x, _)) => x,
+            None => return Err(VerifyError::PointAtInfinity),
         };
 
+        // R = (e + x1) mod n
         let rn = e.add(x).mod_floor(&elliptic.n);
+        if rn != r {
+            return Err(VerifyError::Mismatch);
+        }
+        Ok(())
+    }
+}
 
-        return if rn == r {
-            true
-        } else {
-            false
-        };
+/// 坐标属于[0, p-1]且满足 y² = x³ + ax + b
+pub(crate) fn on_curve(e: &Elliptic, x: &BigUint, y: &BigUint) -> bool {
+    if x >= &e.p || y >= &e.p {
+        return false;
     }
+    let left = y.modpow(&BigUint::from(2u32), &e.p);
+    let right = (x.modpow(&BigUint::from(3u32), &e.p) + &e.a * x + &e.b).mod_floor(&e.p);
+    left == right
 }
 
+/// 密文原始字节的对象名称
+#[cfg(feature = "sm2-encrypt")]
+const CIPHER: &str = "SM2 cipher data";
+#[cfg(feature = "sm2-encrypt")]
+const PLAIN: &str = "SM2 plain data";
+
+/// 解密失败的原因
+#[cfg(feature = "sm2-encrypt")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecryptError {
+    /// 密文超过长度上限，见[`Crypto::with_max_len`]
+    TooLarge(SizeLimitError),
+    /// 密文过短、C1无效或C3校验失败
+    Invalid(&'static str),
+    /// 私钥格式不正确，见[`crate::sm2::simple::decrypt`]
+    InvalidKey(KeyError),
+}
 
-#[cfg(test)]
+#[cfg(feature = "sm2-encrypt")]
+impl Display for DecryptError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecryptError::TooLarge(e) => write!(f, "{}", e),
+            DecryptError::Invalid(reason) => write!(f, "{}", reason),
+            DecryptError::InvalidKey(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[cfg(feature = "sm2-encrypt")]
+impl std::error::Error for DecryptError {}
+
+#[cfg(feature = "sm2-encrypt")]
+impl ErrorCode for DecryptError {
+    fn code(&self) -> Code {
+        match self {
+            DecryptError::TooLarge(e) => e.code(),
+            DecryptError::Invalid(_) => Code::INVALID_CIPHERTEXT,
+            DecryptError::InvalidKey(e) => e.code(),
+        }
+    }
+}
+
+
+#[cfg(all(test, feature = "sm2-sign", feature = "sm2-encrypt"))]
 mod tests {
+    use num_bigint::{BigInt, ToBigInt};
+
+    use crate::sm2::key::HexKey;
+
     use super::*;
 
     #[test]
     fn demo() {
         println!("BigUint::one() = {:?}", BigUint::one());
     }
+
+    #[test]
+    fn shared_curve() {
+        // 推荐曲线的Crypto借用全局参数，而不是各自复制
+        let ec = &P256Elliptic::init().ec;
+        assert!(std::ptr::eq(Crypto::default().builder.blueprint(), ec));
+        assert!(std::ptr::eq(Crypto::new(Mode::C1C2C3).builder.blueprint(), ec));
+    }
+
+    #[test]
+    fn kdf_midstate() {
+        let z: Vec<u8> = (0..64).collect();
+        for len in [0usize, 1, 32, 33, 1000] {
+            // KDF按定义逐个计算 SM3(Z ∥ ct)
+            let expected: Vec<u8> = (1..=((len + 31) / 32) as u32)
+                .flat_map(|ct| sm3::hash(&[z.as_slice(), &ct.to_be_bytes()].concat()))
+                .take(len)
+                .collect();
+            assert_eq!(kdf(&z, len), expected, "len = {}", len);
+        }
+    }
+
+    #[test]
+    fn kdf_len() {
+        // 输出长度随klen变化，曾固定派生64个分组
+        let z = [0x5a; 64];
+        for len in [0usize, 1, 31, 32, 33, 64 * 32, 64 * 32 + 1, 5000] {
+            let t = kdf(&z, len);
+            assert_eq!(t.len(), len);
+            assert_eq!(t[..], kdf(&z, len + 1)[..len]);
+        }
+    }
+
+    #[test]
+    fn verify_checks() {
+        let prk = PrivateKey::decode("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e");
+        let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+        let crypto = Crypto::default();
+        let verifier = crypto.verifier(puk.clone());
+        let data = b"verify";
+        let n = crypto.builder.blueprint().n.clone();
+        let one = BigUint::one();
+
+        let signature = crypto.signer(KeyPair::new(prk.clone(), puk)).sign_bytes(data);
+        assert_eq!(verifier.check_bytes(data, &signature), Ok(()));
+        assert!(bool::from(signature.ct_eq(&signature.clone())));
+        assert!(!bool::from(signature.ct_eq(&Signature::new(signature.r().clone(), signature.s() + 1u8))));
+        assert_eq!(verifier.check_bytes(b"tampered", &signature), Err(VerifyError::Mismatch));
+
+        let (r, s) = (signature.r.clone(), signature.s.clone());
+        for bad in [BigUint::zero(), n.clone()] {
+            assert_eq!(verifier.check_bytes(data, &Signature::new(bad.clone(), s.clone())), Err(VerifyError::ROutOfRange));
+            assert_eq!(verifier.check_bytes(data, &Signature::new(r.clone(), bad)), Err(VerifyError::SOutOfRange));
+        }
+        let sum = Signature::new(one.clone(), n.clone().sub(&one));
+        assert_eq!(verifier.check_bytes(data, &sum), Err(VerifyError::ZeroSum));
+
+        // s = -r·d / (1 + d) mod n 时 sG + (r + s)dG = O
+        let (d, n) = (prk.value().to_bigint().unwrap(), n.to_bigint().unwrap());
+        let inv = (d.clone() + BigInt::one()).extended_gcd(&n).x.mod_floor(&n);
+        let s = (-d * inv).mod_floor(&n).to_biguint().unwrap();
+        let infinity = Signature::new(one, s);
+        assert_eq!(verifier.check_bytes(data, &infinity), Err(VerifyError::PointAtInfinity));
+        assert_eq!(VerifyError::PointAtInfinity.to_string(), "The computed point sG + tP is the point at infinity.");
+
+        let off_curve = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6f");
+        assert_eq!(crypto.verifier(off_curve).check_bytes(data, &signature), Err(VerifyError::InvalidPublicKey));
+    }
+
+    #[test]
+    fn compute_za() {
+        let prk = PrivateKey::decode("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e");
+        let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+        let za = crate::sm2::compute_za(b"1234567812345678", &puk);
+        assert_eq!(hex::encode(za), "7b9e665a727b527b5f7c3e7cd85a5576579ec87cab4fdafd14b0cd5cceb35728");
+
+        // 主机侧计算 e = SM3(ZA ∥ M) 与签名器内部一致
+        let crypto = Crypto::default();
+        let signer = crypto.signer(KeyPair::new(prk, puk.clone()));
+        assert_ne!(crate::sm2::compute_za(b"ALICE123@YAHOO.COM", &puk), za);
+
+        // 缓存的中间状态可重复使用，每条消息得到的e与完整计算一致
+        let verifier = crypto.verifier(puk);
+        for message in [&b""[..], b"a", b"message digest", &[0x5A; 100]] {
+            let e = sm3::hash(&[&za[..], message].concat());
+            assert_eq!(digest(&signer.prefix, message), e);
+            assert_eq!(digest(&verifier.prefix, message), e);
+            assert!(verifier.verify_bytes(message, &signer.sign_bytes(message)));
+        }
+    }
+
+    #[test]
+    fn openssl_signature() {
+        // openssl pkeyutl -sign -rawin -digest sm3 -pkeyopt distid:1234567812345678，私钥同上
+        // ZA须依次杂凑a与b，曾误将a杂凑两次，与OpenSSL的签名互不认可
+        let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+        let der = hex::decode(
+            "304402201b644773ce28c00ed8e871d11c371c627892d4c546f6ab3b564f04b9c7411e31022052ee884e376ba18b0af15e6be0df14d39b5d2a1aeb08699bcc9f333baa155e88",
+        ).unwrap();
+        let signature = Signature::parse(&der).unwrap();
+        let verifier = Crypto::default().verifier(puk);
+        assert_eq!(verifier.check_bytes(b"message digest", &signature), Ok(()));
+        assert_eq!(verifier.check_bytes(b"message digesT", &signature), Err(VerifyError::Mismatch));
+    }
+
+    #[test]
+    fn c1_prefix() {
+        let prk = PrivateKey::decode("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e");
+        let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+        let plain = b"c1 prefix";
+
+        for mode in [Mode::C1C3C2, Mode::C1C2C3] {
+            let crypto = Crypto::new(mode).with_c1_prefix(C1Prefix::Optional);
+            let decryptor = crypto.decryptor(prk.clone());
+            let cipher = crypto.encryptor(puk.clone()).encrypt_bytes(plain);
+
+            assert_eq!(decryptor.decrypt_bytes(&cipher), plain);
+            assert_eq!(decryptor.decrypt_bytes(&cipher[1..]), plain);
+
+            let strict = Crypto::new(mode).decryptor(prk.clone());
+            assert_eq!(strict.try_decrypt_bytes(&cipher), Ok(plain.to_vec()));
+            assert!(strict.try_decrypt_bytes(&cipher[1..]).is_err());
+            let mut tampered = cipher.clone();
+            tampered[70] ^= 1;
+            assert_eq!(strict.try_decrypt_bytes(&tampered), Err(DecryptError::Invalid("The cipher data hash validation failed.")));
+            assert_eq!(strict.try_decrypt_bytes(&[0x00; 97]), Err(DecryptError::Invalid("The cipher data is invalid, C1 must start with 0x04.")));
+        }
+
+        let decryptor = Crypto::default().with_c1_prefix(C1Prefix::Optional).decryptor(prk);
+        assert_eq!(
+            decryptor.try_decrypt_bytes(&[0x04; 95]),
+            Err(DecryptError::Invalid("The cipher data is too short for either a 65 bytes or a 64 bytes C1."))
+        );
+    }
+
+    #[test]
+    fn max_len() {
+        let prk = PrivateKey::decode("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e");
+        let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+        let crypto = Crypto::default().with_max_len(16);
+        let (encryptor, decryptor) = (crypto.encryptor(puk.clone()), crypto.decryptor(prk.clone()));
+
+        let cipher = encryptor.try_encrypt_bytes(&[7; 16]).unwrap();
+        assert_eq!(decryptor.try_decrypt_bytes(&cipher), Ok(vec![7; 16]));
+        assert_eq!(
+            encryptor.try_encrypt_bytes(&[7; 17]),
+            Err(SizeLimitError { what: "SM2 plain data", len: 17, max: 16 }),
+        );
+
+        let long = Crypto::default().encryptor(puk.clone()).encrypt_bytes(&[7; 17]);
+        let e = decryptor.try_decrypt_bytes(&long).unwrap_err();
+        assert_eq!(e, DecryptError::TooLarge(SizeLimitError { what: "SM2 cipher data", len: 114, max: 113 }));
+        assert_eq!(e.code(), Code::INVALID_LENGTH);
+        assert_eq!(Crypto::default().decryptor(prk.clone()).try_decrypt_bytes(&long), Ok(vec![7; 17]));
+
+        // 编码后的密文在解码之前即被拒绝
+        for encoding in [Encoding::Hex, Encoding::Base64, Encoding::Der] {
+            let crypto = Crypto::default().with_encoding(encoding).with_max_len(16);
+            let cipher = crypto.encryptor(puk.clone()).encrypt(&[7; 16]);
+            assert_eq!(crypto.decryptor(prk.clone()).decrypt(&cipher), [7; 16], "{:?}", encoding);
+            let oversized = [&cipher[..], &[b'='; 64]].concat();
+            let decryptor = crypto.decryptor(prk.clone());
+            let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| decryptor.decrypt(&oversized))).unwrap_err();
+            assert!(panic.downcast_ref::<String>().unwrap().contains("exceeding the limit"), "{:?}", encoding);
+        }
+    }
+
+    #[test]
+    fn diagnostics() {
+        let signature = Signature::new(BigUint::from(1u8), BigUint::from(2u8)).encode();
+        assert_eq!(signature, [0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02]);
+        assert!(Signature::parse(&signature).is_ok());
+
+        let e = Signature::parse(&signature[..7]).unwrap_err();
+        assert_eq!((e.reason, e.offset, e.expected), ("DER length exceeds input", 1, Some(8)));
+        let e = Signature::parse(&[&signature[..], &[0x00]].concat()).unwrap_err();
+        assert_eq!((e.reason, e.offset), ("trailing data after SEQUENCE", 8));
+        let e = Signature::parse(&[0x30, 0x06, 0x02, 0x01, 0x01, 0x04, 0x01, 0x02]).unwrap_err();
+        assert_eq!((e.reason, e.offset, e.context.as_str()), ("unexpected DER tag", 5, "3006020101[04]0102"));
+
+        let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+        let raw = Crypto::default().encryptor(puk).encrypt_bytes(b"diagnostics");
+        let der = encode_der(&raw, Mode::C1C3C2);
+        assert_eq!(decode_der(&der, Mode::C1C3C2), Ok(raw));
+
+        // 将C3的长度改为31
+        let hash = der.len() - 2 - 11 - 2 - 32;
+        let mut bad = der.clone();
+        bad[hash + 1] = 31;
+        let e = decode_der(&bad, Mode::C1C3C2).unwrap_err();
+        assert_eq!((e.reason, e.offset, e.expected), ("C3 is not 32 bytes", hash + 2, Some(32)));
+    }
+
+    #[test]
+    fn key_policy() {
+        use crate::sm2::policy::{PolicyError, StandardPolicy};
+
+        let prk = PrivateKey::decode("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e");
+        let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+        let fingerprint = puk.fingerprint();
+        assert_eq!(hex::encode(fingerprint), hex::encode(sm3::hash(&puk.to_bytes())));
+
+        let crypto = Crypto::default().with_policy(Rc::new(StandardPolicy::new().ban(fingerprint)));
+        let banned = KeyError::PolicyViolation(PolicyError::Banned { fingerprint });
+        assert_eq!(crypto.try_encryptor(puk.clone()).err().unwrap(), banned);
+        assert_eq!(crypto.try_signer(KeyPair::new(prk.clone(), puk.clone())).err().unwrap(), banned);
+        let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| { crypto.signer(KeyPair::new(prk.clone(), puk.clone())); })).unwrap_err();
+        assert!(panic.downcast_ref::<String>().unwrap().starts_with("The key pair is rejected. The key "));
+
+        // 解密与验签不受策略限制
+        let cipher = Crypto::default().encryptor(puk.clone()).encrypt_bytes(b"policy");
+        assert_eq!(crypto.decryptor(prk.clone()).decrypt_bytes(&cipher), b"policy");
+        let signature = Crypto::default().signer(KeyPair::new(prk, puk.clone())).sign_bytes(b"policy");
+        assert_eq!(crypto.verifier(puk).check_bytes(b"policy", &signature), Ok(()));
+    }
+
+    #[test]
+    fn convert_mode() {
+        let prk = PrivateKey::decode("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e");
+        let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+        let plain = b"convert mode";
+
+        let legacy = Crypto::new(Mode::C1C2C3).encryptor(puk).encrypt_bytes(plain);
+        let ciphertext = Ciphertext::from_bytes(&legacy, Mode::C1C2C3).unwrap();
+        assert_eq!(ciphertext.c2().len(), plain.len());
+        assert_eq!(ciphertext.to_bytes(), legacy);
+
+        let migrated = ciphertext.clone().convert_mode(Mode::C1C3C2);
+        assert_eq!(migrated.mode(), Mode::C1C3C2);
+        assert_eq!((migrated.c1(), migrated.c2(), migrated.c3()), (ciphertext.c1(), ciphertext.c2(), ciphertext.c3()));
+        assert_eq!(Crypto::default().decryptor(prk).decrypt_bytes(&migrated.to_bytes()), plain);
+        assert_eq!(migrated.convert_mode(Mode::C1C2C3).to_bytes(), legacy);
+
+        let der = ciphertext.to_der();
+        assert_eq!(Ciphertext::from_der(&der, Mode::C1C2C3).unwrap().to_bytes(), legacy);
+        assert_eq!(Ciphertext::from_der(&der, Mode::C1C3C2).unwrap().to_der(), der);
+        assert!(Ciphertext::from_der(&der[1..], Mode::C1C3C2).is_err());
+
+        assert_eq!(Ciphertext::from_bytes(&legacy[..96], Mode::C1C2C3).unwrap_err().expected, Some(97));
+    }
+
+    #[test]
+    fn rng() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let prk = PrivateKey::decode("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e");
+        let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+        let crypto = Crypto::default();
+
+        let signer = crypto.signer(KeyPair::new(prk.clone(), puk.clone()));
+        let first = signer.sign_bytes_with_rng(b"rng", &mut StdRng::seed_from_u64(3));
+        let second = signer.sign_bytes_with_rng(b"rng", &mut StdRng::seed_from_u64(3));
+        assert_eq!(first.to_bytes(), second.to_bytes());
+        assert_ne!(first.to_bytes(), signer.sign_bytes_with_rng(b"rng", &mut StdRng::seed_from_u64(4)).to_bytes());
+        assert!(crypto.verifier(puk.clone()).verify_bytes(b"rng", &first));
+
+        let encryptor = crypto.encryptor(puk);
+        let first = encryptor.encrypt_bytes_with_rng(b"rng", &mut StdRng::seed_from_u64(3));
+        assert_eq!(first, encryptor.encrypt_bytes_with_rng(b"rng", &mut StdRng::seed_from_u64(3)));
+        assert_eq!(crypto.decryptor(prk).decrypt_bytes(&first), b"rng");
+    }
+
+    /// 同一随机数k、同一消息在两种分量顺序下的密文，重排后各分量应完全一致
+    #[test]
+    fn mode_differential() {
+        use rand::{Rng, RngCore, SeedableRng};
+        use rand::rngs::StdRng;
+
+        let prk = PrivateKey::decode("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e");
+        let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+        let (c123, c132) = (Crypto::new(Mode::C1C2C3), Crypto::new(Mode::C1C3C2));
+        let (e123, e132) = (c123.encryptor(puk.clone()), c132.encryptor(puk));
+        let (d123, d132) = (c123.decryptor(prk.clone()), c132.decryptor(prk));
+
+        let mut rng = StdRng::seed_from_u64(1269);
+        let lengths = [0, 1, 31, 32, 33, 64].into_iter().chain((0..26).map(|_| rng.gen_range(0..300)));
+        for len in lengths.collect::<Vec<usize>>() {
+            let mut plain = vec![0u8; len];
+            rng.fill_bytes(&mut plain);
+            let seed = rng.next_u64();
+            let a = e123.encrypt_bytes_with_rng(&plain, &mut StdRng::seed_from_u64(seed));
+            let b = e132.encrypt_bytes_with_rng(&plain, &mut StdRng::seed_from_u64(seed));
+
+            // C1 ∥ C2 ∥ C3 与 C1 ∥ C3 ∥ C2
+            assert_eq!(a.len(), 97 + len);
+            assert_eq!(b.len(), 97 + len);
+            assert_eq!(a[..65], b[..65], "C1, len = {}", len);
+            assert_eq!(a[65..65 + len], b[97..], "C2, len = {}", len);
+            assert_eq!(a[65 + len..], b[65..97], "C3, len = {}", len);
+
+            let (pa, pb) = (Ciphertext::from_bytes(&a, Mode::C1C2C3).unwrap(), Ciphertext::from_bytes(&b, Mode::C1C3C2).unwrap());
+            assert_eq!(pa.clone().convert_mode(Mode::C1C3C2), pb);
+            assert_eq!(pb.convert_mode(Mode::C1C2C3).to_bytes(), a);
+            assert_eq!(encode_der(&a, Mode::C1C2C3), encode_der(&b, Mode::C1C3C2));
+
+            assert_eq!(d123.try_decrypt_bytes(&a).unwrap(), plain);
+            assert_eq!(d132.try_decrypt_bytes(&b).unwrap(), plain);
+            // 空消息两种顺序的密文相同；其余情况按错误的顺序解密时C3校验失败
+            if len > 0 {
+                assert!(d123.try_decrypt_bytes(&b).is_err(), "len = {}", len);
+                assert!(d132.try_decrypt_bytes(&a).is_err(), "len = {}", len);
+            }
+        }
+    }
+
+    #[test]
+    fn deterministic_nonce() {
+        let prk = PrivateKey::decode("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e");
+        let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+        let crypto = Crypto::default().with_nonce(Nonce::Deterministic);
+
+        let signer = crypto.signer(KeyPair::new(prk, puk.clone()));
+        let first = signer.sign_bytes(b"sample");
+        assert_eq!(first.to_bytes(), signer.sign_bytes(b"sample").to_bytes());
+        assert_ne!(first.to_bytes(), signer.sign_bytes(b"samples").to_bytes());
+        assert!(crypto.verifier(puk).verify_bytes(b"sample", &first));
+    }
+
+    #[test]
+    fn ciphertext_base64url() {
+        let prk = PrivateKey::decode("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e");
+        let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+        let raw = Crypto::default().encryptor(puk).encrypt_bytes(b"header");
+
+        let text = Ciphertext::from_bytes(&raw, Mode::C1C3C2).unwrap().to_base64url();
+        assert!(text.bytes().all(|c| c.is_ascii_alphanumeric() || c == b'-' || c == b'_'));
+        let ciphertext = Ciphertext::from_base64url(&text, Mode::C1C3C2).unwrap();
+        assert_eq!(Crypto::default().decryptor(prk).decrypt_bytes(&ciphertext.to_bytes()), b"header");
+
+        // 标准base64的填充与字符集
+        let padded = STANDARD.encode(&raw);
+        let e = Ciphertext::from_base64url(&padded, Mode::C1C3C2).unwrap_err();
+        assert_eq!(e.reason, "invalid base64url");
+        let e = Ciphertext::from_base64url(&text[..100], Mode::C1C3C2).unwrap_err();
+        assert_eq!(e.reason, "too short for C1 and C3");
+    }
+
+    #[test]
+    fn encoding() {
+        let prk = PrivateKey::decode("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e");
+        let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+        let plain = "encoding";
+
+        for mode in [Mode::C1C3C2, Mode::C1C2C3] {
+            for encoding in [Encoding::Raw, Encoding::Hex, Encoding::Base64, Encoding::Der] {
+                let crypto = Crypto::new(mode).with_encoding(encoding);
+                let encryptor = crypto.encryptor(puk.clone());
+                let decryptor = crypto.decryptor(prk.clone());
+
+                let cipher = encryptor.encrypt(plain.as_bytes());
+                assert_eq!(decryptor.decrypt(&cipher), plain.as_bytes());
+                assert_eq!(decryptor.execute(&encryptor.execute(plain)), plain);
+            }
+        }
+
+        let crypto = Crypto::default().with_encoding(Encoding::Der);
+        let cipher = crypto.encryptor(puk.clone()).encrypt(plain.as_bytes());
+        assert_eq!(cipher[0], 0x30);
+        let crypto = Crypto::default().with_encoding(Encoding::Base64);
+        assert!(!crypto.encryptor(puk).execute(plain).starts_with("04"));
+    }
+
+    #[test]
+    fn fingerprint() {
+        let elliptic = &P256Elliptic::init().ec;
+        assert_eq!(elliptic.to_canonical_bytes().len(), 2 + 32 * 6);
+        assert_eq!(
+            hex::encode(elliptic.fingerprint()),
+            "2e7c57135b10a849f64377086263244c7461547b3b5706f836ee3372a137ebb8"
+        );
+
+        let mut other = elliptic.clone();
+        other.b += 1u8;
+        assert_ne!(other.fingerprint(), elliptic.fingerprint());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,96 @@
+//! 验签失败原因的分类
+//!
+//! 审计、合规日志需要记录签名为何未通过，而不只是未通过。[`VerifyError`]区分编码错误、公钥不在曲线上、
+//! r或s超出范围以及最终比对不一致等情形，[`VerifyError::reason`]给出可直接写入日志的稳定代码。
+
+use std::fmt::{Display, Formatter};
+
+use crate::codec::DecodeError;
+use crate::error_code::{Code, ErrorCode};
+
+/// 验签失败的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyError {
+    /// 签名不是合法的DER编码
+    Malformed(DecodeError),
+    /// r ∉ [1, n-1]
+    ROutOfRange,
+    /// s ∉ [1, n-1]
+    SOutOfRange,
+    /// (r + s) mod n = 0
+    ZeroSum,
+    /// 公钥格式不正确或不是曲线上的点
+    InvalidPublicKey,
+    /// sG + tP 为无穷远点
+    PointAtInfinity,
+    /// (e + x1) mod n ≠ r
+    Mismatch,
+}
+
+impl Display for VerifyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerifyError::Malformed(e) => write!(f, "The signature is not valid DER. {}", e),
+            VerifyError::ROutOfRange => write!(f, "The signature component r is not in [1, n-1]."),
+            VerifyError::SOutOfRange => write!(f, "The signature component s is not in [1, n-1]."),
+            VerifyError::ZeroSum => write!(f, "The sum of r and s is zero modulo n."),
+            VerifyError::InvalidPublicKey => write!(f, "The public key is malformed or not a point on the curve."),
+            VerifyError::PointAtInfinity => write!(f, "The computed point sG + tP is the point at infinity."),
+            VerifyError::Mismatch => write!(f, "The computed R does not equal r."),
+        }
+    }
+}
+
+impl VerifyError {
+    /// 审计日志中记录的稳定原因代码，不随[`Display`]文本或版本变化
+    pub fn reason(&self) -> &'static str {
+        match self {
+            VerifyError::Malformed(_) => "malformed_signature",
+            VerifyError::ROutOfRange => "r_out_of_range",
+            VerifyError::SOutOfRange => "s_out_of_range",
+            VerifyError::ZeroSum => "zero_sum",
+            VerifyError::InvalidPublicKey => "invalid_public_key",
+            VerifyError::PointAtInfinity => "point_at_infinity",
+            VerifyError::Mismatch => "mismatch",
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+impl ErrorCode for VerifyError {
+    fn code(&self) -> Code {
+        match self {
+            VerifyError::Malformed(e) => e.code(),
+            VerifyError::InvalidPublicKey => Code::INVALID_KEY,
+            _ => Code::VERIFY_FAILED,
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::sm2::simple::check;
+    use crate::sm2::Signature;
+
+    use super::*;
+
+    const PUK: &str = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+
+    #[test]
+    fn reasons() {
+        let reason = |signature: &[u8]| check(PUK, b"message", signature).unwrap_err().reason();
+        assert_eq!(reason(&[0x30, 0x00]), "malformed_signature");
+        assert_eq!(reason(&Signature::new(0u8.into(), 1u8.into()).to_der()), "r_out_of_range");
+        assert_eq!(reason(&Signature::new(1u8.into(), 0u8.into()).to_der()), "s_out_of_range");
+        assert_eq!(reason(&Signature::new(1u8.into(), 1u8.into()).to_der()), "mismatch");
+
+        let reasons = [
+            VerifyError::ZeroSum, VerifyError::InvalidPublicKey, VerifyError::PointAtInfinity,
+        ].map(|e| e.reason());
+        assert_eq!(reasons, ["zero_sum", "invalid_public_key", "point_at_infinity"]);
+        assert_eq!(VerifyError::InvalidPublicKey.code(), Code::INVALID_KEY);
+    }
+}
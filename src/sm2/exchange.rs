@@ -0,0 +1,356 @@
+//! SM2密钥交换协议（GB/T 32918.3）
+//!
+//! 双方各持长期密钥对与对方的公钥，交换临时公钥RA、RB后协商出相同的会话密钥K，并可用SB、SA相互确认。
+//! [`Transcript`]给出协商过程的全部公开值与K，上层协议可据此把会话密钥绑定到通道标识，或自行组织确认流程。
+
+use std::fmt::{Debug, Display, Formatter};
+
+use rand::{CryptoRng, RngCore};
+
+use crate::ct::ct_eq;
+use crate::env::EnvRng;
+use crate::error_code::{Code, ErrorCode};
+use crate::sm2::ecc::{kdf, za, DEFAULT_USER_ID};
+use crate::sm2::key::{to_32_bytes, KeyPair, PublicKey};
+use crate::sm2::p256::P256Elliptic;
+use crate::sm2::point::{Point, Scalar};
+use crate::sm3;
+
+/// 默认协商16字节的会话密钥，即一个SM4密钥
+pub const DEFAULT_KEY_LEN: usize = 16;
+
+/// 协议中的角色，发起方为用户A，响应方为用户B
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Initiator,
+    Responder,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExchangeError {
+    /// 对方的公钥或临时公钥不是曲线上的点
+    InvalidPublicKey,
+    /// 共享点U（V）为无穷远点
+    PointAtInfinity,
+    /// 对方发来的确认值与本方计算的不一致
+    ConfirmationMismatch,
+}
+
+impl Display for ExchangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExchangeError::InvalidPublicKey => write!(f, "The peer's public key or ephemeral key is not a point on the curve."),
+            ExchangeError::PointAtInfinity => write!(f, "The shared point is the point at infinity."),
+            ExchangeError::ConfirmationMismatch => write!(f, "The key confirmation value does not match."),
+        }
+    }
+}
+
+impl std::error::Error for ExchangeError {}
+
+impl ErrorCode for ExchangeError {
+    fn code(&self) -> Code {
+        match self {
+            ExchangeError::InvalidPublicKey => Code::INVALID_KEY,
+            ExchangeError::PointAtInfinity => Code::FAIL,
+            ExchangeError::ConfirmationMismatch => Code::VERIFY_FAILED,
+        }
+    }
+}
+
+/// 一方的密钥交换状态，临时私钥在构造时生成，每次协商使用新的实例
+///
+/// ```
+/// use yarism::sm2::exchange::{KeyExchange, Role};
+/// use yarism::sm2::{generate_keypair, HexKey, KeyPair, PrivateKey, PublicKey};
+///
+/// let keypair = |(prk, puk): (String, String)| KeyPair::new(PrivateKey::decode(&prk), PublicKey::decode(&puk));
+/// let (alice, bob) = (keypair(generate_keypair()), keypair(generate_keypair()));
+///
+/// let a = KeyExchange::new(Role::Initiator, alice.clone(), bob.puk().clone());
+/// let b = KeyExchange::new(Role::Responder, bob, alice.puk().clone());
+///
+/// // A发送RA，B计算后发送RB与SB，A核对SB后发送SA，B核对SA
+/// let tb = b.agree(&a.ephemeral_key()).unwrap();
+/// let ta = a.agree(&b.ephemeral_key()).unwrap();
+/// ta.verify_confirmation(&tb.confirmation()).unwrap();
+/// tb.verify_confirmation(&ta.confirmation()).unwrap();
+/// assert_eq!(ta.key(), tb.key());
+/// ```
+pub struct KeyExchange {
+    role: Role,
+    keypair: KeyPair,
+    peer_key: PublicKey,
+    user_id: Vec<u8>,
+    peer_id: Vec<u8>,
+    key_len: usize,
+    ephemeral: Scalar,
+    ephemeral_point: Point,
+}
+
+impl KeyExchange {
+    pub fn new(role: Role, keypair: KeyPair, peer_key: PublicKey) -> Self {
+        Self::new_with_rng(role, keypair, peer_key, &mut EnvRng)
+    }
+
+    /// 同[`KeyExchange::new`]，临时私钥取自`rng`
+    pub fn new_with_rng(role: Role, keypair: KeyPair, peer_key: PublicKey, rng: &mut (impl CryptoRng + RngCore)) -> Self {
+        Self::with_ephemeral(role, keypair, peer_key, Scalar::random_with_rng(rng))
+    }
+
+    fn with_ephemeral(role: Role, keypair: KeyPair, peer_key: PublicKey, ephemeral: Scalar) -> Self {
+        KeyExchange {
+            role,
+            keypair,
+            peer_key,
+            user_id: DEFAULT_USER_ID.to_vec(),
+            peer_id: DEFAULT_USER_ID.to_vec(),
+            key_len: DEFAULT_KEY_LEN,
+            ephemeral,
+            ephemeral_point: Point::mul_base(&ephemeral),
+        }
+    }
+
+    /// 本方的用户ID，默认为[`DEFAULT_USER_ID`]
+    pub fn with_user_id(mut self, id: &[u8]) -> Self {
+        self.user_id = id.to_vec();
+        self
+    }
+
+    /// 对方的用户ID，默认为[`DEFAULT_USER_ID`]
+    pub fn with_peer_id(mut self, id: &[u8]) -> Self {
+        self.peer_id = id.to_vec();
+        self
+    }
+
+    /// 会话密钥的字节数，默认为[`DEFAULT_KEY_LEN`]
+    pub fn with_key_len(mut self, len: usize) -> Self {
+        self.key_len = len;
+        self
+    }
+
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    /// 发给对方的临时公钥：发起方为RA，响应方为RB
+    pub fn ephemeral_key(&self) -> PublicKey {
+        match self.ephemeral_point.to_public_key() {
+            Some(key) => key,
+            None => panic!("The ephemeral key must not be the point at infinity."),
+        }
+    }
+
+    /// 由对方的临时公钥计算共享点与会话密钥，两个确认值一并算出
+    ///
+    /// 本方 t = (d + x̄·r) mod n，共享点 U = [t](P' + [x̄']R')，其中 x̄ = 2^127 + (x mod 2^127)，余因子h = 1。
+    pub fn agree(&self, peer_ephemeral: &PublicKey) -> Result<Transcript, ExchangeError> {
+        let peer_point = affine(&self.peer_key).ok_or(ExchangeError::InvalidPublicKey)?;
+        let peer_ephemeral_point = affine(peer_ephemeral).ok_or(ExchangeError::InvalidPublicKey)?;
+        let (x, _) = self.ephemeral_point.to_affine().ok_or(ExchangeError::PointAtInfinity)?;
+        let (peer_x, _) = peer_ephemeral_point.to_affine().ok_or(ExchangeError::InvalidPublicKey)?;
+
+        let t = Scalar::from(self.keypair.prk()) + truncate(&x) * self.ephemeral;
+        let shared = (peer_point + peer_ephemeral_point * truncate(&peer_x)) * t;
+        let (xu, yu) = shared.to_affine().ok_or(ExchangeError::PointAtInfinity)?;
+
+        let ec = &P256Elliptic::init().ec;
+        let (own_z, peer_z) = (za(ec, &self.user_id, self.keypair.puk()), za(ec, &self.peer_id, &self.peer_key));
+        let own_r = self.ephemeral_key();
+        let (za, zb, ra, rb) = match self.role {
+            Role::Initiator => (own_z, peer_z, own_r, peer_ephemeral.clone()),
+            Role::Responder => (peer_z, own_z, peer_ephemeral.clone(), own_r),
+        };
+
+        let key = kdf(&[&xu[..], &yu, &za, &zb].concat(), self.key_len);
+        // Hash(xU ∥ ZA ∥ ZB ∥ x1 ∥ y1 ∥ x2 ∥ y2)
+        let mut hasher = sm3::Hasher::new();
+        hasher.update(&xu).update(&za).update(&zb).update(&ra.to_bytes()[1..]).update(&rb.to_bytes()[1..]);
+        let inner = hasher.finalize();
+        let confirm = |tag: u8| {
+            let mut hasher = sm3::Hasher::new();
+            hasher.update(&[tag]).update(&yu).update(&inner);
+            hasher.finalize()
+        };
+
+        Ok(Transcript { role: self.role, ra, rb, za, zb, key, s_b: confirm(0x02), s_a: confirm(0x03) })
+    }
+}
+
+/// 公钥的坐标不满足曲线方程时返回None
+fn affine(key: &PublicKey) -> Option<Point> {
+    let (x, y) = key.value();
+    Point::from_affine(&to_32_bytes(x.to_bytes_be()), &to_32_bytes(y.to_bytes_be()))
+}
+
+/// x̄ = 2^w + (x mod 2^w)，w = ⌈⌈log2(n)⌉/2⌉ - 1 = 127
+fn truncate(x: &[u8; 32]) -> Scalar {
+    let mut bytes = [0u8; 32];
+    bytes[16..].copy_from_slice(&x[16..]);
+    bytes[16] |= 0x80;
+    Scalar::from_bytes_be(&bytes)
+}
+
+/// 一次密钥交换的全部结果
+///
+/// RA、RB、ZA、ZB与确认值是公开的，可整体写入上层协议的握手记录；会话密钥K须保密，`Debug`不输出K。
+#[derive(Clone)]
+pub struct Transcript {
+    role: Role,
+    ra: PublicKey,
+    rb: PublicKey,
+    za: [u8; 32],
+    zb: [u8; 32],
+    key: Vec<u8>,
+    s_b: [u8; 32],
+    s_a: [u8; 32],
+}
+
+impl Transcript {
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    /// 发起方的临时公钥
+    pub fn ra(&self) -> &PublicKey {
+        &self.ra
+    }
+
+    /// 响应方的临时公钥
+    pub fn rb(&self) -> &PublicKey {
+        &self.rb
+    }
+
+    /// 发起方的用户杂凑值
+    pub fn za(&self) -> &[u8; 32] {
+        &self.za
+    }
+
+    /// 响应方的用户杂凑值
+    pub fn zb(&self) -> &[u8; 32] {
+        &self.zb
+    }
+
+    /// 会话密钥K = KDF(xU ∥ yU ∥ ZA ∥ ZB, klen)
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    /// SB = Hash(0x02 ∥ yU ∥ Hash(xU ∥ ZA ∥ ZB ∥ x1 ∥ y1 ∥ x2 ∥ y2))，由响应方发出；发起方算出的S1与之相同
+    pub fn s_b(&self) -> &[u8; 32] {
+        &self.s_b
+    }
+
+    /// SA = Hash(0x03 ∥ yU ∥ Hash(xU ∥ ZA ∥ ZB ∥ x1 ∥ y1 ∥ x2 ∥ y2))，由发起方发出；响应方算出的S2与之相同
+    pub fn s_a(&self) -> &[u8; 32] {
+        &self.s_a
+    }
+
+    /// 本方发给对方的确认值：响应方为SB，发起方为SA
+    pub fn confirmation(&self) -> [u8; 32] {
+        match self.role {
+            Role::Initiator => self.s_a,
+            Role::Responder => self.s_b,
+        }
+    }
+
+    /// 核对对方发来的确认值：发起方以S1核对SB，响应方以S2核对SA，比较时间不依赖内容
+    pub fn verify_confirmation(&self, received: &[u8]) -> Result<(), ExchangeError> {
+        let expected = match self.role {
+            Role::Initiator => &self.s_b,
+            Role::Responder => &self.s_a,
+        };
+        if !ct_eq(expected, received) {
+            return Err(ExchangeError::ConfirmationMismatch);
+        }
+        Ok(())
+    }
+}
+
+impl Debug for Transcript {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Transcript")
+            .field("role", &self.role)
+            .field("ra", &hex::encode(self.ra.to_bytes()))
+            .field("rb", &hex::encode(self.rb.to_bytes()))
+            .field("za", &hex::encode(self.za))
+            .field("zb", &hex::encode(self.zb))
+            .field("s_b", &hex::encode(self.s_b))
+            .field("s_a", &hex::encode(self.s_a))
+            .finish_non_exhaustive()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use crate::sm2::key::{HexKey, PrivateKey};
+
+    use super::*;
+
+    const PRK_A: &str = "6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e";
+    const PUK_A: &str = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+    const PRK_B: &str = "5be7e4b09a761b0f3d7d3bca1ee3e1ab4b5b5d1b3b6e5c8a0f7d2c6a1b9e8d47";
+    const PUK_B: &str = "049fdb9723a9801febe966be4124bc9ae5469b7f93e46bc222bc0df9416bb3680dbdc4a9ffc3d5ba9e81c8ba266f96d8b94bad0c488088a8ee3f4bf1b30b7f0fa9";
+
+    fn scalar(hex: &str) -> Scalar {
+        Scalar::from_biguint(&BigUint::parse_bytes(hex.as_bytes(), 16).unwrap())
+    }
+
+    fn parties() -> (KeyExchange, KeyExchange) {
+        let alice = KeyPair::new(PrivateKey::decode(PRK_A), PublicKey::decode(PUK_A));
+        let bob = KeyPair::new(PrivateKey::decode(PRK_B), PublicKey::decode(PUK_B));
+        let ra = scalar("83a2c9c8b96e5af70bd480b472409a9a327257f1ebb73f5b073354b248668563");
+        let rb = scalar("33fe21940342161c55619c4a0c060293d543c80af19748ce176d83477de71c80");
+        (
+            KeyExchange::with_ephemeral(Role::Initiator, alice, PublicKey::decode(PUK_B), ra).with_peer_id(b"bob@example.com"),
+            KeyExchange::with_ephemeral(Role::Responder, bob, PublicKey::decode(PUK_A), rb).with_user_id(b"bob@example.com"),
+        )
+    }
+
+    /// 期望值由独立的Python实现（仿射坐标运算与hashlib的SM3）按GB/T 32918.3计算
+    #[test]
+    fn transcript() {
+        let (a, b) = parties();
+        let (ta, tb) = (a.agree(&b.ephemeral_key()).unwrap(), b.agree(&a.ephemeral_key()).unwrap());
+        for t in [&ta, &tb] {
+            assert_eq!(t.ra().encode(), "04698c93c85618d221a6de87ca8f091a89cfaecc9fff2dd978f92769a40af3b740b058698f05ed5aadec7d50616e7a05e9aa151c9b693fdcada01f16cfacc994b4");
+            assert_eq!(t.rb().encode(), "0426891afec73a32fa5bf2cbe91acded37cac48621d85d5965a5044a84dbda5988c2f8f5ddd6190244d5aa85feb8e9cbc583c75401e37d8811d47a800f0d96796f");
+            assert_eq!(hex::encode(t.za()), "7b9e665a727b527b5f7c3e7cd85a5576579ec87cab4fdafd14b0cd5cceb35728");
+            assert_eq!(hex::encode(t.zb()), "f12806a71917ae6d0445538551ff0c156420e37f0651bbf1047c1c94c2823485");
+            assert_eq!(hex::encode(t.key()), "9f0d3c3cf495a33267f2c6a1e9dcad0d");
+            assert_eq!(hex::encode(t.s_b()), "1cfd311bd3163931a0fb4c1bd445a42e5d130908f33245454df567b6766691de");
+            assert_eq!(hex::encode(t.s_a()), "76818da0d3dc0407f89f4d898cfea25bcbf65763aa2e0631a75443d79be0ae30");
+        }
+        assert_eq!(ta.confirmation(), *ta.s_a());
+        assert_eq!(tb.confirmation(), *tb.s_b());
+        assert!(!format!("{:?}", ta).contains("9f0d3c3c"));
+    }
+
+    #[test]
+    fn confirmation() {
+        let (a, b) = parties();
+        let (ta, tb) = (a.agree(&b.ephemeral_key()).unwrap(), b.agree(&a.ephemeral_key()).unwrap());
+        assert_eq!(ta.verify_confirmation(&tb.confirmation()), Ok(()));
+        assert_eq!(tb.verify_confirmation(&ta.confirmation()), Ok(()));
+        // 确认值带有方向，不能原样反射回去
+        assert_eq!(ta.verify_confirmation(&ta.confirmation()), Err(ExchangeError::ConfirmationMismatch));
+        assert_eq!(tb.verify_confirmation(&tb.confirmation()[..31]), Err(ExchangeError::ConfirmationMismatch));
+
+        // 用户ID不一致时密钥与确认值都不同
+        let (a, b) = parties();
+        let tb = b.with_user_id(b"mallory").agree(&a.ephemeral_key()).unwrap();
+        let ta = a.agree(&tb.rb().clone()).unwrap();
+        assert_ne!(ta.key(), tb.key());
+        assert!(ta.verify_confirmation(&tb.confirmation()).is_err());
+
+        let (a, b) = parties();
+        let a = a.with_key_len(48);
+        assert_eq!(a.agree(&b.ephemeral_key()).unwrap().key().len(), 48);
+        let (x, y) = PublicKey::decode(PUK_B).value();
+        let off_curve = PublicKey::new(x, y + 1u8);
+        assert_eq!(a.agree(&off_curve).err(), Some(ExchangeError::InvalidPublicKey));
+    }
+}
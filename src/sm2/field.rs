@@ -0,0 +1,182 @@
+//! SM2推荐曲线的素域GF(p)
+//!
+//! [`Fp256`]即曲线运算内部使用的9个limb的Montgomery形式，加、减、乘、平方、求逆与编解码的执行路径不依赖取值。
+
+use std::fmt::{Debug, Formatter};
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num_bigint::BigUint;
+
+use crate::ct::ct_eq;
+use crate::sm2::key::to_32_bytes;
+use crate::sm2::p256::params::{EC_P, P256FACTOR};
+use crate::sm2::p256::payload::Payload;
+use crate::sm2::p256::P256Elliptic;
+
+/// 模p的域元素，取值范围\[0, p)
+///
+/// ```
+/// use yarism::sm2::{Fp256, EC_A, EC_B, EC_GX, EC_GY};
+///
+/// // 基点满足 y² = x³ + ax + b
+/// let (x, y) = (Fp256::from_bytes(&EC_GX).unwrap(), Fp256::from_bytes(&EC_GY).unwrap());
+/// let (a, b) = (Fp256::from_bytes(&EC_A).unwrap(), Fp256::from_bytes(&EC_B).unwrap());
+/// let rhs = x.square() * x + a * x + b;
+/// assert_eq!(y.square(), rhs);
+/// assert!(rhs.sqrt().unwrap() == y || rhs.sqrt().unwrap() == -y);
+/// ```
+#[derive(Clone, Copy)]
+pub struct Fp256(Payload);
+
+impl Fp256 {
+    pub fn zero() -> Self {
+        Fp256(Payload::init())
+    }
+
+    pub fn one() -> Self {
+        Fp256(Payload::new(P256FACTOR[1]))
+    }
+
+    /// 大端序32字节，不小于p时返回None，比较的执行路径不依赖取值
+    pub fn from_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        // bytes - p 有借位即 bytes < p
+        let borrow = bytes.iter().rev().zip(EC_P.iter().rev()).fold(0u16, |borrow, (a, b)| {
+            ((*a as u16).wrapping_sub(*b as u16).wrapping_sub(borrow) >> 8) & 1
+        });
+        (borrow == 1).then(|| Fp256(Payload::from_bytes(bytes)))
+    }
+
+    /// 大端序32字节，不小于p时模p约简
+    pub fn from_bytes_reduced(bytes: &[u8; 32]) -> Self {
+        Fp256(Payload::from_bytes(bytes))
+    }
+
+    /// 大端序32字节，取值在\[0, p)内
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.to_bytes()
+    }
+
+    /// 任意大小的整数，模p约简
+    pub fn from_biguint(n: &BigUint) -> Self {
+        Self::from_bytes_reduced(&to_32_bytes((n % &P256Elliptic::init().ec.p).to_bytes_be()))
+    }
+
+    pub fn to_biguint(&self) -> BigUint {
+        BigUint::from_bytes_be(&self.to_bytes())
+    }
+
+    pub fn square(&self) -> Self {
+        Fp256(self.0.square())
+    }
+
+    /// 模p逆元，0没有逆元
+    pub fn invert(&self) -> Option<Self> {
+        (!self.is_zero()).then(|| Fp256(self.0.invert()))
+    }
+
+    /// 平方根，两个根任取其一；不是二次剩余时返回None
+    pub fn sqrt(&self) -> Option<Self> {
+        self.0.sqrt().map(Fp256)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.zero_mask() != 0
+    }
+}
+
+impl Add for Fp256 {
+    type Output = Fp256;
+
+    fn add(self, other: Fp256) -> Fp256 {
+        Fp256(self.0.add(&other.0))
+    }
+}
+
+impl Sub for Fp256 {
+    type Output = Fp256;
+
+    fn sub(self, other: Fp256) -> Fp256 {
+        Fp256(self.0.subtract(&other.0))
+    }
+}
+
+impl Mul for Fp256 {
+    type Output = Fp256;
+
+    fn mul(self, other: Fp256) -> Fp256 {
+        Fp256(self.0.multiply(&other.0))
+    }
+}
+
+impl Neg for Fp256 {
+    type Output = Fp256;
+
+    fn neg(self) -> Fp256 {
+        Fp256(self.0.negate())
+    }
+}
+
+/// 比较规范编码，执行路径不依赖取值
+impl PartialEq for Fp256 {
+    fn eq(&self, other: &Fp256) -> bool {
+        ct_eq(&self.to_bytes(), &other.to_bytes())
+    }
+}
+
+impl Eq for Fp256 {}
+
+impl Debug for Fp256 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Fp256({})", hex::encode(self.to_bytes()))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use num_traits::One;
+
+    use crate::sm2::p256::params::{EC_A, EC_B, EC_GX, EC_GY};
+
+    use super::*;
+
+    #[test]
+    fn encoding() {
+        let p = BigUint::from_bytes_be(&EC_P);
+        let p_minus_1 = to_32_bytes((&p - 1u8).to_bytes_be());
+        assert_eq!(Fp256::from_bytes(&p_minus_1).unwrap().to_bytes(), p_minus_1);
+        assert_eq!(Fp256::from_bytes(&p_minus_1).unwrap() + Fp256::one(), Fp256::zero());
+        assert!(Fp256::from_bytes(&EC_P).is_none());
+        assert!(Fp256::from_bytes(&[0xff; 32]).is_none());
+        assert_eq!(Fp256::from_bytes_reduced(&EC_P), Fp256::zero());
+        assert_eq!(Fp256::from_bytes_reduced(&[0xff; 32]).to_biguint(), (BigUint::one() << 256) - 1u8 - &p);
+        assert_eq!(Fp256::from_biguint(&(&p + 7u8)).to_biguint(), BigUint::from(7u8));
+        assert_eq!(Fp256::from_bytes(&[0; 32]), Some(Fp256::zero()));
+        assert_eq!(format!("{:?}", Fp256::one()), format!("Fp256({}01)", "00".repeat(31)));
+    }
+
+    /// 与大整数运算对照，并以GB/T 32918.5的曲线参数检查基点
+    #[test]
+    fn arithmetic() {
+        let p = BigUint::from_bytes_be(&EC_P);
+        let (gx, gy) = (Fp256::from_bytes(&EC_GX).unwrap(), Fp256::from_bytes(&EC_GY).unwrap());
+        let (a, b) = (Fp256::from_bytes(&EC_A).unwrap(), Fp256::from_bytes(&EC_B).unwrap());
+        assert_eq!(gy.square(), gx.square() * gx + a * gx + b);
+        assert_eq!(a, -Fp256::from_biguint(&BigUint::from(3u8)));
+
+        let (x, y) = (gx.to_biguint(), gy.to_biguint());
+        assert_eq!((gx * gy).to_biguint(), &x * &y % &p);
+        assert_eq!((gx - gy).to_biguint(), (&x + &p - &y) % &p);
+        assert_eq!((gy - gx).to_biguint(), (&y + &p - &x) % &p);
+        assert_eq!((gx + gy).to_biguint(), (&x + &y) % &p);
+        assert_eq!(gx.invert().unwrap().to_biguint(), x.modpow(&(&p - 2u8), &p));
+        assert_eq!(gx * gx.invert().unwrap(), Fp256::one());
+        assert_eq!(Fp256::zero().invert(), None);
+        assert!(Fp256::zero().is_zero() && !Fp256::one().is_zero());
+
+        let root = gy.square().sqrt().unwrap();
+        assert!(root == gy || root == -gy);
+        // p ≡ 3 (mod 4)，-1不是二次剩余
+        assert_eq!((-Fp256::one()).sqrt(), None);
+    }
+}
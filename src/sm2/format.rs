@@ -0,0 +1,247 @@
+//! 银行等机构系统间交换SM2公钥时使用的文本格式
+//!
+//! 各家接口规范对同一公钥的写法不一：有的给出不带04前缀的128位16进制 x ∥ y，有的给出Base64编码的
+//! SubjectPublicKeyInfo。[`PublicKeyFormat`]为这些写法命名，[`PublicKey::to_text`]与[`PublicKey::from_text`]在其间转换。
+
+use std::fmt::{Display, Formatter};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+use crate::codec::{decode_hex_fixed, pem_decode, pem_encode, report, DecodeError};
+use crate::sm2::key::{hex_error, KeyError, PublicKey, PUBLIC_KEY};
+
+/// SubjectPublicKeyInfo { AlgorithmIdentifier { id-ecPublicKey, sm2 }, BIT STRING } 中公钥之前的固定部分
+const SPKI_HEADER: [u8; 26] = [
+    0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a,
+    0x81, 0x1c, 0xcf, 0x55, 0x01, 0x82, 0x2d, 0x03, 0x42, 0x00,
+];
+/// SubjectPublicKeyInfo的总长度
+const SPKI_LEN: usize = SPKI_HEADER.len() + 65;
+const PEM_LABEL: &str = "PUBLIC KEY";
+
+/// 公钥的文本格式，16进制解析时不区分大小写，输出为小写
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PublicKeyFormat {
+    /// 128位16进制 x ∥ y，不带04前缀
+    HexXY,
+    /// 130位16进制 04 ∥ x ∥ y
+    HexUncompressed,
+    /// 66位16进制 02/03 ∥ x
+    HexCompressed,
+    /// 16进制的SubjectPublicKeyInfo DER
+    HexSpki,
+    /// Base64编码的64字节 x ∥ y
+    Base64XY,
+    /// Base64编码的65字节 04 ∥ x ∥ y
+    Base64Uncompressed,
+    /// Base64编码的SubjectPublicKeyInfo DER，即去掉首尾行的PEM公钥
+    Base64Spki,
+}
+
+impl PublicKeyFormat {
+    /// 全部格式，即[`PublicKeyFormat::detect`]的尝试顺序
+    pub const ALL: [PublicKeyFormat; 7] = [
+        PublicKeyFormat::HexXY,
+        PublicKeyFormat::HexUncompressed,
+        PublicKeyFormat::HexCompressed,
+        PublicKeyFormat::HexSpki,
+        PublicKeyFormat::Base64XY,
+        PublicKeyFormat::Base64Uncompressed,
+        PublicKeyFormat::Base64Spki,
+    ];
+
+    /// 依次尝试各格式，返回第一个能解析`text`的格式
+    ///
+    /// 各格式解码后的长度互不相同，16进制与Base64文本的长度也不会重合，因此结果是唯一的。
+    pub fn detect(text: &str) -> Option<PublicKeyFormat> {
+        Self::ALL.into_iter().find(|format| PublicKey::from_text(text, *format).is_ok())
+    }
+}
+
+impl Display for PublicKeyFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            PublicKeyFormat::HexXY => "hex x||y",
+            PublicKeyFormat::HexUncompressed => "hex 04||x||y",
+            PublicKeyFormat::HexCompressed => "hex 02/03||x",
+            PublicKeyFormat::HexSpki => "hex SubjectPublicKeyInfo",
+            PublicKeyFormat::Base64XY => "base64 x||y",
+            PublicKeyFormat::Base64Uncompressed => "base64 04||x||y",
+            PublicKeyFormat::Base64Spki => "base64 SubjectPublicKeyInfo",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl PublicKey {
+    /// 按指定格式输出公钥文本
+    pub fn to_text(&self, format: PublicKeyFormat) -> String {
+        let bytes = self.to_bytes();
+        match format {
+            PublicKeyFormat::HexXY => hex::encode(&bytes[1..]),
+            PublicKeyFormat::HexUncompressed => hex::encode(&bytes),
+            PublicKeyFormat::HexCompressed => hex::encode(self.to_compressed_bytes()),
+            PublicKeyFormat::HexSpki => hex::encode([&SPKI_HEADER[..], &bytes].concat()),
+            PublicKeyFormat::Base64XY => STANDARD.encode(&bytes[1..]),
+            PublicKeyFormat::Base64Uncompressed => STANDARD.encode(&bytes),
+            PublicKeyFormat::Base64Spki => STANDARD.encode([&SPKI_HEADER[..], &bytes].concat()),
+        }
+    }
+
+    /// 按指定格式解析公钥文本，忽略换行、空格等空白字符
+    pub fn from_text(text: &str, format: PublicKeyFormat) -> Result<Self, KeyError> {
+        let text: String = text.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+        match format {
+            PublicKeyFormat::HexXY => {
+                let xy = decode_hex_fixed::<64>(&text).map_err(|e| hex_error(PUBLIC_KEY, &text, e))?;
+                Self::from_bytes(&[&[0x04], &xy[..]].concat())
+            }
+            PublicKeyFormat::HexUncompressed => {
+                decode_hex_fixed::<65>(&text).map_err(|e| hex_error(PUBLIC_KEY, &text, e)).and_then(|key| Self::from_bytes(&key))
+            }
+            PublicKeyFormat::HexCompressed => {
+                decode_hex_fixed::<33>(&text).map_err(|e| hex_error(PUBLIC_KEY, &text, e)).and_then(|key| Self::from_bytes(&key))
+            }
+            PublicKeyFormat::HexSpki => {
+                decode_hex_fixed::<SPKI_LEN>(&text).map_err(|e| hex_error(PUBLIC_KEY, &text, e)).and_then(|der| from_spki(&der))
+            }
+            PublicKeyFormat::Base64XY => {
+                let xy = decode_base64(&text, 64)?;
+                Self::from_bytes(&[&[0x04], &xy[..]].concat())
+            }
+            PublicKeyFormat::Base64Uncompressed => Self::from_bytes(&decode_base64(&text, 65)?),
+            PublicKeyFormat::Base64Spki => from_spki(&decode_base64(&text, SPKI_LEN)?),
+        }
+    }
+
+    /// DER编码的SubjectPublicKeyInfo，算法标识为id-ecPublicKey与SM2曲线，公钥为非压缩格式
+    pub fn to_spki_der(&self) -> Vec<u8> {
+        [&SPKI_HEADER[..], &self.to_bytes()].concat()
+    }
+
+    /// 解析DER编码的SubjectPublicKeyInfo
+    pub fn from_spki_der(der: &[u8]) -> Result<Self, KeyError> {
+        if der.len() != SPKI_LEN {
+            let e = DecodeError::new(PUBLIC_KEY, "unexpected length", der, der.len().min(SPKI_LEN)).expected(SPKI_LEN);
+            return Err(KeyError::InvalidLength(report(e)));
+        }
+        from_spki(der)
+    }
+
+    /// `-----BEGIN PUBLIC KEY-----`格式，即OpenSSL等库交换公钥使用的PEM
+    pub fn to_spki_pem(&self) -> String {
+        pem_encode(PEM_LABEL, &self.to_spki_der())
+    }
+
+    /// 解析`-----BEGIN PUBLIC KEY-----`格式，标签之外的内容被忽略
+    pub fn from_spki_pem(pem: &str) -> Result<Self, KeyError> {
+        match pem_decode(PEM_LABEL, pem) {
+            Some(der) => Self::from_spki_der(&der),
+            None => Err(KeyError::InvalidBase64(report(DecodeError::new(PUBLIC_KEY, "malformed PEM", pem.as_bytes(), 0)))),
+        }
+    }
+}
+
+/// 标准字符集、带填充的Base64，解码结果必须恰好为`len`字节
+fn decode_base64(text: &str, len: usize) -> Result<Vec<u8>, KeyError> {
+    let data = STANDARD.decode(text).map_err(|e| {
+        let offset = match e {
+            base64::DecodeError::InvalidByte(offset, _) => offset,
+            base64::DecodeError::InvalidLastSymbol(offset, _) => offset,
+            _ => text.len(),
+        };
+        KeyError::InvalidBase64(report(DecodeError::new(PUBLIC_KEY, "invalid base64", text.as_bytes(), offset)))
+    })?;
+    if data.len() != len {
+        let e = DecodeError::new(PUBLIC_KEY, "unexpected length", &data, data.len().min(len)).expected(len);
+        return Err(KeyError::InvalidLength(report(e)));
+    }
+    Ok(data)
+}
+
+/// SPKI_LEN字节的SubjectPublicKeyInfo，算法标识必须为id-ecPublicKey与SM2曲线
+fn from_spki(der: &[u8]) -> Result<PublicKey, KeyError> {
+    if let Some(offset) = SPKI_HEADER.iter().zip(der).position(|(a, b)| a != b) {
+        let e = DecodeError::new(PUBLIC_KEY, "unexpected SubjectPublicKeyInfo header", der, offset);
+        return Err(KeyError::InvalidPrefix(report(e)));
+    }
+    PublicKey::from_bytes(&der[SPKI_HEADER.len()..])
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::error_code::{Code, ErrorCode};
+    use crate::sm2::key::HexKey;
+
+    use super::*;
+
+    const PUK: &str = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+
+    #[test]
+    fn round_trip() {
+        let key = PublicKey::decode(PUK);
+        assert_eq!(key.to_text(PublicKeyFormat::HexXY), PUK[2..]);
+        assert_eq!(key.to_text(PublicKeyFormat::HexUncompressed), PUK);
+        assert_eq!(key.to_text(PublicKeyFormat::HexSpki), format!("3059301306072a8648ce3d020106082a811ccf5501822d034200{}", PUK));
+        assert_eq!(
+            key.to_text(PublicKeyFormat::Base64Spki),
+            "MFkwEwYHKoZIzj0CAQYIKoEcz1UBgi0DQgAEqK9k447qQcJU33abW0H7qi13siazAaJjbUY8UrRsd3IwrRcU5obdZBueBFllMLOPamQhWw7TsIH4ZBckxUQ6bg==",
+        );
+
+        for format in PublicKeyFormat::ALL {
+            let text = key.to_text(format);
+            assert_eq!(PublicKey::from_text(&text, format).unwrap().encode(), PUK, "{}", format);
+            assert_eq!(PublicKeyFormat::detect(&text), Some(format));
+        }
+    }
+
+    #[test]
+    fn lenient_input() {
+        let key = PublicKey::decode(PUK);
+        // 大写16进制、按64字符换行的Base64
+        let upper = PUK[2..].to_uppercase();
+        assert_eq!(PublicKey::from_text(&upper, PublicKeyFormat::HexXY).unwrap().encode(), PUK);
+        let pem_body = key.to_text(PublicKeyFormat::Base64Spki);
+        let wrapped = format!("{}\r\n{}\n", &pem_body[..64], &pem_body[64..]);
+        assert_eq!(PublicKey::from_text(&wrapped, PublicKeyFormat::Base64Spki).unwrap().encode(), PUK);
+        assert_eq!(PublicKeyFormat::detect("not a key"), None);
+    }
+
+    #[test]
+    fn errors() {
+        let key = PublicKey::decode(PUK);
+        let code = |text: &str, format| PublicKey::from_text(text, format).unwrap_err().code();
+        assert_eq!(code(PUK, PublicKeyFormat::HexXY), Code::INVALID_LENGTH);
+        assert_eq!(code(&PUK[2..].replace('a', "g"), PublicKeyFormat::HexXY), Code::INVALID_DATA);
+        assert_eq!(code(&key.to_text(PublicKeyFormat::Base64XY), PublicKeyFormat::Base64Uncompressed), Code::INVALID_LENGTH);
+        assert!(matches!(PublicKey::from_text("@@@@", PublicKeyFormat::Base64XY), Err(KeyError::InvalidBase64(_))));
+
+        // 算法标识被篡改的SubjectPublicKeyInfo
+        let mut der = hex::decode(key.to_text(PublicKeyFormat::HexSpki)).unwrap();
+        der[8] = 0x86;
+        let e = PublicKey::from_text(&hex::encode(&der), PublicKeyFormat::HexSpki).unwrap_err();
+        assert!(matches!(&e, KeyError::InvalidPrefix(d) if d.offset == 8), "{:?}", e);
+    }
+
+    /// 期望值由`openssl pkey -pubout`从同一私钥导出
+    #[test]
+    fn spki() {
+        const PEM: &str = "\
+-----BEGIN PUBLIC KEY-----
+MFkwEwYHKoZIzj0CAQYIKoEcz1UBgi0DQgAEqK9k447qQcJU33abW0H7qi13siaz
+AaJjbUY8UrRsd3IwrRcU5obdZBueBFllMLOPamQhWw7TsIH4ZBckxUQ6bg==
+-----END PUBLIC KEY-----
+";
+        let key = PublicKey::decode(PUK);
+        assert_eq!(key.to_spki_pem(), PEM);
+        assert_eq!(PublicKey::from_spki_pem(PEM).unwrap().encode(), PUK);
+        let der = key.to_spki_der();
+        assert_eq!(hex::encode(&der), key.to_text(PublicKeyFormat::HexSpki));
+        assert_eq!(PublicKey::from_spki_der(&der).unwrap().encode(), PUK);
+
+        assert_eq!(PublicKey::from_spki_der(&der[..90]).unwrap_err().code(), Code::INVALID_LENGTH);
+        assert!(matches!(PublicKey::from_spki_pem(&PEM.replace("PUBLIC", "PRIVATE")), Err(KeyError::InvalidBase64(_))));
+    }
+}
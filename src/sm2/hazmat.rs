@@ -0,0 +1,144 @@
+use num_bigint::BigUint;
+
+use crate::sm2::ecc::EllipticBuilder;
+use crate::sm2::key::{to_32_bytes, PublicKey};
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+use crate::sm2::key::PrivateKey;
+use crate::sm2::p256::P256Elliptic;
+
+/// 大端序标量，高位字节在前，与私钥、签名分量(r, s)以及GM/T 0009的编码一致
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScalarBytesBe(pub [u8; 32]);
+
+/// 小端序标量，低位字节在前，P256基点乘法内部按此字节序逐位读取标量
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScalarBytesLe(pub [u8; 32]);
+
+impl ScalarBytesBe {
+    /// 标量超过32字节时返回None
+    pub fn from_biguint(scalar: &BigUint) -> Option<Self> {
+        let bytes = scalar.to_bytes_be();
+        if bytes.len() > 32 {
+            return None;
+        }
+        Some(ScalarBytesBe(to_32_bytes(bytes)))
+    }
+
+    pub fn to_biguint(&self) -> BigUint {
+        BigUint::from_bytes_be(&self.0)
+    }
+
+    pub fn to_le(&self) -> ScalarBytesLe {
+        let mut bytes = self.0;
+        bytes.reverse();
+        ScalarBytesLe(bytes)
+    }
+}
+
+impl ScalarBytesLe {
+    /// 标量超过32字节时返回None
+    pub fn from_biguint(scalar: &BigUint) -> Option<Self> {
+        ScalarBytesBe::from_biguint(scalar).map(|e| e.to_le())
+    }
+
+    pub fn to_biguint(&self) -> BigUint {
+        BigUint::from_bytes_le(&self.0)
+    }
+
+    pub fn to_be(&self) -> ScalarBytesBe {
+        let mut bytes = self.0;
+        bytes.reverse();
+        ScalarBytesBe(bytes)
+    }
+}
+
+impl From<ScalarBytesBe> for ScalarBytesLe {
+    fn from(scalar: ScalarBytesBe) -> Self {
+        scalar.to_le()
+    }
+}
+
+impl From<ScalarBytesLe> for ScalarBytesBe {
+    fn from(scalar: ScalarBytesLe) -> Self {
+        scalar.to_be()
+    }
+}
+
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+impl From<&PrivateKey> for ScalarBytesBe {
+    fn from(key: &PrivateKey) -> Self {
+        ScalarBytesBe(to_32_bytes(key.value().to_bytes_be()))
+    }
+}
+
+/// 基点标量乘法 scalar·G，标量模n约简，返回大端序坐标(x, y)
+///
+/// 两种字节序的标量均可传入，由类型决定如何解释字节。
+pub fn scalar_base_multiply(scalar: impl Into<ScalarBytesLe>) -> ([u8; 32], [u8; 32]) {
+    let p256 = P256Elliptic::init();
+    let (x, y) = p256.scalar_base_multiply(scalar.into().to_biguint());
+    (to_32_bytes(x.to_bytes_be()), to_32_bytes(y.to_bytes_be()))
+}
+
+/// 标量乘法 scalar·(x, y)，调用方需保证点在曲线上
+pub fn scalar_multiply(x: &[u8; 32], y: &[u8; 32], scalar: impl Into<ScalarBytesLe>) -> ([u8; 32], [u8; 32]) {
+    let p256 = P256Elliptic::init();
+    let (x, y) = p256.scalar_multiply(
+        BigUint::from_bytes_be(x),
+        BigUint::from_bytes_be(y),
+        scalar.into().to_biguint(),
+    );
+    (to_32_bytes(x.to_bytes_be()), to_32_bytes(y.to_bytes_be()))
+}
+
+/// 验签所需的 s·G + t·(x, y)，两个标量交错计算（Shamir技巧），结果为无穷远点时返回None
+///
+/// 可变时间，标量须是公开的；调用方需保证点在曲线上。
+pub fn double_scalar_multiply(s: impl Into<ScalarBytesLe>, x: &[u8; 32], y: &[u8; 32], t: impl Into<ScalarBytesLe>) -> Option<([u8; 32], [u8; 32])> {
+    let p256 = P256Elliptic::init();
+    let key = PublicKey::new(BigUint::from_bytes_be(x), BigUint::from_bytes_be(y));
+    let (x, y) = p256.double_scalar_multiply(s.into().to_biguint(), &key, t.into().to_biguint())?;
+    Some((to_32_bytes(x.to_bytes_be()), to_32_bytes(y.to_bytes_be())))
+}
+
+
+#[cfg(all(test, feature = "sm2-sign", feature = "sm2-encrypt"))]
+mod tests {
+    use crate::sm2::key::HexKey;
+
+    use super::*;
+
+    #[test]
+    fn main() {
+        let prk = PrivateKey::decode("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e");
+        let puk = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+
+        let be = ScalarBytesBe::from(&prk);
+        let le = be.to_le();
+        assert_eq!(le.to_be(), be);
+        assert_eq!(le.to_biguint(), prk.value());
+
+        let (x, y) = scalar_base_multiply(be);
+        assert_eq!(scalar_base_multiply(le), (x, y));
+        assert_eq!(PublicKey::try_from_bytes(&[&[0x04][..], &x, &y].concat()).unwrap().encode(), puk);
+
+        // 同一组字节按另一种字节序解释得到不同的点
+        assert_ne!(scalar_base_multiply(ScalarBytesLe(be.0)), (x, y));
+
+        let two = ScalarBytesBe::from_biguint(&BigUint::from(2u8)).unwrap();
+        let (x2, y2) = scalar_multiply(&x, &y, two);
+        let p256 = P256Elliptic::init();
+        let (ex, ey) = p256.scalar_base_multiply(prk.value() * 2u8);
+        assert_eq!((x2.to_vec(), y2.to_vec()), (ex.to_bytes_be(), ey.to_bytes_be()));
+
+        // 3·G + 2·P = (2d + 3)·G
+        let three = ScalarBytesBe::from_biguint(&BigUint::from(3u8)).unwrap();
+        let (ex, ey) = p256.scalar_base_multiply(prk.value() * 2u8 + 3u8);
+        let (sx, sy) = double_scalar_multiply(three, &x, &y, two).unwrap();
+        assert_eq!((sx.to_vec(), sy.to_vec()), (ex.to_bytes_be(), ey.to_bytes_be()));
+        // (n − 2)·G + 2·G = O
+        let minus_two = ScalarBytesBe::from_biguint(&(&p256.ec.n - 2u8)).unwrap();
+        let (gx, gy) = scalar_base_multiply(ScalarBytesBe::from_biguint(&BigUint::from(1u8)).unwrap());
+        assert_eq!(double_scalar_multiply(minus_two, &gx, &gy, two), None);
+    }
+}
@@ -0,0 +1,355 @@
+//! JSON Web Key（RFC 7517）格式的SM2密钥，`"kty":"EC"`、`"crv":"SM2"`，与GM JOSE草案一致
+//!
+//! x、y、d均为32字节大端序整数的base64url编码，不带填充。解析时忽略`kid`、`use`等其他成员，
+//! 成员名重复时报错，避免不同实现取到不同的值。
+
+use num_bigint::BigUint;
+
+use crate::codec::{decode_base64url, encode_base64url, report, DecodeError};
+use crate::sm2::key::{to_32_bytes, KeyError, PublicKey};
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+use crate::sm2::key::{KeyPair, PrivateKey};
+
+const JWK: &str = "SM2 JWK";
+/// 嵌套数组、对象的最大深度
+const MAX_DEPTH: usize = 32;
+
+impl PublicKey {
+    /// 紧凑的JWK，成员按字典序排列，即RFC 7638计算指纹时使用的形式
+    pub fn to_jwk(&self) -> String {
+        let (x, y) = self.value();
+        format!(r#"{{"crv":"SM2","kty":"EC","x":"{}","y":"{}"}}"#, coordinate(&x), coordinate(&y))
+    }
+
+    /// 解析JWK公钥，私钥成员`d`即使存在也被忽略
+    pub fn from_jwk(json: &str) -> Result<Self, KeyError> {
+        let members = parse(json)?;
+        public_key(json, &members)
+    }
+}
+
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+impl KeyPair {
+    /// 含私钥成员`d`的JWK，成员按字典序排列
+    pub fn to_jwk(&self) -> String {
+        let (x, y) = self.puk().value();
+        format!(
+            r#"{{"crv":"SM2","d":"{}","kty":"EC","x":"{}","y":"{}"}}"#,
+            coordinate(&self.prk().value()), coordinate(&x), coordinate(&y),
+        )
+    }
+
+    /// 解析含私钥的JWK，并核对d与(x, y)是否匹配
+    pub fn from_jwk(json: &str) -> Result<Self, KeyError> {
+        let members = parse(json)?;
+        let puk = public_key(json, &members)?;
+        let (d, offset) = member(json, &members, "d")?;
+        let keypair = KeyPair::new(PrivateKey::from_bytes(&integer(d)?)?, puk);
+        if !keypair.verify_consistency() {
+            return Err(invalid(json, "d does not match x and y", offset));
+        }
+        Ok(keypair)
+    }
+}
+
+fn coordinate(value: &BigUint) -> String {
+    encode_base64url(&to_32_bytes(value.to_bytes_be()))
+}
+
+fn public_key(json: &str, members: &[(String, String, usize)]) -> Result<PublicKey, KeyError> {
+    let (kty, offset) = member(json, members, "kty")?;
+    if kty != "EC" {
+        return Err(invalid(json, "unsupported kty", offset));
+    }
+    let (crv, offset) = member(json, members, "crv")?;
+    if crv != "SM2" {
+        return Err(invalid(json, "unsupported crv", offset));
+    }
+    let x = integer(member(json, members, "x")?.0)?;
+    let y = integer(member(json, members, "y")?.0)?;
+    PublicKey::from_bytes(&[&[0x04], &x[..], &y[..]].concat())
+}
+
+/// 字符串成员的取值及其在JSON中的偏移
+fn member<'a>(json: &str, members: &'a [(String, String, usize)], name: &'static str) -> Result<(&'a str, usize), KeyError> {
+    let reason = match name {
+        "kty" => "missing kty",
+        "crv" => "missing crv",
+        "x" => "missing x",
+        "y" => "missing y",
+        _ => "missing d",
+    };
+    members.iter()
+        .find(|(key, _, _)| key == name)
+        .map(|(_, value, offset)| (value.as_str(), *offset))
+        .ok_or_else(|| invalid(json, reason, json.len()))
+}
+
+/// 32字节大端序整数的base64url编码
+fn integer(value: &str) -> Result<[u8; 32], KeyError> {
+    let data = decode_base64url(JWK, value).map_err(KeyError::InvalidBase64)?;
+    data.as_slice().try_into().map_err(|_| {
+        let e = DecodeError::new(JWK, "unexpected length", &data, data.len().min(32)).expected(32);
+        KeyError::InvalidLength(report(e))
+    })
+}
+
+fn invalid(json: &str, reason: &'static str, offset: usize) -> KeyError {
+    KeyError::InvalidJwk(report(DecodeError::new(JWK, reason, json.as_bytes(), offset)))
+}
+
+/// 解析顶层JSON对象，返回其中取值为字符串的成员 (名称, 取值, 取值的偏移)
+fn parse(json: &str) -> Result<Vec<(String, String, usize)>, KeyError> {
+    let mut parser = Parser { json, pos: 0 };
+    let mut names = Vec::new();
+    let mut members = Vec::new();
+    parser.expect(b'{')?;
+    if !parser.consume(b'}') {
+        loop {
+            parser.skip_whitespace();
+            let offset = parser.pos;
+            let name = parser.string()?;
+            if names.contains(&name) {
+                return Err(invalid(json, "duplicate member", offset));
+            }
+            parser.expect(b':')?;
+            parser.skip_whitespace();
+            let offset = parser.pos;
+            if parser.peek() == Some(b'"') {
+                members.push((name.clone(), parser.string()?, offset));
+            } else {
+                parser.skip_value(0)?;
+            }
+            names.push(name);
+            if !parser.consume(b',') {
+                parser.expect(b'}')?;
+                break;
+            }
+        }
+    }
+    parser.skip_whitespace();
+    match parser.pos == json.len() {
+        true => Ok(members),
+        false => Err(parser.error()),
+    }
+}
+
+struct Parser<'a> {
+    json: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.json.as_bytes().get(self.pos).copied()
+    }
+
+    fn error(&self) -> KeyError {
+        invalid(self.json, "invalid JSON", self.pos)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    /// 跳过空白后若下一个字节为`byte`则消耗它
+    fn consume(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        let matched = self.peek() == Some(byte);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), KeyError> {
+        match self.consume(byte) {
+            true => Ok(()),
+            false => Err(self.error()),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, KeyError> {
+        if self.peek() != Some(b'"') {
+            return Err(self.error());
+        }
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let c = self.json[self.pos..].chars().next().ok_or_else(|| self.error())?;
+            match c {
+                '"' => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                '\\' => {
+                    self.pos += 1;
+                    let escaped = match self.peek() {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            self.pos += 1;
+                            out.push(self.unicode()?);
+                            continue;
+                        }
+                        _ => return Err(self.error()),
+                    };
+                    out.push(escaped);
+                    self.pos += 1;
+                }
+                c if (c as u32) < 0x20 => return Err(self.error()),
+                c => {
+                    out.push(c);
+                    self.pos += c.len_utf8();
+                }
+            }
+        }
+    }
+
+    /// `\u`之后的4位16进制数，代理对须紧跟低位代理
+    fn unicode(&mut self) -> Result<char, KeyError> {
+        let high = self.hex4()?;
+        let code = match high {
+            0xD800..=0xDBFF => {
+                if !self.json[self.pos..].starts_with("\\u") {
+                    return Err(self.error());
+                }
+                self.pos += 2;
+                let low = self.hex4()?;
+                if !(0xDC00..=0xDFFF).contains(&low) {
+                    return Err(self.error());
+                }
+                0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+            }
+            code => code,
+        };
+        char::from_u32(code).ok_or_else(|| self.error())
+    }
+
+    fn hex4(&mut self) -> Result<u32, KeyError> {
+        let digits = self.json.get(self.pos..self.pos + 4).ok_or_else(|| self.error())?;
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(self.error());
+        }
+        self.pos += 4;
+        Ok(u32::from_str_radix(digits, 16).unwrap())
+    }
+
+    /// 跳过任意JSON值，只检查结构，不检查数字的格式
+    fn skip_value(&mut self, depth: usize) -> Result<(), KeyError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error());
+        }
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'"') => self.string().map(|_| ()),
+            Some(open @ (b'{' | b'[')) => {
+                self.pos += 1;
+                let close = if open == b'{' { b'}' } else { b']' };
+                if self.consume(close) {
+                    return Ok(());
+                }
+                loop {
+                    if open == b'{' {
+                        self.skip_whitespace();
+                        self.string()?;
+                        self.expect(b':')?;
+                    }
+                    self.skip_value(depth + 1)?;
+                    if !self.consume(b',') {
+                        return self.expect(close);
+                    }
+                }
+            }
+            _ => {
+                let start = self.pos;
+                while matches!(self.peek(), Some(b'a'..=b'z' | b'0'..=b'9' | b'+' | b'-' | b'.' | b'E')) {
+                    self.pos += 1;
+                }
+                let token = &self.json[start..self.pos];
+                let number = token.starts_with(|c: char| c == '-' || c.is_ascii_digit());
+                match token == "true" || token == "false" || token == "null" || number {
+                    true => Ok(()),
+                    false => Err(self.error()),
+                }
+            }
+        }
+    }
+}
+
+
+#[cfg(all(test, feature = "sm2-sign", feature = "sm2-encrypt"))]
+mod tests {
+    use crate::error_code::{Code, ErrorCode};
+    use crate::sm2::key::HexKey;
+
+    use super::*;
+
+    const PRK: &str = "6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e";
+    const PUK: &str = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+    const X: &str = "qK9k447qQcJU33abW0H7qi13siazAaJjbUY8UrRsd3I";
+    const Y: &str = "MK0XFOaG3WQbngRZZTCzj2pkIVsO07CB-GQXJMVEOm4";
+
+    #[test]
+    fn round_trip() {
+        let puk = PublicKey::decode(PUK);
+        assert_eq!(puk.to_jwk(), format!(r#"{{"crv":"SM2","kty":"EC","x":"{}","y":"{}"}}"#, X, Y));
+        assert_eq!(PublicKey::from_jwk(&puk.to_jwk()).unwrap().encode(), PUK);
+
+        let keypair = KeyPair::new(PrivateKey::decode(PRK), puk);
+        let jwk = keypair.to_jwk();
+        assert!(jwk.contains(r#""d":"auocz2EEiKqn_duj3W120739UPlX2Ee-PUU977aV8o4""#));
+        let parsed = KeyPair::from_jwk(&jwk).unwrap();
+        assert_eq!(parsed.prk().encode(), PRK);
+        assert_eq!(PublicKey::from_jwk(&jwk).unwrap().encode(), PUK);
+    }
+
+    #[test]
+    fn lenient_members() {
+        let json = format!(
+            "{{ \"kty\" : \"EC\",\n \"use\": \"sig\", \"key_ops\": [\"verify\", {{\"n\": [1, -2.5E3, true, null]}}],\n \
+             \"kid\": \"k\\u00e9y \\ud83d\\udd11\", \"crv\": \"SM2\", \"x\": \"{}\", \"y\": \"{}\" }}",
+            X, Y,
+        );
+        assert_eq!(PublicKey::from_jwk(&json).unwrap().encode(), PUK);
+    }
+
+    #[test]
+    fn errors() {
+        let reason = |json: &str| match PublicKey::from_jwk(json) {
+            Err(KeyError::InvalidJwk(e)) => e.reason,
+            other => panic!("{:?}", other.map(|key| key.encode())),
+        };
+        let jwk = |kty: &str, crv: &str| format!(r#"{{"kty":"{}","crv":"{}","x":"{}","y":"{}"}}"#, kty, crv, X, Y);
+        assert_eq!(reason(&jwk("OKP", "SM2")), "unsupported kty");
+        assert_eq!(reason(&jwk("EC", "P-256")), "unsupported crv");
+        assert_eq!(reason(&format!(r#"{{"kty":"EC","crv":"SM2","x":"{}"}}"#, X)), "missing y");
+        assert_eq!(reason(&format!(r#"{{"kty":"EC","kty":"EC","crv":"SM2","x":"{}","y":"{}"}}"#, X, Y)), "duplicate member");
+        for json in ["", "[]", "{", r#"{"kty":"EC",}"#, r#"{"kty":"EC"} x"#, r#"{"kty":"\q"}"#, r#"{"a":tru}"#, r#"{"a":"\ud83d"}"#] {
+            assert_eq!(reason(json), "invalid JSON", "{}", json);
+        }
+        let nested = format!(r#"{{"a":{}{}}}"#, "[".repeat(64), "]".repeat(64));
+        assert_eq!(reason(&nested), "invalid JSON");
+
+        let short = format!(r#"{{"kty":"EC","crv":"SM2","x":"{}","y":"{}"}}"#, &X[..40], Y);
+        assert_eq!(PublicKey::from_jwk(&short).unwrap_err().code(), Code::INVALID_LENGTH);
+        let padded = jwk("EC", "SM2").replace(X, &format!("{}=", X));
+        assert!(matches!(PublicKey::from_jwk(&padded), Err(KeyError::InvalidBase64(_))));
+
+        // d与公钥不匹配
+        let other = crate::sm2::generate_keypair().0;
+        let jwk = KeyPair::new(PrivateKey::decode(&other), PublicKey::decode(PUK)).to_jwk();
+        let e = KeyPair::from_jwk(&jwk).unwrap_err();
+        assert!(matches!(&e, KeyError::InvalidJwk(d) if d.reason == "d does not match x and y"), "{:?}", e);
+        assert_eq!(e.code(), Code::INVALID_DATA);
+        assert!(matches!(KeyPair::from_jwk(&PublicKey::decode(PUK).to_jwk()), Err(KeyError::InvalidJwk(d)) if d.reason == "missing d"));
+    }
+}
@@ -1,12 +1,41 @@
+use std::fmt::{Display, Formatter};
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
 use std::ops::Sub;
-
-use num_bigint::BigUint;
-use num_traits::{Num, One};
-
-use crate::sm2::ecc::EllipticBuilder;
-
-pub trait HexKey {
+use std::sync::OnceLock;
+
+use num_bigint::{BigUint, ToBigInt};
+use num_traits::One;
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+use num_traits::Zero;
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+use rand::{CryptoRng, RngCore};
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+use subtle::{Choice, ConstantTimeEq};
+
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+use crate::ct::ct_eq;
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+use crate::env::EnvRng;
+use crate::codec::{decode_hex_fixed, encode_hex_fixed, report, DecodeError, HexError};
+use crate::sm2::ecc::{on_curve, EllipticBuilder};
+use crate::sm2::p256::payload::PayloadHelper;
+use crate::sm2::p256::point::{P256AffinePoint, P256PointTable};
+use crate::sm2::p256::P256Elliptic;
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+use crate::sm2::p256::params::EC_N;
+use crate::sm2::policy::PolicyError;
+use crate::sm3;
+use crate::error_code::{Code, ErrorCode};
+
+pub(crate) const PUBLIC_KEY: &str = "SM2 public key";
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+const PRIVATE_KEY: &str = "SM2 private key";
+
+pub trait HexKey: Sized {
     fn encode(&self) -> String;
+    /// 解析16进制编码的密钥，格式不正确时返回错误
+    fn try_decode(key: &str) -> Result<Self, KeyError>;
+    /// 同[`HexKey::try_decode`]，格式不正确时panic
     fn decode(key: &str) -> Self;
 }
 
@@ -15,79 +44,255 @@ pub trait HexKey {
 /// 非压缩格式公钥首字节为0x04。
 /// 压缩格式公钥，若公钥y坐标最后一位为0，则首字节为0x02，否则为0x03。
 /// 签名长度：64字节。
+/// 首次参与标量乘法时缓存坐标的limb形式，同一公钥重复加密、验签时不再转换
 #[derive(Clone, Debug)]
-pub struct PublicKey(BigUint, BigUint);
+pub struct PublicKey(BigUint, BigUint, OnceLock<P256AffinePoint>, OnceLock<P256PointTable>);
 
 impl PublicKey {
+    pub(crate) fn new(x: BigUint, y: BigUint) -> Self {
+        PublicKey(x, y, OnceLock::new(), OnceLock::new())
+    }
+
     pub fn value(&self) -> (BigUint, BigUint) {
         (self.0.clone(), self.1.clone())
     }
+
+    /// 非压缩格式公钥字节串：04 ‖ x ‖ y
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let x = self.0.to_bytes_be();
+        let y = self.1.to_bytes_be();
+        [vec![0x04], to_32_bytes(x).to_vec(), to_32_bytes(y).to_vec()].concat()
+    }
+
+    /// 压缩格式公钥字节串：02/03 ‖ x，前缀由y的奇偶性决定
+    pub fn to_compressed_bytes(&self) -> [u8; 33] {
+        let mut bytes = [0u8; 33];
+        bytes[0] = if self.1.bit(0) { 0x03 } else { 0x02 };
+        bytes[1..].copy_from_slice(&to_32_bytes(self.0.to_bytes_be()));
+        bytes
+    }
+
+    /// 压缩格式公钥的16进制编码
+    pub fn encode_compressed(&self) -> String {
+        encode_hex_fixed(self.to_compressed_bytes()).to_string()
+    }
+
+    /// 公钥指纹：非压缩格式 04 ‖ x ‖ y 的SM3杂凑值
+    pub fn fingerprint(&self) -> [u8; 32] {
+        sm3::hash(&self.to_bytes())
+    }
+
+    /// 解析公钥字节串：非压缩格式 04 ‖ x ‖ y 或压缩格式 02/03 ‖ x
+    pub fn from_bytes(key: &[u8]) -> Result<Self, KeyError> {
+        if key.len() == 33 {
+            return Self::decompress(key);
+        }
+        if key.len() != 65 {
+            let e = DecodeError::new(PUBLIC_KEY, "unexpected length", key, key.len().min(65)).expected(65);
+            return Err(KeyError::InvalidLength(report(e)));
+        }
+        if key[0] != 0x04 {
+            return Err(KeyError::InvalidPrefix(report(DecodeError::new(PUBLIC_KEY, "missing 04 prefix", key, 0))));
+        }
+        Ok(PublicKey::new(
+            BigUint::from_bytes_be(&key[1..33]),
+            BigUint::from_bytes_be(&key[33..]),
+        ))
+    }
+
+    /// 由 y² = x³ + ax + b 恢复y，p ≡ 3 (mod 4)，平方根为 (x³ + ax + b)^((p + 1) / 4)
+    fn decompress(key: &[u8]) -> Result<Self, KeyError> {
+        if key[0] != 0x02 && key[0] != 0x03 {
+            return Err(KeyError::InvalidPrefix(report(DecodeError::new(PUBLIC_KEY, "missing 02 or 03 prefix", key, 0))));
+        }
+        let e = &P256Elliptic::init().ec;
+        let x = BigUint::from_bytes_be(&key[1..]);
+        if x >= e.p {
+            return Err(KeyError::CoordinateOutOfRange);
+        }
+        let rhs = (x.modpow(&BigUint::from(3u8), &e.p) + &e.a * &x + &e.b) % &e.p;
+        let y = rhs.modpow(&((&e.p + BigUint::one()) >> 2), &e.p);
+        if y.modpow(&BigUint::from(2u8), &e.p) != rhs {
+            return Err(KeyError::NotOnCurve);
+        }
+        // y = 0 时两个根相同，其奇偶性无法与前缀03对应
+        let y = if y.bit(0) == (key[0] == 0x03) { y } else { &e.p - y };
+        if y.bit(0) != (key[0] == 0x03) {
+            return Err(KeyError::NotOnCurve);
+        }
+        Ok(PublicKey::new(x, y))
+    }
+
+    /// 预先将坐标转换为limb形式，避免首次加密或验签时再转换
+    pub fn precompute(&self) -> &Self {
+        self.limbs();
+        self
+    }
+
+    /// 预计算与基点相同结构的梳状表（默认约2KB，`window-5`约4.4KB，`window-6`约9KB），此后以该公钥加密、验签的标量乘法与基点乘法速度相当
+    ///
+    /// 适合服务端反复向同一公钥加密或验证同一公钥签名的场合，表随公钥一同克隆。
+    pub fn precompute_table(&self) -> &Self {
+        self.3.get_or_init(|| P256PointTable::new(self.limbs()));
+        self
+    }
+
+    /// 校验公钥：坐标属于\[0, p-1]，不是无穷远点，满足曲线方程且阶为n
+    ///
+    /// 无穷远点没有仿射坐标，按惯例记为(0, 0)。阶的校验以 (n-1)P = -P 代替 nP = O。
+    pub fn validate(&self, builder: &dyn EllipticBuilder) -> Result<(), KeyError> {
+        let e = builder.blueprint();
+        let (x, y) = (&self.0, &self.1);
+        if x >= &e.p || y >= &e.p {
+            return Err(KeyError::CoordinateOutOfRange);
+        }
+        if x.bits() == 0 && y.bits() == 0 {
+            return Err(KeyError::PointAtInfinity);
+        }
+        if !on_curve(e, x, y) {
+            return Err(KeyError::NotOnCurve);
+        }
+        let (nx, ny) = builder.scalar_multiply(x.clone(), y.clone(), &e.n - BigUint::one());
+        if &nx != x || ny != (&e.p - y) % &e.p {
+            return Err(KeyError::WrongOrder);
+        }
+        Ok(())
+    }
+
+    /// 坐标的limb形式，仅在SM2推荐曲线上有意义
+    pub(crate) fn limbs(&self) -> &P256AffinePoint {
+        self.2.get_or_init(|| P256AffinePoint::new(
+            PayloadHelper::transform(&self.0.to_bigint().unwrap()),
+            PayloadHelper::transform(&self.1.to_bigint().unwrap()),
+        ))
+    }
+
+    /// 由[`PublicKey::precompute_table`]计算的梳状表
+    pub(crate) fn table(&self) -> Option<&P256PointTable> {
+        self.3.get()
+    }
+
+    /// 解析非压缩格式公钥字节串，格式不正确时返回None
+    #[cfg(feature = "sm2-sign")]
+    pub(crate) fn try_from_bytes(key: &[u8]) -> Option<Self> {
+        Self::from_bytes(key).ok()
+    }
 }
 
 impl HexKey for PublicKey {
     fn encode(&self) -> String {
-        let key = {
-            let x = self.0.to_bytes_be();
-            let y = self.1.to_bytes_be();
-            [vec![0x04], to_32_bytes(x).to_vec(), to_32_bytes(y).to_vec()].concat()
-        };
-        hex::encode(key)
+        let key: [u8; 65] = self.to_bytes().try_into().unwrap();
+        encode_hex_fixed(key).to_string()
     }
 
-    fn decode(key: &str) -> Self {
-        if key.len() != 130 {
-            panic!("The uncompressed public key's length must be 130.")
+    /// 解析16进制编码的非压缩或压缩格式公钥
+    fn try_decode(key: &str) -> Result<Self, KeyError> {
+        if key.len() == 66 {
+            return match decode_hex_fixed::<33>(key) {
+                Ok(data) => Self::from_bytes(&data),
+                Err(e) => Err(hex_error(PUBLIC_KEY, key, e)),
+            };
         }
-
-        if !key.starts_with("04") {
-            panic!("The compressed public key is invalid.")
+        match decode_hex_fixed::<65>(key) {
+            Ok(data) => Self::from_bytes(&data),
+            Err(e) => Err(hex_error(PUBLIC_KEY, key, e)),
         }
+    }
 
-        let key = match hex::decode(key.trim_start_matches("04")) {
-            Ok(data) => data,
-            Err(_) => panic!("The public key must be composed of hex chars.")
-        };
-
-        PublicKey(
-            BigUint::from_bytes_be(&key[..32]),
-            BigUint::from_bytes_be(&key[32..]),
-        )
+    fn decode(key: &str) -> Self {
+        match Self::try_decode(key) {
+            Ok(key) => key,
+            Err(e) => panic!("The public key is invalid. {}", e)
+        }
     }
 }
 
 
 /// 私钥 32bytes
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
 #[derive(Clone, Debug)]
 pub struct PrivateKey(BigUint);
 
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
 impl PrivateKey {
+    /// 检查d ∈ \[1, n − 2]：d = n − 1时签名所需的(1 + d)⁻¹不存在
+    pub(crate) fn new(d: BigUint) -> Result<Self, KeyError> {
+        let max = BigUint::from_bytes_be(&EC_N) - 2u8;
+        if d.is_zero() || d > max {
+            return Err(KeyError::PrivateKeyOutOfRange);
+        }
+        Ok(PrivateKey(d))
+    }
+
+    /// 由32字节大端序整数构造私钥，不在\[1, n − 2]内时返回[`KeyError::PrivateKeyOutOfRange`]
+    pub fn from_bytes(key: &[u8]) -> Result<Self, KeyError> {
+        if key.len() != 32 {
+            let e = DecodeError::new(PRIVATE_KEY, "unexpected length", key, key.len().min(32)).expected(32);
+            return Err(KeyError::InvalidLength(report(e)));
+        }
+        Self::new(BigUint::from_bytes_be(key))
+    }
+
+    /// 将任意长度的随机字节映射为私钥：d = (x mod (n − 2)) + 1，x为`bytes`的大端序整数
+    ///
+    /// 用于由KDF输出、种子等外部熵派生密钥。结果总在\[1, n − 2]内；`bytes`至少取40字节，
+    /// 取模带来的偏差才可忽略（FIPS 186-5 A.2.1多取64位），不足40字节时返回[`KeyError::InvalidLength`]。
+    pub fn from_bytes_mod_order(bytes: &[u8]) -> Result<Self, KeyError> {
+        if bytes.len() < 40 {
+            let e = DecodeError::new(PRIVATE_KEY, "too short to reduce without bias", bytes, bytes.len()).expected(40);
+            return Err(KeyError::InvalidLength(report(e)));
+        }
+        let modulus = BigUint::from_bytes_be(&EC_N) - 2u8;
+        Ok(PrivateKey(BigUint::from_bytes_be(bytes) % modulus + 1u8))
+    }
+
     pub fn value(&self) -> BigUint {
         self.0.clone()
     }
+
+    /// 由私钥推导公钥 P = dG
+    pub fn derive_public(&self, builder: &dyn EllipticBuilder) -> PublicKey {
+        let (x, y) = builder.scalar_base_multiply(self.value());
+        PublicKey::new(x, y)
+    }
 }
 
+/// 比较32字节的大端序编码，比较时间不依赖私钥内容
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+impl ConstantTimeEq for PrivateKey {
+    fn ct_eq(&self, other: &PrivateKey) -> Choice {
+        to_32_bytes(self.0.to_bytes_be()).ct_eq(&to_32_bytes(other.0.to_bytes_be()))
+    }
+}
+
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
 impl HexKey for PrivateKey {
     fn encode(&self) -> String {
-        hex::encode(to_32_bytes(self.0.to_bytes_be()))
+        encode_hex_fixed(to_32_bytes(self.0.to_bytes_be())).to_string()
+    }
+
+    fn try_decode(key: &str) -> Result<Self, KeyError> {
+        match decode_hex_fixed::<32>(key) {
+            Ok(data) => Self::from_bytes(&data),
+            Err(e) => Err(hex_error(PRIVATE_KEY, key, e)),
+        }
     }
 
     fn decode(key: &str) -> Self {
-        if key.len() != 64 {
-            panic!("The length of the private key must be 64.")
+        match Self::try_decode(key) {
+            Ok(key) => key,
+            Err(e) => panic!("The private key is invalid. {}", e)
         }
-        let key = match BigUint::from_str_radix(&*key, 16) {
-            Ok(data) => data,
-            Err(_) => panic!("The private key must be composed of hex chars.")
-        };
-        PrivateKey(key)
     }
 }
 
 
 /// 秘钥对（d, P）d:私钥 P:公钥
-#[derive(Debug)]
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+#[derive(Clone, Debug)]
 pub struct KeyPair(PrivateKey, PublicKey);
 
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
 impl KeyPair {
     pub fn new(prk: PrivateKey, puk: PublicKey) -> Self {
         KeyPair(prk, puk)
@@ -98,20 +303,41 @@ impl KeyPair {
     pub fn puk(&self) -> &PublicKey {
         &self.1
     }
+
+    /// 校验私钥与公钥是否匹配：重新计算dG并与公钥做常量时间比较
+    ///
+    /// 用于在使用前发现损坏或错配的密钥文件，避免加密出无法解密的数据。
+    pub fn verify_consistency(&self) -> bool {
+        self.verify_consistency_with(P256Elliptic::init())
+    }
+
+    /// 同[`KeyPair::verify_consistency`]，使用指定的曲线实现计算dG
+    pub fn verify_consistency_with(&self, builder: &dyn EllipticBuilder) -> bool {
+        let derived = self.0.derive_public(builder).to_bytes();
+        let stored = self.1.to_bytes();
+        ct_eq(&derived, &stored)
+    }
 }
 
 /// 秘钥生成器
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
 pub struct KeyGenerator {
     builder: Box<dyn EllipticBuilder>,
 }
 
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
 impl KeyGenerator {
     pub fn init(builder: Box<dyn EllipticBuilder>) -> Self {
         KeyGenerator { builder }
     }
 
     pub fn gen_key_pair(&self) -> KeyPair {
-        let private_key = self.gen_private_key();
+        self.gen_key_pair_with_rng(&mut EnvRng)
+    }
+
+    /// 同[`KeyGenerator::gen_key_pair`]，私钥取自`rng`，可接入确定性测试或外部设备播种的随机数生成器
+    pub fn gen_key_pair_with_rng(&self, rng: &mut (impl CryptoRng + RngCore)) -> KeyPair {
+        let private_key = self.gen_private_key(rng);
         let public_key = self.gen_public_key(&private_key);
         KeyPair(private_key.clone(), public_key.clone())
     }
@@ -119,22 +345,87 @@ impl KeyGenerator {
     /// 生成私钥
     ///
     /// d ∈ \[1, n − 2]
-    fn gen_private_key(&self) -> PrivateKey {
+    fn gen_private_key(&self, rng: &mut (impl CryptoRng + RngCore)) -> PrivateKey {
         let e = self.builder.blueprint();
         let from = BigUint::one();
         let to = e.n.clone().sub(BigUint::from(2u8));
-        PrivateKey(e.random(from, to))
+        PrivateKey(e.random_with(rng, from, to))
     }
 
     /// 生成公钥
     ///
     /// P = (x,y) = dG, G为基点，d为私钥
-    fn gen_public_key(&self, private_key: &PrivateKey) -> PublicKey {
+    pub(crate) fn gen_public_key(&self, private_key: &PrivateKey) -> PublicKey {
         let key = self.builder.scalar_base_multiply(private_key.value());
-        PublicKey(key.0, key.1)
+        PublicKey::new(key.0, key.1)
     }
 }
 
+/// 密钥解析或校验失败的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyError {
+    /// 16进制文本含有非法字符
+    InvalidHex(DecodeError),
+    /// Base64文本含有非法字符
+    InvalidBase64(DecodeError),
+    /// 长度不正确
+    InvalidLength(DecodeError),
+    /// 公钥编码的首字节不是04、02或03
+    InvalidPrefix(DecodeError),
+    /// JWK不是合法的JSON、缺少成员或成员取值不正确
+    InvalidJwk(DecodeError),
+    /// PKCS#8结构不正确、算法不是SM2或公钥与私钥不匹配
+    InvalidPkcs8(DecodeError),
+    /// 加密的PKCS#8无法解密，通常是口令错误
+    DecryptionFailed,
+    /// 坐标不小于p
+    CoordinateOutOfRange,
+    /// 无穷远点
+    PointAtInfinity,
+    /// 不满足曲线方程
+    NotOnCurve,
+    /// nP ≠ O
+    WrongOrder,
+    /// 私钥不在\[1, n − 2]内
+    PrivateKeyOutOfRange,
+    /// 未通过[`Crypto::with_policy`](crate::sm2::Crypto::with_policy)设置的密钥策略
+    PolicyViolation(PolicyError),
+}
+
+impl Display for KeyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyError::InvalidHex(e) | KeyError::InvalidBase64(e) | KeyError::InvalidLength(e) | KeyError::InvalidPrefix(e) | KeyError::InvalidJwk(e) | KeyError::InvalidPkcs8(e) => write!(f, "{}", e),
+            KeyError::CoordinateOutOfRange => write!(f, "The public key coordinates are not less than p."),
+            KeyError::PointAtInfinity => write!(f, "The public key is the point at infinity."),
+            KeyError::NotOnCurve => write!(f, "The public key is not a point on the curve."),
+            KeyError::WrongOrder => write!(f, "The order of the public key is not n."),
+            KeyError::PrivateKeyOutOfRange => write!(f, "The private key is not in [1, n - 2]."),
+            KeyError::DecryptionFailed => write!(f, "The encrypted private key cannot be decrypted, the password may be wrong."),
+            KeyError::PolicyViolation(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for KeyError {}
+
+impl ErrorCode for KeyError {
+    fn code(&self) -> Code {
+        match self {
+            KeyError::InvalidHex(e) | KeyError::InvalidBase64(e) | KeyError::InvalidLength(e) | KeyError::InvalidPrefix(e) | KeyError::InvalidJwk(e) | KeyError::InvalidPkcs8(e) => e.code(),
+            KeyError::PolicyViolation(e) => e.code(),
+            KeyError::DecryptionFailed => Code::INVALID_PADDING,
+            _ => Code::INVALID_KEY,
+        }
+    }
+}
+
+pub(crate) fn hex_error(what: &'static str, key: &str, e: HexError) -> KeyError {
+    let invalid_char = matches!(e, HexError::InvalidChar { .. });
+    let e = report(DecodeError::from_hex(what, key, e));
+    if invalid_char { KeyError::InvalidHex(e) } else { KeyError::InvalidLength(e) }
+}
+
 #[inline(always)]
 pub fn copy_slice(dst: &mut [u8], src: &[u8]) {
     for (d, s) in dst.iter_mut().zip(src.iter()) {
@@ -155,9 +446,9 @@ pub fn to_32_bytes(data: Vec<u8>) -> [u8; 32] {
     result
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "sm2-sign", feature = "sm2-encrypt"))]
 mod tests {
-    use crate::sm2::p256::P256Elliptic;
+    use num_traits::Num;
 
     use super::*;
 
@@ -202,4 +493,200 @@ mod tests {
         assert_eq!(public_key.0.to_string(), "76298453107918256108319614943154283626396976993715724710320433578462434588530");
         assert_eq!(public_key.1.to_string(), "22016840577845663905050918262284081863871275223913804750000840645022838962798");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn private_key_range() {
+        const N: &str = "fffffffeffffffffffffffffffffffff7203df6b21c6052b53bbf40939d54123";
+        let n = BigUint::from_bytes_be(&EC_N);
+        let hex = |d: &BigUint| hex::encode(to_32_bytes(d.to_bytes_be()));
+
+        assert_eq!(PrivateKey::try_decode(&"00".repeat(32)).unwrap_err(), KeyError::PrivateKeyOutOfRange);
+        assert_eq!(PrivateKey::try_decode(N).unwrap_err(), KeyError::PrivateKeyOutOfRange);
+        assert_eq!(PrivateKey::try_decode(&hex(&(&n - 1u8))).unwrap_err(), KeyError::PrivateKeyOutOfRange);
+        assert_eq!(PrivateKey::try_decode(&"ff".repeat(32)).unwrap_err().code(), Code::INVALID_KEY);
+        assert_eq!(PrivateKey::try_decode(&hex(&(&n - 2u8))).unwrap().value(), &n - 2u8);
+        assert!(matches!(PrivateKey::from_bytes(&[1; 31]), Err(KeyError::InvalidLength(_))));
+
+        // 不足40字节的输入可能的私钥太少，直接拒绝
+        for short in [&[][..], &[0x01], &[0xff; 39]] {
+            let Err(KeyError::InvalidLength(e)) = PrivateKey::from_bytes_mod_order(short) else { panic!() };
+            assert_eq!((e.expected, e.actual), (Some(40), short.len()));
+        }
+        let wide_be = |d: &[u8]| [&[0u8; 8][..], d].concat();
+        assert_eq!(PrivateKey::from_bytes_mod_order(&wide_be(&hex::decode(N).unwrap())).unwrap().value(), BigUint::from(3u8));
+        assert_eq!(PrivateKey::from_bytes_mod_order(&wide_be(&to_32_bytes((&n - 3u8).to_bytes_be()))).unwrap().value(), &n - 2u8);
+        let wide = PrivateKey::from_bytes_mod_order(&[0xff; 48]).unwrap().value();
+        assert_eq!(wide, (BigUint::from_bytes_be(&[0xff; 48]) % (&n - 2u8)) + 1u8);
+        assert!(wide >= BigUint::one() && wide <= &n - 2u8);
+    }
+
+    #[test]
+    #[should_panic(expected = "The private key is invalid. The private key is not in [1, n - 2].")]
+    fn private_key_zero() {
+        PrivateKey::decode(&"00".repeat(32));
+    }
+
+    #[test]
+    fn consistency() {
+        let prk = PrivateKey::decode("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e");
+        let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+        assert_eq!(prk.derive_public(P256Elliptic::init()).encode(), puk.encode());
+        assert!(KeyPair::new(prk.clone(), puk.clone()).verify_consistency());
+
+        // 私钥被篡改一位
+        let corrupted = PrivateKey(prk.value() ^ BigUint::one());
+        assert!(bool::from(prk.ct_eq(&prk.clone())));
+        assert!(!bool::from(prk.ct_eq(&corrupted)));
+        assert!(!KeyPair::new(corrupted, puk.clone()).verify_consistency());
+
+        // 与另一对密钥的公钥错配
+        let other = KeyGenerator::init(Box::new(P256Elliptic::init())).gen_key_pair();
+        assert!(other.verify_consistency());
+        assert!(!KeyPair::new(prk, other.puk().clone()).verify_consistency());
+    }
+
+    #[test]
+    fn precompute() {
+        let p256 = P256Elliptic::init();
+        let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+        let scalar = BigUint::from_str_radix("59276e27d506861a16680f3ad9c02dccef3cc1fa3cdbe4ce6d54b80deac1bc21", 16).unwrap();
+        let (x, y) = puk.value();
+        let expected = p256.scalar_multiply(x, y, scalar.clone());
+
+        assert!(puk.2.get().is_none());
+        assert_eq!(p256.scalar_multiply_key(puk.precompute(), scalar.clone()), expected);
+        assert!(puk.2.get().is_some());
+
+        // 克隆保留已缓存的limb形式
+        let cloned = puk.clone();
+        assert!(cloned.2.get().is_some());
+        assert_eq!(p256.scalar_multiply_key(&cloned, scalar), expected);
+    }
+
+    #[test]
+    fn precompute_table() {
+        let p256 = P256Elliptic::init();
+        let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+        let (x, y) = puk.value();
+        let scalars = [
+            BigUint::one(),
+            BigUint::from_str_radix("59276e27d506861a16680f3ad9c02dccef3cc1fa3cdbe4ce6d54b80deac1bc21", 16).unwrap(),
+            &p256.ec.n - BigUint::one(),
+        ];
+
+        assert!(puk.table().is_none());
+        let cloned = puk.precompute_table().clone();
+        assert!(cloned.table().is_some());
+        for scalar in scalars {
+            assert_eq!(p256.scalar_multiply_key(&cloned, scalar.clone()), p256.scalar_multiply(x.clone(), y.clone(), scalar));
+        }
+
+        let s = BigUint::from(0xfeedu32);
+        let t = BigUint::from_str_radix("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e", 16).unwrap();
+        assert_eq!(p256.double_scalar_multiply(s.clone(), &cloned, t.clone()), p256.double_scalar_multiply(s, &PublicKey::new(x, y), t));
+    }
+
+    #[test]
+    fn compressed() {
+        let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+        let compressed = puk.encode_compressed();
+        assert_eq!(compressed, "02a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c7772");
+        assert_eq!(PublicKey::decode(&compressed).encode(), puk.encode());
+
+        // 两种奇偶性均可还原
+        let generator = KeyGenerator::init(Box::new(P256Elliptic::init()));
+        let mut prefixes = [false; 2];
+        for _ in 0..16 {
+            let puk = generator.gen_key_pair().puk().clone();
+            let bytes = puk.to_compressed_bytes();
+            prefixes[(bytes[0] - 0x02) as usize] = true;
+            assert_eq!(PublicKey::from_bytes(&bytes).unwrap().to_bytes(), puk.to_bytes());
+        }
+        assert_eq!(prefixes, [true, true]);
+
+        let mut bytes = puk.to_compressed_bytes();
+        bytes[0] = 0x04;
+        assert!(matches!(PublicKey::from_bytes(&bytes), Err(KeyError::InvalidPrefix(e)) if e.reason == "missing 02 or 03 prefix"));
+        // x = 2 时 x³ + ax + b 不是平方剩余
+        let mut bytes = [0u8; 33];
+        (bytes[0], bytes[32]) = (0x02, 0x02);
+        assert_eq!(PublicKey::from_bytes(&bytes).unwrap_err(), KeyError::NotOnCurve);
+        let p = P256Elliptic::init().ec.p.clone();
+        bytes[1..].copy_from_slice(&p.to_bytes_be());
+        assert_eq!(PublicKey::from_bytes(&bytes).unwrap_err(), KeyError::CoordinateOutOfRange);
+    }
+
+    #[test]
+    fn diagnostics() {
+        use std::cell::RefCell;
+
+        use crate::codec::set_decode_logger;
+
+        thread_local! {
+            static LOGGED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+        }
+        set_decode_logger(Some(|e| LOGGED.with(|logged| logged.borrow_mut().push(e.to_string()))));
+
+        let puk = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+        let Err(KeyError::InvalidLength(e)) = PublicKey::try_decode(&puk[..128]) else { panic!() };
+        assert_eq!((e.reason, e.offset, e.expected, e.actual), ("unexpected hex length", 128, Some(130), 128));
+
+        let Err(KeyError::InvalidPrefix(e)) = PublicKey::try_decode(&puk.replacen("04", "02", 1)) else { panic!() };
+        assert_eq!((e.reason, e.offset, e.context.as_str()), ("missing 04 prefix", 0, "[02]a8af64e38eea41c2.."));
+
+        let Err(KeyError::InvalidLength(e)) = PublicKey::from_bytes(&[0x04; 66]) else { panic!() };
+        assert_eq!((e.offset, e.expected, e.actual), (65, Some(65), 66));
+
+        let Err(KeyError::InvalidHex(e)) = PrivateKey::try_decode("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f2xe") else { panic!() };
+        assert_eq!((e.reason, e.offset), ("invalid hex char", 62));
+        assert_eq!(e.to_string(), "Failed to decode SM2 private key: invalid hex char at offset 62 (input is 64 bytes), near `..6566623639356632[78]65`.");
+
+        set_decode_logger(None);
+        LOGGED.with(|logged| assert_eq!(logged.borrow().len(), 4));
+    }
+
+    #[test]
+    fn gen_key_pair_with_rng() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let generator = KeyGenerator::init(Box::new(P256Elliptic::init()));
+        let first = generator.gen_key_pair_with_rng(&mut StdRng::seed_from_u64(1));
+        let second = generator.gen_key_pair_with_rng(&mut StdRng::seed_from_u64(1));
+        let other = generator.gen_key_pair_with_rng(&mut StdRng::seed_from_u64(2));
+        assert_eq!(first.prk().encode(), second.prk().encode());
+        assert_eq!(first.puk().encode(), second.puk().encode());
+        assert_ne!(first.prk().encode(), other.prk().encode());
+        assert!(first.verify_consistency());
+    }
+
+    #[test]
+    fn validate() {
+        use crate::sm2::ecc::Crypto;
+
+        let p256 = P256Elliptic::init();
+        let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+        assert_eq!(puk.validate(p256), Ok(()));
+
+        let (x, y) = puk.value();
+        let p = p256.ec.p.clone();
+        let cases = [
+            (PublicKey::new(&x + &p, y.clone()), KeyError::CoordinateOutOfRange),
+            (PublicKey::new(x.clone(), &y + &p), KeyError::CoordinateOutOfRange),
+            (PublicKey::new(BigUint::from(0u8), BigUint::from(0u8)), KeyError::PointAtInfinity),
+            (PublicKey::new(x.clone(), &y + 1u8), KeyError::NotOnCurve),
+        ];
+        for (key, error) in cases {
+            assert_eq!(key.validate(p256), Err(error.clone()));
+            assert_eq!(Crypto::default().try_encryptor(key).err(), Some(error));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "The public key is invalid. The public key is not a point on the curve.")]
+    fn encryptor_rejects_invalid_key() {
+        use crate::sm2::ecc::Crypto;
+
+        Crypto::default().encryptor(PublicKey::new(BigUint::from(1u8), BigUint::from(1u8)));
+    }
+}
@@ -1,16 +1,25 @@
-use std::mem;
-use std::sync::Once;
+use std::sync::OnceLock;
 
 use num_bigint::{BigUint, ToBigInt};
 
 use crate::sm2::ecc::{Elliptic, EllipticBuilder};
+use crate::sm2::hazmat::ScalarBytesBe;
+use crate::sm2::key::{to_32_bytes, PublicKey};
 use crate::sm2::p256::params::{EC_A, EC_B, EC_GX, EC_GY, EC_N, EC_P, RI};
 use crate::sm2::p256::payload::PayloadHelper;
 use crate::sm2::p256::point::{Multiplication, P256AffinePoint, P256BasePoint};
 
-mod point;
-mod payload;
-mod params;
+#[cfg(all(feature = "avx2", target_arch = "x86_64"))]
+mod avx2;
+#[cfg(feature = "fiat")]
+mod fiat;
+#[cfg(any(target_pointer_width = "64", feature = "fiat"))]
+mod limb64;
+pub(crate) mod point;
+pub(crate) mod payload;
+pub(crate) mod params;
+pub(crate) mod scalar;
+pub(crate) mod window;
 
 #[derive(Clone, Debug)]
 pub struct P256Elliptic {
@@ -19,27 +28,21 @@ pub struct P256Elliptic {
 }
 
 impl P256Elliptic {
-    pub fn init() -> Self {
-        static mut ELLIPTIC: *const P256Elliptic = std::ptr::null::<P256Elliptic>();
-        static INITIALIZER: Once = Once::new();
-        unsafe {
-            INITIALIZER.call_once(|| {
-                let p256 = P256Elliptic {
-                    ec: Elliptic {
-                        p: BigUint::from_bytes_be(&EC_P),
-                        a: BigUint::from_bytes_be(&EC_A),
-                        b: BigUint::from_bytes_be(&EC_B),
-                        gx: BigUint::from_bytes_be(&EC_GX),
-                        gy: BigUint::from_bytes_be(&EC_GY),
-                        n: BigUint::from_bytes_be(&EC_N),
-                        bits: 256,
-                    },
-                    ri: BigUint::from_bytes_be(&RI),
-                };
-                ELLIPTIC = mem::transmute(Box::new(p256));
-            });
-            (*ELLIPTIC).clone()
-        }
+    /// 全局唯一的SM2推荐曲线参数，首次调用时初始化
+    pub fn init() -> &'static Self {
+        static ELLIPTIC: OnceLock<P256Elliptic> = OnceLock::new();
+        ELLIPTIC.get_or_init(|| P256Elliptic {
+            ec: Elliptic {
+                p: BigUint::from_bytes_be(&EC_P),
+                a: BigUint::from_bytes_be(&EC_A),
+                b: BigUint::from_bytes_be(&EC_B),
+                gx: BigUint::from_bytes_be(&EC_GX),
+                gy: BigUint::from_bytes_be(&EC_GY),
+                n: BigUint::from_bytes_be(&EC_N),
+                bits: 256,
+            },
+            ri: BigUint::from_bytes_be(&RI),
+        })
     }
 }
 
@@ -71,6 +74,14 @@ impl EllipticBuilder for P256Elliptic {
         point.multiply(elliptic.scalar_reduce(scalar)).restore()
     }
 
+    fn scalar_multiply_key(&self, key: &PublicKey, scalar: BigUint) -> (BigUint, BigUint) {
+        let scalar = self.blueprint().scalar_reduce(scalar);
+        match key.table() {
+            Some(table) => table.multiply(&ScalarBytesBe(to_32_bytes(scalar.to_bytes_be())).to_le()).to_affine_point().restore(),
+            None => key.limbs().multiply(scalar).restore(),
+        }
+    }
+
     fn scalar_base_multiply(&self, scalar: BigUint) -> (BigUint, BigUint) {
         let elliptic = self.blueprint();
         let base = P256BasePoint::new(
@@ -82,18 +93,50 @@ impl EllipticBuilder for P256Elliptic {
         );
         base.multiply(elliptic.scalar_reduce(scalar)).restore()
     }
+
+    fn double_scalar_multiply(&self, s: BigUint, key: &PublicKey, t: BigUint) -> Option<(BigUint, BigUint)> {
+        let elliptic = self.blueprint();
+        let s = to_32_bytes(elliptic.scalar_reduce(s).to_bytes_be());
+        let t = to_32_bytes(elliptic.scalar_reduce(t).to_bytes_be());
+        // 有预计算表时两次梳状乘法比交错的窗口乘法更快
+        let point = match key.table() {
+            Some(table) => P256BasePoint::comb(&ScalarBytesBe(s).to_le()).add_vartime(&table.multiply(&ScalarBytesBe(t).to_le())),
+            None => P256AffinePoint::double_multiply_vartime(&s, key.limbs(), &t),
+        };
+        (!point.is_infinity()).then(|| point.to_affine_point().restore())
+    }
 }
 
+/// 借用全局唯一的曲线参数，`Crypto`与`KeyGenerator`不必为各自的实例复制一份`BigUint`参数
+impl EllipticBuilder for &'static P256Elliptic {
+    fn blueprint(&self) -> &Elliptic {
+        (*self).blueprint()
+    }
+
+    fn point_add(&self, x1: BigUint, y1: BigUint, x2: BigUint, y2: BigUint) -> (BigUint, BigUint) {
+        (*self).point_add(x1, y1, x2, y2)
+    }
+
+    fn scalar_multiply(&self, x: BigUint, y: BigUint, scalar: BigUint) -> (BigUint, BigUint) {
+        (*self).scalar_multiply(x, y, scalar)
+    }
+
+    fn scalar_multiply_key(&self, key: &PublicKey, scalar: BigUint) -> (BigUint, BigUint) {
+        (*self).scalar_multiply_key(key, scalar)
+    }
+
+    fn scalar_base_multiply(&self, scalar: BigUint) -> (BigUint, BigUint) {
+        (*self).scalar_base_multiply(scalar)
+    }
 
-/// 0xffffffff for 0 < x <= 2^31  0xffffffff = 4294967295 = u32::MAX = 2^31 - 1
-/// 0 for x == 0 or x > 2^31.
-#[inline(always)]
-fn mask(x: u32) -> u32 {
-    x.wrapping_sub(1).wrapping_shr(31).wrapping_sub(1)
+    fn double_scalar_multiply(&self, s: BigUint, key: &PublicKey, t: BigUint) -> Option<(BigUint, BigUint)> {
+        (*self).double_scalar_multiply(s, key, t)
+    }
 }
 
 
-#[cfg(test)]
+
+#[cfg(all(test, feature = "sm2-sign", feature = "sm2-encrypt"))]
 mod tests {
     use std::rc::Rc;
 
@@ -147,9 +190,21 @@ mod tests {
         let verifier = crypto.verifier(puk);
 
         let ans1 = hex::encode(signer.sign(plain).encode());
-        let s = Signature::decode(hex::decode(ans1).unwrap().as_slice());
+        let s = Signature::try_decode(hex::decode(ans1).unwrap().as_slice()).unwrap();
 
         let flag = verifier.verify(plain, &s);
-        assert_eq!(flag, true);
+        assert!(flag);
+    }
+    #[test]
+    fn init() {
+        let elliptic = P256Elliptic::init();
+        assert!(std::ptr::eq(elliptic, P256Elliptic::init()));
+        let address = elliptic as *const P256Elliptic as usize;
+        let others: Vec<usize> = (0..4)
+            .map(|_| std::thread::spawn(|| P256Elliptic::init() as *const P256Elliptic as usize))
+            .map(|handle| handle.join().unwrap())
+            .collect();
+        assert!(others.iter().all(|other| *other == address));
+        assert_eq!(elliptic.ec.n.to_bytes_be(), EC_N);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,78 @@
+//! x86_64 AVX2：域乘法中9×9个limb乘积的计算，每条`vpmuludq`同时计算4个32×32位乘积
+//!
+//! 运行时检测CPU是否支持AVX2，不支持时回退到标量实现；约减仍由`PayloadHelper::reduce_degree`完成。
+
+use std::arch::x86_64::*;
+
+/// CPU是否支持AVX2，检测结果由标准库缓存
+#[inline]
+pub(crate) fn available() -> bool {
+    is_x86_feature_detected!("avx2")
+}
+
+/// tmp\[k] = Σ a_i·b_j (i + j = k)，i、j均为奇数时乘积加倍，与标量实现的结果逐项相同
+///
+/// 按列累加：第c组4个结果为 Σ a_i·b\[4c-i .. 4c-i+4]，b前后补0，累加器始终在寄存器中。
+///
+/// # Safety
+///
+/// 调用方须先以[`available`]确认CPU支持AVX2。
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn product(a: &[u32; 9], b: &[u32; 9]) -> [u64; 17] {
+    // padded[PAD + j] = b_j，奇数行使用奇数位置加倍的副本
+    const PAD: usize = 8;
+    let (mut even, mut odd) = ([0u64; 28], [0u64; 28]);
+    for j in 0..9 {
+        even[PAD + j] = b[j] as u64;
+        odd[PAD + j] = (b[j] as u64) << (j & 1);
+    }
+
+    let mut acc = [_mm256_setzero_si256(); 5];
+    for (i, ai) in a.iter().enumerate() {
+        let ai = _mm256_set1_epi64x(*ai as i64);
+        let row = if i & 1 == 1 { &odd } else { &even };
+        for (c, acc) in acc.iter_mut().enumerate() {
+            let b = _mm256_loadu_si256(row.as_ptr().add(PAD + 4 * c - i) as *const __m256i);
+            *acc = _mm256_add_epi64(*acc, _mm256_mul_epu32(ai, b));
+        }
+    }
+
+    let mut tmp = [0u64; 20];
+    for (c, acc) in acc.iter().enumerate() {
+        _mm256_storeu_si256(tmp.as_mut_ptr().add(4 * c) as *mut __m256i, *acc);
+    }
+    tmp[..17].try_into().unwrap()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    #[test]
+    fn product_matches_schoolbook() {
+        if !available() {
+            return;
+        }
+        let mut rng = StdRng::seed_from_u64(1278);
+        // 各limb取到入口条件允许的最大值：偶数位 < 2^30，奇数位 < 2^29
+        let max: [u32; 9] = std::array::from_fn(|i| if i & 1 == 0 { (1 << 30) - 1 } else { (1 << 29) - 1 });
+        let inputs = (0..256).map(|_| {
+            let mut limbs = || std::array::from_fn(|i| rng.gen_range(0..=max[i]));
+            (limbs(), limbs())
+        }).collect::<Vec<([u32; 9], [u32; 9])>>();
+
+        for (a, b) in inputs.iter().chain([(max, max), ([0; 9], max)].iter()) {
+            let mut expected = [0u64; 17];
+            for i in 0..9 {
+                for j in 0..9 {
+                    expected[i + j] += ((a[i] as u64) * (b[j] as u64)) << (i & j & 1);
+                }
+            }
+            assert_eq!(unsafe { product(a, b) }, expected);
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! 由fiat-crypto从形式化证明中生成的SM2素域乘法与平方
+//!
+//! fiat-crypto的Montgomery形式以2^256为R，要求输入小于p；这里把9个limb拼成饱和字并规约到\[0, p)，
+//! 相乘后再乘以2^-1的Montgomery形式，得到与[`Payload`](super::payload::Payload)一致的a·b·2^-257。
+
+#[cfg(target_pointer_width = "64")]
+use fiat_crypto::sm2_64::{
+    fiat_sm2_montgomery_domain_field_element as Element, fiat_sm2_mul, fiat_sm2_selectznz, fiat_sm2_square,
+    fiat_sm2_subborrowx_u64 as subborrowx,
+};
+#[cfg(not(target_pointer_width = "64"))]
+use fiat_crypto::sm2_32::{
+    fiat_sm2_montgomery_domain_field_element as Element, fiat_sm2_mul, fiat_sm2_selectznz, fiat_sm2_square,
+    fiat_sm2_subborrowx_u32 as subborrowx,
+};
+
+use crate::sm2::p256::limb64::{pack, unpack};
+
+#[cfg(target_pointer_width = "64")]
+type Words = [u64; 4];
+#[cfg(not(target_pointer_width = "64"))]
+type Words = [u32; 8];
+
+/// p，小端序
+#[cfg(target_pointer_width = "64")]
+const P: Words = [0xFFFFFFFFFFFFFFFF, 0xFFFFFFFF00000000, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFEFFFFFFFF];
+#[cfg(not(target_pointer_width = "64"))]
+const P: Words = [0xFFFFFFFF, 0xFFFFFFFF, 0x00000000, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFE];
+
+/// 2^-1的Montgomery形式：2^-1·2^256 = 2^255
+#[cfg(target_pointer_width = "64")]
+const HALF: Element = Element([0, 0, 0, 1 << 63]);
+#[cfg(not(target_pointer_width = "64"))]
+const HALF: Element = Element([0, 0, 0, 0, 0, 0, 0, 1 << 31]);
+
+/// a·b·2^-257 mod p，结果小于p
+pub(crate) fn multiply(a: &[u32; 9], b: &[u32; 9]) -> [u32; 9] {
+    let (mut product, mut result) = (Element(Default::default()), Element(Default::default()));
+    fiat_sm2_mul(&mut product, &canonical(a), &canonical(b));
+    fiat_sm2_mul(&mut result, &product, &HALF);
+    from_words(&result.0)
+}
+
+/// a²·2^-257 mod p，结果小于p
+pub(crate) fn square(a: &[u32; 9]) -> [u32; 9] {
+    let (mut product, mut result) = (Element(Default::default()), Element(Default::default()));
+    fiat_sm2_square(&mut product, &canonical(a));
+    fiat_sm2_mul(&mut result, &product, &HALF);
+    from_words(&result.0)
+}
+
+/// 拼成小于2^256的饱和字后，不小于p时减去p，满足fiat-crypto的输入条件
+fn canonical(limbs: &[u32; 9]) -> Element {
+    let words = to_words(&pack(limbs));
+    let (mut difference, mut borrow) = (Words::default(), 0);
+    for i in 0..words.len() {
+        let borrow_in = borrow;
+        subborrowx(&mut difference[i], &mut borrow, borrow_in, words[i], P[i]);
+    }
+    // 有借位即小于p，保留原值
+    let mut result = Words::default();
+    fiat_sm2_selectznz(&mut result, borrow, &difference, &words);
+    Element(result)
+}
+
+#[cfg(target_pointer_width = "64")]
+fn to_words(words: &[u64; 4]) -> Words {
+    *words
+}
+
+#[cfg(not(target_pointer_width = "64"))]
+fn to_words(words: &[u64; 4]) -> Words {
+    std::array::from_fn(|i| (words[i / 2] >> (32 * (i % 2))) as u32)
+}
+
+#[cfg(target_pointer_width = "64")]
+fn from_words(words: &Words) -> [u32; 9] {
+    unpack(words)
+}
+
+#[cfg(not(target_pointer_width = "64"))]
+fn from_words(words: &Words) -> [u32; 9] {
+    unpack(&std::array::from_fn(|i| words[2 * i] as u64 | (words[2 * i + 1] as u64) << 32))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+    use num_traits::One;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use crate::sm2::p256::params::EC_P;
+
+    use super::*;
+
+    fn value(limbs: &[u32; 9]) -> BigUint {
+        limbs.iter().enumerate().rev().fold(BigUint::default(), |n, (i, limb)| {
+            (n << if i % 2 == 0 { 29 } else { 28 }) + *limb
+        })
+    }
+
+    #[test]
+    fn matches_biguint() {
+        let p = BigUint::from_bytes_be(&EC_P);
+        let r_inv = (BigUint::one() << 257u32).modpow(&(&p - 2u8), &p);
+        let mut rng = StdRng::seed_from_u64(1280);
+        // 各limb取到入口条件允许的最大值：偶数位 < 2^30，奇数位 < 2^29
+        let max: [u32; 9] = std::array::from_fn(|i| if i & 1 == 0 { (1 << 30) - 1 } else { (1 << 29) - 1 });
+        let inputs = (0..256).map(|_| {
+            let mut limbs = || std::array::from_fn(|i| rng.gen_range(0..=max[i]));
+            (limbs(), limbs())
+        }).collect::<Vec<([u32; 9], [u32; 9])>>();
+
+        for (a, b) in inputs.iter().chain([(max, max), ([0; 9], max), (max, [1, 0, 0, 0, 0, 0, 0, 0, 0])].iter()) {
+            let product = multiply(a, b);
+            assert_eq!(value(&product), value(a) * value(b) * &r_inv % &p);
+            assert_eq!(value(&square(a)), value(a) * value(a) * &r_inv % &p);
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! 64位平台的域乘法：把9个29/28位交替的limb拼成4个64位饱和字，以128位乘法计算Montgomery乘积
+//!
+//! 存储格式仍是[`Payload`](super::payload::Payload)的9个limb，预计算表与常量时间选择不受影响，
+//! 只有乘法与平方换成16次64×64位乘法加4轮约减，代替81次32×32位乘法与逐limb的约减。
+
+use crate::ct::ct_select;
+
+/// p = 2^256 - 2^224 - 2^96 + 2^64 - 1，小端序；最低字全1，故 -p^-1 mod 2^64 = 1
+const P: [u64; 4] = [0xFFFFFFFFFFFFFFFF, 0xFFFFFFFF00000000, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFEFFFFFFFF];
+
+/// 2^256 mod p = 2^224 + 2^96 - 2^64 + 1
+const R_MOD_P: [u64; 4] = [0x0000000000000001, 0x00000000FFFFFFFF, 0x0000000000000000, 0x0000000100000000];
+
+/// a·b·2^-257 mod p，结果小于2^256，各limb不超出各自的宽度
+#[cfg_attr(feature = "fiat", allow(dead_code))]
+pub(crate) fn multiply(a: &[u32; 9], b: &[u32; 9]) -> [u32; 9] {
+    let (a, b) = (pack(a), pack(b));
+    let mut t = [0u64; 8];
+    for i in 0..4 {
+        let mut carry = 0u128;
+        for j in 0..4 {
+            let x = t[i + j] as u128 + (a[i] as u128) * (b[j] as u128) + carry;
+            t[i + j] = x as u64;
+            carry = x >> 64;
+        }
+        t[i + 4] = carry as u64;
+    }
+    unpack(&reduce(t))
+}
+
+/// a²·2^-257 mod p，交叉项只算一次再加倍，共10次64×64位乘法
+#[cfg_attr(feature = "fiat", allow(dead_code))]
+pub(crate) fn square(a: &[u32; 9]) -> [u32; 9] {
+    let a = pack(a);
+    let mut t = [0u64; 8];
+    // 交叉项 Σ a_i·a_j (i < j)
+    for i in 0..3 {
+        let mut carry = 0u128;
+        for j in i + 1..4 {
+            let x = t[i + j] as u128 + (a[i] as u128) * (a[j] as u128) + carry;
+            t[i + j] = x as u64;
+            carry = x >> 64;
+        }
+        t[i + 4] = carry as u64;
+    }
+    // 加倍后加上平方项 a_i²
+    let mut high = 0u64;
+    for word in t.iter_mut() {
+        let doubled = (*word << 1) | high;
+        high = *word >> 63;
+        *word = doubled;
+    }
+    let mut carry = 0u128;
+    for i in 0..4 {
+        let square = (a[i] as u128) * (a[i] as u128);
+        let x = t[2 * i] as u128 + (square as u64) as u128 + carry;
+        t[2 * i] = x as u64;
+        let x = t[2 * i + 1] as u128 + (square >> 64) + (x >> 64);
+        t[2 * i + 1] = x as u64;
+        carry = x >> 64;
+    }
+    unpack(&reduce(t))
+}
+
+/// 9个limb拼成4个字，数值小于2^258，高出2^256的部分以 2^256 ≡ R_MOD_P 折回两次
+pub(super) fn pack(limbs: &[u32; 9]) -> [u64; 4] {
+    // 逐个limb累加到128位的窗口中，凑满64位即移出一个字；limb可能超出宽度1位，故用加法而非按位或
+    let mut words = [0u64; 5];
+    let (mut window, mut bits, mut w) = (0u128, 0, 0);
+    for (i, limb) in limbs.iter().enumerate() {
+        window += (*limb as u128) << bits;
+        bits += if i % 2 == 0 { 29 } else { 28 };
+        if bits >= 64 {
+            words[w] = window as u64;
+            (window, bits, w) = (window >> 64, bits - 64, w + 1);
+        }
+    }
+    words[4] = window as u64;
+
+    // 第一次折回后小于2^256 + 2^227，第二次的进位至多为1，折回后不再溢出
+    let mut result = [words[0], words[1], words[2], words[3]];
+    let mut top = words[4];
+    for _ in 0..2 {
+        let mut carry = 0u128;
+        for (word, r) in result.iter_mut().zip(R_MOD_P) {
+            let x = *word as u128 + (top as u128) * (r as u128) + carry;
+            *word = x as u64;
+            carry = x >> 64;
+        }
+        top = carry as u64;
+    }
+    result
+}
+
+/// 4个字拆为29、28位交替的9个limb
+pub(super) fn unpack(words: &[u64; 4]) -> [u32; 9] {
+    let words = [words[0], words[1], words[2], words[3], 0];
+    let mut limbs = [0u32; 9];
+    let mut offset = 0;
+    for (i, limb) in limbs.iter_mut().enumerate() {
+        let width = if i % 2 == 0 { 29 } else { 28 };
+        let (w, s) = (offset / 64, offset % 64);
+        let mut v = words[w] >> s;
+        if s + width > 64 {
+            v |= words[w + 1] << (64 - s);
+        }
+        *limb = (v as u32) & ((1 << width) - 1);
+        offset += width;
+    }
+    limbs
+}
+
+/// t·2^-257 mod p，t < 2^512；结果小于2^256，不一定小于p
+fn reduce(mut t: [u64; 8]) -> [u64; 4] {
+    // 4轮Montgomery约减，每轮 m = t_i，加上m·p后最低字为0；总和小于2^256 + p
+    let mut top = 0u128;
+    for i in 0..4 {
+        let m = t[i] as u128;
+        let mut carry = 0u128;
+        for j in 0..4 {
+            let x = t[i + j] as u128 + m * (P[j] as u128) + carry;
+            t[i + j] = x as u64;
+            carry = x >> 64;
+        }
+        // 上一轮t_(i+3)的进位在本轮加到t_(i+4)
+        let x = t[i + 4] as u128 + carry + top;
+        t[i + 4] = x as u64;
+        top = x >> 64;
+    }
+    let top = top as u64;
+    let mut r = [t[4], t[5], t[6], t[7]];
+
+    // 不小于p时减去p，此后小于2^256
+    let (mut difference, mut borrow) = ([0u64; 4], 0u64);
+    for i in 0..4 {
+        let (d, b1) = r[i].overflowing_sub(P[i]);
+        let (d, b2) = d.overflowing_sub(borrow);
+        difference[i] = d;
+        borrow = (b1 | b2) as u64;
+    }
+    // top为1时必然不小于p
+    r = ct_select(&difference, &r, 0u64.wrapping_sub(borrow & !top & 1));
+
+    // 再除以2：奇数先加p
+    let odd = 0u64.wrapping_sub(r[0] & 1);
+    let mut carry = 0u128;
+    for i in 0..4 {
+        let x = r[i] as u128 + (P[i] & odd) as u128 + carry;
+        r[i] = x as u64;
+        carry = x >> 64;
+    }
+    let high = carry as u64;
+    for i in 0..3 {
+        r[i] = (r[i] >> 1) | (r[i + 1] << 63);
+    }
+    r[3] = (r[3] >> 1) | (high << 63);
+    r
+}
+
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+    use num_traits::One;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use crate::sm2::p256::params::EC_P;
+
+    use super::*;
+
+    fn value(limbs: &[u32; 9]) -> BigUint {
+        limbs.iter().enumerate().rev().fold(BigUint::default(), |n, (i, limb)| {
+            (n << if i % 2 == 0 { 29 } else { 28 }) + *limb
+        })
+    }
+
+    #[test]
+    fn matches_biguint() {
+        let p = BigUint::from_bytes_be(&EC_P);
+        let r_inv = (BigUint::one() << 257u32).modpow(&(&p - 2u8), &p);
+        let mut rng = StdRng::seed_from_u64(1279);
+        // 各limb取到入口条件允许的最大值：偶数位 < 2^30，奇数位 < 2^29
+        let max: [u32; 9] = std::array::from_fn(|i| if i & 1 == 0 { (1 << 30) - 1 } else { (1 << 29) - 1 });
+        let inputs = (0..256).map(|_| {
+            let mut limbs = || std::array::from_fn(|i| rng.gen_range(0..=max[i]));
+            (limbs(), limbs())
+        }).collect::<Vec<([u32; 9], [u32; 9])>>();
+
+        for (a, b) in inputs.iter().chain([(max, max), ([0; 9], max), (max, [1, 0, 0, 0, 0, 0, 0, 0, 0])].iter()) {
+            let expected = value(a) * value(b) * &r_inv % &p;
+            let product = multiply(a, b);
+            assert_eq!(value(&product) % &p, expected);
+            assert!(value(&product) < BigUint::one() << 256u32);
+            assert_eq!(value(&square(a)) % &p, value(a) * value(a) * &r_inv % &p);
+        }
+    }
+}
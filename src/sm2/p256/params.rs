@@ -1,3 +1,4 @@
+/// 素域的特征p，大端序
 pub const EC_P: [u8; 32] = [
     0xFF, 0xFF, 0xFF, 0xFE,
     0xFF, 0xFF, 0xFF, 0xFF,
@@ -9,6 +10,7 @@ pub const EC_P: [u8; 32] = [
     0xFF, 0xFF, 0xFF, 0xFF,
 ];
 
+/// 曲线方程系数a，大端序
 pub const EC_A: [u8; 32] = [
     0xFF, 0xFF, 0xFF, 0xFE,
     0xFF, 0xFF, 0xFF, 0xFF,
@@ -20,6 +22,7 @@ pub const EC_A: [u8; 32] = [
     0xFF, 0xFF, 0xFF, 0xFC,
 ];
 
+/// 曲线方程系数b，大端序
 pub const EC_B: [u8; 32] = [
     0x28, 0xE9, 0xFA, 0x9E,
     0x9D, 0x9F, 0x5E, 0x34,
@@ -31,6 +34,7 @@ pub const EC_B: [u8; 32] = [
     0x4D, 0x94, 0x0E, 0x93,
 ];
 
+/// 基点G的阶n，大端序
 pub const EC_N: [u8; 32] = [
     0xFF, 0xFF, 0xFF, 0xFE,
     0xFF, 0xFF, 0xFF, 0xFF,
@@ -42,6 +46,7 @@ pub const EC_N: [u8; 32] = [
     0x39, 0xD5, 0x41, 0x23,
 ];
 
+/// 基点G的x坐标，大端序
 pub const EC_GX: [u8; 32] = [
     0x32, 0xC4, 0xAE, 0x2C,
     0x1F, 0x19, 0x81, 0x19,
@@ -53,6 +58,7 @@ pub const EC_GX: [u8; 32] = [
     0x33, 0x4C, 0x74, 0xC7,
 ];
 
+/// 基点G的y坐标，大端序
 pub const EC_GY: [u8; 32] = [
     0xBC, 0x37, 0x36, 0xA2,
     0xF4, 0xF6, 0x77, 0x9C,
@@ -83,6 +89,7 @@ pub const RI: [u8; 32] = [
 ///
 /// The first table contains (x,y) field element pairs for 16 multiples of the base point, G.
 ///
+/// ```text
 ///   Index  |  Index (binary) | Value
 ///
 ///       0  |           0000  | 0G (all zeros, omitted)
@@ -101,9 +108,11 @@ pub const RI: [u8; 32] = [
 ///      13  |           1101  | 2**192G + 2**128G + G
 ///      14  |           1110  | 2**192G + 2**128G + 2**64G
 ///      15  |           1111  | 2**192G + 2**128G + 2**64G + G
+/// ```
 ///
 /// The second table follows the same style, but the terms are 2^32G, 2^96G, 2^160G, 2^224G.
 ///
+/// ```text
 ///      16  |          10000  | 2**32G
 ///      17  |          10010  | 2**96G
 ///      18  |          10001  | 2**96G  + 2**32G
@@ -119,11 +128,13 @@ pub const RI: [u8; 32] = [
 ///      28  |          11101  | 2**224G + 2**160G  + 2**32G
 ///      29  |          11110  | 2**224G + 2**160G + 2**96G
 ///      30  |          11111  | 2**224G + 2**160G + 2**96G + 2**32G
+/// ```
 ///
 /// precompute(1) => \[u32; 15 * 9 * 2]
 /// precompute(2**32) => \[u32; 15 * 9 * 2]
 /// MULTI_BASE_POINT_PRECOMPUTED = \[precompute(1), precompute(2**32)]
-pub const BASE_TABLE: [u32; 15 * 2 * 9 * 2] = [
+#[cfg(not(any(feature = "window-5", feature = "window-6")))]
+pub static BASE_TABLE: [u32; 15 * 2 * 9 * 2] = [
     0x0830053D, 0x0328990F, 0x06C04FE1, 0x0C0F72E5, 0x01E19F3C, 0x0666B093, 0x0175A87B, 0x0EC38276, 0x0222CF4B,
     0x185A1BBA, 0x0354E593, 0x1295FAC1, 0x0F2BC469, 0x047C60FA, 0x0C19B8A9, 0x0F63533E, 0x0903AE6B, 0x0C79ACBA,
     0x15B061A4, 0x033E020B, 0x0DFFB34B, 0x00FCF2C8, 0x16582E08, 0x0262F203, 0x0FB34381, 0x00A55452, 0x0604F0FF,
@@ -186,21 +197,35 @@ pub const BASE_TABLE: [u32; 15 * 2 * 9 * 2] = [
     0x011902A0, 0x06C29CC9, 0x1D5FFBE6, 0x0DB0B4C7, 0x10144C14, 0x02F2B719, 0x00301189, 0x02343336, 0x0A0BF2AC,
 ];
 
-pub const P256CARRY: [u32; 8 * 9] = [
-    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
-    0x00000002, 0x00000000, 0x1FFFFF00, 0x000007FF, 0x00000000, 0x00000000, 0x00000000, 0x02000000, 0x00000000,
-    0x00000004, 0x00000000, 0x1FFFFE00, 0x00000FFF, 0x00000000, 0x00000000, 0x00000000, 0x04000000, 0x00000000,
-    0x00000006, 0x00000000, 0x1FFFFD00, 0x000017FF, 0x00000000, 0x00000000, 0x00000000, 0x06000000, 0x00000000,
-    0x00000008, 0x00000000, 0x1FFFFC00, 0x00001FFF, 0x00000000, 0x00000000, 0x00000000, 0x08000000, 0x00000000,
-    0x0000000A, 0x00000000, 0x1FFFFB00, 0x000027FF, 0x00000000, 0x00000000, 0x00000000, 0x0A000000, 0x00000000,
-    0x0000000C, 0x00000000, 0x1FFFFA00, 0x00002FFF, 0x00000000, 0x00000000, 0x00000000, 0x0C000000, 0x00000000,
-    0x0000000E, 0x00000000, 0x1FFFF900, 0x000037FF, 0x00000000, 0x00000000, 0x00000000, 0x0E000000, 0x00000000,
+/// 基点的奇数倍 G, 3G, 5G, ..., 15G，仿射坐标 (x, y) 依次排列，供验签时wNAF的混合加法使用
+#[cfg(not(any(feature = "window-5", feature = "window-6")))]
+pub static BASE_ODD_MULTIPLES: [u32; 8 * 9 * 2] = [
+    0x0830053D, 0x0328990F, 0x06C04FE1, 0x0C0F72E5, 0x01E19F3C, 0x0666B093, 0x0175A87B, 0x0EC38276, 0x0222CF4B,
+    0x185A1BBA, 0x0354E593, 0x1295FAC1, 0x0F2BC469, 0x047C60FA, 0x0C19B8A9, 0x0F63533E, 0x0903AE6B, 0x0C79ACBA,
+    0x16B13C94, 0x0DA54FDA, 0x0F0A0D1C, 0x02944EDA, 0x0505133B, 0x08C233AD, 0x19028996, 0x07FD10F8, 0x06033FAD,
+    0x165F8320, 0x010FBE94, 0x0BCE600A, 0x05295C3E, 0x1E4BFA05, 0x0FE9B704, 0x0D33125A, 0x00B767F7, 0x0515A68F,
+    0x153902C4, 0x05756336, 0x1FCC539A, 0x02C7910E, 0x1A628AD5, 0x02FC7BD7, 0x0E5EB4D5, 0x06C7F0EB, 0x01E887DE,
+    0x068A0266, 0x081D68FD, 0x007D182E, 0x0A5DEF1B, 0x0C77D987, 0x0930D889, 0x1A2C609A, 0x0C2854CE, 0x0F682A4E,
+    0x03E0CF09, 0x03531C78, 0x1325202A, 0x0A0C9C38, 0x0C6285C4, 0x0DA6B3F8, 0x0A213B96, 0x02B3F7D8, 0x077E925C,
+    0x0BEC30DA, 0x03A35C1E, 0x1A01B831, 0x04D173AB, 0x0DD78722, 0x0648AF66, 0x1DE5B26E, 0x0E7709AE, 0x05740B51,
+    0x01F7BD0C, 0x0E795C33, 0x01AF8F98, 0x0024AD59, 0x14DA472F, 0x0E0ED219, 0x120E496F, 0x008D2A45, 0x0EFB10E8,
+    0x10F6099D, 0x0BF8B5B9, 0x1F44280F, 0x0B1D45D7, 0x14B4B1E2, 0x05954E9B, 0x13177044, 0x0964D2FE, 0x00454D84,
+    0x0843C22A, 0x0252AD03, 0x0FFC012B, 0x0E463E35, 0x0933BAAB, 0x07F62942, 0x12364306, 0x01C1B0E3, 0x0907B42D,
+    0x020E537E, 0x0D6F9B3C, 0x17061E72, 0x020108AD, 0x0C3EAEEC, 0x0B6E7CB1, 0x10A6FFA8, 0x0F30C83D, 0x0A507314,
+    0x0001FC9C, 0x0E51C6A8, 0x04694134, 0x05EFEC4E, 0x15973ED1, 0x047786F0, 0x17199EEA, 0x00214E7C, 0x0790193A,
+    0x0FEB8C9A, 0x0ADEBBEC, 0x0350524F, 0x04F5A1F5, 0x0AFCD778, 0x046C9BB1, 0x18059FB8, 0x050A3124, 0x08BF4A11,
+    0x1BD2FA9A, 0x0489343D, 0x12CE1FF3, 0x071C55DD, 0x1910CE0A, 0x04473F77, 0x1C49619A, 0x028C1DFD, 0x01BF1282,
+    0x075D064C, 0x013F30E7, 0x1274A1F7, 0x0335466E, 0x0CA3ECDF, 0x0B88DA21, 0x0077E456, 0x0CF0F16F, 0x058B83D3,
 ];
 
+/// k·2^257 mod p（k = 0..7）的limb形式，即[`P256FACTOR`]的前8行依次拼接
+pub const P256CARRY: [u32; 8 * 9] = flatten_carry(&P256FACTOR);
+
 pub const P256ZERO31: [u32; 9] = [
     0x7FFFFFF8, 0x3FFFFFFC, 0x800003FC, 0x3FFFDFFC, 0x7FFFFFFC, 0x3FFFFFFC, 0x7FFFFFFC, 0x37FFFFFC, 0x7FFFFFFC
 ];
 
+/// k·2^257 mod p（k = 0..8）的limb形式
 pub const P256FACTOR: [[u32; 9]; 9] = [
     [0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000],
     [0x00000002, 0x00000000, 0x1FFFFF00, 0x000007FF, 0x00000000, 0x00000000, 0x00000000, 0x02000000, 0x00000000],
@@ -213,13 +238,80 @@ pub const P256FACTOR: [[u32; 9]; 9] = [
     [0x00000010, 0x00000000, 0x1FFFF800, 0x00003FFF, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000001],
 ];
 
+/// 曲线参数a的Montgomery形式 a·R mod p
+pub const P256A: [u32; 9] = [0x1FFFFFF9, 0x0FFFFFFF, 0x0000037F, 0x0FFFE400, 0x1FFFFFFF, 0x0FFFFFFF, 0x1FFFFFFF, 0x08FFFFFF, 0x0FFFFFFF];
+
+/// 曲线参数b的Montgomery形式 b·R mod p
+pub const P256B: [u32; 9] = [0x1781BA84, 0x0D230632, 0x1537AB90, 0x09BCD74D, 0x0E1E38E7, 0x05417A94, 0x12149E60, 0x017441C5, 0x0481FC31];
+
+/// R^2 mod p，按limb拆分，与之相乘即可转为Montgomery形式
+pub const P256RR: [u32; 9] = [0x0000000C, 0x00000040, 0x1FFFFE00, 0x00002FFF, 0x00010000, 0x00080000, 0x01000000, 0x00000000, 0x00000001];
+
+/// p - n，签名验证中判断 x1 是否可能为 (r - e) mod n + n
+pub const EC_P_MINUS_N: [u8; 32] = sub(&EC_P, &EC_N);
+
+/// (p + 1) / 4，p ≡ 3 (mod 4)，模p平方根的指数
+pub const EC_P_PLUS_1_DIV_4: [u8; 32] = shr2(&increment(&EC_P));
+
+/// 大端序 a - b，要求 a ≥ b
+const fn sub(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    let mut borrow = 0i16;
+    let mut i = 32;
+    while i > 0 {
+        i -= 1;
+        let d = a[i] as i16 - b[i] as i16 - borrow;
+        out[i] = d as u8;
+        borrow = (d < 0) as i16;
+    }
+    out
+}
+
+/// 大端序 a + 1，要求不溢出
+const fn increment(a: &[u8; 32]) -> [u8; 32] {
+    let mut out = *a;
+    let mut i = 32;
+    while i > 0 {
+        i -= 1;
+        out[i] = out[i].wrapping_add(1);
+        if out[i] != 0 {
+            break;
+        }
+    }
+    out
+}
+
+/// 大端序 a >> 2
+const fn shr2(a: &[u8; 32]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    let mut i = 0;
+    while i < 32 {
+        out[i] = a[i] >> 2;
+        if i > 0 {
+            out[i] |= a[i - 1] << 6;
+        }
+        i += 1;
+    }
+    out
+}
+
+const fn flatten_carry(factor: &[[u32; 9]; 9]) -> [u32; 8 * 9] {
+    let mut out = [0u32; 8 * 9];
+    let mut i = 0;
+    while i < 8 * 9 {
+        out[i] = factor[i / 9][i % 9];
+        i += 1;
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use std::ops::{Add, Shl, Shr, Sub};
 
     use num_bigint::{BigInt, Sign, ToBigInt};
     use num_integer::Integer;
-    use num_traits::FromPrimitive;
+    use num_traits::{FromPrimitive, One};
 
     use crate::sm2::p256::P256Elliptic;
     use crate::sm2::p256::payload::PayloadHelper;
@@ -238,6 +330,117 @@ mod tests {
         let ext = r.extended_gcd(&p);
         let ri = ext.x.mod_floor(&p);
         assert_eq!(ri.to_str_radix(16), ri_hex);
+        assert_eq!(ri, BigInt::from_bytes_be(Sign::Plus, &RI));
+        assert!((r * ri).mod_floor(&p).is_one());
+    }
+
+    /// GB/T 32918.5-2017 推荐曲线参数
+    #[test]
+    fn curve() {
+        let hex = |s: &str| BigInt::parse_bytes(s.as_bytes(), 16).unwrap();
+        let big = |b: &[u8; 32]| BigInt::from_bytes_be(Sign::Plus, b);
+        let p = hex("FFFFFFFEFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF00000000FFFFFFFFFFFFFFFF");
+        assert_eq!(big(&EC_P), p);
+        assert_eq!(big(&EC_A), hex("FFFFFFFEFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF00000000FFFFFFFFFFFFFFFC"));
+        assert_eq!(big(&EC_B), hex("28E9FA9E9D9F5E344D5A9E4BCF6509A7F39789F515AB8F92DDBCBD414D940E93"));
+        assert_eq!(big(&EC_N), hex("FFFFFFFEFFFFFFFFFFFFFFFFFFFFFFFF7203DF6B21C6052B53BBF40939D54123"));
+        assert_eq!(big(&EC_GX), hex("32C4AE2C1F1981195F9904466A39C9948FE30BBFF2660BE1715A4589334C74C7"));
+        assert_eq!(big(&EC_GY), hex("BC3736A2F4F6779C59BDCEE36B692153D0A9877CC62A474002DF32E52139F0A0"));
+
+        // p = 2^256 - 2^224 - 2^96 + 2^64 - 1，a = p - 3
+        let two = BigInt::from(2u8);
+        assert_eq!(p, two.pow(256) - two.pow(224) - two.pow(96) + two.pow(64) - BigInt::one());
+        assert_eq!(big(&EC_A), &p - BigInt::from(3u8));
+
+        // G 在曲线上且阶为 n：(n - 1)G = -G
+        let g = (big(&EC_GX), big(&EC_GY));
+        assert_eq!(g.1.modpow(&two, &p), (g.0.pow(3) + big(&EC_A) * &g.0 + big(&EC_B)).mod_floor(&p));
+        let n1 = multiply(&g, &(big(&EC_N) - BigInt::one()));
+        assert_eq!((n1.0, n1.1), (g.0.clone(), &p - &g.1));
+    }
+
+    #[test]
+    fn base_table() {
+        use crate::sm2::p256::window::{BASE_ODD_MULTIPLES, BASE_TABLE, COMB_ENTRIES, COMB_SPACING, ODD_MULTIPLES, WINDOW};
+
+        let big = |b: &[u8; 32]| BigInt::from_bytes_be(Sign::Plus, b);
+        let check = |entry: &[u32], point: &Affine, what: String| {
+            let (x, y) = (PayloadHelper::transform(&point.0), PayloadHelper::transform(&point.1));
+            assert_eq!(&entry[..9], &x.data(), "{} x", what);
+            assert_eq!(&entry[9..], &y.data(), "{} y", what);
+        };
+        let g = (big(&EC_GX), big(&EC_GY));
+        // 2^(kh)G, k = 0..2w，h为半个齿距，w = 4时为2^(32k)G
+        let mut powers = vec![g.clone()];
+        for _ in 1..2 * WINDOW {
+            let mut point = powers.last().unwrap().clone();
+            for _ in 0..COMB_SPACING / 2 {
+                point = double(&point);
+            }
+            powers.push(point);
+        }
+
+        for (t, table) in BASE_TABLE.chunks(COMB_ENTRIES * 18).enumerate() {
+            for (j, entry) in table.chunks(18).enumerate() {
+                // 第t张表的第j项为 Σ 2^(bs + th)G，b取 j + 1 中置位的比特，s为齿距
+                let point = (0..WINDOW).filter(|b| (j + 1) >> b & 1 == 1)
+                    .map(|b| powers[2 * b + t].clone())
+                    .reduce(|sum, q| add(&sum, &q))
+                    .unwrap();
+                check(entry, &point, format!("table {} entry {}", t, j + 1));
+            }
+        }
+
+        let g2 = double(&g);
+        let mut point = g;
+        for (i, entry) in BASE_ODD_MULTIPLES.chunks(18).enumerate() {
+            check(entry, &point, format!("{}G", 2 * i + 1));
+            point = add(&point, &g2);
+        }
+        assert_eq!(BASE_ODD_MULTIPLES.len(), ODD_MULTIPLES * 18);
+    }
+
+    type Affine = (BigInt, BigInt);
+
+    /// 独立于limb实现的仿射坐标运算，仅用于校验常量
+    fn field() -> BigInt {
+        BigInt::from_bytes_be(Sign::Plus, &EC_P)
+    }
+
+    fn inverse(v: &BigInt) -> BigInt {
+        let p = field();
+        v.modpow(&(&p - BigInt::from(2u8)), &p)
+    }
+
+    fn chord(a: &Affine, b: &Affine, lambda: BigInt) -> Affine {
+        let p = field();
+        let x = (&lambda * &lambda - &a.0 - &b.0).mod_floor(&p);
+        let y = (lambda * (&a.0 - &x) - &a.1).mod_floor(&p);
+        (x, y)
+    }
+
+    fn add(a: &Affine, b: &Affine) -> Affine {
+        let p = field();
+        chord(a, b, ((&b.1 - &a.1) * inverse(&(&b.0 - &a.0).mod_floor(&p))).mod_floor(&p))
+    }
+
+    fn double(a: &Affine) -> Affine {
+        let p = field();
+        let a_coef = BigInt::from_bytes_be(Sign::Plus, &EC_A);
+        let lambda = ((BigInt::from(3u8) * &a.0 * &a.0 + a_coef) * inverse(&(BigInt::from(2u8) * &a.1))).mod_floor(&p);
+        chord(a, a, lambda)
+    }
+
+    fn multiply(point: &Affine, scalar: &BigInt) -> Affine {
+        let bits = scalar.bits();
+        let mut acc = point.clone();
+        for i in (0..bits - 1).rev() {
+            acc = double(&acc);
+            if scalar.bit(i) {
+                acc = add(&acc, point);
+            }
+        }
+        acc
     }
 
     #[test]
@@ -258,7 +461,7 @@ mod tests {
     #[test]
     fn factor_table() {
         let mut table: [[u32; 9]; 9] = [[0; 9]; 9];
-        for i in 0..9 {
+        for (i, row) in table.iter_mut().enumerate() {
             let value = BigInt::from(i as i64);
             let payload = PayloadHelper::transform(&value);
 
@@ -267,7 +470,7 @@ mod tests {
                 temp[j] = *e;
                 print!("0x{:>08X}, ", *e);
             }
-            table[i] = temp;
+            *row = temp;
             println!();
         }
         assert_eq!(table, P256FACTOR)
@@ -331,4 +534,20 @@ mod tests {
         println!("{:08X?}", out);
         assert_eq!(out, P256ZERO31);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn montgomery_constants() {
+        let p = BigInt::from_bytes_be(Sign::Plus, &EC_P);
+        let n = BigInt::from_bytes_be(Sign::Plus, &EC_N);
+        let a = PayloadHelper::transform(&BigInt::from_bytes_be(Sign::Plus, &EC_A));
+        let b = PayloadHelper::transform(&BigInt::from_bytes_be(Sign::Plus, &EC_B));
+        assert_eq!(a.data(), P256A);
+        assert_eq!(b.data(), P256B);
+
+        // R^2 mod p 的原始limb拆分即 R mod p 的Montgomery形式
+        let r = BigInt::from(2u64).pow(257).mod_floor(&p);
+        assert_eq!(PayloadHelper::transform(&r).data(), P256RR);
+        assert_eq!(BigInt::from_bytes_be(Sign::Plus, &EC_P_MINUS_N), p.clone().sub(n));
+        assert_eq!(BigInt::from_bytes_be(Sign::Plus, &EC_P_PLUS_1_DIV_4), (p + 1) / 4);
+    }
+}
@@ -3,13 +3,20 @@ use std::ops::{Add, Mul, Shl, Shr};
 use num_bigint::{BigInt, ToBigInt};
 use num_integer::Integer;
 use num_traits::FromPrimitive;
-use crate::sm2::p256::{mask, P256Elliptic};
+#[cfg(all(feature = "avx2", target_arch = "x86_64"))]
+use crate::sm2::p256::avx2;
+use crate::ct::{ct_mask, ct_select};
+use crate::sm2::p256::P256Elliptic;
+#[cfg(feature = "fiat")]
+use crate::sm2::p256::fiat;
+#[cfg(all(target_pointer_width = "64", not(feature = "fiat")))]
+use crate::sm2::p256::limb64;
 
-use crate::sm2::p256::params::{P256CARRY, P256FACTOR, P256ZERO31};
+use crate::sm2::p256::params::{EC_P, EC_P_PLUS_1_DIV_4, P256CARRY, P256FACTOR, P256RR, P256ZERO31};
 
 /// Field elements are represented as nine, unsigned 32-bit words. The value of a field element is:
 ///
-/// ```
+/// ```text
 /// Value = (x8 * 2^228) + (x7 * 2^200) + (x6 * 2^171) + (x5 * 2^143) + (x4 * 2^114) + (x3 * 2^86) +
 ///         (x2 * 2^57)  + (x1 * 2^29)  + x0
 /// ```
@@ -125,12 +132,40 @@ impl Payload {
         result
     }
 
+    /// -self mod p，即 0 - self
+    pub(crate) fn negate(&self) -> Payload {
+        Payload::init().subtract(self)
+    }
+
     /// multiply sets payload3 = payload1 * payload2.
     ///
     /// On entry: payload1\[0,2,...] < 2^30, payload1\[1,3,...] < 2^29 and
     ///           payload2\[0,2,...] < 2^30, payload2\[1,3,...] < 2^29.
     /// On exit:  payload3\[0,2,...] < 2^30, payload3\[1,3,...] < 2^29.
+    ///
+    /// 启用`fiat`特性时使用fiat-crypto生成的实现；64位平台上拼成4个64位字计算，见`limb64`；其余平台逐limb计算。
     pub(crate) fn multiply(&self, other: &Payload) -> Payload {
+        #[cfg(feature = "fiat")]
+        return Payload { data: fiat::multiply(&self.data, &other.data) };
+        #[cfg(all(target_pointer_width = "64", not(feature = "fiat")))]
+        return Payload { data: limb64::multiply(&self.data, &other.data) };
+        #[cfg(all(not(target_pointer_width = "64"), not(feature = "fiat")))]
+        return self.multiply_limbs(other);
+    }
+
+    /// 按29、28位交替的limb计算乘积，32位平台使用
+    #[allow(clippy::identity_op)]
+    #[cfg_attr(any(target_pointer_width = "64", feature = "fiat"), allow(dead_code))]
+    fn multiply_limbs(&self, other: &Payload) -> Payload {
+        #[cfg(all(feature = "avx2", target_arch = "x86_64"))]
+        if avx2::available() {
+            // SAFETY: 已确认CPU支持AVX2
+            let mut tmp = unsafe { avx2::product(&self.data, &other.data) };
+            let mut result = Payload::init();
+            PayloadHelper::reduce_degree(&mut result, &mut tmp);
+            return result;
+        }
+
         let mut result = Payload::init();
         let mut tmp: [u64; 17] = [0; 17];
         tmp[0] = (self.data[0] as u64) * (other.data[0] as u64);
@@ -219,7 +254,28 @@ impl Payload {
         result
     }
 
+    /// 启用`fiat`特性时使用fiat-crypto生成的实现；64位平台上拼成4个64位字计算，见`limb64`；其余平台逐limb计算。
     pub(crate) fn square(&self) -> Payload {
+        #[cfg(feature = "fiat")]
+        return Payload { data: fiat::square(&self.data) };
+        #[cfg(all(target_pointer_width = "64", not(feature = "fiat")))]
+        return Payload { data: limb64::square(&self.data) };
+        #[cfg(all(not(target_pointer_width = "64"), not(feature = "fiat")))]
+        return self.square_limbs();
+    }
+
+    #[allow(clippy::identity_op)]
+    #[cfg_attr(any(target_pointer_width = "64", feature = "fiat"), allow(dead_code))]
+    fn square_limbs(&self) -> Payload {
+        #[cfg(all(feature = "avx2", target_arch = "x86_64"))]
+        if avx2::available() {
+            // SAFETY: 已确认CPU支持AVX2
+            let mut tmp = unsafe { avx2::product(&self.data, &self.data) };
+            let mut result = Payload::init();
+            PayloadHelper::reduce_degree(&mut result, &mut tmp);
+            return result;
+        }
+
         let mut result = Payload::init();
         let mut tmp: [u64; 17] = [0; 17];
         tmp[0] = (self.data[0] as u64) * (self.data[0] as u64);
@@ -276,6 +332,133 @@ impl Payload {
         let p = Payload { data: P256FACTOR[n] };
         self.multiply(&p)
     }
+
+    /// 大端字节串转为Montgomery形式，不经过BigInt，不分配内存
+    ///
+    /// 先按29、28位交替拆分为原始limb，再乘以R^2 mod p。输入不必小于p。
+    pub(crate) fn from_bytes(bytes: &[u8; 32]) -> Payload {
+        let words = words_of(bytes);
+        let mut data = [0u32; 9];
+        let mut offset = 0;
+        for (i, limb) in data.iter_mut().enumerate() {
+            let width = if i % 2 == 0 { 29 } else { 28 };
+            let (w, s) = (offset / 64, offset % 64);
+            let mut v = words[w] >> s;
+            if s + width > 64 {
+                v |= words[w + 1] << (64 - s);
+            }
+            *limb = (v as u32) & ((1 << width) - 1);
+            offset += width;
+        }
+        Payload { data }.multiply(&Payload { data: P256RR })
+    }
+
+    /// 还原为\[0, p)内的大端字节串，不经过BigInt，不分配内存
+    pub(crate) fn to_bytes(self) -> [u8; 32] {
+        // 乘以原始的1去掉因子R，limb仍可能超出各自的宽度，累加到64位字中再约减
+        let mut one = [0u32; 9];
+        one[0] = 1;
+        let limbs = self.multiply(&Payload { data: one }).data;
+
+        let mut words = [0u64; 5];
+        let mut offset = 0;
+        for (i, limb) in limbs.iter().enumerate() {
+            // 进位一直传递到最高字，循环次数只取决于limb的位置
+            let mut carry = (*limb as u128) << (offset % 64);
+            for word in words.iter_mut().skip(offset / 64) {
+                let sum = *word as u128 + (carry as u64) as u128;
+                *word = sum as u64;
+                carry = (carry >> 64) + (sum >> 64);
+            }
+            offset += if i % 2 == 0 { 29 } else { 28 };
+        }
+
+        // 各limb至多超出其宽度1位，数值小于2^258 < 5p，固定做4次条件减法，不依赖数值
+        let p = words_of(&EC_P);
+        for _ in 0..4 {
+            let (mut difference, mut borrow) = ([0u64; 5], 0u64);
+            for i in 0..5 {
+                let (d, b1) = words[i].overflowing_sub(p[i]);
+                let (d, b2) = d.overflowing_sub(borrow);
+                difference[i] = d;
+                borrow = (b1 | b2) as u64;
+            }
+            // 无借位时 words ≥ p，取差
+            words = ct_select(&difference, &words, 0u64.wrapping_sub(borrow));
+        }
+
+        let mut bytes = [0u8; 32];
+        for (i, b) in bytes.iter_mut().rev().enumerate() {
+            *b = (words[i / 8] >> (8 * (i % 8))) as u8;
+        }
+        bytes
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        self.to_bytes() == [0u8; 32]
+    }
+
+    /// 模p为0时返回0xffffffff，否则返回0，常量时间
+    pub(crate) fn zero_mask(&self) -> u32 {
+        !ct_mask(self.to_bytes().iter().fold(0u32, |acc, b| acc | *b as u32))
+    }
+
+    /// 求逆 a^(p-2) mod p，0的逆元记为0
+    ///
+    /// p-2 = 2^256 - 2^224 - 2^96 + 2^64 - 3，二进制依次为31个1、1个0、128个1、32个0、62个1（倒数第二位为0）。
+    /// 先构造 x_k = a^(2^k - 1)，再按固定的加法链拼接，平方256次、乘法15次，不依赖a的取值。
+    pub(crate) fn invert(&self) -> Payload {
+        let x1 = *self;
+        let x2 = x1.square().multiply(&x1);
+        let x3 = x2.square().multiply(&x1);
+        let x6 = x3.square_times(3).multiply(&x3);
+        let x12 = x6.square_times(6).multiply(&x6);
+        let x24 = x12.square_times(12).multiply(&x12);
+        let x30 = x24.square_times(6).multiply(&x6);
+        let x31 = x30.square().multiply(&x1);
+        let x32 = x31.square().multiply(&x1);
+
+        let mut t = x31.square();
+        for _ in 0..4 {
+            t = t.square_times(32).multiply(&x32);
+        }
+        t = t.square_times(32);
+        t = t.square_times(32).multiply(&x32);
+        t = t.square_times(30).multiply(&x30);
+        t.square_times(2).multiply(&x1)
+    }
+
+    /// 连续平方n次
+    fn square_times(&self, n: usize) -> Payload {
+        let mut result = *self;
+        for _ in 0..n {
+            result = result.square();
+        }
+        result
+    }
+
+    /// 模p平方根 a^((p+1)/4)，任选其一；不是二次剩余时为None
+    pub(crate) fn sqrt(&self) -> Option<Payload> {
+        let mut root = Payload { data: P256FACTOR[1] };
+        for byte in EC_P_PLUS_1_DIV_4 {
+            for bit in (0..8).rev() {
+                root = root.square();
+                if (byte >> bit) & 1 == 1 {
+                    root = root.multiply(self);
+                }
+            }
+        }
+        (root.square().to_bytes() == self.to_bytes()).then_some(root)
+    }
+}
+
+/// 大端字节串转为5个64位小端字，最高字留作进位
+fn words_of(bytes: &[u8; 32]) -> [u64; 5] {
+    let mut words = [0u64; 5];
+    for (i, b) in bytes.iter().rev().enumerate() {
+        words[i / 8] |= (*b as u64) << (8 * (i % 8));
+    }
+    words
 }
 
 pub(crate) struct PayloadHelper;
@@ -286,32 +469,33 @@ impl PayloadHelper {
     /// n: 115792089210356248756420345214020892766250353991924191454421193933289684991996
     ///
     /// * step 1 :
-    ///     ```
+    ///     ```text
     ///     x = (n * 2^257) % p;
     ///     x = 115792089048596568753516506446018802244132569949625955944202853485549017104377
     ///       = 1111111111111111111111111111100011111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111001000000000000000000000000000001101111111111111111111111111111111111111111111111111111111111111001
     ///     ```
     /// * step 2: while loop, every time extract 29bits and 28 bits.
     ///     + step 2.1.1: change x: bigint to x: vec\<u32>
-    ///         ```
+    ///         ```text
     ///         x = [4294967289, 4294967295, 6, 4294967289, 4294967295, 4294967295, 4294967295, 4294967288]
     ///         ```
     ///     + step 2.1.2: extract 29 bits of x using operator &, `x[0] & 0x1FFFFFFF`
-    ///         ```
+    ///         ```text
     ///         11111111111111111111111111111001
     ///         &  11111111111111111111111111111
     ///         =  11111111111111111111111111001
     ///         ```
     ///     + step 2.1.3: right shift 29 bits on purpose to delete the extracted 29 bits.
-    ///        ```
+    ///        ```text
     ///         x = 11111111111111111111111111111000111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111110010000000000000000000000000000011011111111111111111111111111111111111
     ///        ```
     ///     + step 2.2.1: some operation like 2.1.1
     ///     + step 2.2.2: extract 28 bits of x using operator &, `x[0] & 0xFFFFFFF`
     ///     + step 2.2.3: right shift 28 bits.
     /// * step 3: get the result
-    ///     ```
+    ///     ```text
     ///     data = [536870905, 268435455, 895, 268428288, 536870911, 268435455, 536870911, 150994943, 268435455]
+    ///     ```
     pub(crate) fn transform(n: &BigInt) -> Payload {
         let elliptic = P256Elliptic::init();
         let mut data: [u32; 9] = [0; 9];
@@ -350,7 +534,7 @@ impl PayloadHelper {
 
     /// Example: payload = \[x0, x1, x2, x3, x4, x5, x6, x7, x8]
     ///
-    ///           n = x8
+    /// `n = x8`
     /// * i=7  => n = n * 2^28 + x7 = x8 * 2^28 + x7
     /// * i=6  => n = n * 2^29 + x6 = x8 * 2^57 + x7 * 2^29 + x6
     /// * i=5  => n = n * 2^28 + x5 = x8 * 2^85 + x7 * 2^57 + x6 * 2^28 + x5
@@ -402,6 +586,7 @@ impl PayloadHelper {
     ///
     /// On entry: carry < 2^3, payload\[0,2,...] < 2^29, payload\[1,3,...] < 2^28.
     /// On exit: payload\[0,2,..] < 2^30, payload\[1,3,...] < 2^29.
+    #[allow(clippy::identity_op)]
     fn reduce_carry(payload: &mut Payload, carry: usize) {
         payload.data[0] += P256CARRY[carry * 9 + 0];
         payload.data[2] += P256CARRY[carry * 9 + 2];
@@ -432,17 +617,17 @@ impl PayloadHelper {
         tmp[0] = (b[0] as u32) & (LimbPattern::WIDTH29BITS as u32);
         tmp[1] = (b[0] as u32) >> 29;
         tmp[1] |= (((b[0] >> 32) as u32) << 3) & (LimbPattern::WIDTH28BITS as u32);
-        tmp[1] += (b[1] as u32) & (LimbPattern::WIDTH28BITS as u32);
+        tmp[1] = tmp[1].wrapping_add((b[1] as u32) & (LimbPattern::WIDTH28BITS as u32));
         carry = tmp[1] >> 28;
         tmp[1] &= LimbPattern::WIDTH28BITS as u32;
 
         let mut i = 2;
         while i < 17 {
             tmp[i] = ((b[i - 2] >> 32) as u32) >> 25;
-            tmp[i] += ((b[i - 1]) as u32) >> 28;
-            tmp[i] += (((b[i - 1] >> 32) as u32) << 4) & (LimbPattern::WIDTH29BITS as u32);
-            tmp[i] += (b[i] as u32) & (LimbPattern::WIDTH29BITS as u32);
-            tmp[i] += carry;
+            tmp[i] = tmp[i].wrapping_add(((b[i - 1]) as u32) >> 28);
+            tmp[i] = tmp[i].wrapping_add((((b[i - 1] >> 32) as u32) << 4) & (LimbPattern::WIDTH29BITS as u32));
+            tmp[i] = tmp[i].wrapping_add((b[i] as u32) & (LimbPattern::WIDTH29BITS as u32));
+            tmp[i] = tmp[i].wrapping_add(carry);
             carry = tmp[i] >> 29;
             tmp[i] &= LimbPattern::WIDTH29BITS as u32;
 
@@ -452,10 +637,10 @@ impl PayloadHelper {
             }
 
             tmp[i] = ((b[i - 2] >> 32) as u32) >> 25;
-            tmp[i] += (b[i - 1] as u32) >> 29;
-            tmp[i] += (((b[i - 1] >> 32) as u32) << 3) & (LimbPattern::WIDTH28BITS as u32);
-            tmp[i] += (b[i] as u32) & (LimbPattern::WIDTH28BITS as u32);
-            tmp[i] += carry;
+            tmp[i] = tmp[i].wrapping_add((b[i - 1] as u32) >> 29);
+            tmp[i] = tmp[i].wrapping_add((((b[i - 1] >> 32) as u32) << 3) & (LimbPattern::WIDTH28BITS as u32));
+            tmp[i] = tmp[i].wrapping_add((b[i] as u32) & (LimbPattern::WIDTH28BITS as u32));
+            tmp[i] = tmp[i].wrapping_add(carry);
             carry = tmp[i] >> 28;
             tmp[i] &= LimbPattern::WIDTH28BITS as u32;
 
@@ -463,78 +648,78 @@ impl PayloadHelper {
         }
 
         tmp[17] = ((b[15] >> 32) as u32) >> 25;
-        tmp[17] += (b[16] as u32) >> 29;
-        tmp[17] += ((b[16] >> 32) as u32) << 3;
-        tmp[17] += carry;
+        tmp[17] = tmp[17].wrapping_add((b[16] as u32) >> 29);
+        tmp[17] = tmp[17].wrapping_add(((b[16] >> 32) as u32) << 3);
+        tmp[17] = tmp[17].wrapping_add(carry);
 
         i = 0;
         loop {
-            tmp[i + 1] += tmp[i] >> 29;
+            tmp[i + 1] = tmp[i + 1].wrapping_add(tmp[i] >> 29);
             x = tmp[i] & (LimbPattern::WIDTH29BITS as u32);
             tmp[i] = 0;
 
             if x > 0 {
                 let mut set4: u32 = 0;
                 let mut set7: u32 = 0;
-                x_mask = mask(x);
-                tmp[i + 2] += (x << 7) & (LimbPattern::WIDTH29BITS as u32);
-                tmp[i + 3] += x >> 22;
+                x_mask = ct_mask(x);
+                tmp[i + 2] = tmp[i + 2].wrapping_add((x << 7) & (LimbPattern::WIDTH29BITS as u32));
+                tmp[i + 3] = tmp[i + 3].wrapping_add(x >> 22);
 
                 if tmp[i + 3] < 0x10000000 {
                     set4 = 1;
-                    tmp[i + 3] += 0x10000000 & x_mask;
-                    tmp[i + 3] -= (x << 10) & (LimbPattern::WIDTH28BITS as u32);
+                    tmp[i + 3] = tmp[i + 3].wrapping_add(0x10000000 & x_mask);
+                    tmp[i + 3] = tmp[i + 3].wrapping_sub((x << 10) & (LimbPattern::WIDTH28BITS as u32));
                 } else {
-                    tmp[i + 3] -= (x << 10) & (LimbPattern::WIDTH28BITS as u32);
+                    tmp[i + 3] = tmp[i + 3].wrapping_sub((x << 10) & (LimbPattern::WIDTH28BITS as u32));
                 }
                 if tmp[i + 4] < 0x20000000 {
-                    tmp[i + 4] += 0x20000000 & x_mask;
-                    tmp[i + 4] -= set4;
-                    tmp[i + 4] -= x >> 18;
+                    tmp[i + 4] = tmp[i + 4].wrapping_add(0x20000000 & x_mask);
+                    tmp[i + 4] = tmp[i + 4].wrapping_sub(set4);
+                    tmp[i + 4] = tmp[i + 4].wrapping_sub(x >> 18);
                     if tmp[i + 5] < 0x10000000 {
-                        tmp[i + 5] += 0x10000000 & x_mask;
-                        tmp[i + 5] -= 1;
+                        tmp[i + 5] = tmp[i + 5].wrapping_add(0x10000000 & x_mask);
+                        tmp[i + 5] = tmp[i + 5].wrapping_sub(1);
                         if tmp[i + 6] < 0x20000000 {
                             set7 = 1;
-                            tmp[i + 6] += 0x20000000 & x_mask;
-                            tmp[i + 6] -= 1;
+                            tmp[i + 6] = tmp[i + 6].wrapping_add(0x20000000 & x_mask);
+                            tmp[i + 6] = tmp[i + 6].wrapping_sub(1);
                         } else {
-                            tmp[i + 6] -= 1;
+                            tmp[i + 6] = tmp[i + 6].wrapping_sub(1);
                         }
                     } else {
-                        tmp[i + 5] -= 1;
+                        tmp[i + 5] = tmp[i + 5].wrapping_sub(1);
                     }
                 } else {
-                    tmp[i + 4] -= set4;
-                    tmp[i + 4] -= x >> 18;
+                    tmp[i + 4] = tmp[i + 4].wrapping_sub(set4);
+                    tmp[i + 4] = tmp[i + 4].wrapping_sub(x >> 18);
                 }
 
                 if tmp[i + 7] < 0x10000000 {
-                    tmp[i + 7] += 0x10000000 & x_mask;
-                    tmp[i + 7] -= set7;
-                    tmp[i + 7] -= (x << 24) & (LimbPattern::WIDTH28BITS as u32);
-                    tmp[i + 8] += (x << 28) & (LimbPattern::WIDTH29BITS as u32);
+                    tmp[i + 7] = tmp[i + 7].wrapping_add(0x10000000 & x_mask);
+                    tmp[i + 7] = tmp[i + 7].wrapping_sub(set7);
+                    tmp[i + 7] = tmp[i + 7].wrapping_sub((x << 24) & (LimbPattern::WIDTH28BITS as u32));
+                    tmp[i + 8] = tmp[i + 8].wrapping_add((x << 28) & (LimbPattern::WIDTH29BITS as u32));
                     if tmp[i + 8] < 0x20000000 {
-                        tmp[i + 8] += 0x20000000 & x_mask;
-                        tmp[i + 8] -= 1;
-                        tmp[i + 8] -= x >> 4;
-                        tmp[i + 9] += ((x >> 1) - 1) & x_mask;
+                        tmp[i + 8] = tmp[i + 8].wrapping_add(0x20000000 & x_mask);
+                        tmp[i + 8] = tmp[i + 8].wrapping_sub(1);
+                        tmp[i + 8] = tmp[i + 8].wrapping_sub(x >> 4);
+                        tmp[i + 9] = tmp[i + 9].wrapping_add((x >> 1).wrapping_sub(1) & x_mask);
                     } else {
-                        tmp[i + 8] -= 1;
-                        tmp[i + 8] -= x >> 4;
-                        tmp[i + 9] += (x >> 1) & x_mask;
+                        tmp[i + 8] = tmp[i + 8].wrapping_sub(1);
+                        tmp[i + 8] = tmp[i + 8].wrapping_sub(x >> 4);
+                        tmp[i + 9] = tmp[i + 9].wrapping_add((x >> 1) & x_mask);
                     }
                 } else {
-                    tmp[i + 7] -= set7;
-                    tmp[i + 7] -= (x << 24) & (LimbPattern::WIDTH28BITS as u32);
-                    tmp[i + 8] += (x << 28) & (LimbPattern::WIDTH29BITS as u32);
+                    tmp[i + 7] = tmp[i + 7].wrapping_sub(set7);
+                    tmp[i + 7] = tmp[i + 7].wrapping_sub((x << 24) & (LimbPattern::WIDTH28BITS as u32));
+                    tmp[i + 8] = tmp[i + 8].wrapping_add((x << 28) & (LimbPattern::WIDTH29BITS as u32));
                     if tmp[i + 8] < 0x20000000 {
-                        tmp[i + 8] += 0x20000000 & x_mask;
-                        tmp[i + 8] -= x >> 4;
-                        tmp[i + 9] += ((x >> 1) - 1) & x_mask;
+                        tmp[i + 8] = tmp[i + 8].wrapping_add(0x20000000 & x_mask);
+                        tmp[i + 8] = tmp[i + 8].wrapping_sub(x >> 4);
+                        tmp[i + 9] = tmp[i + 9].wrapping_add((x >> 1).wrapping_sub(1) & x_mask);
                     } else {
-                        tmp[i + 8] -= x >> 4;
-                        tmp[i + 9] += (x >> 1) & x_mask;
+                        tmp[i + 8] = tmp[i + 8].wrapping_sub(x >> 4);
+                        tmp[i + 9] = tmp[i + 9].wrapping_add((x >> 1) & x_mask);
                     }
                 }
             }
@@ -542,7 +727,7 @@ impl PayloadHelper {
             if (i + 1) == 9 {
                 break;
             }
-            tmp[i + 2] += tmp[i + 1] >> 28;
+            tmp[i + 2] = tmp[i + 2].wrapping_add(tmp[i + 1] >> 28);
             x = tmp[i + 1] & (LimbPattern::WIDTH28BITS as u32);
             tmp[i + 1] = 0;
 
@@ -550,57 +735,57 @@ impl PayloadHelper {
                 let mut set5 = 0;
                 let mut set8 = 0;
                 let mut set9 = 0;
-                x_mask = mask(x);
-                tmp[i + 3] += (x << 7) & (LimbPattern::WIDTH28BITS as u32);
-                tmp[i + 4] += x >> 21;
+                x_mask = ct_mask(x);
+                tmp[i + 3] = tmp[i + 3].wrapping_add((x << 7) & (LimbPattern::WIDTH28BITS as u32));
+                tmp[i + 4] = tmp[i + 4].wrapping_add(x >> 21);
 
                 if tmp[i + 4] < 0x20000000 {
                     set5 = 1;
-                    tmp[i + 4] += 0x20000000 & x_mask;
-                    tmp[i + 4] -= (x << 11) & (LimbPattern::WIDTH29BITS as u32);
+                    tmp[i + 4] = tmp[i + 4].wrapping_add(0x20000000 & x_mask);
+                    tmp[i + 4] = tmp[i + 4].wrapping_sub((x << 11) & (LimbPattern::WIDTH29BITS as u32));
                 } else {
-                    tmp[i + 4] -= (x << 11) & (LimbPattern::WIDTH29BITS as u32);
+                    tmp[i + 4] = tmp[i + 4].wrapping_sub((x << 11) & (LimbPattern::WIDTH29BITS as u32));
                 }
                 if tmp[i + 5] < 0x10000000 {
-                    tmp[i + 5] += 0x10000000 & x_mask;
-                    tmp[i + 5] -= set5;
-                    tmp[i + 5] -= x >> 18;
+                    tmp[i + 5] = tmp[i + 5].wrapping_add(0x10000000 & x_mask);
+                    tmp[i + 5] = tmp[i + 5].wrapping_sub(set5);
+                    tmp[i + 5] = tmp[i + 5].wrapping_sub(x >> 18);
                     if tmp[i + 6] < 0x20000000 {
-                        tmp[i + 6] += 0x20000000 & x_mask;
-                        tmp[i + 6] -= 1;
+                        tmp[i + 6] = tmp[i + 6].wrapping_add(0x20000000 & x_mask);
+                        tmp[i + 6] = tmp[i + 6].wrapping_sub(1);
                         if tmp[i + 7] < 0x10000000 {
                             set8 = 1;
-                            tmp[i + 7] += 0x10000000 & x_mask;
-                            tmp[i + 7] -= 1;
+                            tmp[i + 7] = tmp[i + 7].wrapping_add(0x10000000 & x_mask);
+                            tmp[i + 7] = tmp[i + 7].wrapping_sub(1);
                         } else {
-                            tmp[i + 7] -= 1;
+                            tmp[i + 7] = tmp[i + 7].wrapping_sub(1);
                         }
                     } else {
-                        tmp[i + 6] -= 1;
+                        tmp[i + 6] = tmp[i + 6].wrapping_sub(1);
                     }
                 } else {
-                    tmp[i + 5] -= set5;
-                    tmp[i + 5] -= x >> 18;
+                    tmp[i + 5] = tmp[i + 5].wrapping_sub(set5);
+                    tmp[i + 5] = tmp[i + 5].wrapping_sub(x >> 18);
                 }
 
                 if tmp[i + 8] < 0x20000000 {
                     set9 = 1;
-                    tmp[i + 8] += 0x20000000 & x_mask;
-                    tmp[i + 8] -= set8;
-                    tmp[i + 8] -= (x << 25) & (LimbPattern::WIDTH29BITS as u32);
+                    tmp[i + 8] = tmp[i + 8].wrapping_add(0x20000000 & x_mask);
+                    tmp[i + 8] = tmp[i + 8].wrapping_sub(set8);
+                    tmp[i + 8] = tmp[i + 8].wrapping_sub((x << 25) & (LimbPattern::WIDTH29BITS as u32));
                 } else {
-                    tmp[i + 8] -= set8;
-                    tmp[i + 8] -= (x << 25) & (LimbPattern::WIDTH29BITS as u32);
+                    tmp[i + 8] = tmp[i + 8].wrapping_sub(set8);
+                    tmp[i + 8] = tmp[i + 8].wrapping_sub((x << 25) & (LimbPattern::WIDTH29BITS as u32));
                 }
                 if tmp[i + 9] < 0x10000000 {
-                    tmp[i + 9] += 0x10000000 & x_mask;
-                    tmp[i + 9] -= set9;
-                    tmp[i + 9] -= x >> 4;
-                    tmp[i + 10] += (x - 1) & x_mask;
+                    tmp[i + 9] = tmp[i + 9].wrapping_add(0x10000000 & x_mask);
+                    tmp[i + 9] = tmp[i + 9].wrapping_sub(set9);
+                    tmp[i + 9] = tmp[i + 9].wrapping_sub(x >> 4);
+                    tmp[i + 10] = tmp[i + 10].wrapping_add((x - 1) & x_mask);
                 } else {
-                    tmp[i + 9] -= set9;
-                    tmp[i + 9] -= x >> 4;
-                    tmp[i + 10] += x & x_mask;
+                    tmp[i + 9] = tmp[i + 9].wrapping_sub(set9);
+                    tmp[i + 9] = tmp[i + 9].wrapping_sub(x >> 4);
+                    tmp[i + 10] = tmp[i + 10].wrapping_add(x & x_mask);
                 }
             }
             i += 2;
@@ -610,14 +795,14 @@ impl PayloadHelper {
         i = 0;
         while i < 8 {
             a.data[i] = tmp[i + 9];
-            a.data[i] += carry;
-            a.data[i] += (tmp[i + 10] << 28) & (LimbPattern::WIDTH29BITS as u32);
+            a.data[i] = a.data[i].wrapping_add(carry);
+            a.data[i] = a.data[i].wrapping_add((tmp[i + 10] << 28) & (LimbPattern::WIDTH29BITS as u32));
             carry = a.data[i] >> 29;
             a.data[i] &= LimbPattern::WIDTH29BITS as u32;
 
             i += 1;
             a.data[i] = tmp[i + 9] >> 1;
-            a.data[i] += carry;
+            a.data[i] = a.data[i].wrapping_add(carry);
             carry = a.data[i] >> 28;
             a.data[i] &= LimbPattern::WIDTH28BITS as u32;
 
@@ -625,7 +810,7 @@ impl PayloadHelper {
         }
 
         a.data[8] = tmp[17];
-        a.data[8] += carry;
+        a.data[8] = a.data[8].wrapping_add(carry);
         carry = a.data[8] >> 29;
         a.data[8] &= LimbPattern::WIDTH29BITS as u32;
 
@@ -636,6 +821,7 @@ impl PayloadHelper {
 
 #[cfg(test)]
 mod tests {
+    use num_bigint::Sign;
     use num_traits::Num;
 
     use super::*;
@@ -653,4 +839,103 @@ mod tests {
         let m = PayloadHelper::restore(&payload);
         assert_eq!(m, n);
     }
+
+    #[test]
+    fn negate() {
+        let p = P256Elliptic::init().ec.p.to_bigint().unwrap();
+        let values = [
+            BigInt::from(0u8),
+            BigInt::from(1u8),
+            BigInt::from_str_radix("bc3736a2f4f6779c59bdcee36b692153d0a9877cc62a474002df32e52139f0a0", 16).unwrap(),
+            &p - BigInt::from(1u8),
+        ];
+        for n in values {
+            let payload = PayloadHelper::transform(&n);
+            let negated = payload.negate();
+            assert_eq!(PayloadHelper::restore(&negated), (-&n).mod_floor(&p));
+            assert_eq!(PayloadHelper::restore(&payload.add(&negated)).mod_floor(&p), BigInt::from(0u8));
+        }
+    }
+
+    #[test]
+    fn bytes() {
+        let p = P256Elliptic::init().ec.p.to_bigint().unwrap();
+        let values = [
+            BigInt::from(0u8),
+            BigInt::from(1u8),
+            BigInt::from_str_radix("32c4ae2c1f1981195f9904466a39c9948fe30bbff2660be1715a4589334c74c7", 16).unwrap(),
+            &p - BigInt::from(1u8),
+        ];
+        for n in values {
+            let mut bytes = [0u8; 32];
+            let be = n.to_bytes_be().1;
+            bytes[32 - be.len()..].copy_from_slice(&be);
+
+            let payload = Payload::from_bytes(&bytes);
+            assert_eq!(PayloadHelper::restore(&payload), n);
+            assert_eq!(payload.to_bytes(), bytes);
+            assert_eq!(PayloadHelper::transform(&n).to_bytes(), bytes);
+            assert_eq!(payload.is_zero(), n == BigInt::from(0u8));
+        }
+
+        // 不小于p的输入按模p约减
+        let mut bytes = [0xFF; 32];
+        let payload = Payload::from_bytes(&bytes);
+        assert_eq!(PayloadHelper::restore(&payload), (BigInt::from(1u8) << 256u32) - 1u8 - &p);
+        bytes.copy_from_slice(&EC_P);
+        assert!(Payload::from_bytes(&bytes).is_zero());
+
+        // 未约减的limb表示
+        let sum = PayloadHelper::transform(&(&p - BigInt::from(1u8))).add(&PayloadHelper::transform(&(&p - BigInt::from(2u8))));
+        assert_eq!(BigInt::from_bytes_be(Sign::Plus, &sum.to_bytes()), &p - BigInt::from(3u8));
+    }
+
+    #[test]
+    fn sqrt() {
+        let p = P256Elliptic::init().ec.p.to_bigint().unwrap();
+        let x = BigInt::from_str_radix("32c4ae2c1f1981195f9904466a39c9948fe30bbff2660be1715a4589334c74c7", 16).unwrap();
+        for n in [BigInt::from(0u8), BigInt::from(4u8), (&x * &x).mod_floor(&p)] {
+            let root = PayloadHelper::transform(&n).sqrt().unwrap();
+            assert_eq!(PayloadHelper::restore(&root.square()), n);
+        }
+        // p ≡ 3 (mod 4)，-1、-9不是二次剩余
+        assert!(PayloadHelper::transform(&(&p - BigInt::from(1u8))).sqrt().is_none());
+        assert!(PayloadHelper::transform(&(&p - BigInt::from(9u8))).sqrt().is_none());
+    }
+
+    #[test]
+    fn zero_mask() {
+        let p = P256Elliptic::init().ec.p.to_bigint().unwrap();
+        let one = PayloadHelper::transform(&BigInt::from(1u8));
+        let minus_one = PayloadHelper::transform(&(&p - BigInt::from(1u8)));
+        assert_eq!(Payload::init().zero_mask(), u32::MAX);
+        assert_eq!(minus_one.add(&one).zero_mask(), u32::MAX);
+        assert_eq!(one.subtract(&one).zero_mask(), u32::MAX);
+        assert_eq!(one.zero_mask(), 0);
+        assert_eq!(minus_one.zero_mask(), 0);
+    }
+
+    #[test]
+    fn invert() {
+        let p = P256Elliptic::init().ec.p.to_bigint().unwrap();
+        let x = BigInt::from_str_radix("32c4ae2c1f1981195f9904466a39c9948fe30bbff2660be1715a4589334c74c7", 16).unwrap();
+        for n in [BigInt::from(1u8), BigInt::from(2u8), x, &p - BigInt::from(1u8)] {
+            let inverse = PayloadHelper::transform(&n).invert();
+            assert_eq!(PayloadHelper::restore(&inverse), n.extended_gcd(&p).x.mod_floor(&p), "n = {:x}", n);
+        }
+        assert!(PayloadHelper::transform(&BigInt::from(0u8)).invert().is_zero());
+    }
+
+    /// 64位平台的乘法与逐limb的乘法得到相同的域元素
+    #[test]
+    fn limb_backends() {
+        let x = BigInt::from_str_radix("32c4ae2c1f1981195f9904466a39c9948fe30bbff2660be1715a4589334c74c7", 16).unwrap();
+        let mut a = PayloadHelper::transform(&x);
+        let mut b = Payload::new(P256RR);
+        for _ in 0..64 {
+            assert_eq!(a.multiply(&b).to_bytes(), a.multiply_limbs(&b).to_bytes());
+            assert_eq!(a.square().to_bytes(), a.square_limbs().to_bytes());
+            (a, b) = (a.add(&b), a.multiply(&b));
+        }
+    }
 }
@@ -1,18 +1,44 @@
-use std::cmp::Ordering;
-use std::ops::{BitAnd, Neg, Shr};
+#[cfg(feature = "bench")]
+use std::cell::Cell;
+use std::fmt::{Debug, Formatter};
 
-use num_bigint::{BigInt, BigUint, Sign, ToBigInt};
-use num_integer::Integer;
-use num_traits::{One, ToPrimitive};
+use num_bigint::BigUint;
 
-use crate::sm2::p256::{mask, P256Elliptic};
-use crate::sm2::p256::params::{BASE_TABLE, P256FACTOR};
+use crate::ct::{ct_eq_mask, ct_mask, ct_select, ct_swap};
+use crate::sm2::hazmat::ScalarBytesLe;
+use crate::sm2::p256::params::{P256A, P256B, P256FACTOR};
 use crate::sm2::p256::payload::{Payload, PayloadHelper};
+use crate::sm2::p256::window::{BASE_ODD_MULTIPLES, BASE_TABLE, COMB_ENTRIES, COMB_SPACING, COMB_TABLE_LEN, NAF_WIDTH, ODD_MULTIPLES, WINDOW};
 
 pub(crate) trait Multiplication {
     fn multiply(&self, scalar: BigUint) -> P256AffinePoint;
 }
 
+#[cfg(feature = "bench")]
+thread_local! {
+    /// 本线程累计的倍点与加法次数
+    static OPS: Cell<(u64, u64)> = const { Cell::new((0, 0)) };
+}
+
+/// 本线程累计的（倍点，加法）次数，只增不减，调用方取前后差值
+#[cfg(feature = "bench")]
+pub(crate) fn op_counts() -> (u64, u64) {
+    OPS.with(|ops| ops.get())
+}
+
+#[cfg(feature = "bench")]
+fn count(doublings: u64, additions: u64) {
+    OPS.with(|ops| {
+        let (d, a) = ops.get();
+        ops.set((d + doublings, a + additions));
+    });
+}
+
+/// 未启用`bench`时不计数，倍点与点加中不访问线程局部变量
+#[cfg(not(feature = "bench"))]
+#[inline(always)]
+fn count(_: u64, _: u64) {}
+
 /// Jacobian coordinates: (x, y, z)  y^2 = x^3 + axz^4 + bz^6
 /// Affine coordinates: (X = x/z^2, Y = y/z^3)  Y^2 = X^3 + aX +b
 #[derive(Clone, Debug)]
@@ -23,129 +49,202 @@ impl P256AffinePoint {
         P256AffinePoint(x, y)
     }
 
+    /// 大端序坐标，不检查是否在曲线上
+    pub(crate) fn from_bytes(x: &[u8; 32], y: &[u8; 32]) -> Self {
+        P256AffinePoint(Payload::from_bytes(x), Payload::from_bytes(y))
+    }
+
+    /// 大端序坐标，不经过BigInt
+    pub(crate) fn to_bytes(&self) -> ([u8; 32], [u8; 32]) {
+        (self.0.to_bytes(), self.1.to_bytes())
+    }
+
     pub(crate) fn restore(&self) -> (BigUint, BigUint) {
         let x = PayloadHelper::restore(&self.0).to_biguint().unwrap();
         let y = PayloadHelper::restore(&self.1).to_biguint().unwrap();
         (x, y)
     }
 
-    /// get the entry of table by index.
-    /// On entry: index < 16, table[0] must be zero.
-    fn select(index: u32, table: Vec<u32>) -> Self {
+    /// get the entry of a comb table by index.
+    /// On entry: index < 2^w, the omitted entry 0 is the point at infinity.
+    fn select(index: u32, table: &[u32]) -> Self {
         let (mut x, mut y) = (Payload::init().data(), Payload::init().data());
-        for i in 1..16 {
-            let mut mask: u32 = i ^ index;
-            mask |= mask >> 2;
-            mask |= mask >> 1;
-            mask &= 1;
-
-            if mask == 0 {
-                mask = u32::MAX;    // 4294967295
-            } else {
-                mask -= 1;
-            }
-
+        for i in 1..=COMB_ENTRIES as u32 {
+            let mask = ct_eq_mask(i, index);
             let offset = ((i - 1) * 18) as usize;
-            for j in 0..9 {
-                x[j] |= table[offset + j] & mask;
-                y[j] |= table[offset + 9 + j] & mask;
-            }
+            x = ct_select(&x, table[offset..offset + 9].try_into().unwrap(), mask);
+            y = ct_select(&y, table[offset + 9..offset + 18].try_into().unwrap(), mask);
         }
         P256AffinePoint(Payload::new(x), Payload::new(y))
     }
 
     pub(crate) fn to_jacobian(&self) -> P256JacobianPoint {
         P256JacobianPoint(
-            self.0.clone(),
-            self.1.clone(),
-            PayloadHelper::transform(&BigInt::one()),
+            self.0,
+            self.1,
+            Payload::new(P256FACTOR[1]),
         )
     }
-}
 
+    /// Montgomery阶梯标量乘法，常量时间，不使用预计算表
+    ///
+    /// 以完备加法公式在射影坐标下计算，从无穷远点开始逐位处理全部256位，
+    /// 每位做一次条件交换、一次加法、一次倍点，两个累加点之差始终为P，不存在例外情形。
+    pub(crate) fn multiply_ladder(&self, scalar: &[u8; 32]) -> P256JacobianPoint {
+        let mut r0 = P256ProjectivePoint::identity();
+        let mut r1 = P256ProjectivePoint(self.0, self.1, Payload::new(P256FACTOR[1]));
+        for i in (0..256).rev() {
+            let bit = 0u32.wrapping_sub(((scalar[31 - i / 8] >> (i % 8)) & 1) as u32);
+            P256ProjectivePoint::conditional_swap(&mut r0, &mut r1, bit);
+            r1 = r0.add_complete(&r1);
+            r0 = r0.double_complete();
+            P256ProjectivePoint::conditional_swap(&mut r0, &mut r1, bit);
+        }
+        r0.to_jacobian()
+    }
 
-impl Multiplication for P256AffinePoint {
-    fn multiply(&self, scalar: BigUint) -> P256AffinePoint {
-        let points = {
-            let mut precomp: [[[u32; 9]; 3]; 16] = [[[0; 9]; 3]; 16];
-
-            precomp[1][0] = self.0.data();
-            precomp[1][1] = self.1.data();
-            precomp[1][2] = P256FACTOR[1];
-
-            let mut i = 2;
-            while i < 8 {
-                let p = P256JacobianPoint(
-                    Payload::new(precomp[i / 2][0]),
-                    Payload::new(precomp[i / 2][1]),
-                    Payload::new(precomp[i / 2][2]),
-                );
-                let temp = p.double();
-                precomp[i][0] = temp.0.data();
-                precomp[i][1] = temp.1.data();
-                precomp[i][2] = temp.2.data();
-
-                let p = P256JacobianPoint(
-                    Payload::new(precomp[i][0]),
-                    Payload::new(precomp[i][1]),
-                    Payload::new(precomp[i][2]),
-                );
-                let temp = p.add_affine(&self);
-                precomp[i + 1][0] = temp.0.data();
-                precomp[i + 1][1] = temp.1.data();
-                precomp[i + 1][2] = temp.2.data();
-
-                i += 2;
+    /// 可变时间的标量乘法，固定4位窗口，不分配内存
+    ///
+    /// 加法次数随标量变化，只用于签名验证等标量公开的场合。
+    pub(crate) fn multiply_vartime(&self, scalar: &[u8; 32]) -> P256JacobianPoint {
+        let table = self.window_table();
+
+        let mut result = P256JacobianPoint(Payload::init(), Payload::init(), Payload::init());
+        for byte in scalar {
+            for nibble in [byte >> 4, byte & 0x0F] {
+                // 跳过高位的零，短标量不做多余的倍点
+                if !result.is_infinity() {
+                    for _ in 0..4 {
+                        result = result.double();
+                    }
+                }
+                if nibble != 0 {
+                    result = result.add_vartime(&table[nibble as usize]);
+                }
             }
-            precomp
-        };
-
-        let scalar = w_naf(scalar);
-        let mut n_is_infinity_mask = u32::MAX;
-        let mut counter = 0u16;
+        }
+        result
+    }
 
-        let mut p1 = P256JacobianPoint(
-            Payload::init(), Payload::init(), Payload::init(),
-        );
-        // let mut p2 = P256JacobianPoint(
-        //     Payload::init(), Payload::init(), Payload::init(),
-        // );
-
-        for i in 0..scalar.len() {
-            if scalar[i] == 0 {
-                counter += 1;
-                continue;
+    /// 多标量乘法 Σ k_i·P_i（Straus），各点共用同一组倍点运算，可变时间
+    ///
+    /// 各点的窗口表批量转换为仿射坐标后以混合加法累加。
+    pub(crate) fn multi_multiply_vartime(terms: &[(P256AffinePoint, [u8; 32])]) -> P256JacobianPoint {
+        let jacobian: Vec<P256JacobianPoint> = terms.iter().flat_map(|(point, _)| point.window_table()).collect();
+        let affine = P256JacobianPoint::to_affine_points(&jacobian);
+        let tables: Vec<&[P256AffinePoint]> = affine.chunks_exact(16).collect();
+
+        let mut result = P256JacobianPoint(Payload::init(), Payload::init(), Payload::init());
+        for i in 0..64 {
+            if !result.is_infinity() {
+                for _ in 0..4 {
+                    result = result.double();
+                }
             }
-            while counter > 0 {
-                p1 = p1.double();
-                counter -= 1;
+            for ((_, scalar), table) in terms.iter().zip(&tables) {
+                let nibble = if i % 2 == 0 { scalar[i / 2] >> 4 } else { scalar[i / 2] & 0x0F };
+                if nibble != 0 {
+                    result = result.add_affine_vartime(&table[nibble as usize]);
+                }
             }
+        }
+        result
+    }
 
-            let idx = (scalar[i].abs()) as u32;
-            p1 = p1.double();
-            let p2 = P256JacobianPoint::select(idx, points);
-
-            let p3 = {
-                if scalar[i] > 0 {
-                    p1.add(&p2)
+    /// 验签所需的 s·G + t·P（Shamir技巧），可变时间，不分配内存
+    ///
+    /// 两个标量按wNAF交错处理，共用同一组倍点运算。G的奇数倍取自预计算表，用混合加法，wNAF宽度为w + 1（默认5位）；
+    /// P的奇数倍表在运行时构造，宽度固定为5位，更宽时构造表的加法抵消了主循环省下的加法。
+    pub(crate) fn double_multiply_vartime(s: &[u8; 32], point: &P256AffinePoint, t: &[u8; 32]) -> P256JacobianPoint {
+        let table = point.odd_multiples();
+        let (s, t) = (signed_window(s, NAF_WIDTH), signed_window(t, 5));
+
+        let mut result = P256JacobianPoint(Payload::init(), Payload::init(), Payload::init());
+        let mut started = false;
+        for i in (0..257).rev() {
+            if started {
+                result = result.double();
+            }
+            if s[i] != 0 {
+                let affine = P256BasePoint::odd_multiple((s[i].unsigned_abs() / 2) as usize);
+                result = if s[i] > 0 {
+                    result.add_affine_vartime(&affine)
                 } else {
-                    p1.subtract(&p2)
-                }
-            };
+                    result.add_affine_vartime(&P256AffinePoint(affine.0, affine.1.negate()))
+                };
+                started = true;
+            }
+            if t[i] != 0 {
+                let jacobian = &table[(t[i].unsigned_abs() / 2) as usize];
+                result = if t[i] > 0 { result.add_vartime(jacobian) } else { result.subtract_vartime(jacobian) };
+                started = true;
+            }
+        }
+        result
+    }
 
-            p1 = p1.copy_from_with_conditional(p2, n_is_infinity_mask);
-            let p_is_finite_mask = mask(idx);
-            let msk = p_is_finite_mask & !(n_is_infinity_mask);
-            p1 = p1.copy_from_with_conditional(p3, msk);
-            n_is_infinity_mask &= !(p_is_finite_mask);
+    /// 奇数倍表 \[P, 3P, 5P, ..., 15P]
+    fn odd_multiples(&self) -> [P256JacobianPoint; 8] {
+        let double = self.to_jacobian().double();
+        let mut table = [self.to_jacobian(); 8];
+        for i in 1..8 {
+            table[i] = table[i - 1].add_vartime(&double);
         }
+        table
+    }
+
+    /// 4位窗口表 \[O, P, 2P, ..., 15P]
+    fn window_table(&self) -> [P256JacobianPoint; 16] {
+        let infinity = P256JacobianPoint(Payload::init(), Payload::init(), Payload::init());
+        let mut table = [infinity; 16];
+        table[1] = self.to_jacobian();
+        for i in 2..16 {
+            table[i] = table[i - 1].add_vartime(&table[1]);
+        }
+        table
+    }
+}
+
 
-        while counter > 0 {
-            p1 = p1.double();
-            counter -= 1;
+impl Multiplication for P256AffinePoint {
+    /// 固定4位窗口的常量时间标量乘法，用于解密d·C1、加密k·P等标量保密的场合
+    ///
+    /// 无论标量取值，均依次处理全部64个窗口，每个窗口做4次倍点、一次遍历全表的选择和一次加法，
+    /// 无穷远点与零窗口的情形以掩码合并，不产生分支。标量小于n时累加点与表项不会相同或互为负元，
+    /// 主循环因此使用不检查这两种情形的加法，省去[`P256JacobianPoint::add`]中的倍点。
+    fn multiply(&self, scalar: BigUint) -> P256AffinePoint {
+        let scalar = match ScalarBytesLe::from_biguint(&scalar) {
+            Some(scalar) => scalar.to_be().0,
+            None => panic!("The scalar must not exceed 32 bytes."),
+        };
+
+        // table[i] = iP，table[0]为全零；表只依赖公开的点
+        let mut table: [[[u32; 9]; 3]; 16] = [[[0; 9]; 3]; 16];
+        let point = self.to_jacobian();
+        let mut entry = point;
+        for item in table.iter_mut().skip(1) {
+            *item = [entry.0.data(), entry.1.data(), entry.2.data()];
+            entry = entry.add(&point);
         }
 
-        p1.to_affine_point()
+        let mut result = P256JacobianPoint(Payload::init(), Payload::init(), Payload::init());
+        let mut n_is_infinity_mask = u32::MAX;
+        for byte in scalar {
+            for nibble in [(byte >> 4) as u32, (byte & 0x0F) as u32] {
+                for _ in 0..4 {
+                    result = result.double();
+                }
+                let point = P256JacobianPoint::select(nibble, table);
+                let sum = result.add_unchecked(&point);
+
+                // 此前全为零窗口时直接取表项，窗口非零时取和
+                result = result.copy_from_with_conditional(point, n_is_infinity_mask);
+                let p_is_finite_mask = ct_mask(nibble);
+                result = result.copy_from_with_conditional(sum, p_is_finite_mask & !n_is_infinity_mask);
+                n_is_infinity_mask &= !p_is_finite_mask;
+            }
+        }
+        result.to_affine_point()
     }
 }
 
@@ -162,71 +261,133 @@ impl P256BasePoint {
     pub(crate) fn new(point: P256AffinePoint, order: BigUint) -> Self {
         P256BasePoint { point, order }
     }
+
+    /// 基点的奇数倍 (2i+1)G，i < 2^(w−1)
+    fn odd_multiple(i: usize) -> P256AffinePoint {
+        debug_assert!(i < ODD_MULTIPLES);
+        let entry = &BASE_ODD_MULTIPLES[i * 18..(i + 1) * 18];
+        P256AffinePoint(Payload::new(entry[..9].try_into().unwrap()), Payload::new(entry[9..].try_into().unwrap()))
+    }
 }
 
 impl Multiplication for P256BasePoint {
-    /// multiply sets P256Point = scalar*G where scalar is a little-endian number.
     fn multiply(&self, scalar: BigUint) -> P256AffinePoint {
-        let scalar = {
-            let mut bytes = [0u8; 32];
-            for (i, v) in scalar.to_bytes_le().iter().enumerate() {
-                bytes[i] = *v;
-            }
-            bytes
-        };
+        match ScalarBytesLe::from_biguint(&scalar) {
+            Some(scalar) => self.multiply_le(&scalar),
+            None => panic!("The scalar must not exceed 32 bytes."),
+        }
+    }
+}
 
-        let mut jacobian = P256JacobianPoint(
-            Payload::init(), Payload::init(), Payload::init(),
-        );
+impl P256BasePoint {
+    /// multiply_le sets P256Point = scalar*G, the comb reads the scalar bit by bit from the
+    /// little-endian bytes, so the byte order is carried by [`ScalarBytesLe`].
+    pub(crate) fn multiply_le(&self, scalar: &ScalarBytesLe) -> P256AffinePoint {
+        Self::comb(scalar).to_affine_point()
+    }
 
-        let mut n_is_infinity_mask = !(0 as u32);   // u32::MAX
-        // The loop adds bits at positions 0, 64, 128 and 192, followed by positions 32, 96, 160
-        // and 224 and does this 32 times.
-        for i in 0..32 {
-            if i != 0 {
-                jacobian = jacobian.double();
-            }
-            let mut offset = 0;
-            let mut j = 0;
-            while j <= 32 {
-                let bit0 = bit_of_scalar(scalar, 31 - i + j);
-                let bit1 = bit_of_scalar(scalar, 95 - i + j);
-                let bit2 = bit_of_scalar(scalar, 159 - i + j);
-                let bit3 = bit_of_scalar(scalar, 223 - i + j);
-                let idx = bit0 | (bit1 << 1) | (bit2 << 2) | (bit3 << 3);
-
-                let affine = P256AffinePoint::select(
-                    idx,
-                    Vec::from(&BASE_TABLE[offset..]),
-                );
-
-                offset += 30 * 9;
-
-                let temp = jacobian.add_affine(&affine);
-                jacobian = jacobian.copy_from_with_conditional(
-                    P256JacobianPoint(
-                        affine.0.clone(),
-                        affine.1.clone(),
-                        Payload::new(P256FACTOR[1]),
-                    ),
-                    n_is_infinity_mask,
-                );
-
-                let p_is_finite_mask = mask(idx);
-                let mask = p_is_finite_mask & !n_is_infinity_mask;
-
-                jacobian = jacobian.copy_from_with_conditional(temp, mask);
-
-                // If p was not zero, then n is now non-zero.
-                n_is_infinity_mask = n_is_infinity_mask & !p_is_finite_mask;
-
-                j += 32;
+    /// 基点乘法的Jacobian结果，不转换为仿射坐标，不分配内存
+    pub(crate) fn comb(scalar: &ScalarBytesLe) -> P256JacobianPoint {
+        comb(&BASE_TABLE, scalar)
+    }
+}
+
+/// 任意点P的梳状预计算表，布局与[`BASE_TABLE`]相同
+///
+/// 两组各2^w − 1个仿射点，默认w = 4时分别由 {P, 2^64P, 2^128P, 2^192P} 与 {2^32P, 2^96P, 2^160P, 2^224P} 组合而成。
+#[derive(Clone)]
+pub(crate) struct P256PointTable(Box<[u32; COMB_TABLE_LEN]>);
+
+impl P256PointTable {
+    /// 构造时做约224次倍点与一次批量求逆，适合同一点反复参与标量乘法的场合
+    pub(crate) fn new(point: &P256AffinePoint) -> Self {
+        // 2^(ih)P, i = 0..2w，h为半个齿距
+        let half = COMB_SPACING / 2;
+        let mut powers = [point.to_jacobian(); 2 * WINDOW];
+        for i in 1..2 * WINDOW {
+            powers[i] = powers[i - 1];
+            for _ in 0..half {
+                powers[i] = powers[i].double();
             }
         }
-        jacobian.to_affine_point()
+
+        let infinity = P256JacobianPoint(Payload::init(), Payload::init(), Payload::init());
+        let sums: Vec<P256JacobianPoint> = (0..2)
+            .flat_map(|half| (1..=COMB_ENTRIES).map(move |index| (half, index)))
+            .map(|(half, index)| {
+                (0..WINDOW)
+                    .filter(|bit| (index >> bit) & 1 == 1)
+                    .fold(infinity, |sum, bit| sum.add_vartime(&powers[2 * bit + half]))
+            })
+            .collect();
+
+        let mut table = Box::new([0u32; COMB_TABLE_LEN]);
+        for (entry, affine) in table.chunks_exact_mut(18).zip(P256JacobianPoint::to_affine_points(&sums)) {
+            entry[..9].copy_from_slice(&affine.0.data());
+            entry[9..].copy_from_slice(&affine.1.data());
+        }
+        P256PointTable(table)
+    }
+
+    /// 与[`P256BasePoint::comb`]相同的梳状乘法
+    pub(crate) fn multiply(&self, scalar: &ScalarBytesLe) -> P256JacobianPoint {
+        comb(&self.0[..], scalar)
+    }
+}
+
+impl Debug for P256PointTable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("P256PointTable").finish_non_exhaustive()
     }
 }
 
+/// 以梳状表计算标量乘法，表的布局见[`BASE_TABLE`]
+fn comb(table: &[u32], scalar: &ScalarBytesLe) -> P256JacobianPoint {
+    let scalar = scalar.0;
+    let half = COMB_SPACING / 2;
+
+    let mut jacobian = P256JacobianPoint(
+        Payload::init(), Payload::init(), Payload::init(),
+    );
+
+    let mut n_is_infinity_mask = !0_u32;   // u32::MAX
+    // With w = 4 the loop adds bits at positions 0, 64, 128 and 192, followed by positions 32, 96, 160
+    // and 224 and does this 32 times. Wider combs use a spacing of 52 or 44 bits and fewer rounds.
+    for i in 0..half {
+        if i != 0 {
+            jacobian = jacobian.double();
+        }
+        for group in 0..2 {
+            let first = group * half + half - 1 - i;
+            let idx = (0..WINDOW).fold(0, |idx, tooth| idx | bit_of_scalar(scalar, first + tooth * COMB_SPACING) << tooth);
+
+            let affine = P256AffinePoint::select(
+                idx,
+                &table[group * COMB_ENTRIES * 18..],
+            );
+
+            let temp = jacobian.add_affine(&affine);
+            jacobian = jacobian.copy_from_with_conditional(
+                P256JacobianPoint(
+                    affine.0,
+                    affine.1,
+                    Payload::new(P256FACTOR[1]),
+                ),
+                n_is_infinity_mask,
+            );
+
+            let p_is_finite_mask = ct_mask(idx);
+            let mask = p_is_finite_mask & !n_is_infinity_mask;
+
+            jacobian = jacobian.copy_from_with_conditional(temp, mask);
+
+            // If p was not zero, then n is now non-zero.
+            n_is_infinity_mask &= !p_is_finite_mask;
+        }
+    }
+    jacobian
+}
+
 /// Jacobian coordinates: (x, y, z)  y^2 = x^3 + axz^4 + bz^6
 #[derive(Copy, Clone, Debug)]
 pub(crate) struct P256JacobianPoint(Payload, Payload, Payload);
@@ -236,7 +397,8 @@ impl P256JacobianPoint {
     /// (x, y, z) => 2 * (x, y, z)
     /// [Formulas](https://www.hyperelliptic.org/EFD/g1p/auto-shortw-jacobian-0.html#doubling-dbl-2009-l)
     fn double(&self) -> Self {
-        let a = PayloadHelper::transform(&P256Elliptic::init().ec.a.to_bigint().unwrap());
+        count(1, 0);
+        let a = Payload::new(P256A);
         let (x, y, z) = (&self.0, &self.1, &self.2);
 
         let (alpha, beta) = (z.square(), y.square());
@@ -252,7 +414,7 @@ impl P256JacobianPoint {
         let rx = gama.square().subtract(&delta).subtract(&delta);
         let ry = delta.subtract(&rx).multiply(&gama).subtract(&t2);
         // rz = (y+z)^2 - z^2 - y^2
-        let rz = y.add(&z).square().subtract(&alpha).subtract(&beta);
+        let rz = y.add(z).square().subtract(&alpha).subtract(&beta);
 
         P256JacobianPoint(rx, ry, rz)
     }
@@ -264,15 +426,16 @@ impl P256JacobianPoint {
     ///
     /// Note that this function does not handle P+P, infinity+P nor P+infinity correctly.
     pub(crate) fn add_affine(&self, affine: &P256AffinePoint) -> Self {
+        count(0, 1);
         let (x1, y1, z1) = (&self.0, &self.1, &self.2);
         let (x2, y2) = (&affine.0, &affine.1);
 
         let z1z1 = z1.square();
-        let temp = z1.add(&z1);
+        let temp = z1.add(z1);
         let u2 = x2.multiply(&z1z1);
         let z1z1z1 = z1.multiply(&z1z1);
         let s2 = y2.multiply(&z1z1z1);
-        let h = u2.subtract(&x1);
+        let h = u2.subtract(x1);
 
         let i = h.add(&h).square();
         let j = h.multiply(&i);
@@ -295,33 +458,33 @@ impl P256JacobianPoint {
         P256JacobianPoint(x_out, y_out, z_out)
     }
 
+    /// 可变时间的混合加法，正确处理无穷远点及两点相同或互为负元的情形
+    fn add_affine_vartime(&self, affine: &P256AffinePoint) -> Self {
+        if self.is_infinity() {
+            return affine.to_jacobian();
+        }
+        let sum = self.add_affine(affine);
+        // h = 0时add_affine的结果无意义，交给完整的加法区分倍点与无穷远点
+        if sum.is_infinity() {
+            return self.add_vartime(&affine.to_jacobian());
+        }
+        sum
+    }
+
     /// sets out=source if mask = 0xffffffff in constant time.
     /// On entry: mask is either 0 or 0xffffffff.
     fn copy_from_with_conditional(&self, source: P256JacobianPoint, mask: u32) -> Self {
-        let (mut x, mut y, mut z) = (
-            Payload::init().data(), Payload::init().data(), Payload::init().data()
-        );
-        for i in 0..9 {
-            x[i] = self.0.data()[i] ^ (mask & (source.0.data()[i] ^ self.0.data()[i]));
-            y[i] = self.1.data()[i] ^ (mask & (source.1.data()[i] ^ self.1.data()[i]));
-            z[i] = self.2.data()[i] ^ (mask & (source.2.data()[i] ^ self.2.data()[i]));
-        }
         P256JacobianPoint(
-            Payload::new(x),
-            Payload::new(y),
-            Payload::new(z),
+            Payload::new(ct_select(&self.0.data(), &source.0.data(), mask)),
+            Payload::new(ct_select(&self.1.data(), &source.1.data(), mask)),
+            Payload::new(ct_select(&self.2.data(), &source.2.data(), mask)),
         )
     }
 
     /// Jacobian coordinates: (x, y, z)  y^2 = x^3 + axz^4 + bz^6
     /// Affine coordinates: (X = x/z^2, Y = y/z^3)  Y^2 = X^3 + aX +b
-    pub(crate) fn to_affine_point(&self) -> P256AffinePoint {
-        let elliptic = P256Elliptic::init();
-        let z = PayloadHelper::restore(&self.2);
-        let p = elliptic.ec.p.to_bigint().unwrap();
-        let zi = z.extended_gcd(&p).x.mod_floor(&p);
-
-        let alpha = PayloadHelper::transform(&zi);
+    pub(crate) fn to_affine_point(self) -> P256AffinePoint {
+        let alpha = self.2.invert();
         let beta = alpha.square();
         let gama = alpha.multiply(&beta);
 
@@ -331,24 +494,47 @@ impl P256JacobianPoint {
         P256AffinePoint(x, y)
     }
 
+    /// 批量转换为仿射坐标（Montgomery技巧），n个点共用一次求逆，另需约3n次乘法
+    ///
+    /// 无穷远点转换为(0, 0)，与[`P256JacobianPoint::to_affine_point`]一致；是否为无穷远点的判断是可变时间的。
+    pub(crate) fn to_affine_points(points: &[Self]) -> Vec<P256AffinePoint> {
+        let one = Payload::new(P256FACTOR[1]);
+        let finite: Vec<bool> = points.iter().map(|point| !point.is_infinity()).collect();
+        let z: Vec<Payload> = points.iter().zip(&finite).map(|(point, finite)| if *finite { point.2 } else { one }).collect();
+
+        // prefix[i] = z_0 · z_1 · ... · z_(i-1)
+        let mut prefix = Vec::with_capacity(z.len());
+        let mut product = one;
+        for zi in &z {
+            prefix.push(product);
+            product = product.multiply(zi);
+        }
+
+        // 从后向前，inverse始终为 (z_0 · ... · z_i)^-1
+        let mut inverse = product.invert();
+        let mut affine = vec![P256AffinePoint(Payload::init(), Payload::init()); points.len()];
+        for i in (0..points.len()).rev() {
+            let alpha = inverse.multiply(&prefix[i]);
+            inverse = inverse.multiply(&z[i]);
+            if finite[i] {
+                let beta = alpha.square();
+                affine[i] = P256AffinePoint(points[i].0.multiply(&beta), points[i].1.multiply(&alpha.multiply(&beta)));
+            }
+        }
+        affine
+    }
+
     /// get the entry of table by index.
     /// On entry: index < 16, table[0] must be zero.
     fn select(index: u32, table: [[[u32; 9]; 3]; 16]) -> Self {
         let (mut x, mut y, mut z) = ([0u32; 9], [0u32; 9], [0u32; 9]);
         // The implicit value at index 0 is all zero.
         // We don't need to perform that iteration of the loop because we already set out_* to zero.
-        for i in 0..16 {
-            let mut mask = i ^ index;
-            mask |= mask >> 2;
-            mask |= mask >> 1;
-            mask &= 1;
-            mask = mask.wrapping_sub(1);
-
-            for j in 0..9 {
-                x[j] |= table[i as usize][0][j] & mask;
-                y[j] |= table[i as usize][1][j] & mask;
-                z[j] |= table[i as usize][2][j] & mask;
-            }
+        for (i, [tx, ty, tz]) in (0..).zip(table.iter()) {
+            let mask = ct_eq_mask(i, index);
+            x = ct_select(&x, tx, mask);
+            y = ct_select(&y, ty, mask);
+            z = ct_select(&z, tz, mask);
         }
 
         let x = Payload::new(x);
@@ -358,21 +544,51 @@ impl P256JacobianPoint {
         P256JacobianPoint(x, y, z)
     }
 
-    /// (x3, y3, z3) = (x1, y1, z1) + (x2, y2, z2)
+    /// (x3, y3, z3) = (x1, y1, z1) + (x2, y2, z2)，常量时间
     ///
-    /// See https://www.hyperelliptic.org/EFD/g1p/auto-shortw-jacobian-0.html#addition-add-2007-bl
-    fn add(&self, other: &P256JacobianPoint) -> Self {
-        let (x1, y1, z1) = (&self.0, &self.1, &self.2);
-        let (x2, y2, z2) = (&other.0, &other.1, &other.2);
+    /// 总是同时计算加法公式与倍点，再按掩码选择：两点相同取倍点，任一点为无穷远点取另一点，
+    /// 互为负元时公式本身得到z3 = 0。没有依赖于点的分支，代价约为一次加法加一次倍点。
+    pub(crate) fn add(&self, other: &P256JacobianPoint) -> Self {
+        let (sum, h, r) = self.add_formula(other);
+        let doubled = self.double();
+        let same = h.zero_mask() & r.zero_mask();
+        let (self_infinity, other_infinity) = (self.2.zero_mask(), other.2.zero_mask());
+        sum.copy_from_with_conditional(doubled, same)
+            .copy_from_with_conditional(*self, other_infinity)
+            .copy_from_with_conditional(*other, self_infinity)
+    }
 
+    /// 可变时间的加法，结果同[`P256JacobianPoint::add`]，只用于点公开的场合
+    ///
+    /// See https://www.hyperelliptic.org/EFD/g1p/auto-shortw-jacobian-0.html#addition-add-2007-bl
+    pub(crate) fn add_vartime(&self, other: &P256JacobianPoint) -> Self {
         // z1 = 0
-        if let Sign::NoSign = PayloadHelper::restore(z1).sign() {
-            return P256JacobianPoint(x2.clone(), y2.clone(), z2.clone());
+        if self.2.is_zero() {
+            return *other;
         }
         // z2 = 0
-        if let Sign::NoSign = PayloadHelper::restore(z2).sign() {
-            return P256JacobianPoint(x1.clone(), y1.clone(), z1.clone());
+        if other.2.is_zero() {
+            return *self;
+        }
+
+        // 两点相同时改用倍点公式；互为负元时h = 0，公式得到z3 = 0即无穷远点
+        let (sum, h, r) = self.add_formula(other);
+        if h.is_zero() && r.is_zero() {
+            return self.double();
         }
+        sum
+    }
+
+    /// 没有分支的加法，不处理无穷远点与两点相同的情形
+    fn add_unchecked(&self, other: &P256JacobianPoint) -> Self {
+        self.add_formula(other).0
+    }
+
+    /// 加法公式，同时返回 h = u2 - u1 与 r = s2 - s1
+    fn add_formula(&self, other: &P256JacobianPoint) -> (Self, Payload, Payload) {
+        count(0, 1);
+        let (x1, y1, z1) = (&self.0, &self.1, &self.2);
+        let (x2, y2, z2) = (&other.0, &other.1, &other.2);
 
         let z12 = z1.square();
         let z22 = z2.square();
@@ -399,90 +615,153 @@ impl P256JacobianPoint {
 
         let x3 = r2.subtract(&h2.multiply(&h)).subtract(&tmp.scalar_multiply(2));
         let y3 = r.multiply(&tmp.subtract(&x3)).subtract(&h3.multiply(&s1));
-        let z3 = {
-            let u1b = PayloadHelper::restore(&u1);
-            let u2b = PayloadHelper::restore(&u2);
-            let s1b = PayloadHelper::restore(&s1);
-            let s2b = PayloadHelper::restore(&s2);
-
-            if Ordering::Equal == u1b.cmp(&u2b) && Ordering::Equal == s1b.cmp(&s2b) {
-                let p = self.double();
-                p.2.multiply(&z2).multiply(&h)
-            } else {
-                z1.multiply(&z2).multiply(&h)
-            }
-        };
+        let z3 = z1.multiply(z2).multiply(&h);
 
-        P256JacobianPoint(x3, y3, z3)
+        (P256JacobianPoint(x3, y3, z3), h, r)
     }
 
-    /// (x3, y3, z3) = (x1, y1, z1) - (x2, y2, z2)
-    fn subtract(&self, other: &P256JacobianPoint) -> Self {
-        let another = P256JacobianPoint(
-            other.0.clone(),
-            PayloadHelper::transform(&PayloadHelper::restore(&other.1).neg()),
-            other.2.clone(),
-        );
-        self.add(&another)
+    /// 无穷远点，z = 0
+    pub(crate) fn infinity() -> Self {
+        P256JacobianPoint(Payload::init(), Payload::init(), Payload::init())
     }
-}
 
+    pub(crate) fn is_infinity(&self) -> bool {
+        self.2.is_zero()
+    }
 
-#[inline(always)]
-fn bit_of_scalar(scalar: [u8; 32], bit: usize) -> u32 {
-    (((scalar[bit >> 3]) >> (bit & 7)) & 1) as u32
+    /// (x, -y, z)
+    pub(crate) fn negate(&self) -> Self {
+        P256JacobianPoint(self.0, self.1.negate(), self.2)
+    }
+
+    /// 仿射横坐标 x/z^2 是否等于给定值，比较 x 与 X·z^2，免去求逆
+    pub(crate) fn x_equals(&self, x: &Payload) -> bool {
+        self.0.to_bytes() == x.multiply(&self.2.square()).to_bytes()
+    }
+
+    /// (x3, y3, z3) = (x1, y1, z1) - (x2, y2, z2) = (x1, y1, z1) + (x2, -y2, z2)，可变时间
+    pub(crate) fn subtract_vartime(&self, other: &P256JacobianPoint) -> Self {
+        self.add_vartime(&other.negate())
+    }
 }
 
-#[inline(always)]
-fn w_naf(scalar: BigUint) -> Vec<i8> {
-    let mut k = scalar;
 
-    let bits = k.bits() as usize;
-    let mut naf: Vec<i8> = vec![0; bits + 1];
+/// 射影坐标：(X, Y, Z)  x = X/Z，y = Y/Z，无穷远点为 (0, 1, 0)
+#[derive(Copy, Clone, Debug)]
+struct P256ProjectivePoint(Payload, Payload, Payload);
 
-    if let Sign::NoSign = k.to_bigint().unwrap().sign() {
-        return naf;
+impl P256ProjectivePoint {
+    fn identity() -> Self {
+        P256ProjectivePoint(Payload::init(), Payload::new(P256FACTOR[1]), Payload::init())
     }
 
-    let mut carry = false;
-    let mut length: usize = 0;
-    let mut pos: usize = 0;
+    /// a = -3 的素数阶曲线上的完备加法，对 P + P、P + O、P + (-P) 同样成立，不含分支
+    ///
+    /// [Renes–Costello–Batina 2016](https://eprint.iacr.org/2015/1060) Algorithm 4
+    fn add_complete(&self, other: &Self) -> Self {
+        count(0, 1);
+        self.complete_formula(other)
+    }
 
-    while pos <= bits {
-        let s = k.clone().shr(pos).bitand(BigUint::from(1u64));
-        if s.to_usize().unwrap() == (carry as usize) {
+    /// 以完备加法公式倍点
+    fn double_complete(&self) -> Self {
+        count(1, 0);
+        self.complete_formula(self)
+    }
+
+    fn complete_formula(&self, other: &Self) -> Self {
+        let b = Payload::new(P256B);
+        let (x1, y1, z1) = (&self.0, &self.1, &self.2);
+        let (x2, y2, z2) = (&other.0, &other.1, &other.2);
+
+        let (xx, yy, zz) = (x1.multiply(x2), y1.multiply(y2), z1.multiply(z2));
+        let xy = x1.add(y1).multiply(&x2.add(y2)).subtract(&xx.add(&yy));
+        let yz = y1.add(z1).multiply(&y2.add(z2)).subtract(&yy.add(&zz));
+        let xz = x1.add(z1).multiply(&x2.add(z2)).subtract(&xx.add(&zz));
+
+        let bzz = xz.subtract(&b.multiply(&zz));
+        let bzz3 = bzz.add(&bzz).add(&bzz);
+        let (yy_m_bzz3, yy_p_bzz3) = (yy.subtract(&bzz3), yy.add(&bzz3));
+        let zz3 = zz.add(&zz).add(&zz);
+        let bxz = b.multiply(&xz).subtract(&zz3.add(&xx));
+        let bxz3 = bxz.add(&bxz).add(&bxz);
+        let xx3_m_zz3 = xx.add(&xx).add(&xx).subtract(&zz3);
+
+        P256ProjectivePoint(
+            yy_p_bzz3.multiply(&xy).subtract(&yz.multiply(&bxz3)),
+            yy_p_bzz3.multiply(&yy_m_bzz3).add(&xx3_m_zz3.multiply(&bxz3)),
+            yy_m_bzz3.multiply(&yz).add(&xy.multiply(&xx3_m_zz3)),
+        )
+    }
+
+    /// mask为全1时交换a与b，为0时不变
+    fn conditional_swap(a: &mut Self, b: &mut Self, mask: u32) {
+        let swap = |u: &mut Payload, v: &mut Payload| {
+            let (mut s, mut t) = (u.data(), v.data());
+            ct_swap(&mut s, &mut t, mask);
+            (*u, *v) = (Payload::new(s), Payload::new(t));
+        };
+        swap(&mut a.0, &mut b.0);
+        swap(&mut a.1, &mut b.1);
+        swap(&mut a.2, &mut b.2);
+    }
+
+    /// (X, Y, Z) => (XZ, YZ^2, Z)，无穷远点对应z = 0
+    fn to_jacobian(self) -> P256JacobianPoint {
+        let (x, y, z) = (&self.0, &self.1, &self.2);
+        P256JacobianPoint(x.multiply(z), y.multiply(&z.square()), self.2)
+    }
+}
+
+/// 大端序标量的`width`位宽wNAF，下标为比特位置；非零位均为奇数，绝对值小于2^(width−1)，相邻非零位至少间隔`width`位
+fn signed_window(scalar: &[u8; 32], width: usize) -> [i8; 257] {
+    let mut words = [0u64; 6];
+    for (i, chunk) in scalar.rchunks(8).enumerate() {
+        words[i] = u64::from_be_bytes(chunk.try_into().unwrap());
+    }
+
+    let (mask, half) = ((1u64 << width) - 1, 1u64 << (width - 1));
+    let mut naf = [0i8; 257];
+    let (mut pos, mut carry) = (0, 0u64);
+    while pos < 257 {
+        let (index, shift) = (pos / 64, pos % 64);
+        let bits = match shift {
+            0 => words[index],
+            _ => (words[index] >> shift) | (words[index + 1] << (64 - shift)),
+        };
+        let window = carry + (bits & mask);
+        if window & 1 == 0 {
             pos += 1;
             continue;
         }
-        k = k.shr(pos);
-        let mask = BigUint::from(15usize);
-        let mut digit: isize = k.clone().bitand(mask).to_isize().unwrap();
-        if carry {
-            digit += 1;
+        if window < half {
+            carry = 0;
+            naf[pos] = window as i8;
+        } else {
+            carry = 1;
+            naf[pos] = (window as i64 - (1 << width)) as i8;
         }
-        carry = (digit & 8) != 0;
-        if carry {
-            digit -= 16;
-        }
-        length += pos;
-        naf[length] = digit as i8;
-        pos = 4usize;
+        pos += width;
     }
+    naf
+}
 
-    if naf.len() > length + 1 {
-        let mut t = vec![0; length + 1];
-        for (d, s) in t.iter_mut().zip(naf[0..(length + 1)].iter()) {
-            *d = *s;
-        }
-        naf = t
+/// 小端序标量的第`bit`位，超出256位时为0；位置是公开的，这一判断不影响常量时间
+#[inline(always)]
+fn bit_of_scalar(scalar: [u8; 32], bit: usize) -> u32 {
+    if bit >= 256 {
+        return 0;
     }
-    naf.reverse();
-    naf
+    (((scalar[bit >> 3]) >> (bit & 7)) & 1) as u32
 }
 
+
 #[cfg(test)]
 mod tests {
-    use num_traits::Num;
+    use num_bigint::ToBigInt;
+    use num_traits::{Num, One, Zero};
+
+    use crate::sm2::p256::P256Elliptic;
 
     use super::*;
 
@@ -500,9 +779,10 @@ mod tests {
         let dy: [u32; 9] = [109858056, 93563162, 762162539, 50265907, 127330792, 104238630, 142585591, 352255388, 504506288];
         let dz: [u32; 9] = [33808385, 18870127, 959285037, 176378705, 331289063, 266887158, 195778472, 241280794, 433045898];
 
-        assert_eq!(dx, point.0.data());
-        assert_eq!(dy, point.1.data());
-        assert_eq!(dz, point.2.data());
+        // limb的取值与乘法的实现有关，比较规范编码
+        assert_eq!(Payload::new(dx).to_bytes(), point.0.to_bytes());
+        assert_eq!(Payload::new(dy).to_bytes(), point.1.to_bytes());
+        assert_eq!(Payload::new(dz).to_bytes(), point.2.to_bytes());
     }
 
     #[test]
@@ -516,8 +796,8 @@ mod tests {
         let y: [u32; 9] = [26049626, 112805900, 275795042, 259495837, 289529507, 146296588, 220416178, 146512122, 266185762];
 
         let p = jacobian.to_affine_point();
-        assert_eq!(p.0.data(), x);
-        assert_eq!(p.1.data(), y);
+        assert_eq!(p.0.to_bytes(), Payload::new(x).to_bytes());
+        assert_eq!(p.1.to_bytes(), Payload::new(y).to_bytes());
     }
 
 
@@ -540,9 +820,9 @@ mod tests {
         );
 
         let p = p1.add_affine(&p2);
-        assert_eq!(p.0.data(), p3.0.data());
-        assert_eq!(p.1.data(), p3.1.data());
-        assert_eq!(p.2.data(), p3.2.data());
+        assert_eq!(p.0.to_bytes(), p3.0.to_bytes());
+        assert_eq!(p.1.to_bytes(), p3.1.to_bytes());
+        assert_eq!(p.2.to_bytes(), p3.2.to_bytes());
     }
 
     #[test]
@@ -580,11 +860,11 @@ mod tests {
             Payload::new([397113909, 50459711, 307720741, 153235104, 7288360, 139155237, 213953529, 206075123, 230363768]),
         );
 
-        let p3 = p1.subtract(&p2);
+        let p3 = p1.subtract_vartime(&p2);
 
-        assert_eq!(p3.0.data(), [295090358, 236992739, 800053525, 147234841, 281370475, 197897281, 305280418, 361835277, 162960459]);
-        assert_eq!(p3.1.data(), [229663282, 133755872, 656501873, 17946166, 228212011, 56920858, 395700549, 125368282, 347100819]);
-        assert_eq!(p3.2.data(), [234698535, 154439292, 363189331, 134307834, 513337116, 113297570, 189927841, 204178274, 333316045]);
+        assert_eq!(p3.0.to_bytes(), Payload::new([295090358, 236992739, 800053525, 147234841, 281370475, 197897281, 305280418, 361835277, 162960459]).to_bytes());
+        assert_eq!(p3.1.to_bytes(), Payload::new([229663282, 133755872, 656501873, 17946166, 228212011, 56920858, 395700549, 125368282, 347100819]).to_bytes());
+        assert_eq!(p3.2.to_bytes(), Payload::new([234698535, 154439292, 363189331, 134307834, 513337116, 113297570, 189927841, 204178274, 333316045]).to_bytes());
     }
 
     #[test]
@@ -604,4 +884,230 @@ mod tests {
         assert_eq!(x.to_biguint().unwrap(), rx);
         assert_eq!(y.to_biguint().unwrap(), ry);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn multiply_arbitrary() {
+        use crate::sm2::ecc::EllipticBuilder;
+
+        let p256 = P256Elliptic::init();
+        let n = p256.ec.n.clone();
+        let d = BigUint::from_str_radix("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e", 16).unwrap();
+        let (px, py) = p256.scalar_base_multiply(d.clone());
+        let point = P256AffinePoint::new(
+            PayloadHelper::transform(&px.to_bigint().unwrap()),
+            PayloadHelper::transform(&py.to_bigint().unwrap()),
+        );
+
+        // k(dG) = (kd mod n)G，覆盖零窗口、全1窗口与较短的标量
+        let scalars = [
+            BigUint::one(),
+            BigUint::from(2u8),
+            BigUint::from(15u8),
+            BigUint::from(16u8),
+            BigUint::from(0x10001u32),
+            BigUint::from_str_radix("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff", 16).unwrap() % &n,
+            BigUint::from_str_radix("59276e27d506861a16680f3ad9c02dccef3cc1fa3cdbe4ce6d54b80deac1bc21", 16).unwrap(),
+            BigUint::from_str_radix("f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0", 16).unwrap() % &n,
+        ];
+        for k in scalars {
+            let expected = p256.scalar_base_multiply((&k * &d) % &n);
+            assert_eq!(point.multiply(k.clone()).restore(), expected, "k = {:x}", k);
+        }
+
+        // (n - 1)P = -P
+        let (x, y) = point.multiply(&n - BigUint::one()).restore();
+        assert_eq!((x, y), (px, &p256.ec.p - py));
+    }
+
+    #[test]
+    fn multiply_vartime() {
+        use crate::sm2::ecc::EllipticBuilder;
+        use crate::sm2::hazmat::ScalarBytesBe;
+        use crate::sm2::key::to_32_bytes;
+
+        let p256 = P256Elliptic::init();
+        let n = p256.ec.n.clone();
+        let d = BigUint::from_str_radix("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e", 16).unwrap();
+        let (px, py) = p256.scalar_base_multiply(d.clone());
+        let point = P256AffinePoint::new(
+            PayloadHelper::transform(&px.to_bigint().unwrap()),
+            PayloadHelper::transform(&py.to_bigint().unwrap()),
+        );
+
+        let scalars = [
+            BigUint::one(),
+            BigUint::from(2u8),
+            BigUint::from(0x10001u32),
+            BigUint::from_str_radix("59276e27d506861a16680f3ad9c02dccef3cc1fa3cdbe4ce6d54b80deac1bc21", 16).unwrap(),
+            &n - BigUint::one(),
+        ];
+        for k in scalars {
+            let bytes = to_32_bytes(k.to_bytes_be());
+            let expected = p256.scalar_base_multiply((&k * &d) % &n);
+            assert_eq!(point.multiply_vartime(&bytes).to_affine_point().restore(), expected, "k = {:x}", k);
+            assert_eq!(P256BasePoint::comb(&ScalarBytesBe(bytes).to_le()).to_affine_point().restore(), p256.scalar_base_multiply(k));
+        }
+        assert!(point.multiply_vartime(&[0u8; 32]).is_infinity());
+        assert!(point.multiply_vartime(&to_32_bytes(n.to_bytes_be())).is_infinity());
+
+        // P + P 按倍点计算，P + (-P) 为无穷远点
+        let p = point.to_jacobian();
+        assert_eq!(p.add(&p).to_affine_point().restore(), p256.scalar_base_multiply(&d * 2u8 % &n));
+        let negative = P256JacobianPoint(p.0, p.1.negate(), p.2);
+        assert!(p.add(&negative).is_infinity());
+        assert!(p.x_equals(&point.0) && !p.x_equals(&point.1));
+    }
+
+    #[test]
+    fn add_exceptional() {
+        use crate::sm2::ecc::EllipticBuilder;
+
+        let p256 = P256Elliptic::init();
+        let (x, y) = p256.ec.gx.to_bigint().zip(p256.ec.gy.to_bigint()).unwrap();
+        let g = P256AffinePoint::new(PayloadHelper::transform(&x), PayloadHelper::transform(&y)).to_jacobian();
+        let g2 = g.double();
+        let g3 = g2.add_vartime(&g);
+        let negative = P256JacobianPoint(g2.0, g2.1.negate(), g2.2);
+        let zero = P256JacobianPoint(Payload::init(), Payload::init(), Payload::init());
+        // z = 0但x、y不为0的无穷远点
+        let infinity = g2.add_unchecked(&negative);
+        assert!(infinity.is_infinity() && !infinity.0.is_zero());
+
+        let cases = [(g, g2), (g2, g2), (g2, negative), (zero, g3), (g3, zero), (infinity, g3), (g3, infinity), (zero, infinity)];
+        for (i, (a, b)) in cases.iter().enumerate() {
+            let (sum, expected) = (a.add(b), a.add_vartime(b));
+            assert_eq!(sum.is_infinity(), expected.is_infinity(), "case {}", i);
+            assert_eq!(sum.to_affine_point().restore(), expected.to_affine_point().restore(), "case {}", i);
+        }
+        assert_eq!(g.add(&g2).to_affine_point().restore(), g3.to_affine_point().restore());
+        assert_eq!(g2.add(&g2).to_affine_point().restore(), p256.scalar_base_multiply(BigUint::from(4u8)));
+    }
+
+    #[test]
+    fn to_affine_points() {
+        let p256 = P256Elliptic::init();
+        let (x, y) = p256.ec.gx.to_bigint().zip(p256.ec.gy.to_bigint()).unwrap();
+        let g = P256AffinePoint::new(PayloadHelper::transform(&x), PayloadHelper::transform(&y));
+        let infinity = P256JacobianPoint(Payload::init(), Payload::init(), Payload::init());
+        let mut points = vec![g.to_jacobian(), infinity];
+        for _ in 0..5 {
+            let next = points[points.len() - 2].double().add(&g.to_jacobian());
+            points.push(next);
+        }
+        points.push(infinity);
+
+        let batch = P256JacobianPoint::to_affine_points(&points);
+        assert_eq!(batch.len(), points.len());
+        for (point, affine) in points.iter().zip(&batch) {
+            assert_eq!(affine.restore(), point.to_affine_point().restore());
+        }
+        assert!(P256JacobianPoint::to_affine_points(&[]).is_empty());
+    }
+
+    #[test]
+    fn multi_multiply_vartime() {
+        use crate::sm2::ecc::EllipticBuilder;
+        use crate::sm2::key::to_32_bytes;
+
+        let p256 = P256Elliptic::init();
+        let n = p256.ec.n.clone();
+        let keys = [BigUint::from(7u8), BigUint::from_str_radix("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e", 16).unwrap()];
+        let scalars = [&n - BigUint::one(), BigUint::from(0xfeedu32)];
+
+        let terms: Vec<(P256AffinePoint, [u8; 32])> = keys.iter().zip(&scalars).map(|(d, k)| {
+            let (x, y) = p256.scalar_base_multiply(d.clone());
+            let point = P256AffinePoint::new(PayloadHelper::transform(&x.to_bigint().unwrap()), PayloadHelper::transform(&y.to_bigint().unwrap()));
+            (point, to_32_bytes(k.to_bytes_be()))
+        }).collect();
+        let expected = (&keys[0] * &scalars[0] + &keys[1] * &scalars[1]) % &n;
+        let sum = P256AffinePoint::multi_multiply_vartime(&terms);
+        assert_eq!(sum.to_affine_point().restore(), p256.scalar_base_multiply(expected));
+        assert!(P256AffinePoint::multi_multiply_vartime(&[]).is_infinity());
+    }
+
+    #[test]
+    fn double_multiply_vartime() {
+        use crate::sm2::ecc::EllipticBuilder;
+        use crate::sm2::key::to_32_bytes;
+
+        let p256 = P256Elliptic::init();
+        let n = p256.ec.n.clone();
+        let d = BigUint::from_str_radix("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e", 16).unwrap();
+        let lift = |d: &BigUint| {
+            let (x, y) = p256.scalar_base_multiply(d.clone());
+            P256AffinePoint::new(PayloadHelper::transform(&x.to_bigint().unwrap()), PayloadHelper::transform(&y.to_bigint().unwrap()))
+        };
+        let bytes = |k: &BigUint| to_32_bytes(k.to_bytes_be());
+
+        let cases = [
+            (BigUint::one(), BigUint::one()),
+            (&n - BigUint::one(), BigUint::from(0xfeedu32)),
+            (BigUint::from_str_radix("59276e27d506861a16680f3ad9c02dccef3cc1fa3cdbe4ce6d54b80deac1bc21", 16).unwrap(), &n - BigUint::from(2u8)),
+            (BigUint::zero(), d.clone()),
+        ];
+        for i in 0..ODD_MULTIPLES {
+            assert_eq!(P256BasePoint::odd_multiple(i).restore(), p256.scalar_base_multiply(BigUint::from(2 * i + 1)));
+        }
+
+        let point = lift(&d);
+        for (s, t) in cases {
+            let expected = p256.scalar_base_multiply((&s + &t * &d) % &n);
+            let sum = P256AffinePoint::double_multiply_vartime(&bytes(&s), &point, &bytes(&t));
+            assert_eq!(sum.to_affine_point().restore(), expected, "s = {:x}, t = {:x}", s, t);
+        }
+
+        // P = G时两组加法会落到同一点上，需按倍点计算；s + t = n时结果为无穷远点
+        let g = lift(&BigUint::one());
+        let (s, t) = (BigUint::from(0x1234u32), BigUint::from(0x1234u32));
+        let sum = P256AffinePoint::double_multiply_vartime(&bytes(&s), &g, &bytes(&t));
+        assert_eq!(sum.to_affine_point().restore(), p256.scalar_base_multiply(&s + &t));
+        assert!(P256AffinePoint::double_multiply_vartime(&bytes(&s), &g, &bytes(&(&n - &s))).is_infinity());
+        assert!(P256AffinePoint::double_multiply_vartime(&[0; 32], &point, &[0; 32]).is_infinity());
+    }
+
+    #[test]
+    fn signed_window() {
+        use num_bigint::BigInt;
+
+        for width in 4..=7 {
+            for hex in ["01", "1f", "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff", "8000000000000000000000000000000000000000000000000000000000000000"] {
+                let k = BigUint::from_str_radix(hex, 16).unwrap();
+                let naf = super::signed_window(&crate::sm2::key::to_32_bytes(k.to_bytes_be()), width);
+                let sum = naf.iter().enumerate().fold(BigInt::zero(), |acc, (i, d)| acc + (BigInt::from(*d) << i));
+                assert_eq!(sum, k.to_bigint().unwrap(), "k = {}, width = {}", hex, width);
+                assert!(naf.iter().all(|d| *d == 0 || (d % 2 != 0 && d.unsigned_abs() < 1 << (width - 1))));
+                let nonzero: Vec<usize> = (0..257).filter(|&i| naf[i] != 0).collect();
+                assert!(nonzero.windows(2).all(|w| w[1] - w[0] >= width));
+            }
+        }
+    }
+
+    #[test]
+    fn point_table() {
+        use crate::sm2::ecc::EllipticBuilder;
+        use crate::sm2::hazmat::ScalarBytesBe;
+        use crate::sm2::key::to_32_bytes;
+
+        let p256 = P256Elliptic::init();
+        let n = p256.ec.n.clone();
+        let d = BigUint::from_str_radix("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e", 16).unwrap();
+        let (x, y) = p256.scalar_base_multiply(d.clone());
+        let point = P256AffinePoint::new(PayloadHelper::transform(&x.to_bigint().unwrap()), PayloadHelper::transform(&y.to_bigint().unwrap()));
+        let table = P256PointTable::new(&point);
+        let generator = P256PointTable::new(&P256AffinePoint::select(1, &BASE_TABLE));
+
+        let scalars = [
+            BigUint::one(),
+            BigUint::from(0x10001u32),
+            BigUint::from_str_radix("59276e27d506861a16680f3ad9c02dccef3cc1fa3cdbe4ce6d54b80deac1bc21", 16).unwrap(),
+            &n - BigUint::one(),
+        ];
+        for k in scalars {
+            let scalar = ScalarBytesBe(to_32_bytes(k.to_bytes_be())).to_le();
+            let expected = p256.scalar_base_multiply((&k * &d) % &n);
+            assert_eq!(table.multiply(&scalar).to_affine_point().restore(), expected, "k = {:x}", k);
+            // 以G构造的表与BASE_TABLE给出相同的结果
+            assert_eq!(generator.multiply(&scalar).to_affine_point().restore(), p256.scalar_base_multiply(k));
+        }
+    }
+}
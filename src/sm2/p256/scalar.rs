@@ -0,0 +1,181 @@
+use num_bigint::BigUint;
+
+use crate::ct::ct_select;
+use crate::sm2::key::to_32_bytes;
+
+/// 阶n，64位小端limb
+const N: [u64; 4] = [0x53BBF40939D54123, 0x7203DF6B21C6052B, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFEFFFFFFFF];
+/// -n^-1 mod 2^64
+const N_PRIME: u64 = 0x327F9E8872350975;
+/// R^2 mod n，R = 2^256
+const R2: [u64; 4] = [0x901192AF7C114F20, 0x3464504ADE6FA2FA, 0x620FC84C3AFFE0D4, 0x1EB5E412A22B3D3B];
+/// n - 2，费马小定理求逆的指数
+const N_MINUS_2: [u64; 4] = [0x53BBF40939D54121, 0x7203DF6B21C6052B, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFEFFFFFFFF];
+
+/// 模n的标量，4个64位小端limb，取值范围\[0, n)
+///
+/// 加、减、乘、求逆的执行路径与标量的值无关，用于签名中涉及私钥的运算。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Scalar([u64; 4]);
+
+impl Scalar {
+    pub(crate) const ZERO: Scalar = Scalar([0, 0, 0, 0]);
+    pub(crate) const ONE: Scalar = Scalar([1, 0, 0, 0]);
+
+    /// 大端字节串，不小于n时减去n
+    pub(crate) fn from_bytes_be(bytes: &[u8; 32]) -> Self {
+        let mut limbs = [0u64; 4];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            *limb = u64::from_be_bytes(bytes[24 - i * 8..32 - i * 8].try_into().unwrap());
+        }
+        Scalar(reduce(limbs, 0))
+    }
+
+    pub(crate) fn from_biguint(n: &BigUint) -> Self {
+        Self::from_bytes_be(&to_32_bytes(n.to_bytes_be()))
+    }
+
+    pub(crate) fn to_bytes_be(self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (i, limb) in self.0.iter().enumerate() {
+            bytes[24 - i * 8..32 - i * 8].copy_from_slice(&limb.to_be_bytes());
+        }
+        bytes
+    }
+
+    pub(crate) fn to_biguint(self) -> BigUint {
+        BigUint::from_bytes_be(&self.to_bytes_be())
+    }
+
+    pub(crate) fn add(&self, other: &Scalar) -> Scalar {
+        let mut sum = [0u64; 4];
+        let mut carry = 0u64;
+        for (s, (a, b)) in sum.iter_mut().zip(self.0.iter().zip(other.0.iter())) {
+            let (x, c1) = a.overflowing_add(*b);
+            let (x, c2) = x.overflowing_add(carry);
+            *s = x;
+            carry = (c1 | c2) as u64;
+        }
+        Scalar(reduce(sum, carry))
+    }
+
+    pub(crate) fn subtract(&self, other: &Scalar) -> Scalar {
+        let (diff, borrow) = sub(self.0, other.0);
+        // 借位时加回n
+        let mask = 0u64.wrapping_sub(borrow);
+        let mut result = [0u64; 4];
+        let mut carry = 0u64;
+        for i in 0..4 {
+            let (s, c1) = diff[i].overflowing_add(N[i] & mask);
+            let (s, c2) = s.overflowing_add(carry);
+            result[i] = s;
+            carry = (c1 | c2) as u64;
+        }
+        Scalar(result)
+    }
+
+    pub(crate) fn multiply(&self, other: &Scalar) -> Scalar {
+        Scalar(montgomery_multiply(&montgomery_multiply(&self.0, &other.0), &R2))
+    }
+
+    /// 求逆 a^(n-2) mod n，0的逆元记为0
+    ///
+    /// 指数n-2是公开常量，平方-乘的分支只依赖指数，与a无关。
+    pub(crate) fn invert(&self) -> Scalar {
+        let a = montgomery_multiply(&self.0, &R2);
+        let mut result = montgomery_multiply(&Self::ONE.0, &R2);
+        for i in (0..256).rev() {
+            result = montgomery_multiply(&result, &result);
+            if (N_MINUS_2[i / 64] >> (i % 64)) & 1 == 1 {
+                result = montgomery_multiply(&result, &a);
+            }
+        }
+        Scalar(montgomery_multiply(&result, &Self::ONE.0))
+    }
+}
+
+/// Montgomery乘法 a·b·R^-1 mod n（CIOS）
+fn montgomery_multiply(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+    let mut t = [0u64; 6];
+    for bi in b {
+        let mut carry = 0u128;
+        for j in 0..4 {
+            let uv = t[j] as u128 + a[j] as u128 * *bi as u128 + carry;
+            t[j] = uv as u64;
+            carry = uv >> 64;
+        }
+        let uv = t[4] as u128 + carry;
+        t[4] = uv as u64;
+        t[5] = (uv >> 64) as u64;
+
+        let m = t[0].wrapping_mul(N_PRIME);
+        let mut carry = (t[0] as u128 + m as u128 * N[0] as u128) >> 64;
+        for j in 1..4 {
+            let uv = t[j] as u128 + m as u128 * N[j] as u128 + carry;
+            t[j - 1] = uv as u64;
+            carry = uv >> 64;
+        }
+        let uv = t[4] as u128 + carry;
+        t[3] = uv as u64;
+        t[4] = t[5] + (uv >> 64) as u64;
+    }
+    reduce([t[0], t[1], t[2], t[3]], t[4])
+}
+
+/// carry·2^256 + a < 2n 时，结果不小于n则减去n
+fn reduce(a: [u64; 4], carry: u64) -> [u64; 4] {
+    let (diff, borrow) = sub(a, N);
+    // carry为1或无借位时取差值
+    ct_select(&a, &diff, 0u64.wrapping_sub(carry | (borrow ^ 1)))
+}
+
+fn sub(a: [u64; 4], b: [u64; 4]) -> ([u64; 4], u64) {
+    let mut diff = [0u64; 4];
+    let mut borrow = 0u64;
+    for i in 0..4 {
+        let (d, b1) = a[i].overflowing_sub(b[i]);
+        let (d, b2) = d.overflowing_sub(borrow);
+        diff[i] = d;
+        borrow = (b1 | b2) as u64;
+    }
+    (diff, borrow)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use num_traits::Num;
+
+    use crate::sm2::p256::params::EC_N;
+
+    use super::*;
+
+    #[test]
+    fn main() {
+        let n = BigUint::from_bytes_be(&EC_N);
+        let values = [
+            BigUint::from(1u8),
+            BigUint::from(2u8),
+            &n - 1u8,
+            BigUint::from_str_radix("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e", 16).unwrap(),
+            BigUint::from_str_radix("fffffffeffffffffffffffffffffffff7203df6b21c6052b53bbf40939d5411f", 16).unwrap(),
+        ];
+        for a in &values {
+            let x = Scalar::from_biguint(a);
+            assert_eq!(x.to_biguint(), *a);
+            assert_eq!(x.invert().to_biguint(), a.modpow(&(&n - 2u8), &n));
+            assert_eq!(x.multiply(&x.invert()), Scalar::ONE);
+            for b in &values {
+                let y = Scalar::from_biguint(b);
+                assert_eq!(x.add(&y).to_biguint(), (a + b) % &n);
+                assert_eq!(x.subtract(&y).to_biguint(), (a + &n - b) % &n);
+                assert_eq!(x.multiply(&y).to_biguint(), (a * b) % &n);
+            }
+        }
+
+        // 不小于n的输入在转换时约减
+        assert_eq!(Scalar::from_biguint(&n), Scalar::from_biguint(&BigUint::from(0u8)));
+        assert_eq!(Scalar::from_biguint(&(&n + 5u8)).to_biguint(), BigUint::from(5u8));
+        assert_eq!(Scalar::from_bytes_be(&[0; 32]).invert().to_biguint(), BigUint::from(0u8));
+    }
+}
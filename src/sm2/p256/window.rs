@@ -0,0 +1,525 @@
+//! 标量乘法的窗口宽度w，由`window-5`、`window-6`特性在编译时选择，默认为4，同时启用时取较大者
+//!
+//! w决定两处的时间与空间：
+//! * 基点与[`P256PointTable`](crate::sm2::p256::point::P256PointTable)的梳状乘法有w个齿，两组各2^w − 1个仿射点，
+//!   加法次数约为256/w，但每次常量时间的查表要遍历整组
+//! * 验签s·G + t·P中G一侧的wNAF宽度为w + 1，取预计算的2^(w−1)个奇数倍
+//!
+//! 任意点的常量时间乘法与P一侧的wNAF需要在运行时构造表，窗口加宽后构造表的加法多于主循环省下的加法，
+//! 因此分别固定为4位与5位。SM2曲线的a = −3，没有可用于GLV分解的高效自同态。
+//!
+//! w = 4时使用[`params`](crate::sm2::p256::params)中的表，w = 5、6的表在本模块中，只编译所选的一组。
+
+/// 窗口宽度
+pub(crate) const WINDOW: usize = if cfg!(feature = "window-6") {
+    6
+} else if cfg!(feature = "window-5") {
+    5
+} else {
+    4
+};
+/// 梳状表每组的表项数，不含无穷远点
+pub(crate) const COMB_ENTRIES: usize = (1 << WINDOW) - 1;
+/// 相邻齿在标量中的间距，取偶数以分成错开半个间距的两组：w = 4、5、6时为64、52、44
+pub(crate) const COMB_SPACING: usize = (256 + 2 * WINDOW - 1) / (2 * WINDOW) * 2;
+/// 梳状表的u32个数，两组表项依次排列，每项为仿射坐标 (x, y) 的limb
+pub(crate) const COMB_TABLE_LEN: usize = COMB_ENTRIES * 2 * 9 * 2;
+/// 可变时间乘法的wNAF宽度
+pub(crate) const NAF_WIDTH: usize = WINDOW + 1;
+/// 基点奇数倍表的项数，即G, 3G, ..., (2^w − 1)G
+pub(crate) const ODD_MULTIPLES: usize = 1 << (WINDOW - 1);
+
+#[cfg(not(any(feature = "window-5", feature = "window-6")))]
+pub(crate) use crate::sm2::p256::params::{BASE_ODD_MULTIPLES, BASE_TABLE};
+
+/// 5齿梳状表，两组的齿分别为 2^(52b)G 与 2^(52b + 26)G，b = 0..5，布局同[`params::BASE_TABLE`](crate::sm2::p256::params::BASE_TABLE)
+#[cfg(all(feature = "window-5", not(feature = "window-6")))]
+pub(crate) static BASE_TABLE: [u32; COMB_TABLE_LEN] = [
+    0x0830053D, 0x0328990F, 0x06C04FE1, 0x0C0F72E5, 0x01E19F3C, 0x0666B093, 0x0175A87B, 0x0EC38276, 0x0222CF4B,
+    0x185A1BBA, 0x0354E593, 0x1295FAC1, 0x0F2BC469, 0x047C60FA, 0x0C19B8A9, 0x0F63533E, 0x0903AE6B, 0x0C79ACBA,
+    0x0A75B7EE, 0x0E5C43B1, 0x151FECBF, 0x0376FC01, 0x0F3003D3, 0x08D4EC42, 0x188F6010, 0x0D506582, 0x09B92E2E,
+    0x03B63CB9, 0x0FEC75FB, 0x1B98FF83, 0x016D390A, 0x0E7FB550, 0x023D3EA8, 0x0997E7A0, 0x0C4C8254, 0x02154517,
+    0x13C7B252, 0x0F0308D0, 0x1A0F2311, 0x0B9BE9FF, 0x1C2A6826, 0x0FF44FD3, 0x0854CEEF, 0x0017BAAF, 0x096962E5,
+    0x00DFB96F, 0x0D8E5165, 0x0EB3721B, 0x0BD10A32, 0x1CC71C24, 0x07D7D7CA, 0x18E911C9, 0x0FDAE748, 0x07BE9D06,
+    0x12335FFD, 0x0372D2D4, 0x09BD15C0, 0x01752100, 0x175896A5, 0x0FE30698, 0x09123C79, 0x0BB0F422, 0x09C106E4,
+    0x0FD75BA6, 0x074261BE, 0x1B93E9E8, 0x0E0AB02B, 0x137FBE52, 0x0797F8E7, 0x10FA2CE0, 0x0FA6102B, 0x05A2D665,
+    0x183C094F, 0x088D8EF8, 0x1F027485, 0x047AF162, 0x09714B70, 0x06A09A5D, 0x0EDA1AA1, 0x026AC752, 0x00AC5175,
+    0x0652E50E, 0x0E5E38A5, 0x0CACD926, 0x0F4DE9D2, 0x0601CE8F, 0x0015B424, 0x015CF55D, 0x0DDE7871, 0x01B0465E,
+    0x1B126317, 0x0BC88953, 0x1111B4A3, 0x0F9FCAF2, 0x17F80260, 0x0B65F8C4, 0x070E1A6F, 0x07C0FE64, 0x05431343,
+    0x09644A27, 0x08E068C2, 0x1E40251F, 0x0D07F668, 0x035C72D1, 0x09E8DA98, 0x02F24824, 0x049FD6F8, 0x073C9C85,
+    0x1374C00C, 0x039C6C1F, 0x1BC4BFD3, 0x0AA4A6DF, 0x0A131863, 0x06A17B76, 0x021D5EDD, 0x03783EDD, 0x0EC79A26,
+    0x16BBE90E, 0x0FA4C4DE, 0x1B951E99, 0x06569677, 0x0B077AD3, 0x0BC95FAE, 0x1DB62AAD, 0x0C0B2BAF, 0x0976897F,
+    0x144D43A9, 0x033BD2E5, 0x02D78C24, 0x05D8F28E, 0x128A88A4, 0x06799040, 0x0112AB90, 0x03CC9127, 0x0A077327,
+    0x0C353232, 0x01D58B1D, 0x0EF3D4E2, 0x0C61B250, 0x006E8BB0, 0x0835BFA3, 0x1D494640, 0x0C0170F4, 0x091B06FA,
+    0x13CF5472, 0x00C2FD1B, 0x02FBE4AB, 0x001D62DC, 0x1B5693E7, 0x008C21C8, 0x154E0170, 0x09AB3816, 0x0685AF3A,
+    0x0E4664FF, 0x0B784D94, 0x01426CCC, 0x0D07EB71, 0x069F65B1, 0x0C6CD6CB, 0x009026E9, 0x0AAE2513, 0x06EAADC4,
+    0x0B6975A5, 0x030EC1E2, 0x0881607E, 0x0CE8E3D8, 0x037E1E81, 0x0B37F6F5, 0x10E4A6A8, 0x0FD47E94, 0x04DF3BC0,
+    0x0695F9F7, 0x089E38F3, 0x01835AD4, 0x02A1448A, 0x081BC009, 0x0069F26A, 0x0B841325, 0x0F83FB04, 0x0A6425E8,
+    0x1E993E94, 0x00DDAEE1, 0x0F95B39A, 0x05B9ADE3, 0x18D38EED, 0x0C2AE432, 0x0DD117E0, 0x067FA4F6, 0x046C61CF,
+    0x0D8106B0, 0x00035E7F, 0x07418C4B, 0x0AB1AAF9, 0x1E4C6549, 0x0AAEFCFD, 0x0EC53E0C, 0x01618059, 0x0259B7AA,
+    0x0D73C0EE, 0x06016C26, 0x0493A441, 0x06B93742, 0x14C0B709, 0x09B318B5, 0x1806F302, 0x02C74C93, 0x0F497469,
+    0x19685765, 0x08EF33C1, 0x13EECA01, 0x0A746C56, 0x1E5800B6, 0x0C138226, 0x1CBB1801, 0x0A07328D, 0x06B509CE,
+    0x1E64DA4E, 0x09A555AB, 0x1215374C, 0x0D2E93B2, 0x1A7418EB, 0x04099C5E, 0x1533A05F, 0x0F7FAF1D, 0x0193DB5A,
+    0x1E5B46A3, 0x03C551FE, 0x11E7F08E, 0x0986447D, 0x1B37BDEB, 0x0127859B, 0x066DC412, 0x04707FDB, 0x07E64B0B,
+    0x1430C50B, 0x0A62B5F1, 0x0714C467, 0x072E6B8D, 0x192A5B35, 0x0DA0978C, 0x1FE309DB, 0x0D645BC3, 0x01CFBBF1,
+    0x032D8FDA, 0x0C02EFCA, 0x1A1CF4BB, 0x0BBD0D7C, 0x1A0BDF5C, 0x0FB41F6F, 0x1C85E64B, 0x0EFA73B6, 0x04A873E2,
+    0x1D33ED29, 0x0A3DFF05, 0x06FE524D, 0x0DC113C2, 0x197749D6, 0x08D718DE, 0x04918D8A, 0x0B9110FD, 0x0238DE08,
+    0x1BB1720E, 0x0EDA91A1, 0x09AD8CA8, 0x06B3306A, 0x01105082, 0x0BE18007, 0x199A407B, 0x0AE07783, 0x060DC782,
+    0x0F50DC30, 0x0DE99791, 0x02911122, 0x01190DF0, 0x0D0BF156, 0x0E5CD731, 0x13EFF4D0, 0x0EA4BC27, 0x0F5268C3,
+    0x0245ADE2, 0x033542C5, 0x1982C7D4, 0x06CEF419, 0x0693A0E9, 0x0A6A3B09, 0x163A7C7C, 0x07E7733A, 0x095C4A3E,
+    0x05326E14, 0x0606B2EB, 0x0376FB18, 0x03EF2F68, 0x143D763B, 0x056494F9, 0x0507EFB7, 0x03CE4402, 0x0F6C97E6,
+    0x148A4282, 0x0C41B729, 0x19D29647, 0x0C2CD504, 0x0DB30F4D, 0x054E5547, 0x1383AC31, 0x00BC3928, 0x07EEE988,
+    0x0DF8DFBB, 0x071DB6C9, 0x1E71ECF4, 0x02DB6623, 0x1D64DF50, 0x0CD73C68, 0x02582D0E, 0x0BD08CD6, 0x02EA447B,
+    0x1C88F59E, 0x09124CF1, 0x1DFE113E, 0x0BD3F0D9, 0x11616966, 0x0E112AF0, 0x1612EE89, 0x042383B5, 0x01B3151E,
+    0x02C012AF, 0x07FB1790, 0x15AC274B, 0x05E1FEB1, 0x1A1AA00B, 0x09B87EB3, 0x1B132AB5, 0x032376CA, 0x059A70C3,
+    0x1CE618BB, 0x0CDAD5B3, 0x0D7AC38B, 0x0AAB87D8, 0x06B58FC3, 0x029728D1, 0x1DC3D5AB, 0x0A739739, 0x06E567DB,
+    0x0FCC5823, 0x0F5D589A, 0x0C7A1709, 0x0DDD8FCA, 0x08E23F50, 0x0625EFC5, 0x044F60F0, 0x0E7D9BEA, 0x090E1D6A,
+    0x10EFC650, 0x0DAAF801, 0x12532933, 0x0A6605F7, 0x0569B0BB, 0x030EDD93, 0x0E0609DB, 0x0119702A, 0x07E3D273,
+    0x00134FDF, 0x0EC5BFF8, 0x0C8431CC, 0x04422466, 0x09DA3D69, 0x02D842E8, 0x0DE0131C, 0x012E3525, 0x05183FEE,
+    0x1F380CC6, 0x0432392C, 0x12A43689, 0x0622B572, 0x1E3FD533, 0x0AC7F633, 0x1986E0CE, 0x0AFEBE8A, 0x0D43A509,
+    0x0C1BE51F, 0x056235DA, 0x03BD45CA, 0x096C49C1, 0x1036F679, 0x0BC4D37E, 0x0FEFAE20, 0x0C11B5A0, 0x0ED665D5,
+    0x10CDDAC4, 0x060B1DA5, 0x015AE829, 0x0A9360E8, 0x03B6F441, 0x05D1CE59, 0x0239E6C6, 0x0401EC77, 0x0F64C130,
+    0x0CB3E018, 0x076D2D18, 0x0C8E216A, 0x0AC47BB8, 0x1B4CDA02, 0x08724184, 0x1FABE424, 0x07FC50A0, 0x02E0D15F,
+    0x0C6C74A8, 0x021F315E, 0x0D586AED, 0x046B18B5, 0x15F83FE5, 0x060AC41C, 0x0BC2EB1C, 0x00F96CDD, 0x0955F77F,
+    0x01E2CAED, 0x0F6707FF, 0x053DF5BC, 0x0C4F2426, 0x1D78BF5C, 0x068D821C, 0x1A882227, 0x01D462ED, 0x09D007C3,
+    0x0E1578C6, 0x00D3E9B9, 0x00B7709D, 0x0D2A3F46, 0x02F06227, 0x0EC50F59, 0x0A8DDFE4, 0x06898B3F, 0x051800F9,
+    0x0879F5BF, 0x008D9401, 0x0ED4443A, 0x0EFA5F93, 0x1600064E, 0x05DF61D5, 0x1E4F6C4B, 0x014A5A96, 0x064AA82B,
+    0x09CC9DA4, 0x075CD295, 0x08B381BD, 0x03BA85D7, 0x1D9197EA, 0x0CE49578, 0x14DC2D02, 0x0B658758, 0x0A248514,
+    0x1DFF649D, 0x0549E408, 0x1A9F4CD5, 0x0DEBBA7E, 0x05A6CFE8, 0x03A1311B, 0x02BC06D6, 0x0FDB6945, 0x03FF6CFE,
+    0x07DE56EE, 0x0902368C, 0x15D56D77, 0x0428FA63, 0x113E01A4, 0x0DCFA88B, 0x131B97AB, 0x034E62F1, 0x067D4FD1,
+    0x035ADF6A, 0x0421EE73, 0x0B4A5910, 0x0C1E62F4, 0x05C65625, 0x0C66B3B7, 0x1C1A81E1, 0x09733AE2, 0x06626B92,
+    0x0BE778D2, 0x077233DE, 0x016C7C39, 0x01D502EF, 0x0136A676, 0x0B695CC2, 0x007D7D40, 0x0EA1DA38, 0x01D5F894,
+    0x1EDB194B, 0x0AD5E92E, 0x06469CC9, 0x00618480, 0x150C9B82, 0x0DAEA32C, 0x15C345CE, 0x09C70738, 0x06A2B196,
+    0x1719A842, 0x017A2DAA, 0x1ECD2D4C, 0x0A321D99, 0x1FA4193D, 0x077B5927, 0x114E0A13, 0x0C894EBA, 0x032B3CEF,
+    0x14D6C2DA, 0x00B13201, 0x1004719C, 0x0A7F80E7, 0x1C79BC79, 0x0A1364C7, 0x04922A2A, 0x00D62002, 0x00930EE7,
+    0x0C255C8D, 0x0833997B, 0x14160F0C, 0x0EDBC50F, 0x021B9AB0, 0x0832FBFC, 0x137C19A9, 0x01C00DFE, 0x01BC4768,
+    0x039A2D5B, 0x0ED4112E, 0x006E3961, 0x065E250E, 0x187EA3C1, 0x0DE8FB52, 0x10D84435, 0x0A9C2F63, 0x0EC0CF6D,
+    0x174CE598, 0x0013761B, 0x14910AAC, 0x08DEC43C, 0x1EAC1D21, 0x04F3B9BE, 0x08796D3D, 0x022461E7, 0x0E544E54,
+    0x1FA3C3B3, 0x082CD1ED, 0x0AA6C0F7, 0x0E667146, 0x182AA2F0, 0x05ACF8B7, 0x1042B599, 0x09C9CAFE, 0x0EEE1050,
+    0x0745F565, 0x047322A8, 0x12250405, 0x00255A23, 0x12B0B947, 0x0EDE5271, 0x163BD0D2, 0x0DADB259, 0x0116028C,
+    0x11E8329B, 0x0A1DE973, 0x196B637F, 0x039355DC, 0x09D399E9, 0x0B0C3481, 0x031E1F03, 0x08DD9AB8, 0x0288FD39,
+    0x108EE781, 0x07F121C2, 0x1829A087, 0x013F280A, 0x1845A4B2, 0x0E1A537B, 0x1E12C465, 0x0E382B4E, 0x03CF653C,
+    0x1CC23280, 0x0CEE5AE3, 0x0090EC40, 0x0CF54855, 0x17212716, 0x0FBC6E49, 0x08DCF459, 0x0EBAA225, 0x0FFFE6DB,
+    0x15FF47F7, 0x0587745C, 0x083503A5, 0x0BFA7A2D, 0x1F951127, 0x0060ED1E, 0x11973BFC, 0x08D9F56B, 0x0DB37F4E,
+    0x0BCF3975, 0x0CBCFA80, 0x05AEED81, 0x03280596, 0x082C41C7, 0x0B7CD7FF, 0x0A147695, 0x0EE017C0, 0x0FC02E42,
+    0x0E715354, 0x047339B8, 0x08FF7219, 0x0F3208A8, 0x01DDF75D, 0x03BA4653, 0x08E3647B, 0x0026064F, 0x00ECAFF4,
+    0x00FDD9B3, 0x0DAE1B82, 0x07B47C39, 0x086B2360, 0x096D69FA, 0x0424D90A, 0x16C0AD36, 0x08F86B2E, 0x02334DBE,
+    0x0C2C8A5C, 0x01ED4AAB, 0x1C6ABBB4, 0x044E6AE7, 0x1812F2C3, 0x0767CE26, 0x197AB0C9, 0x0D1E4ABB, 0x08B9D63C,
+    0x1AD0DDB1, 0x0A12C114, 0x0F4F37B0, 0x0E138D65, 0x1CCCEACD, 0x07230F8A, 0x00133F92, 0x0CDB29B4, 0x0BACFDDC,
+    0x1AAFAACF, 0x0EECB0BE, 0x1757CF59, 0x05347242, 0x132155BC, 0x06B6B385, 0x1C0A593A, 0x031B4F98, 0x0D3A1A21,
+    0x08CD2C24, 0x0E0E1274, 0x19A9358E, 0x05531552, 0x0175125A, 0x0A2FA281, 0x0519A419, 0x099CD378, 0x0C63B4BE,
+    0x03341095, 0x0BC2F9DB, 0x151F23C8, 0x00802A70, 0x1F7B6CEE, 0x029C7472, 0x1BD27608, 0x0AAFC88A, 0x075689D3,
+    0x08B672AE, 0x038B1B48, 0x0536E6B3, 0x0FAF7C64, 0x07161845, 0x01E3CA6D, 0x18C3E5D7, 0x07F63303, 0x06EF6005,
+    0x1B402C8B, 0x09C88284, 0x01571E35, 0x022AC0E1, 0x1CA36D20, 0x03243CB0, 0x1033FCB0, 0x063C5077, 0x0556ACAF,
+    0x1367FDA6, 0x012A5620, 0x19966A46, 0x060BD1AB, 0x04B8F94B, 0x0DBC1E58, 0x01684928, 0x08701894, 0x0DA07642,
+    0x01E3E4D1, 0x00746E27, 0x01AEA44B, 0x0A40009A, 0x0CE6664D, 0x0B55B4BD, 0x1746CD98, 0x0CA96342, 0x04D8CB65,
+    0x0032AED6, 0x0C327B6C, 0x0508720F, 0x00838054, 0x16AC5738, 0x024424DC, 0x04E6382A, 0x01D16089, 0x07697E57,
+    0x0B8262A2, 0x0F295D6F, 0x1B83FE8B, 0x0835B6EC, 0x01BB0ECF, 0x024761F3, 0x16730F24, 0x0E057922, 0x04BD82C2,
+    0x1E6A166A, 0x0F68DF7D, 0x101C8B87, 0x0EABAD39, 0x11A72784, 0x098C3D69, 0x046F9B91, 0x03C5576A, 0x0A493914,
+    0x15BE0CFA, 0x0BE6A43C, 0x1E589C7E, 0x0630F12F, 0x0C53C452, 0x0190E17B, 0x18BBA6DC, 0x08A157D8, 0x0267BBE0,
+    0x1FE91A5E, 0x01B0325E, 0x0FB6E6A5, 0x0A2C8E62, 0x0B4C5D14, 0x0D979316, 0x1AF49FEE, 0x0923BBFD, 0x0734AA0B,
+    0x041EEDD0, 0x080C1EB1, 0x1DADD0BA, 0x0B807907, 0x14D0C0FE, 0x040A38AF, 0x0011F73A, 0x0647E20C, 0x04843417,
+    0x03FD22AA, 0x037EBAA2, 0x02ED0F10, 0x07A74904, 0x14243256, 0x0D3C5D80, 0x005852B9, 0x0E0C2798, 0x06A0FFA1,
+    0x097A6315, 0x0FB2110F, 0x1B25F028, 0x0FFED4AF, 0x0C2A4592, 0x056570DB, 0x15BFAE0D, 0x0D941FBA, 0x0DF6103E,
+    0x087F3126, 0x0091FC46, 0x1A48D5AB, 0x04551403, 0x1FAF9C1E, 0x077CE9B4, 0x0D4A2188, 0x0753CAD2, 0x0B79BDC1,
+    0x0CC40FEF, 0x07CB6E9E, 0x0F527E34, 0x0F96F324, 0x1C19448A, 0x0F3C1225, 0x0BE30263, 0x087B2FE1, 0x02D1050B,
+    0x0C297FBC, 0x0EC145A9, 0x106B7CA5, 0x0C28172E, 0x01BB4DE4, 0x0DB96404, 0x14927CDA, 0x09200164, 0x08CD8A67,
+    0x1FD02D0C, 0x0C19411F, 0x0E9710F2, 0x07697C46, 0x09890450, 0x0A11BB98, 0x197DDE59, 0x01D433DB, 0x092A86AF,
+    0x01D394CE, 0x0E534A83, 0x1A476CDC, 0x02395BF0, 0x077640DA, 0x0419F66B, 0x02207322, 0x0722408D, 0x01AF9E49,
+    0x10121C85, 0x0EEDAA4A, 0x118317E2, 0x03F349A6, 0x1CC5CE98, 0x0B8074BB, 0x041F4BB0, 0x0A13D6C8, 0x032BA872,
+    0x1B3F9D6D, 0x0CF4C6F8, 0x128F37B8, 0x02FBC95A, 0x1D9588F4, 0x049548C0, 0x0178A25B, 0x06DAD18C, 0x0F252CEB,
+    0x018E1153, 0x0D223741, 0x17E5A77B, 0x0650BE06, 0x023FA341, 0x07164104, 0x101090AD, 0x071F38D0, 0x05EAE20A,
+    0x089C2E4C, 0x08849ED8, 0x095EFC0E, 0x0D126BB4, 0x150D5058, 0x00A0D6BD, 0x12999001, 0x0413F9C6, 0x012CE081,
+    0x0D068A25, 0x0F14D321, 0x02C2EA10, 0x0874B4EF, 0x1CD403B1, 0x0DE8C672, 0x1C2F975A, 0x03223ECE, 0x0A29868E,
+    0x1B23CB2E, 0x0962D891, 0x0A84A8AF, 0x04F88E60, 0x061EFED7, 0x0A6B7CA1, 0x08747218, 0x057B5D8E, 0x0A293CCF,
+    0x0E5A324C, 0x05E81381, 0x1137A5C3, 0x02728CFB, 0x1E21ACE4, 0x0FE61351, 0x16C8F640, 0x04C63ED0, 0x03DAEF46,
+    0x1E7A57B4, 0x0D9322E2, 0x1FD29ACF, 0x0C9FAB08, 0x1090913A, 0x0CB2FF83, 0x0DDC0B4F, 0x000930C0, 0x07D2FF53,
+    0x02140B1C, 0x08AB7E2D, 0x043D4AA1, 0x06CF6C25, 0x1FB7FC89, 0x026B8F52, 0x0554631B, 0x0F1C23EC, 0x052F2916,
+    0x00D0893B, 0x04CB49E7, 0x1206C7A7, 0x0F4F030A, 0x183BC8F8, 0x0D02E8A7, 0x00499540, 0x05360AD7, 0x01AC1AF7,
+    0x13DE74A2, 0x0951597C, 0x12B1ADAE, 0x051B3E67, 0x1BB44D55, 0x0DD9140E, 0x070E737F, 0x05A11EE5, 0x0563EDB0,
+    0x098F4482, 0x0E436FC7, 0x14B1C0AD, 0x08298C3A, 0x0AD0D99A, 0x0DDC85A1, 0x185C9681, 0x0A2BFB35, 0x0347EBA9,
+    0x18D78BA2, 0x01D807C2, 0x1F7BC7F6, 0x04235875, 0x140EB8BF, 0x0A1B5BD0, 0x1C183BD6, 0x01575B75, 0x09E78D28,
+    0x1EA09669, 0x07CC4158, 0x05AB399B, 0x0D2F8D36, 0x0E4E2D0B, 0x0BC07448, 0x1862D8D8, 0x0AEA11DF, 0x03CB0D38,
+    0x16160629, 0x05407B23, 0x024E2354, 0x0D58E92B, 0x15D310C1, 0x0F0AD8AF, 0x19C519E0, 0x05119157, 0x067F2719,
+    0x0368AEBE, 0x088C8400, 0x1A0177C6, 0x04918C1D, 0x031F82A9, 0x072147A6, 0x090932E7, 0x078344DF, 0x0749C657,
+    0x164BCC59, 0x09C183ED, 0x1340FF4E, 0x08B2506F, 0x1E0E51A1, 0x042A30DD, 0x03F7F290, 0x01CC8D02, 0x08015E1C,
+    0x075AFB1B, 0x005A8A94, 0x0ABCFB34, 0x0D0735F8, 0x087BBC1A, 0x011547EC, 0x019527EF, 0x0279DB6A, 0x06E7C2B9,
+    0x173AD751, 0x0E759566, 0x06B6213E, 0x05ADF831, 0x02B644B8, 0x0C9161D1, 0x032C8B0F, 0x0E1BBAED, 0x04CD9EAF,
+    0x11C95DAE, 0x03F42378, 0x09043C7F, 0x0AFE1D9D, 0x1A999917, 0x00A88DBA, 0x0FB6E294, 0x0ACE8608, 0x0CFB3F5C,
+    0x04F0526B, 0x0D27FEA5, 0x174C40F8, 0x061456CD, 0x1F9C7373, 0x02421A85, 0x0B4FA148, 0x00D60BEE, 0x0455FFBB,
+    0x1FF2A4F7, 0x01D5271D, 0x09E4AFF7, 0x0D7F785E, 0x145521CC, 0x0BF5EDCB, 0x15C2EC51, 0x0252BAF1, 0x02700F6D,
+    0x16640F66, 0x01AC1419, 0x12208FAD, 0x0EB51846, 0x1FCFEC79, 0x0D980504, 0x103CB056, 0x0609A9B3, 0x0ECC8D6D,
+    0x151F907A, 0x07BEF6BF, 0x1EF7BB85, 0x00B4E971, 0x1BABAFF6, 0x0DC946C3, 0x09620E98, 0x04B671D6, 0x0AE97FD2,
+    0x111D31DE, 0x000052C4, 0x092923E8, 0x07AC3B20, 0x17E86B2D, 0x017BC737, 0x099589CB, 0x08DCBB02, 0x05DF9F7E,
+    0x1F703AC7, 0x0BD7278B, 0x0EB6D2AB, 0x06E91AE6, 0x18BBEB0E, 0x0F807C93, 0x0FAE0326, 0x08F0A41F, 0x04A0B138,
+    0x1C4F7282, 0x04E12160, 0x03B2DA7D, 0x0CAB51A5, 0x0340038A, 0x0ABA43CE, 0x1E7BCE9E, 0x05FDF19E, 0x06CC006A,
+    0x0A4CA462, 0x053D7163, 0x11FC11BE, 0x00FBAC38, 0x074F4321, 0x01123A78, 0x0B021AAD, 0x0F4B063A, 0x0E7E1E51,
+    0x0F9F29F5, 0x017C4A26, 0x1D3D25A9, 0x0A4A0B96, 0x09FCBBA3, 0x027CFDA9, 0x0FAE312C, 0x02F520D0, 0x0C453277,
+    0x19D3CA43, 0x0892019A, 0x1EF9A4DC, 0x09F071B9, 0x00D2F4B3, 0x0FCF2767, 0x06D507FE, 0x09D89F88, 0x0656B1D0,
+    0x01BF2BDC, 0x01CF5755, 0x197A5544, 0x02A48E6C, 0x1D07CD36, 0x0E36C20E, 0x134663B0, 0x02CBD346, 0x0AA42962,
+    0x1D1F409C, 0x0B56DD00, 0x1F506751, 0x07741504, 0x17D7D7C9, 0x08486188, 0x0C7599FF, 0x02E24BBA, 0x00D64B4D,
+    0x034CEBAC, 0x089E6487, 0x063CDD8A, 0x021A3BA3, 0x1F1ED37B, 0x0E767048, 0x0DC9B61C, 0x0B45D716, 0x0363F8C0,
+];
+
+/// 基点的奇数倍 G, 3G, ..., 31G
+#[cfg(all(feature = "window-5", not(feature = "window-6")))]
+pub(crate) static BASE_ODD_MULTIPLES: [u32; ODD_MULTIPLES * 9 * 2] = [
+    0x0830053D, 0x0328990F, 0x06C04FE1, 0x0C0F72E5, 0x01E19F3C, 0x0666B093, 0x0175A87B, 0x0EC38276, 0x0222CF4B,
+    0x185A1BBA, 0x0354E593, 0x1295FAC1, 0x0F2BC469, 0x047C60FA, 0x0C19B8A9, 0x0F63533E, 0x0903AE6B, 0x0C79ACBA,
+    0x16B13C94, 0x0DA54FDA, 0x0F0A0D1C, 0x02944EDA, 0x0505133B, 0x08C233AD, 0x19028996, 0x07FD10F8, 0x06033FAD,
+    0x165F8320, 0x010FBE94, 0x0BCE600A, 0x05295C3E, 0x1E4BFA05, 0x0FE9B704, 0x0D33125A, 0x00B767F7, 0x0515A68F,
+    0x153902C4, 0x05756336, 0x1FCC539A, 0x02C7910E, 0x1A628AD5, 0x02FC7BD7, 0x0E5EB4D5, 0x06C7F0EB, 0x01E887DE,
+    0x068A0266, 0x081D68FD, 0x007D182E, 0x0A5DEF1B, 0x0C77D987, 0x0930D889, 0x1A2C609A, 0x0C2854CE, 0x0F682A4E,
+    0x03E0CF09, 0x03531C78, 0x1325202A, 0x0A0C9C38, 0x0C6285C4, 0x0DA6B3F8, 0x0A213B96, 0x02B3F7D8, 0x077E925C,
+    0x0BEC30DA, 0x03A35C1E, 0x1A01B831, 0x04D173AB, 0x0DD78722, 0x0648AF66, 0x1DE5B26E, 0x0E7709AE, 0x05740B51,
+    0x01F7BD0C, 0x0E795C33, 0x01AF8F98, 0x0024AD59, 0x14DA472F, 0x0E0ED219, 0x120E496F, 0x008D2A45, 0x0EFB10E8,
+    0x10F6099D, 0x0BF8B5B9, 0x1F44280F, 0x0B1D45D7, 0x14B4B1E2, 0x05954E9B, 0x13177044, 0x0964D2FE, 0x00454D84,
+    0x0843C22A, 0x0252AD03, 0x0FFC012B, 0x0E463E35, 0x0933BAAB, 0x07F62942, 0x12364306, 0x01C1B0E3, 0x0907B42D,
+    0x020E537E, 0x0D6F9B3C, 0x17061E72, 0x020108AD, 0x0C3EAEEC, 0x0B6E7CB1, 0x10A6FFA8, 0x0F30C83D, 0x0A507314,
+    0x0001FC9C, 0x0E51C6A8, 0x04694134, 0x05EFEC4E, 0x15973ED1, 0x047786F0, 0x17199EEA, 0x00214E7C, 0x0790193A,
+    0x0FEB8C9A, 0x0ADEBBEC, 0x0350524F, 0x04F5A1F5, 0x0AFCD778, 0x046C9BB1, 0x18059FB8, 0x050A3124, 0x08BF4A11,
+    0x1BD2FA9A, 0x0489343D, 0x12CE1FF3, 0x071C55DD, 0x1910CE0A, 0x04473F77, 0x1C49619A, 0x028C1DFD, 0x01BF1282,
+    0x075D064C, 0x013F30E7, 0x1274A1F7, 0x0335466E, 0x0CA3ECDF, 0x0B88DA21, 0x0077E456, 0x0CF0F16F, 0x058B83D3,
+    0x1F7BFDFF, 0x0126193C, 0x16D844BC, 0x0EE9064C, 0x1B2C2518, 0x0E8067CA, 0x0F1229DA, 0x06112151, 0x07C66028,
+    0x01154083, 0x07B34E13, 0x1ADF28A8, 0x0ED12809, 0x021CE5FA, 0x09633737, 0x1D737F16, 0x053314C0, 0x07B477B4,
+    0x0CE2422D, 0x0FD33808, 0x0EFE463E, 0x04B84042, 0x089FCDCF, 0x0BB23279, 0x1B184687, 0x0B76DB79, 0x0C5B1C7E,
+    0x1F12C9C9, 0x0EDAB1C4, 0x0139F7FC, 0x06831BA7, 0x1A79F709, 0x07026A6B, 0x08E398F2, 0x081720F6, 0x0C648DB7,
+    0x00AA3901, 0x0879486A, 0x0E61BE03, 0x0EF5072C, 0x08340B08, 0x02086E4D, 0x08C7FA57, 0x03B64524, 0x01E03C03,
+    0x1D278712, 0x0017E057, 0x00DD6423, 0x064CECC2, 0x11CCE725, 0x040077A1, 0x0177726E, 0x071002AF, 0x0D57CB98,
+    0x1D74FE73, 0x05CBED22, 0x088D4392, 0x0173B762, 0x0AE263E2, 0x07E59DA4, 0x07F6E264, 0x0A230707, 0x0BED5CB2,
+    0x0F3F17A2, 0x069C304C, 0x1662FA4F, 0x05838550, 0x12CCB1C6, 0x01CAE9BC, 0x0A71FDA3, 0x023EDDBF, 0x0237709B,
+    0x0E92464D, 0x0957D29E, 0x03F82945, 0x0E1EFC33, 0x1EE09D6E, 0x05A5CA33, 0x0D23EA8C, 0x09D72EA2, 0x0BCA754F,
+    0x1FBB8EC9, 0x081759BE, 0x0DAFF374, 0x0A3A5C4F, 0x0464EB02, 0x0AE594F5, 0x04B1F7C7, 0x0A8A0804, 0x04E3CD0A,
+    0x06F84998, 0x0861816D, 0x07975A32, 0x0A771721, 0x01D4ADDA, 0x0F48CBE5, 0x195B6804, 0x05378405, 0x00E8D4EF,
+    0x1A3BDEB6, 0x0B07A84C, 0x1D9F9B6D, 0x0FF3FC30, 0x00FDCA10, 0x0A49A486, 0x075C5D9C, 0x0B93A488, 0x00AC1CFC,
+    0x09E3E4AE, 0x064C4B05, 0x1196DCDC, 0x019E4580, 0x0ADEF658, 0x0D36080B, 0x14553997, 0x06565FA8, 0x0C75F992,
+    0x00C81F6F, 0x0929A393, 0x1617179E, 0x09C3F6D9, 0x1DF46E48, 0x0B2FE3C2, 0x0EC42AB7, 0x0D12EFC5, 0x003B1E8C,
+    0x11138F14, 0x0179A8BB, 0x0A32C57B, 0x03D5C856, 0x0D1B034D, 0x0579646E, 0x0C393948, 0x0C77AA8C, 0x0F005F67,
+    0x0678DDD7, 0x04793132, 0x1CBABC1A, 0x00956E70, 0x179D8C63, 0x09CF038D, 0x098A28A4, 0x03FA7BD6, 0x0E9C5BF6,
+];
+
+/// 6齿梳状表，两组的齿分别为 2^(44b)G 与 2^(44b + 22)G，b = 0..6
+#[cfg(feature = "window-6")]
+pub(crate) static BASE_TABLE: [u32; COMB_TABLE_LEN] = [
+    0x0830053D, 0x0328990F, 0x06C04FE1, 0x0C0F72E5, 0x01E19F3C, 0x0666B093, 0x0175A87B, 0x0EC38276, 0x0222CF4B,
+    0x185A1BBA, 0x0354E593, 0x1295FAC1, 0x0F2BC469, 0x047C60FA, 0x0C19B8A9, 0x0F63533E, 0x0903AE6B, 0x0C79ACBA,
+    0x116426C5, 0x0E8A78A0, 0x1E0B35DC, 0x0AF402CF, 0x01BE79BF, 0x01FCB21E, 0x04F7299B, 0x0917E5D3, 0x024A4972,
+    0x02E8AF11, 0x00F020C7, 0x1FBF4C7C, 0x06F235E5, 0x10E4300C, 0x0A46B0F7, 0x106A1119, 0x03BBCB5C, 0x06329A83,
+    0x015D9BA6, 0x0DE74CC7, 0x0BF22CC4, 0x092A09EC, 0x1654F5F4, 0x07CDD293, 0x1FFC1205, 0x09A60517, 0x043516CF,
+    0x1817E5EF, 0x0BFF2790, 0x01743CED, 0x065AAB2D, 0x00B47E6F, 0x0A6F5F67, 0x0441FD6A, 0x0EE14503, 0x00FFA2DB,
+    0x03DBAC5E, 0x00ED50CF, 0x1F48D9CA, 0x0D737E94, 0x174461E3, 0x0CA229CA, 0x19BFD1EF, 0x0A6918CC, 0x0B09BBFC,
+    0x0CE63FBF, 0x01137E93, 0x198BC7A7, 0x0E3EE046, 0x1F1838A5, 0x0B04369A, 0x0973AA44, 0x0C7CC5AD, 0x02397089,
+    0x0443A082, 0x0332DE4D, 0x0B4BBA45, 0x06919941, 0x1D689FE2, 0x088B1DFE, 0x0908A61C, 0x0D7F076E, 0x0DEDCC6E,
+    0x078B13AF, 0x0DDE2C1A, 0x1CA2635F, 0x08292701, 0x1029EC5F, 0x03E7E88B, 0x0A552D66, 0x0E257889, 0x01D4C9A3,
+    0x0217BA13, 0x07927598, 0x1DDB9A51, 0x041379DA, 0x00322D62, 0x0EFD126D, 0x15C95638, 0x070A3E0D, 0x0301102B,
+    0x142D97E7, 0x09D8A521, 0x1C77899C, 0x0ABA4034, 0x0E9D6F28, 0x00A0F5D5, 0x10344FAB, 0x05A57B67, 0x0E36AA96,
+    0x1A0077A4, 0x045833C0, 0x1A32A662, 0x00B1F9FE, 0x1D5F9C98, 0x06ED5659, 0x0297D9A3, 0x08E86602, 0x07E97D60,
+    0x03D05E81, 0x03DF8B9A, 0x1B750FC5, 0x07D05269, 0x1188A722, 0x0D531B6B, 0x0A08A943, 0x0CCCAE1D, 0x06543E88,
+    0x086665DE, 0x0E0671FE, 0x1C221843, 0x0EEADA20, 0x1D0C38E2, 0x007FA48A, 0x19D3074B, 0x0FAC3396, 0x0AB1D385,
+    0x1D8A24AB, 0x04ACDE01, 0x02450750, 0x09BA097C, 0x157F4125, 0x0779D131, 0x18D8B46E, 0x08561AA5, 0x0732C2DB,
+    0x1B6873CB, 0x09EC1D6B, 0x03C8A7B2, 0x0970116B, 0x1CA75A92, 0x02A3C9D8, 0x123520A5, 0x065452C9, 0x0DE58458,
+    0x0FA9C472, 0x0F81CBBD, 0x0404ECD8, 0x02FAD6F7, 0x01E367F4, 0x024D5E9C, 0x01B42BC3, 0x00FFA38C, 0x003D1977,
+    0x1D777FBD, 0x0C03C044, 0x1CDE4018, 0x0C4D3DA7, 0x19EDC64A, 0x05333053, 0x19301D28, 0x01E296D5, 0x08DE4770,
+    0x0BC229E2, 0x0F7FF188, 0x13B2D5FE, 0x0FE61B2C, 0x0F14A31D, 0x03B4EDD8, 0x1202CD2D, 0x0BBA5897, 0x06088C6F,
+    0x0CEEAAF5, 0x0DD14C01, 0x08C6505D, 0x01D73964, 0x090791EC, 0x0A877424, 0x10E54058, 0x0E412CD0, 0x015084FB,
+    0x0077EC4D, 0x00802226, 0x16574F2D, 0x09E5659A, 0x0F5CAEA1, 0x0D168225, 0x0CA75DC3, 0x07517A5A, 0x0A649FEC,
+    0x04FAACB3, 0x0986F712, 0x068E36F2, 0x0D69697A, 0x16A1E508, 0x05020684, 0x075D8E6C, 0x091A0F60, 0x0B7430F9,
+    0x175DC39F, 0x0A89A4F8, 0x16EA3934, 0x0FAFD29A, 0x0C152C0B, 0x037AA514, 0x08F3340D, 0x0ABBE5F1, 0x005D687A,
+    0x1A8F5B7B, 0x07B97432, 0x1CE3F5B3, 0x0FF7D86D, 0x0F44F782, 0x02C4CC1C, 0x0A472767, 0x019A1F97, 0x089DAAB8,
+    0x0FB47E2B, 0x06DD5BE9, 0x1B0C0E01, 0x0ED42E50, 0x06D0F548, 0x0CF96A40, 0x05372991, 0x020B9C09, 0x0BD462F6,
+    0x06546864, 0x018CDCEB, 0x0DD5BB18, 0x02318D5C, 0x06C9F712, 0x00CB802D, 0x0C527950, 0x07261248, 0x07E3FBEC,
+    0x1E907C62, 0x040EFA75, 0x0663EF6F, 0x08001A80, 0x1AB1F99B, 0x05DA2EF2, 0x1C10DAF1, 0x0D42DDB0, 0x0EE6B59E,
+    0x1AF1F8CB, 0x069FDE3B, 0x11691205, 0x0C88D997, 0x128FCA49, 0x09CC509D, 0x1F5E319A, 0x0AA87906, 0x0217A7DF,
+    0x051D2764, 0x035872BD, 0x007E2D36, 0x02954478, 0x1F9381CC, 0x0A05EE9A, 0x109D4D43, 0x096C63BA, 0x0C5F87B8,
+    0x1FE4C5F7, 0x06D20BC9, 0x15868B20, 0x0197F283, 0x0CB9E5D0, 0x009A50FF, 0x009C0DD8, 0x06C6B8BC, 0x0038AF97,
+    0x150E3E37, 0x064E700B, 0x065F5699, 0x04602759, 0x012CF81D, 0x04482E54, 0x02F23AF2, 0x025A6595, 0x0A561DC6,
+    0x141EF13D, 0x07E7E197, 0x0D669D65, 0x0C373353, 0x08501292, 0x075ADA65, 0x0BFCCB7E, 0x0B913F09, 0x04A4BAA6,
+    0x1CA1DF4D, 0x09E29BD7, 0x0867974B, 0x0713BC26, 0x10B62533, 0x02D8E525, 0x1A5BF179, 0x0B1BF48F, 0x00B4AD09,
+    0x1B6675BB, 0x0EEB0681, 0x0E73FD9C, 0x0F131B3E, 0x123E6629, 0x0BE92760, 0x0FA7FB92, 0x0057ED31, 0x0E4EE693,
+    0x13B88F3C, 0x0CC0A061, 0x09BF099F, 0x0F45AC65, 0x0B86873F, 0x00107C78, 0x1E26C92E, 0x0525890A, 0x0D4AFA10,
+    0x0BBAC45E, 0x0022A3A1, 0x1BFCB6E9, 0x04CC865F, 0x0A682C92, 0x097222A2, 0x0FD1ECB1, 0x07277C57, 0x04B16CD7,
+    0x12B659CA, 0x0ED3EF89, 0x1A7FF3B9, 0x003341D0, 0x19488AFA, 0x02B0AE9A, 0x0B7A6D6C, 0x04690E71, 0x06F4EA93,
+    0x069E1C95, 0x0EFFEAC6, 0x14541851, 0x0FA6F91D, 0x063595A2, 0x0DEBA99C, 0x14032312, 0x0A3258D4, 0x0050FCCF,
+    0x0B2C9024, 0x00646434, 0x152A3BB6, 0x010B2E1B, 0x1ED4A896, 0x0B47FA32, 0x1D82BB17, 0x0BB633A0, 0x02FB944D,
+    0x1960D8C6, 0x083D61DF, 0x13BB1B50, 0x08E5D25D, 0x17D0DD6B, 0x07A8A755, 0x0B7D6670, 0x072BA0DF, 0x0FC389B0,
+    0x042EBEF9, 0x02F87D01, 0x0F039DFE, 0x010A53E1, 0x1C2A4065, 0x05B94CF4, 0x126379C8, 0x090F280D, 0x09C42993,
+    0x0B57EAC3, 0x030529AF, 0x15D0EDA0, 0x0B0A8E08, 0x17F8C52F, 0x0F4BAAA2, 0x18B8EBB7, 0x0AEF56BE, 0x09C5A431,
+    0x17B3A547, 0x0AA396EF, 0x06FFA93A, 0x06CB4683, 0x0177186D, 0x0F362FDB, 0x1E3E2292, 0x0C3F46A7, 0x07721E29,
+    0x07FF9801, 0x04D88025, 0x0691A364, 0x03B093F6, 0x11262AC2, 0x0C5A0054, 0x1EBD3B90, 0x0FD6CF85, 0x0BC628DD,
+    0x00B6166E, 0x0BE37093, 0x0E03040C, 0x016B3F57, 0x1C0A6F41, 0x0F9882FB, 0x0C9FB707, 0x02B5C9C7, 0x0F6C973F,
+    0x11AC9174, 0x06BE753A, 0x0AD6E772, 0x0A89411E, 0x1CA1E5AF, 0x0A05AD0F, 0x086D71C5, 0x085A040C, 0x04191976,
+    0x082CF57E, 0x0BFC7168, 0x1B885CBC, 0x09895148, 0x1D3BE1A5, 0x003A4E80, 0x06AAAEC4, 0x03ABA6A8, 0x0575C6D2,
+    0x10C46818, 0x0DE6B950, 0x094D8CDD, 0x0E83A441, 0x13F2373C, 0x0B59D0C8, 0x1711CF47, 0x02EB465B, 0x0D6963F5,
+    0x0CBC528E, 0x0B7A87CC, 0x1BAD404D, 0x0E711460, 0x1EC49096, 0x05FD6B68, 0x14FA1EAE, 0x022FEC9D, 0x01605551,
+    0x021AF29A, 0x0180B83E, 0x1F18A832, 0x02941765, 0x10F015B0, 0x0488049D, 0x1711CFC9, 0x0EA91591, 0x0B07A08D,
+    0x01099EFF, 0x0620A44A, 0x0ED52110, 0x0A0B5717, 0x07447C28, 0x0492E28E, 0x0C328486, 0x0EBEE2D2, 0x05F4F27F,
+    0x1F33E3AA, 0x0E47B34E, 0x0EA1803B, 0x053A60DD, 0x1A68486F, 0x0A439618, 0x1A991D95, 0x057704ED, 0x02DF563B,
+    0x140A4B57, 0x07130BAF, 0x13C7BCAE, 0x0E6EC93C, 0x01826C55, 0x0F932A16, 0x095D0836, 0x0EFCFA7D, 0x01ADA726,
+    0x06B5AF39, 0x004A6418, 0x07113D96, 0x04055A4F, 0x18ACBFC7, 0x0EB843E0, 0x080C43DB, 0x0DCF7DED, 0x0EEEC6F5,
+    0x1A90942D, 0x0F773FD1, 0x003B8DE8, 0x0539B070, 0x16C828F7, 0x0B868D6E, 0x0A85EAE4, 0x07277603, 0x02F1B399,
+    0x0020DE46, 0x0041555A, 0x167652A9, 0x0012B497, 0x099AE649, 0x09A51462, 0x084DC866, 0x00325EFB, 0x05571DC2,
+    0x14FDD73C, 0x0FC124D4, 0x01B1B7EA, 0x06F4C651, 0x15B5B0CD, 0x0BA28DC0, 0x10500B6A, 0x0D7EE6B3, 0x035AD895,
+    0x1B151707, 0x024F7346, 0x083617D3, 0x05929F9C, 0x08D6B3C3, 0x0A578DEF, 0x01E2D01F, 0x05CF6A85, 0x004B261F,
+    0x06FBFE18, 0x017DA0B2, 0x127F8DAA, 0x0E31BBCA, 0x1DDDB650, 0x0D1A9249, 0x02DA8861, 0x005B0EBE, 0x023DA715,
+    0x091491D4, 0x03FDDABC, 0x1BD06E38, 0x05063030, 0x1B81E5A5, 0x03A22258, 0x1DB021E4, 0x0068E946, 0x0E68CBE5,
+    0x14D34A1E, 0x08E766D8, 0x0A20B5CE, 0x030CD73F, 0x00CB3F74, 0x0F15F13C, 0x061AA4FE, 0x041A5D00, 0x026C7331,
+    0x143FE95E, 0x058E0F2A, 0x0503A415, 0x01A439C8, 0x1B78B0FA, 0x0CC8F13D, 0x0B998591, 0x0D3C2686, 0x0A1A0E0D,
+    0x0BD741F6, 0x01A87CAF, 0x1DA2C55F, 0x0AE07C05, 0x12311454, 0x0F804AB9, 0x098435A4, 0x01E8BCD7, 0x0F30E268,
+    0x0848663B, 0x05F162CD, 0x11C8368B, 0x0A84146E, 0x12AA3FC0, 0x06A47139, 0x19180E28, 0x0E1AC802, 0x0F706781,
+    0x15C7B5D8, 0x06B97B0E, 0x09569A8A, 0x01C3D25F, 0x16130A9A, 0x0EFD0D46, 0x1C746142, 0x073A41E1, 0x05A60731,
+    0x1FF389D7, 0x0BC9BA35, 0x05BC2E1D, 0x0E529E6E, 0x141D3319, 0x0B1F7562, 0x057C3BFA, 0x05287587, 0x03EB3935,
+    0x0549B13E, 0x03D1298B, 0x162B67C4, 0x009802DF, 0x153FFAF6, 0x0E8A7768, 0x00E7C861, 0x044B4C14, 0x08861863,
+    0x121242BD, 0x0A5F77FF, 0x1C63AAB1, 0x0D9F808E, 0x06FCD2A4, 0x0BDF173E, 0x118D46D0, 0x02DED02B, 0x0DC38A16,
+    0x16AC7FF3, 0x035B1B9C, 0x0DCB4C80, 0x05B34EB2, 0x13E8DF70, 0x05C22449, 0x16F9379D, 0x07DBD178, 0x0E7FB279,
+    0x113A767B, 0x039112CF, 0x14F17078, 0x07D0BB2A, 0x1005FCCD, 0x0855F8C9, 0x1FAD667F, 0x0B7FD630, 0x0250D3ED,
+    0x02D19800, 0x0D69317A, 0x17739FD2, 0x010D29D7, 0x085DBF83, 0x05CA6DAF, 0x0488459A, 0x0C4A1038, 0x0FB62ECD,
+    0x1D5DE31D, 0x00C37852, 0x141E859E, 0x0C5BF34B, 0x0C9E8A7B, 0x0BC491D5, 0x00D7F8CC, 0x05CA8042, 0x088A57F8,
+    0x1DEDF7E2, 0x09DD829F, 0x177395AB, 0x0361EFFE, 0x1EEA540D, 0x06F98FC2, 0x1C62EF9F, 0x0282607D, 0x00A15569,
+    0x1A974CC8, 0x09C8D8E0, 0x07081FCB, 0x0DBADC60, 0x18169924, 0x0FABE97E, 0x0BE4D224, 0x045A223E, 0x0D12A843,
+    0x05E14F30, 0x01732D0C, 0x169B9AD0, 0x0CB60430, 0x058FCE8B, 0x029EF121, 0x189E2F8A, 0x056AE768, 0x0CECAF91,
+    0x078C1E57, 0x018BC3FC, 0x156419A6, 0x05598C70, 0x12AD5395, 0x07A435B9, 0x10EA2C46, 0x0F79B33A, 0x055FCF99,
+    0x17A31B55, 0x08301A7D, 0x0591C58C, 0x045ECA85, 0x1B033331, 0x0FBBAB3C, 0x1417B1BD, 0x0D4B927C, 0x0012FA44,
+    0x141217E8, 0x043D63A1, 0x14A91345, 0x04E705ED, 0x130CDA48, 0x01E6FE5E, 0x1F52970A, 0x05913A27, 0x096155C1,
+    0x14E71A0C, 0x0D6F1A34, 0x0A578003, 0x0EF95DBD, 0x079FDE8E, 0x056F1350, 0x144D7CE2, 0x080AF8DB, 0x0C46ECB0,
+    0x0DDF2B5E, 0x0350BF82, 0x01A40EAB, 0x0B57D96C, 0x07D8CC7E, 0x0BA04DB8, 0x1EBB1C08, 0x0498A1BA, 0x030345EA,
+    0x1F76E929, 0x0D0CD580, 0x0E678909, 0x06BF65E1, 0x01E53058, 0x0BBD786C, 0x09DCA3D1, 0x0DFC8555, 0x0BF153B2,
+    0x13AA180C, 0x0B147888, 0x1C796F68, 0x0D2C3D6C, 0x1C22ADF4, 0x0B0F6163, 0x18C4BD86, 0x0B5EBDF7, 0x051FEDD3,
+    0x17C6E53A, 0x0C53703A, 0x0E7F96BF, 0x0F2EC288, 0x06CC9925, 0x02CE1B16, 0x040FD223, 0x0CA28079, 0x0DC792DC,
+    0x035DC60F, 0x0C77B301, 0x13575BD2, 0x08A3B4B8, 0x13C86C1A, 0x088B150F, 0x075A77E8, 0x0CF376F5, 0x0C98ED87,
+    0x16F13E0A, 0x05081775, 0x17F82585, 0x0B1B9E9F, 0x1DF8B4DE, 0x0681E600, 0x145DDD33, 0x0FA05741, 0x0851B741,
+    0x04CE9048, 0x038F2464, 0x08555D6E, 0x01A2D322, 0x1C19AC42, 0x0C49FD12, 0x05414D52, 0x052CB157, 0x0F9C2981,
+    0x0D22C73C, 0x07FCFA8C, 0x0FC51640, 0x0A8771CA, 0x08D9CAFF, 0x0B02CB40, 0x11F5528B, 0x09BB03C3, 0x0D4BABCB,
+    0x05E2B3AE, 0x0E4777C3, 0x0AF9543E, 0x079C54F4, 0x00E0B371, 0x01958CA7, 0x00D4C3AD, 0x0D2065E1, 0x00D7D340,
+    0x068F553D, 0x09A0E74A, 0x198F8631, 0x0806C4FA, 0x02BB4D33, 0x07B60386, 0x1A690010, 0x046A1F1C, 0x0A536D33,
+    0x11FB9DEF, 0x07B272A5, 0x1B7A623B, 0x03902A61, 0x10129B33, 0x01AD4441, 0x116560FD, 0x0CA297C0, 0x0BB61712,
+    0x1CCFEB56, 0x0963D82C, 0x04FF6F99, 0x0A403BCD, 0x14889171, 0x0FF373D4, 0x0506859A, 0x00255388, 0x034F94DD,
+    0x14A013D0, 0x0460FDE4, 0x1A0A9767, 0x09C01A4C, 0x0CB1C920, 0x076933A4, 0x0B960876, 0x00BE95D4, 0x0812F40C,
+    0x07C23CB8, 0x02E5A208, 0x04D3953E, 0x09DAB23C, 0x1AF9F3D0, 0x00356F61, 0x077E99AE, 0x0B29FEA2, 0x0716F8E2,
+    0x1C4E6EDE, 0x0BDAF7F7, 0x15A1739B, 0x0DB8CEA3, 0x101A6278, 0x0A97C898, 0x09504D30, 0x08F792B2, 0x008661EA,
+    0x11F53F6A, 0x0DF71C5C, 0x14A10301, 0x041F303D, 0x149E4244, 0x0ECEA321, 0x0EE3AE51, 0x00572309, 0x0B8DA78E,
+    0x1B04BECF, 0x0A5D0C36, 0x0DDDC67E, 0x0E9D10F3, 0x10526632, 0x02AF6074, 0x1B066BFC, 0x02155DD2, 0x083E28B7,
+    0x096F842B, 0x039DBB79, 0x12568410, 0x0B349252, 0x1F167D89, 0x00C17D47, 0x06B43688, 0x0EE9E49A, 0x0FD2BC9D,
+    0x01142FC5, 0x092A4669, 0x10236075, 0x050B22B1, 0x1E8FD9B0, 0x04A2C080, 0x13A89B11, 0x0DE5F1D3, 0x0C829082,
+    0x1F9A90E2, 0x0DA56DAE, 0x08C2C300, 0x0BC49B33, 0x1495737F, 0x03249E5F, 0x012E8EBC, 0x0CC3478B, 0x0C053A33,
+    0x098589D5, 0x017F3195, 0x1BB0753F, 0x0E0A93C8, 0x158A3708, 0x03340282, 0x1D6548A7, 0x0364ABA0, 0x09022CAF,
+    0x124829A9, 0x0CB1E8CE, 0x0F0343F0, 0x0946D2EE, 0x0A3A10ED, 0x01A3474A, 0x15329DA3, 0x0CB839A2, 0x062EDA30,
+    0x14332CD2, 0x0974796D, 0x0901815C, 0x06336ACC, 0x16229C59, 0x07D0CAC9, 0x0D20E098, 0x01EC3ED3, 0x07279EA6,
+    0x05B05B37, 0x02EA91CA, 0x03016B8B, 0x0033DD07, 0x1EAFE648, 0x0EF224C1, 0x1F1FAC51, 0x0AD10AB2, 0x0A05FB57,
+    0x1111C250, 0x023E3B2A, 0x1666CA67, 0x00CCC14C, 0x0A005157, 0x015F9BD9, 0x0FA9A9AB, 0x091DABB4, 0x0C0F2816,
+    0x0B46EAC1, 0x098CB645, 0x1A864566, 0x048E35AB, 0x0BBE7322, 0x0FDC89F1, 0x0DF94EFC, 0x0F339482, 0x0A1B7BBE,
+    0x1CE6781F, 0x0D2C514E, 0x13F0836D, 0x0DABBA06, 0x09DAA4C9, 0x00C0E56F, 0x160CF39D, 0x0F8B244D, 0x0BD83E94,
+    0x1FF1F16D, 0x00945433, 0x1412A30F, 0x02AC647C, 0x03050706, 0x0F622ED6, 0x16623C91, 0x07410AAA, 0x06E5F6CD,
+    0x065529FA, 0x0F686A87, 0x17224C2F, 0x061CEBE7, 0x1797868B, 0x05C259F4, 0x12A51721, 0x0B7F0165, 0x06050FDB,
+    0x00C8DEA6, 0x0F185F5E, 0x18C3FF5C, 0x0D208640, 0x1BCBBE41, 0x0583E2BA, 0x10139A93, 0x089BBC36, 0x0C89ACBA,
+    0x14DD3625, 0x07D2C9B9, 0x0C7B640C, 0x03C79EBC, 0x048A2329, 0x02CDB76B, 0x10D3249F, 0x0CE306F7, 0x0C4EC8E2,
+    0x10B604B9, 0x0F47F78A, 0x1FEFC7A0, 0x09FC3EFB, 0x1FA0B96E, 0x04B65310, 0x03EF6865, 0x0E5A5A5D, 0x075D64CB,
+    0x133F2460, 0x060F5622, 0x14A3CA97, 0x09CA47FA, 0x1D9E514A, 0x0AA3C2EC, 0x1F54DA8A, 0x08E9D032, 0x0F8027C0,
+    0x01DF76EB, 0x0D4526A2, 0x15428A67, 0x0ACDEBEF, 0x1606534A, 0x00F881E4, 0x0E5A0886, 0x06EA0D88, 0x090D76E2,
+    0x16EC02BC, 0x0B24E18C, 0x19CDA696, 0x0D6BCDE7, 0x0D8330F1, 0x08583E2B, 0x1D3615C6, 0x029B033F, 0x034B0C3F,
+    0x09C61E07, 0x05CEB316, 0x121B43F8, 0x08F54F6E, 0x097F20C4, 0x07961B39, 0x008DF9AD, 0x0BD2F7BC, 0x0B0EB88E,
+    0x0B000C24, 0x0FAA1349, 0x1B863310, 0x079C97DD, 0x1B62CAC6, 0x06C1ECA7, 0x153B1462, 0x0BA7E031, 0x07168699,
+    0x0F024CD6, 0x0A5A6313, 0x17D440AC, 0x0D450626, 0x0CEFEC0C, 0x083CF7FA, 0x115E0487, 0x0BA97594, 0x028196B0,
+    0x1F1DACDA, 0x08893E76, 0x15A2176B, 0x0443EF96, 0x1D696582, 0x0191E5E5, 0x11A0728C, 0x04474180, 0x0516A2C1,
+    0x0529864B, 0x054823D5, 0x1F472688, 0x0AC7CC6C, 0x1386EE61, 0x0B2140E6, 0x15D2923B, 0x0AC3E1D6, 0x0C207EB8,
+    0x023A316A, 0x0D4EA95F, 0x1A84A1E7, 0x0788310F, 0x128D3595, 0x0F7A2D17, 0x1C9E88C7, 0x05B06112, 0x0F80073B,
+    0x1966CBB6, 0x0363750A, 0x0673BBEC, 0x038B42ED, 0x164D3D28, 0x01BCE39A, 0x076813FE, 0x06FDDB5B, 0x089BEB7A,
+    0x1409C368, 0x09F4C8B0, 0x0B531093, 0x083D5F52, 0x00B9AE5F, 0x0165538A, 0x1EAAAF7E, 0x05A57A38, 0x02A147E1,
+    0x119F1BB6, 0x071A50CE, 0x0F0DAB2C, 0x0960F24A, 0x15139793, 0x0D281773, 0x15182D14, 0x045DC780, 0x09252A08,
+    0x0584A6A5, 0x084DBB41, 0x1846BEFD, 0x08CC2548, 0x0D645874, 0x0CE15868, 0x0422019A, 0x071B485E, 0x09A815E1,
+    0x07F93C9F, 0x04D61B7B, 0x06E39836, 0x0C559D86, 0x0F2A4940, 0x0877874C, 0x022852C1, 0x08A1D6A2, 0x03E99B91,
+    0x0BC71186, 0x099DC559, 0x04E3EA50, 0x087FAF52, 0x0FBDE833, 0x064D86D0, 0x017484B2, 0x03F5E266, 0x09EB295F,
+    0x0B802465, 0x0508BF20, 0x1F08A53C, 0x09A9B5CF, 0x1A0D3B41, 0x02F6E0DD, 0x0AA873DC, 0x0E55EF7A, 0x0751CCC2,
+    0x15352A4C, 0x03CAADDC, 0x0303E9B1, 0x031FD34D, 0x079E380E, 0x09EAC294, 0x1FEE5F59, 0x08D1CCAA, 0x04F807AF,
+    0x1620AC0E, 0x024E712E, 0x186CCBAB, 0x0576EC6E, 0x16B4249B, 0x0E282A61, 0x194C865B, 0x0C491EB4, 0x0060F704,
+    0x1C565988, 0x06B05F12, 0x1865222D, 0x0D5343AE, 0x100F8A18, 0x01591570, 0x15A6FC20, 0x0E93A7C8, 0x0D90C369,
+    0x1DFFC030, 0x06489551, 0x147E1354, 0x078C0EFF, 0x1F492861, 0x032B4DE4, 0x09F06BC5, 0x0BB441F7, 0x0988853F,
+    0x16C76CD3, 0x0F18C448, 0x12D2D106, 0x075D374B, 0x0CDE29E3, 0x063233FD, 0x194A948B, 0x0BDDA4B9, 0x078303A2,
+    0x1FD61D95, 0x03F173D5, 0x07BD7111, 0x09FC7110, 0x0AD71A13, 0x0CBBE85C, 0x0B906DD5, 0x05FE2DE9, 0x0151FFD4,
+    0x11EF5D02, 0x08B1E0E8, 0x168AE9D5, 0x0E40CEC4, 0x12C3BF1A, 0x0740056C, 0x037D36C6, 0x0EB4275E, 0x0B4881CC,
+    0x01D8887E, 0x058C223D, 0x0C284E81, 0x0C5427EA, 0x1219D092, 0x0DBAA409, 0x185BC826, 0x0A5C6D65, 0x0B1AE947,
+    0x0405748F, 0x0A60357D, 0x1D69A4FC, 0x04C85168, 0x04F77E2A, 0x04C70AA3, 0x14BDA842, 0x0D4A1FFE, 0x065D8E6C,
+    0x028F7972, 0x0503E32D, 0x053D949A, 0x0510F8DC, 0x184193E2, 0x063429B4, 0x021B4E0B, 0x01E600FE, 0x0F4D2A2B,
+    0x02DD12C5, 0x04D5E4AC, 0x1DED0EE1, 0x0F77C450, 0x128EDD2A, 0x0E9E4448, 0x05D02BA2, 0x0E710E34, 0x0D6E73A9,
+    0x07897A59, 0x0B694596, 0x11DF883A, 0x08B93EE2, 0x13ACED8D, 0x0E45E50C, 0x1B690563, 0x0521200E, 0x06D8A560,
+    0x13C5F3F0, 0x012AAF7B, 0x1419ACE4, 0x060D507F, 0x05C80242, 0x062505EF, 0x12A76512, 0x07FCE631, 0x056E7B7C,
+    0x05BAF615, 0x01B71E55, 0x14AFEB39, 0x0C7755CA, 0x1CAC6B0F, 0x04F281F8, 0x126ABAAC, 0x050543FE, 0x0BC88406,
+    0x1DE8BC29, 0x0D097A76, 0x10082DD9, 0x0726B275, 0x13C6CB5D, 0x006DAACE, 0x0DED7332, 0x094A4B16, 0x0D7B5B9A,
+    0x080A72D5, 0x0FEFA681, 0x1C631945, 0x01961064, 0x127B556E, 0x00B19BA5, 0x1788CBD4, 0x003E7FC7, 0x06E12B31,
+    0x1B96BDB4, 0x000541BE, 0x0C7329A3, 0x0D55EDF5, 0x0BB5656E, 0x068D6EBF, 0x1295146F, 0x09DB4A22, 0x0304423F,
+    0x17F65760, 0x0FAC284C, 0x00C3FD76, 0x04FE1A07, 0x16BEB82C, 0x0D98B893, 0x1C3B46C7, 0x069E5C14, 0x0A177F1C,
+    0x1DF87AEB, 0x01A6F2D9, 0x1771BB13, 0x0EFABD60, 0x1D3884B7, 0x0E2A2310, 0x14EA5614, 0x0DB2B319, 0x0BB8D8B0,
+    0x1BB088D8, 0x0C20F577, 0x18D9702E, 0x0C2954CF, 0x1595F8D6, 0x0E83648F, 0x15877BAE, 0x01D46293, 0x0C2FED97,
+    0x01BC3E77, 0x0224EA57, 0x18683138, 0x0F56051C, 0x0441E250, 0x0C7259CF, 0x0D126631, 0x04F77676, 0x08E1A789,
+    0x03A2D76C, 0x0BA48170, 0x18085A63, 0x0F5AE48E, 0x03A390A1, 0x066023D7, 0x1AC2373C, 0x0D5A5EAA, 0x0C900439,
+    0x0C4DB7D0, 0x057876E1, 0x0BEC63E2, 0x061ECBAE, 0x1BDE6121, 0x0FBF03F6, 0x1B1368EA, 0x0F637717, 0x0E154882,
+    0x1A3023FD, 0x0C38B998, 0x1A85021D, 0x02E83BF9, 0x098D9A85, 0x0066DCFC, 0x0E3E9DB3, 0x0392CF0A, 0x0811DEAA,
+    0x17A6D3E8, 0x045603B0, 0x1B27EC3F, 0x02D99252, 0x097ED46E, 0x0280D957, 0x114EED45, 0x079003DB, 0x0DF4BFA4,
+    0x0FE7CC76, 0x01A856B4, 0x1130B5C6, 0x042EA81F, 0x01CD4E16, 0x0F69C16D, 0x052721DC, 0x05E351EE, 0x00EB69A6,
+    0x1DF97764, 0x0F6298C3, 0x11F8AA9B, 0x025B891A, 0x150115F7, 0x08FBCBC4, 0x1FDD839B, 0x0E4D4CA3, 0x00C19C36,
+    0x06427586, 0x01029299, 0x117BD906, 0x004CB9D0, 0x08F99415, 0x09E40E87, 0x00D80FD3, 0x0D85E6C5, 0x0408AD2F,
+    0x1D9DBC7A, 0x0424508E, 0x0440D0DC, 0x04D9A9C3, 0x06BB88C4, 0x0E4E6378, 0x11DEC8A7, 0x046014E8, 0x0E7F9DB1,
+    0x06391F10, 0x05E1C79E, 0x077E4FF7, 0x005EEDD9, 0x001646D9, 0x09C0AE1F, 0x06A37B42, 0x0A9A2C98, 0x02CC5036,
+    0x16B8C97B, 0x0F394EE9, 0x0ED5FAF0, 0x0F40C487, 0x0D85F7E4, 0x0F822F12, 0x17176CF4, 0x092B1018, 0x06DCC3F8,
+    0x0D926BB6, 0x06C17F75, 0x03102B50, 0x0F6692F0, 0x1FA9A41A, 0x00E62DE3, 0x1D47C998, 0x05D04EB2, 0x0BCBF56D,
+    0x1A7F7BD2, 0x0CE29D80, 0x18FB7BE2, 0x061DD961, 0x0BE13C68, 0x09B5ECD0, 0x0AC90F6A, 0x034CD67E, 0x0CF51D31,
+    0x10EE7D8D, 0x08A0279A, 0x03B92706, 0x0A5F5B8F, 0x17E06514, 0x0D50DA5D, 0x17C4EDAA, 0x0526877F, 0x0F5102F4,
+    0x1CFFC294, 0x0F216526, 0x0D6ABA57, 0x0327A713, 0x02710230, 0x0D922D92, 0x0A6D6C98, 0x0B40486C, 0x0C8D1CE4,
+    0x1F70B4AE, 0x0CF0E430, 0x0F4874C8, 0x0C5E3099, 0x0F8B65B9, 0x0947C46A, 0x08AB8F31, 0x0FF15FCB, 0x0D380ECD,
+    0x10144CBB, 0x089D5C76, 0x03526AD3, 0x097A6A0C, 0x0E98E2C3, 0x0B486A38, 0x1064F767, 0x0A89E209, 0x09662041,
+    0x1C8465AB, 0x0FED86DE, 0x11F62A34, 0x0A0F827F, 0x0A5DA04C, 0x017BB8F6, 0x08D2D236, 0x0C698553, 0x0F0094CE,
+    0x137D5065, 0x066BC774, 0x1656E3B9, 0x0AF176DF, 0x14A21C59, 0x08978AE5, 0x069C35C4, 0x0ABA9B82, 0x0B4171F8,
+    0x1A7DF323, 0x0F130476, 0x1ED3FF6C, 0x0BEFD780, 0x01CA3F29, 0x0A1CAC25, 0x035100D1, 0x0FB1919C, 0x06EA09F5,
+    0x18A764C6, 0x0F3D30BD, 0x14DBD527, 0x074ADD90, 0x0E5355C0, 0x03979D05, 0x11EC3A5C, 0x0037E505, 0x07699226,
+    0x1EA2294A, 0x03B0B597, 0x0B31D38A, 0x04C9F682, 0x030B187D, 0x012D723F, 0x046A489E, 0x0ACAE384, 0x09F07A16,
+    0x11C005E7, 0x02A47DE9, 0x0398F18E, 0x0097BCAF, 0x17E31809, 0x08CF7783, 0x089DCE99, 0x072523B7, 0x0497196A,
+    0x10BF967C, 0x08739336, 0x11CE0561, 0x0ED2C868, 0x05E77CD3, 0x0E742210, 0x077FC518, 0x0832D842, 0x02BF8CC1,
+    0x053C4639, 0x0128A89D, 0x166894F3, 0x0427CA39, 0x03DC0AA8, 0x083171D6, 0x1DB782FF, 0x04D9D53A, 0x04DB6079,
+    0x0243BE64, 0x048A1030, 0x08254D3F, 0x08C638C6, 0x1622BB34, 0x080B11F2, 0x1BCEAAD2, 0x0C57EF8E, 0x01BCA934,
+    0x1A324BF5, 0x0ECA584D, 0x0AF6D008, 0x0E2C00FB, 0x05DC6BEB, 0x08462ED4, 0x16B530CF, 0x066F4590, 0x08E1AD00,
+    0x1B324935, 0x054528EE, 0x02C65FC2, 0x0EBAC55D, 0x09D61E60, 0x0280F755, 0x105C8FCE, 0x00F545EF, 0x0D4EEB2E,
+    0x1701CD29, 0x01EA56C4, 0x081476DB, 0x0ACA86BF, 0x0682C6FE, 0x0D8E86DA, 0x0665DA9A, 0x092A2557, 0x049DBD83,
+    0x1D7ABF82, 0x007E5C8B, 0x066B9DAE, 0x04E645BC, 0x1F995F48, 0x09068D86, 0x16E8B846, 0x0E60D9DB, 0x08767C6A,
+    0x0922739C, 0x06A018F8, 0x1A712B99, 0x0E3EE07B, 0x07A24C3E, 0x070D3347, 0x0C87FFBE, 0x08C368CB, 0x064358D3,
+    0x14CD8369, 0x0C6EB680, 0x0F404F88, 0x0AC748F2, 0x0A7DB47C, 0x01E39346, 0x103007EF, 0x0C314FE4, 0x02F61B06,
+    0x1E76B365, 0x001C847A, 0x14B56F42, 0x02B816D7, 0x111957D9, 0x0D84C804, 0x106C4562, 0x0350C297, 0x05C3DEE1,
+    0x009BD45E, 0x0B3AF2FB, 0x11FA33D8, 0x01CB952A, 0x0AD0EDD7, 0x031ABE1F, 0x073A9F30, 0x0F031B7D, 0x039613AE,
+    0x18C97D6B, 0x0042EF95, 0x1FD9C8C9, 0x07CB19AA, 0x08A425B3, 0x063DE061, 0x033ABD4C, 0x07F53F61, 0x04555442,
+    0x1D5BA91A, 0x0D0B58C2, 0x1690A1E7, 0x05137305, 0x1727FE6D, 0x0A537748, 0x1FF2AC71, 0x0804D0A6, 0x0C91D5BC,
+    0x194EE5BA, 0x08BC59FF, 0x0EA8C44D, 0x05BADC76, 0x147B7412, 0x0E54DA74, 0x01FC716C, 0x0D2F5562, 0x0DBA5F9A,
+    0x17328AE5, 0x042F78EB, 0x0A00B28C, 0x07F05CC6, 0x066BE241, 0x0FB699E1, 0x1B0B4B37, 0x09D56932, 0x0D7DA890,
+    0x123D08ED, 0x096A0BD3, 0x0C813192, 0x03810570, 0x18FC0632, 0x07E5DD06, 0x10776318, 0x03A74E26, 0x037899C8,
+    0x06E40647, 0x0A77FFE7, 0x12C3AFA6, 0x0E960FD8, 0x0C7733C1, 0x0ED4EF1E, 0x11720E49, 0x09479C88, 0x0131C6FB,
+    0x1DB8592E, 0x0A02241C, 0x03264131, 0x0A44D95B, 0x0C47F87A, 0x07772FED, 0x19EC2424, 0x00BB28F1, 0x0BFFA1E7,
+    0x15C6BDB5, 0x0F141A1D, 0x10B26B93, 0x0B1D3717, 0x0F0C692A, 0x0C5C6E77, 0x0DA00052, 0x03FB4936, 0x03F9F1F0,
+    0x073EEC6E, 0x0E3A4D78, 0x0B661DA9, 0x03389231, 0x090E832A, 0x03C85C63, 0x0A979682, 0x05EBD13B, 0x0CDCC911,
+    0x16BD96B6, 0x07E185DB, 0x1CB7DFEA, 0x01C00DAC, 0x063027BA, 0x07010F68, 0x022E9D7A, 0x00C85634, 0x0B28CE27,
+    0x0573F4D7, 0x09272687, 0x00F8AD04, 0x07C7F2FC, 0x019570C1, 0x03DF0AA5, 0x0A723FF7, 0x064ED39E, 0x00F06700,
+    0x1C3D0C13, 0x028C011B, 0x03F62EA4, 0x0F5833FF, 0x1E9AE4C9, 0x0FDC56F1, 0x1C53B565, 0x0635A406, 0x0B7B8B06,
+    0x188D4EE1, 0x083784A3, 0x0B1E3370, 0x00EBEFFA, 0x1459DD3D, 0x0A73E6F2, 0x1C19DF57, 0x0B798D79, 0x071640D2,
+    0x09A0E317, 0x0CA0A9B0, 0x160BF1DF, 0x06285FF1, 0x12E862B4, 0x00F3CCA4, 0x1898DAA6, 0x0F5EF765, 0x0CAE138A,
+    0x0C1958FA, 0x0961330A, 0x05D2F9D5, 0x0F16E495, 0x0075B6B3, 0x0940D119, 0x1F340124, 0x078CF2C5, 0x040ADF13,
+    0x1FE5A11D, 0x069BBC11, 0x0B1BBA11, 0x00671DB8, 0x0AC13535, 0x00682D2C, 0x03E31A0B, 0x0307DF02, 0x03168489,
+    0x1E062EC0, 0x029E247E, 0x0E154F9D, 0x02590F96, 0x09471AF7, 0x0A3B3EB4, 0x014400EB, 0x07EB1DE7, 0x0C540A2A,
+    0x01E5DD2E, 0x0D8D8969, 0x0DC24639, 0x0406DF7C, 0x0E6BC541, 0x029659B1, 0x13126681, 0x035B11FC, 0x0FC14A94,
+    0x1A21A7EA, 0x0EB08CC2, 0x1E033A62, 0x06BE8BF8, 0x07B6BF23, 0x0632D38F, 0x185963F6, 0x0F2A0AC7, 0x0F6710BD,
+    0x05F07757, 0x03849AE0, 0x1B29C3EE, 0x01950B9F, 0x17C05912, 0x0ADFA73E, 0x1F5705CD, 0x0BC6F211, 0x0C310D5F,
+    0x0103398E, 0x0791C2D4, 0x070ADED6, 0x05242BBA, 0x134364BC, 0x050F980F, 0x193DD451, 0x0984FFE3, 0x06AADE40,
+    0x02604A2A, 0x07D25B14, 0x07F9DD51, 0x00A2206B, 0x04009D6C, 0x0245D0CB, 0x18C60896, 0x0A62EA85, 0x0C624D28,
+    0x15FB54E1, 0x09B8DB3C, 0x1A5C9A2F, 0x033F58A6, 0x07C81EBF, 0x016B6C8C, 0x13C935E0, 0x0FDD092F, 0x00AEB14E,
+    0x0C9C414A, 0x080CFE64, 0x01C19DF9, 0x0B89F9BE, 0x0B87631C, 0x074A5DB8, 0x02EB449F, 0x0BF8410E, 0x05F31D42,
+    0x0F184BF7, 0x06083129, 0x0E1DAEE3, 0x0DAA19E9, 0x077D42CA, 0x0B86B270, 0x044D1F05, 0x0C3456E3, 0x005F637F,
+    0x199EE684, 0x0AA03B80, 0x1AD7DDA5, 0x062EAF03, 0x072B165D, 0x0B02E041, 0x13E743DE, 0x087EA407, 0x01D8F666,
+    0x07AA8783, 0x03316ABC, 0x15E99B46, 0x0EBCACAA, 0x0A4AB9C2, 0x06B31B9E, 0x14092F9E, 0x03F3D676, 0x0CC303EB,
+    0x181A084F, 0x0461999C, 0x0E68A565, 0x05FA4773, 0x0A633671, 0x05F0B576, 0x14F10C8F, 0x055A8CC6, 0x054BA000,
+    0x1145B84B, 0x07D1A198, 0x168D81EC, 0x0B28A8B2, 0x087B971B, 0x0A3082F3, 0x0BBB4DA6, 0x0CD015C3, 0x07CD7CA2,
+    0x10DF7D4B, 0x058C2913, 0x09769AFA, 0x09B15BC6, 0x0BD75287, 0x0FC6B1AE, 0x0B242A8D, 0x0AD378C5, 0x067B78E7,
+    0x0D19D138, 0x07F1F1D2, 0x10E9468C, 0x0ECF7780, 0x1768C2C0, 0x0D8F6334, 0x1F515BAC, 0x08E575E7, 0x087223AB,
+    0x11DC34CC, 0x05CE5EF9, 0x0788BEDE, 0x02B6E6F5, 0x11145A89, 0x0B20349D, 0x010B4FF8, 0x0204B1F2, 0x0FF24BA3,
+    0x1B75880C, 0x020992D8, 0x1BEB476B, 0x05B31BF0, 0x017E3A73, 0x032977C4, 0x05D76549, 0x09EA40E0, 0x0200BDAA,
+    0x194E0FE8, 0x0529E408, 0x181F0BD8, 0x0C9D3149, 0x182C94DA, 0x0A681B17, 0x055C2D08, 0x024CF7D3, 0x02574034,
+    0x145E719B, 0x03DBB377, 0x1ED529F1, 0x01A3F8F5, 0x18ACF879, 0x0E7603AB, 0x137C03F9, 0x02E8F24E, 0x029DF740,
+    0x13257693, 0x081543EC, 0x00D474FD, 0x087D171E, 0x0AA7B0BB, 0x029C29D0, 0x0E06C623, 0x0E909460, 0x0F30F14B,
+    0x1FBA1C99, 0x004A9ABE, 0x19251829, 0x05B27886, 0x1329C734, 0x086DDB6E, 0x159EF83D, 0x034FE562, 0x04500493,
+    0x0DC42DE1, 0x039062AD, 0x1FCDB2EF, 0x0209AA88, 0x1FBE93D3, 0x0297882D, 0x15FFAF28, 0x003F0006, 0x0E848D73,
+    0x0259708D, 0x0CDA4D3E, 0x1E1227F0, 0x03003BD3, 0x1FDB0221, 0x0981AD26, 0x0AF49FC9, 0x0312238F, 0x00A09559,
+    0x0D6E1F41, 0x0A3CF640, 0x0582B899, 0x0A76F036, 0x19FBADB5, 0x0E8B9273, 0x10E4BECD, 0x07A8F608, 0x0DBB688C,
+    0x1B5F6C74, 0x041D079E, 0x04DF965C, 0x06641451, 0x06956839, 0x06732B8A, 0x1D7FD128, 0x04307A03, 0x0953C29F,
+    0x0142FBF9, 0x03B93B88, 0x18293350, 0x0D9AA8D9, 0x04CA50B6, 0x05217F91, 0x0FB0EEF1, 0x0E2BF964, 0x08834D82,
+    0x0C9654BD, 0x03E7F7D5, 0x1586B49E, 0x024FB806, 0x1C1954D8, 0x035BBE90, 0x049FFC9A, 0x0BA5CD2F, 0x08165686,
+    0x0B0C4B37, 0x0C67D922, 0x13BE1FD9, 0x0864F757, 0x0AA4362E, 0x05794894, 0x0DC7D2C1, 0x017C54EE, 0x0E521F0B,
+    0x083A7678, 0x0BA7AB7C, 0x03500E7A, 0x0EC63090, 0x12AE962C, 0x03FEA3CF, 0x0D1549A8, 0x0D7A2460, 0x0D363D64,
+    0x1FE4F89F, 0x0B8B1DB5, 0x140087A8, 0x0EA9C97C, 0x1E3D584A, 0x07F221C5, 0x1011399F, 0x0C9054CD, 0x0B904595,
+    0x089121DA, 0x07A744FE, 0x070F1420, 0x06BB47C7, 0x0A3BD25F, 0x03AE39BD, 0x056716E2, 0x0AFF24AA, 0x0272E099,
+    0x1C568C31, 0x07F7E2EF, 0x166226F5, 0x020F9D97, 0x09F3F949, 0x058CB6AA, 0x102BA8C2, 0x0B965F38, 0x09E36B5C,
+    0x0B319563, 0x0F11654C, 0x1F938503, 0x0D9E928A, 0x04952AE1, 0x0C4DBD40, 0x1EF7E884, 0x0ABBA065, 0x0E242403,
+    0x1C011FE2, 0x03A76C56, 0x07307778, 0x061B8568, 0x0D1538C6, 0x0A4395E0, 0x1F60B47C, 0x0604F19A, 0x08435664,
+    0x1DDC96D9, 0x07FCAFE1, 0x10B6A429, 0x00CFFD8F, 0x08F1D289, 0x00BC2ED8, 0x077F6AB2, 0x0B94371C, 0x01BA8EB2,
+    0x04809733, 0x083DCC07, 0x0E39F4F3, 0x06267E50, 0x1E1400FB, 0x0A4EA44F, 0x009C14B4, 0x027C8234, 0x0DC09C4D,
+    0x1B52190D, 0x090517D8, 0x00173BF2, 0x0222B5A2, 0x0BA864DA, 0x02BBA7F7, 0x1A8809BA, 0x04B5299A, 0x0A0BF7A5,
+    0x0741C428, 0x07E36D5D, 0x12C587C1, 0x0FC3CEC1, 0x0CCA0D50, 0x05DCED13, 0x0E27699C, 0x088F2117, 0x0C7A85E8,
+    0x115C3651, 0x03D7CA15, 0x0899FA85, 0x09587268, 0x1B723E24, 0x07233825, 0x0B8ED606, 0x0FE9D2E5, 0x0EF779EE,
+    0x06819674, 0x060EAE0B, 0x1E3F3AFB, 0x0DC7A749, 0x1578A712, 0x092590A2, 0x18D879A8, 0x03986AB4, 0x090FEEE8,
+    0x1F8D7B76, 0x0AB3DA71, 0x08B1418D, 0x01EF245A, 0x053CB13F, 0x064C691A, 0x01A3C6D8, 0x09C06C93, 0x0C79C9B7,
+    0x0BF027C3, 0x07139B82, 0x129D2A64, 0x0BC8306F, 0x0BACF6B4, 0x04471364, 0x11FE34F7, 0x076DE51D, 0x09480851,
+    0x0840D6EF, 0x01107D8D, 0x13D69FC5, 0x04A5FE0D, 0x03501AC8, 0x0D69F929, 0x1E04D403, 0x02AD212A, 0x01BF887F,
+    0x09BF70ED, 0x07987F52, 0x00ADD4D8, 0x0E5711DB, 0x19F95DD9, 0x0FD78828, 0x08198FE4, 0x08D15CC6, 0x00DC2A4A,
+    0x11E3FD1A, 0x067E76C7, 0x0E6C1433, 0x07C26119, 0x17D999DA, 0x0E5E51A6, 0x0AF7EB4B, 0x09200386, 0x0135C28C,
+    0x0090C23E, 0x09D16E34, 0x10C4663C, 0x04FB1B4D, 0x0639A9A9, 0x04FAE3C4, 0x0A5338A3, 0x091E3662, 0x0C61BC9B,
+    0x0F558276, 0x0EA57E64, 0x1AD15AF0, 0x0BE7492E, 0x1B53B105, 0x032E6A4E, 0x07A6DCD7, 0x09B94F5F, 0x04C49ED6,
+    0x07BC4017, 0x0FC94132, 0x0BFC579E, 0x0EF814B8, 0x10021362, 0x0354A6DA, 0x0EB228E3, 0x0886D903, 0x03FFB03A,
+    0x09C899AD, 0x06485723, 0x02DD7B1A, 0x04C184DC, 0x160CB41D, 0x05B58341, 0x147027BA, 0x0CDD49CF, 0x05B23414,
+    0x00A031DB, 0x0F2F2AC5, 0x0D638315, 0x092EE2EC, 0x0A6AFCB8, 0x080248A5, 0x1FA9429D, 0x081C3E39, 0x0DFD0578,
+    0x174CC33A, 0x03F67A8D, 0x1BF536E4, 0x0A1A5DB1, 0x1BD720CA, 0x017CF784, 0x104CE2E9, 0x054447F7, 0x053EE397,
+    0x0A2828F8, 0x0BD28692, 0x15F6B1CC, 0x09DADEF7, 0x1ECA16D1, 0x065E7B23, 0x0623206B, 0x0FB0FDAA, 0x051174E6,
+    0x016EF143, 0x0962B20D, 0x0CEE3305, 0x00355458, 0x1AF559E6, 0x0E5F09B5, 0x1B5E7C2F, 0x019EAD05, 0x0B301DD8,
+    0x1753F978, 0x0840F954, 0x0AF9C1CC, 0x01939BAD, 0x088995BC, 0x0AD662B1, 0x1CBC3D58, 0x0676A680, 0x0EDA046B,
+];
+
+/// 基点的奇数倍 G, 3G, ..., 63G
+#[cfg(feature = "window-6")]
+pub(crate) static BASE_ODD_MULTIPLES: [u32; ODD_MULTIPLES * 9 * 2] = [
+    0x0830053D, 0x0328990F, 0x06C04FE1, 0x0C0F72E5, 0x01E19F3C, 0x0666B093, 0x0175A87B, 0x0EC38276, 0x0222CF4B,
+    0x185A1BBA, 0x0354E593, 0x1295FAC1, 0x0F2BC469, 0x047C60FA, 0x0C19B8A9, 0x0F63533E, 0x0903AE6B, 0x0C79ACBA,
+    0x16B13C94, 0x0DA54FDA, 0x0F0A0D1C, 0x02944EDA, 0x0505133B, 0x08C233AD, 0x19028996, 0x07FD10F8, 0x06033FAD,
+    0x165F8320, 0x010FBE94, 0x0BCE600A, 0x05295C3E, 0x1E4BFA05, 0x0FE9B704, 0x0D33125A, 0x00B767F7, 0x0515A68F,
+    0x153902C4, 0x05756336, 0x1FCC539A, 0x02C7910E, 0x1A628AD5, 0x02FC7BD7, 0x0E5EB4D5, 0x06C7F0EB, 0x01E887DE,
+    0x068A0266, 0x081D68FD, 0x007D182E, 0x0A5DEF1B, 0x0C77D987, 0x0930D889, 0x1A2C609A, 0x0C2854CE, 0x0F682A4E,
+    0x03E0CF09, 0x03531C78, 0x1325202A, 0x0A0C9C38, 0x0C6285C4, 0x0DA6B3F8, 0x0A213B96, 0x02B3F7D8, 0x077E925C,
+    0x0BEC30DA, 0x03A35C1E, 0x1A01B831, 0x04D173AB, 0x0DD78722, 0x0648AF66, 0x1DE5B26E, 0x0E7709AE, 0x05740B51,
+    0x01F7BD0C, 0x0E795C33, 0x01AF8F98, 0x0024AD59, 0x14DA472F, 0x0E0ED219, 0x120E496F, 0x008D2A45, 0x0EFB10E8,
+    0x10F6099D, 0x0BF8B5B9, 0x1F44280F, 0x0B1D45D7, 0x14B4B1E2, 0x05954E9B, 0x13177044, 0x0964D2FE, 0x00454D84,
+    0x0843C22A, 0x0252AD03, 0x0FFC012B, 0x0E463E35, 0x0933BAAB, 0x07F62942, 0x12364306, 0x01C1B0E3, 0x0907B42D,
+    0x020E537E, 0x0D6F9B3C, 0x17061E72, 0x020108AD, 0x0C3EAEEC, 0x0B6E7CB1, 0x10A6FFA8, 0x0F30C83D, 0x0A507314,
+    0x0001FC9C, 0x0E51C6A8, 0x04694134, 0x05EFEC4E, 0x15973ED1, 0x047786F0, 0x17199EEA, 0x00214E7C, 0x0790193A,
+    0x0FEB8C9A, 0x0ADEBBEC, 0x0350524F, 0x04F5A1F5, 0x0AFCD778, 0x046C9BB1, 0x18059FB8, 0x050A3124, 0x08BF4A11,
+    0x1BD2FA9A, 0x0489343D, 0x12CE1FF3, 0x071C55DD, 0x1910CE0A, 0x04473F77, 0x1C49619A, 0x028C1DFD, 0x01BF1282,
+    0x075D064C, 0x013F30E7, 0x1274A1F7, 0x0335466E, 0x0CA3ECDF, 0x0B88DA21, 0x0077E456, 0x0CF0F16F, 0x058B83D3,
+    0x1F7BFDFF, 0x0126193C, 0x16D844BC, 0x0EE9064C, 0x1B2C2518, 0x0E8067CA, 0x0F1229DA, 0x06112151, 0x07C66028,
+    0x01154083, 0x07B34E13, 0x1ADF28A8, 0x0ED12809, 0x021CE5FA, 0x09633737, 0x1D737F16, 0x053314C0, 0x07B477B4,
+    0x0CE2422D, 0x0FD33808, 0x0EFE463E, 0x04B84042, 0x089FCDCF, 0x0BB23279, 0x1B184687, 0x0B76DB79, 0x0C5B1C7E,
+    0x1F12C9C9, 0x0EDAB1C4, 0x0139F7FC, 0x06831BA7, 0x1A79F709, 0x07026A6B, 0x08E398F2, 0x081720F6, 0x0C648DB7,
+    0x00AA3901, 0x0879486A, 0x0E61BE03, 0x0EF5072C, 0x08340B08, 0x02086E4D, 0x08C7FA57, 0x03B64524, 0x01E03C03,
+    0x1D278712, 0x0017E057, 0x00DD6423, 0x064CECC2, 0x11CCE725, 0x040077A1, 0x0177726E, 0x071002AF, 0x0D57CB98,
+    0x1D74FE73, 0x05CBED22, 0x088D4392, 0x0173B762, 0x0AE263E2, 0x07E59DA4, 0x07F6E264, 0x0A230707, 0x0BED5CB2,
+    0x0F3F17A2, 0x069C304C, 0x1662FA4F, 0x05838550, 0x12CCB1C6, 0x01CAE9BC, 0x0A71FDA3, 0x023EDDBF, 0x0237709B,
+    0x0E92464D, 0x0957D29E, 0x03F82945, 0x0E1EFC33, 0x1EE09D6E, 0x05A5CA33, 0x0D23EA8C, 0x09D72EA2, 0x0BCA754F,
+    0x1FBB8EC9, 0x081759BE, 0x0DAFF374, 0x0A3A5C4F, 0x0464EB02, 0x0AE594F5, 0x04B1F7C7, 0x0A8A0804, 0x04E3CD0A,
+    0x06F84998, 0x0861816D, 0x07975A32, 0x0A771721, 0x01D4ADDA, 0x0F48CBE5, 0x195B6804, 0x05378405, 0x00E8D4EF,
+    0x1A3BDEB6, 0x0B07A84C, 0x1D9F9B6D, 0x0FF3FC30, 0x00FDCA10, 0x0A49A486, 0x075C5D9C, 0x0B93A488, 0x00AC1CFC,
+    0x09E3E4AE, 0x064C4B05, 0x1196DCDC, 0x019E4580, 0x0ADEF658, 0x0D36080B, 0x14553997, 0x06565FA8, 0x0C75F992,
+    0x00C81F6F, 0x0929A393, 0x1617179E, 0x09C3F6D9, 0x1DF46E48, 0x0B2FE3C2, 0x0EC42AB7, 0x0D12EFC5, 0x003B1E8C,
+    0x11138F14, 0x0179A8BB, 0x0A32C57B, 0x03D5C856, 0x0D1B034D, 0x0579646E, 0x0C393948, 0x0C77AA8C, 0x0F005F67,
+    0x0678DDD7, 0x04793132, 0x1CBABC1A, 0x00956E70, 0x179D8C63, 0x09CF038D, 0x098A28A4, 0x03FA7BD6, 0x0E9C5BF6,
+    0x1B74581D, 0x011B5E53, 0x0D4F1170, 0x04224413, 0x037DD363, 0x06CF0FC8, 0x0F04565B, 0x09528294, 0x09238EDB,
+    0x129BB8F8, 0x039B9D1B, 0x0E861018, 0x00673AB5, 0x02006FD9, 0x036761CD, 0x100F8AC7, 0x05E03D4A, 0x0B3F2653,
+    0x06E67978, 0x0494A833, 0x0D815760, 0x0115013F, 0x16C846D3, 0x055011C6, 0x00C008AC, 0x0BA6AD00, 0x077A1498,
+    0x1265BE46, 0x08E2D03D, 0x1F5159DC, 0x06CA03D0, 0x0D87C2CF, 0x098CA8DD, 0x05EB59C3, 0x0D2A41B7, 0x02B245F5,
+    0x1B521F92, 0x0086D5E7, 0x0277822C, 0x02FFEAE6, 0x049CA2C7, 0x0846E49A, 0x0430FD21, 0x07BC9722, 0x073FDB0E,
+    0x1B4D3199, 0x0EF8F78F, 0x13BB4F96, 0x06E1114B, 0x192DD814, 0x027B8A6D, 0x1A517AE1, 0x0B298DD5, 0x0A836B6D,
+    0x073AC8DB, 0x01913900, 0x0BA338C5, 0x0BD17989, 0x13EB4C1D, 0x04F9648F, 0x1AB7F4C3, 0x02753A40, 0x05C6B21E,
+    0x0DABCC54, 0x058D334B, 0x002B127D, 0x082A23F2, 0x0700D8AD, 0x044D8ABA, 0x1BAB15F8, 0x09E8E7DB, 0x09B07009,
+    0x1658E079, 0x08CA01B8, 0x171A5097, 0x0DD29C55, 0x046AB02D, 0x0238F6E5, 0x10485025, 0x03BF6D71, 0x088B64D1,
+    0x02F68F7A, 0x01F66DEF, 0x1B3EE542, 0x03D2D174, 0x019CABB1, 0x08299985, 0x11BB7CE4, 0x0696C714, 0x0FD4C320,
+    0x0428E253, 0x0AAA417E, 0x0A0A2F91, 0x07AE1BC4, 0x1FCB9E67, 0x01EA40A0, 0x0D9E3548, 0x04A4A44D, 0x07424C3D,
+    0x03CF4038, 0x0C79248D, 0x1AB2BDBF, 0x08E6ACA0, 0x13459B9F, 0x08846681, 0x02BC2207, 0x023EEF7E, 0x0F6F26F1,
+    0x1F740876, 0x0C1F5D3B, 0x15F03BB5, 0x07C4B74B, 0x1D80D7FA, 0x06F6B8B2, 0x1FFA87CE, 0x074C522B, 0x09983899,
+    0x13D28770, 0x0B4AFA38, 0x0E565A5E, 0x093F28AA, 0x076B4626, 0x09F9FCAF, 0x08D38B96, 0x04890FED, 0x0F5E811C,
+    0x0423157B, 0x06389971, 0x1DA3A776, 0x073AA44B, 0x0CC295D3, 0x02143F7E, 0x12877E08, 0x05A8D90C, 0x07EE6A05,
+    0x005B39C1, 0x0DE9323C, 0x1D0E7D5F, 0x048F1B26, 0x1CDA2C02, 0x0B4AB90E, 0x1079C548, 0x0FD44634, 0x00EDC6C4,
+    0x0529AFD4, 0x03D9CA2A, 0x12569B12, 0x0831DA24, 0x0361C34C, 0x0BA6A376, 0x0E1A943A, 0x002580D8, 0x07AEAFA2,
+    0x1C83CAAD, 0x092C2C03, 0x10C219DA, 0x0CAF5F19, 0x0C92D322, 0x006B8613, 0x011C3605, 0x0E45E206, 0x00A87E59,
+    0x00802F1C, 0x08B28E32, 0x1725FC8E, 0x0C79F4B8, 0x1660E77F, 0x0BEBF3B8, 0x1592A5ED, 0x037590B6, 0x0EEBBED5,
+    0x05144398, 0x0E5CBE6D, 0x0B82DB32, 0x0356D571, 0x074B745C, 0x0D421B8E, 0x12D137EE, 0x0A35F85A, 0x08BA7FCF,
+    0x050CDCAE, 0x0B1701F3, 0x1E0F2EF3, 0x04239ACE, 0x0B16F83B, 0x0BB6AD5E, 0x0EF9575E, 0x0A56C195, 0x08A00930,
+    0x0A931111, 0x0539D6ED, 0x00FF6A0D, 0x070D092F, 0x06280BB6, 0x07BF7022, 0x0BF2DF05, 0x0693535C, 0x020727AF,
+    0x041A42DA, 0x0C84E345, 0x06171C36, 0x07B5AE33, 0x0E9B9597, 0x047CB38A, 0x0AA51A6E, 0x0F82BE41, 0x07D9B09C,
+    0x125DBA58, 0x00906352, 0x1447F6F1, 0x0C9B13E1, 0x1FEFB6A1, 0x02A4FBA8, 0x14146E68, 0x013CAFC8, 0x0D1C1A3F,
+    0x012E7ACC, 0x05183EB0, 0x1AF07C30, 0x033B04AD, 0x17098E73, 0x05C5D208, 0x1BDD3276, 0x06ED6BCD, 0x0599AFF7,
+    0x07C39430, 0x0688B49A, 0x03F2F151, 0x0ADDDD30, 0x1A5B0DF5, 0x07465894, 0x1D42688C, 0x035F525E, 0x0E082F9C,
+    0x183EBA77, 0x092C36C7, 0x1DF7569D, 0x058CE308, 0x1B7B0F30, 0x04078DF8, 0x18C80985, 0x0553790D, 0x06D15355,
+    0x0C3A4794, 0x0A81EAAF, 0x0DAA18FB, 0x0041A6AD, 0x1DDC31A2, 0x0BB697E4, 0x002A035D, 0x0AB4E509, 0x064C8507,
+    0x18683F09, 0x0302D589, 0x1130B9AF, 0x088D4027, 0x01A19324, 0x0020FB99, 0x028C2FB5, 0x0BBB8B0D, 0x0AFADD25,
+    0x015B67F6, 0x00745688, 0x13123E92, 0x0599E509, 0x0D28002D, 0x07F50B87, 0x1A041446, 0x0B6FED63, 0x0D30E22F,
+    0x0CB8CCB5, 0x0F6110A8, 0x008B4C55, 0x0D7F0D86, 0x137E6EE1, 0x012BBFB0, 0x17537CF4, 0x00606B5E, 0x0E4537BC,
+    0x03236872, 0x04913CFD, 0x1FA8A436, 0x0C7B6AD3, 0x090CFA0D, 0x046DBDB0, 0x1919DF31, 0x042916FE, 0x0B275FC9,
+];
@@ -0,0 +1,304 @@
+//! SM2推荐曲线上的点与模n标量，可在其上构造承诺、DH变体等协议
+//!
+//! 点加使用完备公式，标量乘法使用Montgomery阶梯，执行路径不依赖标量与点的取值（无穷远点除外）。
+
+use std::fmt::{Debug, Formatter};
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num_bigint::BigUint;
+use num_traits::One;
+use rand::{CryptoRng, RngCore};
+
+use crate::env::EnvRng;
+use crate::sm2::ecc::on_curve;
+use crate::sm2::hazmat::ScalarBytesBe;
+use crate::sm2::key::{to_32_bytes, PublicKey};
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+use crate::sm2::key::PrivateKey;
+use crate::sm2::p256::P256Elliptic;
+use crate::sm2::p256::point::{P256AffinePoint, P256BasePoint, P256JacobianPoint};
+use crate::sm2::p256::scalar::Scalar as Limbs;
+
+/// 模n的标量，取值范围\[0, n)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Scalar(Limbs);
+
+impl Scalar {
+    pub const ZERO: Scalar = Scalar(Limbs::ZERO);
+    pub const ONE: Scalar = Scalar(Limbs::ONE);
+
+    /// 大端字节串，不小于n时减去n
+    pub fn from_bytes_be(bytes: &[u8; 32]) -> Self {
+        Scalar(Limbs::from_bytes_be(bytes))
+    }
+
+    /// 任意大小的整数，模n约简
+    pub fn from_biguint(n: &BigUint) -> Self {
+        Scalar(Limbs::from_biguint(&(n % &P256Elliptic::init().ec.n)))
+    }
+
+    pub fn to_bytes_be(&self) -> [u8; 32] {
+        self.0.to_bytes_be()
+    }
+
+    pub fn to_biguint(&self) -> BigUint {
+        self.0.to_biguint()
+    }
+
+    /// 随机取自\[1, n-1]
+    pub fn random() -> Self {
+        Self::random_with_rng(&mut EnvRng)
+    }
+
+    /// 同[`Scalar::random`]，随机字节取自`rng`
+    pub fn random_with_rng(rng: &mut (impl CryptoRng + RngCore)) -> Self {
+        let ec = &P256Elliptic::init().ec;
+        Scalar::from_biguint(&ec.random_with(rng, BigUint::one(), &ec.n - 1u8))
+    }
+
+    /// 模n逆元，0没有逆元
+    pub fn invert(&self) -> Option<Self> {
+        (*self != Scalar::ZERO).then(|| Scalar(self.0.invert()))
+    }
+}
+
+impl Add for Scalar {
+    type Output = Scalar;
+
+    fn add(self, other: Scalar) -> Scalar {
+        Scalar(self.0.add(&other.0))
+    }
+}
+
+impl Sub for Scalar {
+    type Output = Scalar;
+
+    fn sub(self, other: Scalar) -> Scalar {
+        Scalar(self.0.subtract(&other.0))
+    }
+}
+
+impl Mul for Scalar {
+    type Output = Scalar;
+
+    fn mul(self, other: Scalar) -> Scalar {
+        Scalar(self.0.multiply(&other.0))
+    }
+}
+
+impl Neg for Scalar {
+    type Output = Scalar;
+
+    fn neg(self) -> Scalar {
+        Scalar(Limbs::ZERO.subtract(&self.0))
+    }
+}
+
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+impl From<&PrivateKey> for Scalar {
+    fn from(key: &PrivateKey) -> Self {
+        Scalar::from_biguint(&key.value())
+    }
+}
+
+/// SM2推荐曲线上的点，含无穷远点
+///
+/// ```
+/// use yarism::sm2::{Point, Scalar};
+///
+/// // DH：双方各自的临时私钥与公钥
+/// let (a, b) = (Scalar::random(), Scalar::random());
+/// let (pa, pb) = (Point::mul_base(&a), Point::mul_base(&b));
+/// assert_eq!(pb * a, pa * b);
+///
+/// // Pedersen承诺 vG + rH
+/// let h = Point::mul_base(&Scalar::random());
+/// let commit = |v: Scalar, r: Scalar| Point::generator() * v + h * r;
+/// let (v1, r1, v2, r2) = (Scalar::from_biguint(&3u8.into()), Scalar::random(), Scalar::ONE, Scalar::random());
+/// assert_eq!(commit(v1, r1) + commit(v2, r2), commit(v1 + v2, r1 + r2));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Point(P256JacobianPoint);
+
+impl Point {
+    /// 无穷远点，加法单位元
+    pub fn identity() -> Self {
+        Point(P256JacobianPoint::infinity())
+    }
+
+    /// 基点G
+    pub fn generator() -> Self {
+        let ec = &P256Elliptic::init().ec;
+        Point(P256AffinePoint::from_bytes(&to_32_bytes(ec.gx.to_bytes_be()), &to_32_bytes(ec.gy.to_bytes_be())).to_jacobian())
+    }
+
+    /// scalar·G，使用基点的预计算表，比`Point::generator() * scalar`快
+    pub fn mul_base(scalar: &Scalar) -> Self {
+        Point(P256BasePoint::comb(&ScalarBytesBe(scalar.to_bytes_be()).to_le()))
+    }
+
+    /// a·G + b·point（Shamir技巧），两个标量按wNAF交错、共用一组倍点运算，耗时约为分别相乘再相加的一半
+    ///
+    /// 可变时间，只用于标量公开的场合，如验证签名、Schnorr证明等。
+    pub fn mul_base_add_vartime(a: &Scalar, point: &Point, b: &Scalar) -> Self {
+        if point.is_identity() {
+            return Point::mul_base(a);
+        }
+        Point(P256AffinePoint::double_multiply_vartime(&a.to_bytes_be(), &point.0.to_affine_point(), &b.to_bytes_be()))
+    }
+
+    /// 大端序仿射坐标，不在曲线上时返回None
+    pub fn from_affine(x: &[u8; 32], y: &[u8; 32]) -> Option<Self> {
+        on_curve(&P256Elliptic::init().ec, &BigUint::from_bytes_be(x), &BigUint::from_bytes_be(y))
+            .then(|| Point(P256AffinePoint::from_bytes(x, y).to_jacobian()))
+    }
+
+    /// 大端序仿射坐标，无穷远点返回None
+    pub fn to_affine(&self) -> Option<([u8; 32], [u8; 32])> {
+        (!self.is_identity()).then(|| self.0.to_affine_point().to_bytes())
+    }
+
+    /// 无穷远点不是合法的公钥，返回None
+    pub fn to_public_key(&self) -> Option<PublicKey> {
+        self.to_affine().map(|(x, y)| PublicKey::new(BigUint::from_bytes_be(&x), BigUint::from_bytes_be(&y)))
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.0.is_infinity()
+    }
+}
+
+impl From<&PublicKey> for Point {
+    fn from(key: &PublicKey) -> Self {
+        Point(key.limbs().to_jacobian())
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point(self.0.add(&other.0))
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        self + -other
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point(self.0.negate())
+    }
+}
+
+impl Mul<Scalar> for Point {
+    type Output = Point;
+
+    fn mul(self, scalar: Scalar) -> Point {
+        if self.is_identity() {
+            return self;
+        }
+        Point(self.0.to_affine_point().multiply_ladder(&scalar.to_bytes_be()))
+    }
+}
+
+impl PartialEq for Point {
+    fn eq(&self, other: &Point) -> bool {
+        self.0.to_affine_point().to_bytes() == other.0.to_affine_point().to_bytes()
+    }
+}
+
+impl Eq for Point {}
+
+impl Debug for Point {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.to_affine() {
+            Some((x, y)) => f.debug_struct("Point").field("x", &hex::encode(x)).field("y", &hex::encode(y)).finish(),
+            None => f.write_str("Point(identity)"),
+        }
+    }
+}
+
+
+#[cfg(all(test, feature = "sm2-sign", feature = "sm2-encrypt"))]
+mod tests {
+    use crate::sm2::hazmat;
+    use crate::sm2::key::{HexKey, PrivateKey};
+
+    use super::*;
+
+    const PRK: &str = "6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e";
+    const PUK: &str = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+
+    #[test]
+    fn arithmetic() {
+        let (g, o) = (Point::generator(), Point::identity());
+        let two = Scalar::ONE + Scalar::ONE;
+        assert_eq!(g + g, g * two);
+        assert_eq!(g + g, Point::mul_base(&two));
+        assert_eq!(g - g, o);
+        assert_eq!(g + -g, o);
+        assert_eq!(o + g, g);
+        assert_eq!(g + o, g);
+        assert_eq!(o * two, o);
+        assert_eq!(g * Scalar::ZERO, o);
+        assert_eq!(-o, o);
+        assert!(o.to_affine().is_none() && o.to_public_key().is_none());
+        assert_eq!(format!("{:?}", o), "Point(identity)");
+
+        let (a, b) = (Scalar::random(), Scalar::random());
+        assert_eq!(g * a + g * b, Point::mul_base(&(a + b)));
+        assert_eq!((g * b) * a, g * (a * b));
+        assert_eq!(g * a - g * b, g * (a - b));
+        assert_eq!(Point::mul_base(&-a), -(g * a));
+        assert_eq!(hazmat::scalar_base_multiply(ScalarBytesBe(a.to_bytes_be())), Point::mul_base(&a).to_affine().unwrap());
+    }
+
+    #[test]
+    fn mul_base_add_vartime() {
+        let (g, o) = (Point::generator(), Point::identity());
+        let (a, b) = (Scalar::random(), Scalar::random());
+        let p = Point::mul_base(&Scalar::random());
+        assert_eq!(Point::mul_base_add_vartime(&a, &p, &b), Point::mul_base(&a) + p * b);
+        assert_eq!(Point::mul_base_add_vartime(&a, &o, &b), Point::mul_base(&a));
+        assert_eq!(Point::mul_base_add_vartime(&Scalar::ZERO, &p, &b), p * b);
+        assert_eq!(Point::mul_base_add_vartime(&a, &g, &-a), o);
+        assert_eq!(Point::mul_base_add_vartime(&-Scalar::ONE, &g, &Scalar::ONE), o);
+    }
+
+    #[test]
+    fn scalar() {
+        let a = Scalar::random();
+        assert_eq!(a * a.invert().unwrap(), Scalar::ONE);
+        assert_eq!(Scalar::ZERO.invert(), None);
+        assert_eq!(a + -a, Scalar::ZERO);
+        assert_eq!(-Scalar::ZERO, Scalar::ZERO);
+
+        let n = P256Elliptic::init().ec.n.clone();
+        assert_eq!(Scalar::from_biguint(&(&n + 5u8)), Scalar::from_biguint(&BigUint::from(5u8)));
+        assert_eq!(Scalar::from_bytes_be(&to_32_bytes(n.to_bytes_be())), Scalar::ZERO);
+        assert_eq!(-Scalar::ONE, Scalar::from_biguint(&(&n - 1u8)));
+        assert_eq!(Scalar::from_bytes_be(&a.to_bytes_be()), a);
+        assert_eq!(Scalar::from_biguint(&a.to_biguint()), a);
+    }
+
+    #[test]
+    fn keys() {
+        let prk = PrivateKey::decode(PRK);
+        let puk = Point::mul_base(&Scalar::from(&prk)).to_public_key().unwrap();
+        assert_eq!(puk.encode(), PUK);
+        assert_eq!(Point::from(&PublicKey::decode(PUK)), Point::generator() * Scalar::from(&prk));
+
+        let (x, mut y) = Point::from(&puk).to_affine().unwrap();
+        assert_eq!(Point::from_affine(&x, &y), Some(Point::from(&puk)));
+        y[31] ^= 1;
+        assert_eq!(Point::from_affine(&x, &y), None);
+    }
+}
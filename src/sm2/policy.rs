@@ -0,0 +1,206 @@
+//! 密钥使用策略
+//!
+//! 以[`Crypto::with_policy`](crate::sm2::Crypto::with_policy)设置后，创建签名器与加密器时先以[`KeyPolicy::check`]
+//! 检查密钥，不通过时拒绝创建。组织的密钥管理要求（禁用的密钥、有效期、密钥生成时的随机源）由此集中执行，
+//! 不必在每个调用处重复检查。[`StandardPolicy`]按公钥指纹登记这些信息，也可以实现[`KeyPolicy`]接入已有的密钥管理系统。
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+use crate::clock::Clock;
+use crate::env::EnvClock;
+use crate::error_code::{Code, ErrorCode};
+use crate::sm2::key::PublicKey;
+
+/// 密钥的用途
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyUsage {
+    /// 签名，检查的是签名方自己的公钥
+    Sign,
+    /// 加密，检查的是接收方的公钥
+    Encrypt,
+}
+
+/// 生成密钥时使用的随机源，按可信程度递增排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum EntropySource {
+    /// 未登记或来源不明
+    #[default]
+    Unspecified,
+    /// 操作系统随机源等软件实现的CSPRNG
+    Software,
+    /// 密码机、密码卡或经检测的物理随机源
+    Hardware,
+}
+
+/// 创建签名器或加密器前的密钥检查
+pub trait KeyPolicy {
+    /// 允许`key`用于`usage`时返回Ok
+    fn check(&self, key: &PublicKey, usage: KeyUsage) -> Result<(), PolicyError>;
+}
+
+/// 密钥未通过策略检查的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyError {
+    /// 公钥指纹在禁用列表中
+    Banned { fingerprint: [u8; 32] },
+    /// 已超过有效期，`not_after`为UNIX时间戳（秒）
+    Expired { not_after: i64 },
+    /// 生成密钥的随机源低于要求
+    WeakEntropySource { source: EntropySource, required: EntropySource },
+    /// 自定义策略拒绝，附带原因
+    Rejected(String),
+}
+
+impl Display for PolicyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolicyError::Banned { fingerprint } => write!(f, "The key {} is banned.", hex::encode(fingerprint)),
+            PolicyError::Expired { not_after } => write!(f, "The key expired at {}.", not_after),
+            PolicyError::WeakEntropySource { source, required } => {
+                write!(f, "The key was generated from {:?} entropy, {:?} is required.", source, required)
+            }
+            PolicyError::Rejected(reason) => write!(f, "The key is rejected by the policy. {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for PolicyError {}
+
+impl ErrorCode for PolicyError {
+    fn code(&self) -> Code {
+        Code::INVALID_KEY
+    }
+}
+
+/// 登记在[`StandardPolicy`]中的密钥信息
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KeyRecord {
+    /// 生成密钥时使用的随机源
+    pub source: EntropySource,
+    /// 有效期截止的UNIX时间戳（秒），None为不限
+    pub not_after: Option<i64>,
+}
+
+/// 按公钥指纹（[`PublicKey::fingerprint`]）登记禁用列表、有效期与随机源的策略
+///
+/// 未登记的密钥视为来源不明、不限有效期。
+///
+/// ```
+/// # #[cfg(feature = "sm2-sign")] {
+/// use std::rc::Rc;
+/// use yarism::sm2::{Crypto, HexKey, KeyError, KeyPair, PrivateKey, PublicKey};
+/// use yarism::sm2::policy::{EntropySource, KeyRecord, PolicyError, StandardPolicy};
+///
+/// let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+/// let prk = PrivateKey::decode("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e");
+///
+/// let policy = StandardPolicy::new()
+///     .with_min_source(EntropySource::Hardware)
+///     .register(puk.fingerprint(), KeyRecord { source: EntropySource::Software, not_after: None });
+/// let crypto = Crypto::default().with_policy(Rc::new(policy));
+///
+/// let Err(KeyError::PolicyViolation(e)) = crypto.try_signer(KeyPair::new(prk, puk)) else { panic!() };
+/// assert_eq!(e, PolicyError::WeakEntropySource { source: EntropySource::Software, required: EntropySource::Hardware });
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct StandardPolicy {
+    banned: HashSet<[u8; 32]>,
+    records: HashMap<[u8; 32], KeyRecord>,
+    min_source: EntropySource,
+    clock: Rc<dyn Clock>,
+}
+
+impl Default for StandardPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StandardPolicy {
+    /// 不禁用任何密钥、不要求随机源，有效期按运行环境的时钟判断
+    pub fn new() -> Self {
+        StandardPolicy { banned: HashSet::new(), records: HashMap::new(), min_source: EntropySource::Unspecified, clock: Rc::new(EnvClock) }
+    }
+
+    /// 禁用指纹为`fingerprint`的密钥
+    pub fn ban(mut self, fingerprint: [u8; 32]) -> Self {
+        self.banned.insert(fingerprint);
+        self
+    }
+
+    /// 登记指纹为`fingerprint`的密钥的随机源与有效期，重复登记时覆盖
+    pub fn register(mut self, fingerprint: [u8; 32], record: KeyRecord) -> Self {
+        self.records.insert(fingerprint, record);
+        self
+    }
+
+    /// 要求密钥由不低于`source`的随机源生成，未登记的密钥视为[`EntropySource::Unspecified`]
+    pub fn with_min_source(mut self, source: EntropySource) -> Self {
+        self.min_source = source;
+        self
+    }
+
+    /// 判断有效期时使用的时钟
+    pub fn with_clock(mut self, clock: Rc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+}
+
+impl KeyPolicy for StandardPolicy {
+    fn check(&self, key: &PublicKey, _usage: KeyUsage) -> Result<(), PolicyError> {
+        let fingerprint = key.fingerprint();
+        if self.banned.contains(&fingerprint) {
+            return Err(PolicyError::Banned { fingerprint });
+        }
+        let record = self.records.get(&fingerprint).copied().unwrap_or_default();
+        if let Some(not_after) = record.not_after {
+            if self.clock.now() > not_after {
+                return Err(PolicyError::Expired { not_after });
+            }
+        }
+        if record.source < self.min_source {
+            return Err(PolicyError::WeakEntropySource { source: record.source, required: self.min_source });
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::clock::FixedClock;
+    use crate::sm2::key::HexKey;
+
+    use super::*;
+
+    const PUBLIC_KEY: &str = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+
+    #[test]
+    fn standard_policy() {
+        let key = PublicKey::decode(PUBLIC_KEY);
+        let fingerprint = key.fingerprint();
+        assert_eq!(StandardPolicy::new().check(&key, KeyUsage::Sign), Ok(()));
+
+        let banned = StandardPolicy::new().ban(fingerprint);
+        assert_eq!(banned.check(&key, KeyUsage::Encrypt), Err(PolicyError::Banned { fingerprint }));
+        assert_eq!(banned.check(&key, KeyUsage::Encrypt).unwrap_err().code(), Code::INVALID_KEY);
+
+        let clock = Rc::new(FixedClock::new(1_700_000_000));
+        let expiring = StandardPolicy::new()
+            .with_clock(clock.clone())
+            .register(fingerprint, KeyRecord { source: EntropySource::Hardware, not_after: Some(1_700_000_000) });
+        assert_eq!(expiring.check(&key, KeyUsage::Sign), Ok(()));
+        clock.advance(1);
+        assert_eq!(expiring.check(&key, KeyUsage::Sign), Err(PolicyError::Expired { not_after: 1_700_000_000 }));
+
+        let strict = StandardPolicy::new().with_min_source(EntropySource::Software);
+        let weak = PolicyError::WeakEntropySource { source: EntropySource::Unspecified, required: EntropySource::Software };
+        assert_eq!(strict.check(&key, KeyUsage::Sign), Err(weak));
+        let strict = strict.register(fingerprint, KeyRecord { source: EntropySource::Hardware, not_after: None });
+        assert_eq!(strict.check(&key, KeyUsage::Sign), Ok(()));
+    }
+}
@@ -0,0 +1,81 @@
+//! RFC 6979确定性随机数k的派生，以HMAC-SM3代替原文的HMAC-SHA-256
+//!
+//! k由私钥与待签名摘要唯一确定，签名不再依赖运行环境的随机数质量，也不会因随机数重复泄露私钥。
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+use crate::sm2::key::to_32_bytes;
+use crate::sm3::Hmac;
+
+/// RFC 6979 3.2节的HMAC_DRBG，qlen = hlen = 256，每次迭代产生一个k ∈ [1, n-1]
+pub(crate) struct NonceGenerator {
+    k: [u8; 32],
+    v: [u8; 32],
+    n: BigUint,
+    started: bool,
+}
+
+impl NonceGenerator {
+    /// `d`为私钥，`e`为待签名摘要，`n`为基点的阶
+    pub(crate) fn new(d: &BigUint, e: &[u8; 32], n: &BigUint) -> Self {
+        let x = to_32_bytes(d.to_bytes_be());
+        // bits2octets(e) = int2octets(e mod n)
+        let h = to_32_bytes((BigUint::from_bytes_be(e) % n).to_bytes_be());
+
+        let mut generator = NonceGenerator { k: [0; 32], v: [1; 32], n: n.clone(), started: false };
+        for tag in [0x00, 0x01] {
+            generator.k = generator.mac(&[&generator.v, &[tag], &x, &h]);
+            generator.v = generator.mac(&[&generator.v]);
+        }
+        generator
+    }
+
+    fn mac(&self, parts: &[&[u8]]) -> [u8; 32] {
+        let mut hmac = Hmac::new(&self.k);
+        for part in parts {
+            hmac.update(part);
+        }
+        hmac.finalize()
+    }
+}
+
+impl Iterator for NonceGenerator {
+    type Item = BigUint;
+
+    fn next(&mut self) -> Option<BigUint> {
+        loop {
+            // 上一个k被拒绝后（越界或签名时r、s不合法）重新混合状态，对应3.2节h.3
+            if self.started {
+                self.k = self.mac(&[&self.v, &[0x00]]);
+                self.v = self.mac(&[&self.v]);
+            }
+            self.started = true;
+
+            self.v = self.mac(&[&self.v]);
+            let k = BigUint::from_bytes_be(&self.v);
+            if !k.is_zero() && k < self.n {
+                return Some(k);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sm2::curve;
+    use crate::sm3;
+
+    /// 期望值由Python hmac模块以HMAC-SM3按RFC 6979 3.2节独立计算
+    #[test]
+    fn known_answer() {
+        let d = BigUint::parse_bytes(b"6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e", 16).unwrap();
+        let e = sm3::hash(b"sample");
+        let nonces: Vec<String> = NonceGenerator::new(&d, &e, &curve().n).take(2).map(|k| format!("{:064x}", k)).collect();
+        assert_eq!(nonces, [
+            "381edae2e619a8835b86a3f0ba53e15115b1ce7a96fdd1ac7b72fc8d064b1c81",
+            "73bd46a5c8c4d04419fd0d93809842c9cbc3cc9e34e6562d66d8b812af33a2ce",
+        ]);
+    }
+}
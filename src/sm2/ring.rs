@@ -0,0 +1,414 @@
+//! SM2曲线上的环签名
+//!
+//! 签名者以自己的私钥和一组公钥（环）签名，验证者只能确认签名出自环中某一成员，无法确定是哪一个，
+//! 适用于匿名证明等场景。挑战值以SM3计算：
+//!
+//! - 普通环签名（AOS）：c(i+1) = H(环 ∥ M ∥ si·G + ci·Pi)
+//! - 可链接环签名（LSAG）：另附密钥像 I = d·Hp(P)，c(i+1) = H(环 ∥ I ∥ M ∥ si·G + ci·Pi ∥ si·Hp(Pi) + ci·I)。
+//!   同一私钥产生的签名密钥像相同，可据此发现重复签名，但仍不暴露签名者
+//!
+//! Hp为以SM3逐次尝试的哈希到曲线点映射，仅作用于公开的公钥。
+
+use std::fmt::{Display, Formatter};
+
+use num_bigint::BigUint;
+#[cfg(feature = "sm2-sign")]
+use num_traits::One;
+use num_traits::Zero;
+#[cfg(feature = "sm2-sign")]
+use rand::{CryptoRng, RngCore};
+
+#[cfg(feature = "sm2-sign")]
+use crate::env::EnvRng;
+use crate::error_code::{Code, ErrorCode};
+use crate::sm2::ecc::EllipticBuilder;
+use crate::sm2::key::{to_32_bytes, KeyError, PublicKey};
+#[cfg(feature = "sm2-sign")]
+use crate::sm2::key::KeyPair;
+use crate::sm2::p256::P256Elliptic;
+use crate::sm2::point::{Point, Scalar};
+use crate::sm3;
+
+const CHALLENGE_TAG: &[u8] = b"SM2-RING-CHALLENGE";
+const HASH_TO_POINT_TAG: &[u8] = b"SM2-RING-HASH-TO-POINT";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RingError {
+    /// 环中没有公钥
+    EmptyRing,
+    /// 签名者的公钥不在环中
+    SignerNotInRing,
+    /// 环中的公钥或密钥像不是合法的曲线点
+    InvalidKey(KeyError),
+    /// 签名编码错误，或签名的成员数与环不一致
+    Malformed,
+    /// 签名验证失败
+    Mismatch,
+}
+
+impl Display for RingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RingError::EmptyRing => write!(f, "The ring has no public keys."),
+            RingError::SignerNotInRing => write!(f, "The signer public key is not in the ring."),
+            RingError::InvalidKey(e) => write!(f, "{}", e),
+            RingError::Malformed => write!(f, "The ring signature is malformed."),
+            RingError::Mismatch => write!(f, "The ring signature validation failed."),
+        }
+    }
+}
+
+impl std::error::Error for RingError {}
+
+impl ErrorCode for RingError {
+    fn code(&self) -> Code {
+        match self {
+            RingError::EmptyRing | RingError::Malformed => Code::INVALID_DATA,
+            RingError::SignerNotInRing => Code::KEY_NOT_FOUND,
+            RingError::InvalidKey(e) => e.code(),
+            RingError::Mismatch => Code::VERIFY_FAILED,
+        }
+    }
+}
+
+/// 环签名：(c0, s0, ..., s(n-1))，可链接时附带密钥像I
+///
+/// 字节编码：`00 ∥ c0 ∥ s0 ∥ ... ∥ s(n-1)`，可链接时为`01 ∥ I ∥ c0 ∥ s0 ∥ ...`，I为非压缩格式点，标量均为32字节。
+#[derive(Clone, Debug)]
+pub struct RingSignature {
+    key_image: Option<PublicKey>,
+    c0: BigUint,
+    s: Vec<BigUint>,
+}
+
+impl RingSignature {
+    /// 可链接环签名的密钥像，普通环签名为None
+    pub fn key_image(&self) -> Option<&PublicKey> {
+        self.key_image.as_ref()
+    }
+
+    /// 两个可链接环签名是否出自同一私钥
+    pub fn is_linked(&self, other: &RingSignature) -> bool {
+        match (&self.key_image, &other.key_image) {
+            (Some(a), Some(b)) => a.to_bytes() == b.to_bytes(),
+            _ => false,
+        }
+    }
+
+    /// 环的成员数
+    pub fn len(&self) -> usize {
+        self.s.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.s.is_empty()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = vec![self.key_image.is_some() as u8];
+        if let Some(image) = &self.key_image {
+            out.extend_from_slice(&image.to_bytes());
+        }
+        for scalar in std::iter::once(&self.c0).chain(&self.s) {
+            out.extend_from_slice(&to_32_bytes(scalar.to_bytes_be()));
+        }
+        out
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, RingError> {
+        let (key_image, scalars) = match data.split_first() {
+            Some((0x00, rest)) => (None, rest),
+            Some((0x01, rest)) if rest.len() >= 65 => {
+                let image = PublicKey::from_bytes(&rest[..65]).map_err(|_| RingError::Malformed)?;
+                (Some(image), &rest[65..])
+            }
+            _ => return Err(RingError::Malformed),
+        };
+        if scalars.len() < 64 || scalars.len() % 32 != 0 {
+            return Err(RingError::Malformed);
+        }
+        let mut scalars = scalars.chunks(32).map(BigUint::from_bytes_be);
+        let c0 = scalars.next().unwrap();
+        Ok(RingSignature { key_image, c0, s: scalars.collect() })
+    }
+}
+
+/// 普通环签名，随机数取自运行环境
+#[cfg(feature = "sm2-sign")]
+pub fn ring_sign(message: &[u8], signer: &KeyPair, ring: &[PublicKey]) -> Result<RingSignature, RingError> {
+    ring_sign_with_rng(message, signer, ring, false, &mut EnvRng)
+}
+
+/// 可链接环签名，随机数取自运行环境
+#[cfg(feature = "sm2-sign")]
+pub fn ring_sign_linkable(message: &[u8], signer: &KeyPair, ring: &[PublicKey]) -> Result<RingSignature, RingError> {
+    ring_sign_with_rng(message, signer, ring, true, &mut EnvRng)
+}
+
+/// 以`rng`生成随机数签名，`linkable`为true时生成可链接环签名
+#[cfg(feature = "sm2-sign")]
+pub fn ring_sign_with_rng(
+    message: &[u8],
+    signer: &KeyPair,
+    ring: &[PublicKey],
+    linkable: bool,
+    rng: &mut (impl CryptoRng + RngCore),
+) -> Result<RingSignature, RingError> {
+    if ring.is_empty() {
+        return Err(RingError::EmptyRing);
+    }
+    let own = signer.puk().to_bytes();
+    let index = ring.iter().position(|key| key.to_bytes() == own).ok_or(RingError::SignerNotInRing)?;
+
+    let p256 = P256Elliptic::init();
+    let n = &p256.ec.n;
+    let d = signer.prk().value();
+    let key_image = match linkable {
+        true => Some(point(p256.scalar_multiply_key(&hash_to_point(signer.puk()), d.clone()))),
+        false => None,
+    };
+    let transcript = Transcript::new(ring, key_image.as_ref(), message);
+
+    // c0与sπ为0的签名会被验证拒绝，概率可忽略，出现时重新签名
+    loop {
+        let k = p256.ec.random_with(rng, BigUint::one(), n - 1u8);
+        let mut c = vec![BigUint::default(); ring.len()];
+        let mut s = vec![BigUint::default(); ring.len()];
+        let l = point(p256.scalar_base_multiply(k.clone()));
+        let r = key_image.as_ref().map(|_| point(p256.scalar_multiply_key(&hash_to_point(signer.puk()), k.clone())));
+        let mut next = (index + 1) % ring.len();
+        c[next] = transcript.challenge(&l, r.as_ref());
+
+        while next != index {
+            let i = next;
+            s[i] = p256.ec.random_with(rng, BigUint::one(), n - 1u8);
+            let (l, r) = commitments(p256, &ring[i], key_image.as_ref(), &c[i], &s[i], false);
+            next = (i + 1) % ring.len();
+            c[next] = transcript.challenge(&l, r.as_ref());
+        }
+        // sπ = k - cπ·d mod n
+        s[index] = (k + n - (&c[index] * &d) % n) % n;
+
+        if !c[0].is_zero() && !s[index].is_zero() {
+            return Ok(RingSignature { key_image, c0: c.swap_remove(0), s });
+        }
+    }
+}
+
+/// 验证环签名，`ring`须与签名时的公钥及顺序相同
+pub fn ring_verify(message: &[u8], signature: &RingSignature, ring: &[PublicKey]) -> Result<(), RingError> {
+    if ring.is_empty() {
+        return Err(RingError::EmptyRing);
+    }
+    if signature.s.len() != ring.len() {
+        return Err(RingError::Malformed);
+    }
+    let p256 = P256Elliptic::init();
+    let n = &p256.ec.n;
+    for key in ring.iter().chain(&signature.key_image) {
+        key.validate(p256).map_err(RingError::InvalidKey)?;
+    }
+    // 标量为0时对应的项为无穷远点，不应出现在合法签名中
+    if std::iter::once(&signature.c0).chain(&signature.s).any(|v| v.is_zero() || v >= n) {
+        return Err(RingError::Mismatch);
+    }
+
+    let transcript = Transcript::new(ring, signature.key_image.as_ref(), message);
+    let mut c = signature.c0.clone();
+    for (key, s) in ring.iter().zip(&signature.s) {
+        let (l, r) = commitments(p256, key, signature.key_image.as_ref(), &c, s, true);
+        c = transcript.challenge(&l, r.as_ref());
+    }
+    match c == signature.c0 {
+        true => Ok(()),
+        false => Err(RingError::Mismatch),
+    }
+}
+
+/// 成员i的承诺 L = s·G + c·Pi，可链接时另有 R = s·Hp(Pi) + c·I
+///
+/// 签名时各诱饵成员的耗时若随标量变化，可由公开的(s, c)推算出哪个成员是签名者，因此只有验证使用可变时间的交错乘法。
+/// 两项之和可能是无穷远点，或两项相等，因此使用[`Point`]的完备点加，无穷远点按(0, 0)编码。
+fn commitments(p256: &P256Elliptic, key: &PublicKey, key_image: Option<&PublicKey>, c: &BigUint, s: &BigUint, vartime: bool) -> (PublicKey, Option<PublicKey>) {
+    let (sp, cp) = (Scalar::from_biguint(s), Scalar::from_biguint(c));
+    let l = match vartime {
+        true => point(p256.double_scalar_multiply(s.clone(), key, c.clone()).unwrap_or_default()),
+        false => affine(Point::mul_base(&sp) + Point::from(key) * cp),
+    };
+    let r = key_image.map(|image| affine(Point::from(&hash_to_point(key)) * sp + Point::from(image) * cp));
+    (l, r)
+}
+
+fn affine(p: Point) -> PublicKey {
+    p.to_public_key().unwrap_or_else(|| point(Default::default()))
+}
+
+fn point((x, y): (BigUint, BigUint)) -> PublicKey {
+    PublicKey::new(x, y)
+}
+
+/// 以SM3逐次尝试：x = SM3(标签 ∥ P ∥ ctr)，取y为偶数的点
+fn hash_to_point(key: &PublicKey) -> PublicKey {
+    let encoded = key.to_bytes();
+    (0u32..)
+        .find_map(|counter| {
+            let mut hasher = sm3::Hasher::new();
+            hasher.update(HASH_TO_POINT_TAG).update(&encoded).update(&counter.to_be_bytes());
+            let compressed = [&[0x02][..], &hasher.finalize()].concat();
+            PublicKey::from_bytes(&compressed).ok()
+        })
+        .unwrap()
+}
+
+/// 挑战值中各成员共用的前缀：标签 ∥ 环 ∥ [I] ∥ M
+struct Transcript {
+    prefix: sm3::Hasher,
+    n: BigUint,
+}
+
+impl Transcript {
+    fn new(ring: &[PublicKey], key_image: Option<&PublicKey>, message: &[u8]) -> Self {
+        let mut prefix = sm3::Hasher::new();
+        prefix.update(CHALLENGE_TAG).update(&(ring.len() as u32).to_be_bytes());
+        for key in ring.iter().chain(key_image) {
+            prefix.update(&key.to_bytes());
+        }
+        prefix.update(message);
+        Transcript { prefix, n: P256Elliptic::init().ec.n.clone() }
+    }
+
+    fn challenge(&self, l: &PublicKey, r: Option<&PublicKey>) -> BigUint {
+        let mut hasher = self.prefix.clone();
+        hasher.update(&l.to_bytes());
+        if let Some(r) = r {
+            hasher.update(&r.to_bytes());
+        }
+        BigUint::from_bytes_be(&hasher.finalize()) % &self.n
+    }
+}
+
+
+#[cfg(all(test, feature = "sm2-sign"))]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::sm2::key::KeyGenerator;
+
+    use super::*;
+
+    fn members(count: usize, rng: &mut StdRng) -> Vec<KeyPair> {
+        let generator = KeyGenerator::init(Box::new(P256Elliptic::init()));
+        (0..count).map(|_| generator.gen_key_pair_with_rng(rng)).collect()
+    }
+
+    #[test]
+    fn plain() {
+        let mut rng = StdRng::seed_from_u64(1266);
+        let keypairs = members(4, &mut rng);
+        let ring: Vec<PublicKey> = keypairs.iter().map(|k| k.puk().clone()).collect();
+
+        for signer in &keypairs {
+            let signature = ring_sign_with_rng(b"attest", signer, &ring, false, &mut rng).unwrap();
+            assert!(signature.key_image().is_none());
+            assert_eq!(ring_verify(b"attest", &signature, &ring), Ok(()));
+            assert_eq!(ring_verify(b"attest!", &signature, &ring), Err(RingError::Mismatch));
+
+            let decoded = RingSignature::from_bytes(&signature.to_bytes()).unwrap();
+            assert_eq!(decoded.to_bytes().len(), 1 + 32 * 5);
+            assert_eq!(ring_verify(b"attest", &decoded, &ring), Ok(()));
+        }
+
+        // 环的顺序或成员变化后验证失败
+        let signature = ring_sign(b"attest", &keypairs[2], &ring).unwrap();
+        let mut reordered = ring.clone();
+        reordered.swap(0, 3);
+        assert_eq!(ring_verify(b"attest", &signature, &reordered), Err(RingError::Mismatch));
+        assert_eq!(ring_verify(b"attest", &signature, &ring[..3]), Err(RingError::Malformed));
+
+        let single = ring_sign(b"solo", &keypairs[0], &ring[..1]).unwrap();
+        assert_eq!(ring_verify(b"solo", &single, &ring[..1]), Ok(()));
+    }
+
+    #[test]
+    fn linkable() {
+        let mut rng = StdRng::seed_from_u64(1267);
+        let keypairs = members(3, &mut rng);
+        let ring: Vec<PublicKey> = keypairs.iter().map(|k| k.puk().clone()).collect();
+
+        let first = ring_sign_with_rng(b"vote 1", &keypairs[1], &ring, true, &mut rng).unwrap();
+        let second = ring_sign_linkable(b"vote 2", &keypairs[1], &ring).unwrap();
+        let other = ring_sign_linkable(b"vote 1", &keypairs[2], &ring).unwrap();
+        for (message, signature) in [(&b"vote 1"[..], &first), (b"vote 2", &second), (b"vote 1", &other)] {
+            assert_eq!(ring_verify(message, signature, &ring), Ok(()));
+        }
+        assert!(first.is_linked(&second));
+        assert!(!first.is_linked(&other));
+
+        let decoded = RingSignature::from_bytes(&first.to_bytes()).unwrap();
+        assert_eq!(decoded.to_bytes().len(), 1 + 65 + 32 * 4);
+        assert!(decoded.is_linked(&first));
+        assert_eq!(ring_verify(b"vote 1", &decoded, &ring), Ok(()));
+
+        // 替换密钥像后验证失败，密钥像不能用于伪造其他签名者
+        let mut forged = first.clone();
+        forged.key_image = other.key_image.clone();
+        assert_eq!(ring_verify(b"vote 1", &forged, &ring), Err(RingError::Mismatch));
+        // 去掉密钥像即改变了挑战值
+        let mut stripped = first.clone();
+        stripped.key_image = None;
+        assert_eq!(ring_verify(b"vote 1", &stripped, &ring), Err(RingError::Mismatch));
+    }
+
+    #[test]
+    fn degenerate() {
+        let mut rng = StdRng::seed_from_u64(1269);
+        let keypairs = members(2, &mut rng);
+        let ring: Vec<PublicKey> = keypairs.iter().map(|k| k.puk().clone()).collect();
+        let p256 = P256Elliptic::init();
+        let n = &p256.ec.n;
+
+        // s = 0或c0 = 0时承诺退化为c·P或s·G，直接拒绝
+        let signature = ring_sign_linkable(b"m", &keypairs[0], &ring).unwrap();
+        let mut zero_s = signature.clone();
+        zero_s.s[1] = BigUint::zero();
+        assert_eq!(ring_verify(b"m", &zero_s, &ring), Err(RingError::Mismatch));
+        let mut zero_c = signature.clone();
+        zero_c.c0 = BigUint::zero();
+        assert_eq!(ring_verify(b"m", &zero_c, &ring), Err(RingError::Mismatch));
+
+        // 取s = ∓c·d使两项互为相反数或相等，L与R分别为无穷远点或倍点
+        let (key, d) = (&ring[0], keypairs[0].prk().value());
+        let image = point(p256.scalar_multiply_key(&hash_to_point(key), d.clone()));
+        let c = BigUint::from(1269u32);
+        let cd = (&c * &d) % n;
+        for vartime in [false, true] {
+            let (l, r) = commitments(p256, key, Some(&image), &c, &(n - &cd), vartime);
+            assert_eq!((l.to_bytes(), r.unwrap().to_bytes()), (point(Default::default()).to_bytes(), point(Default::default()).to_bytes()));
+
+            let (l, r) = commitments(p256, key, Some(&image), &c, &cd, vartime);
+            let twice = (&cd * 2u8) % n;
+            assert_eq!(l.to_bytes(), point(p256.scalar_base_multiply(twice.clone())).to_bytes());
+            assert_eq!(r.unwrap().to_bytes(), point(p256.scalar_multiply_key(&hash_to_point(key), twice)).to_bytes());
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let mut rng = StdRng::seed_from_u64(1268);
+        let keypairs = members(2, &mut rng);
+        let ring = vec![keypairs[0].puk().clone()];
+
+        assert_eq!(ring_sign(b"m", &keypairs[1], &ring).unwrap_err(), RingError::SignerNotInRing);
+        assert_eq!(ring_sign(b"m", &keypairs[0], &[]).unwrap_err(), RingError::EmptyRing);
+        assert_eq!(RingError::SignerNotInRing.code(), Code::KEY_NOT_FOUND);
+
+        for malformed in [&[][..], &[0x00; 33], &[0x02; 65], &[0x00; 66]] {
+            assert_eq!(RingSignature::from_bytes(malformed).unwrap_err(), RingError::Malformed);
+        }
+
+        let signature = ring_sign(b"m", &keypairs[0], &ring).unwrap();
+        let off_curve = PublicKey::from_bytes(&[&[0x04][..], &[0x01; 64]].concat()).unwrap();
+        assert_eq!(ring_verify(b"m", &signature, &[off_curve]), Err(RingError::InvalidKey(KeyError::NotOnCurve)));
+    }
+}
@@ -0,0 +1,238 @@
+//! 密钥、签名与密文的serde支持
+//!
+//! JSON、TOML等可读格式中编码为16进制字符串，bincode、CBOR等二进制格式中编码为字节串：公钥为65字节非压缩格式，
+//! 解析时也接受33字节压缩格式；私钥为32字节；签名为64字节的r ∥ s；密文按C1C3C2顺序，解析结果的分量顺序为
+//! [`Mode::C1C3C2`]，需要C1C2C3时以[`Ciphertext::convert_mode`]调整。密钥对编码为含`private_key`、
+//! `public_key`两个成员的结构，解析时校验两者匹配。
+
+use std::fmt::Formatter;
+
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "sm2-encrypt")]
+use crate::sm2::ecc::{Ciphertext, Mode};
+use crate::sm2::ecc::Signature;
+use crate::sm2::key::PublicKey;
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+use crate::sm2::key::{to_32_bytes, KeyPair, PrivateKey};
+
+/// 可读格式中写16进制字符串，二进制格式中写字节串
+fn serialize_bytes<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&hex::encode(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+fn deserialize_bytes<'de, D: Deserializer<'de>>(deserializer: D, what: &'static str) -> Result<Vec<u8>, D::Error> {
+    if deserializer.is_human_readable() {
+        // 可读格式中也接受u8数组，即未定制序列化时Vec<u8>的形式
+        deserializer.deserialize_any(BytesVisitor(what))
+    } else {
+        deserializer.deserialize_bytes(BytesVisitor(what))
+    }
+}
+
+/// 接受16进制字符串、字节串与u8序列
+struct BytesVisitor(&'static str);
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{} as hex or bytes", self.0)
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        hex::decode(v).map_err(|e| E::custom(format!("The {} is not valid hex. {}", self.0, e)))
+    }
+
+    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+}
+
+impl Serialize for PublicKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&self.to_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = deserialize_bytes(deserializer, "SM2 public key")?;
+        PublicKey::from_bytes(&bytes).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+impl Serialize for PrivateKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&to_32_bytes(self.value().to_bytes_be()), serializer)
+    }
+}
+
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+impl<'de> Deserialize<'de> for PrivateKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = deserialize_bytes(deserializer, "SM2 private key")?;
+        if bytes.len() != 32 {
+            return Err(D::Error::invalid_length(bytes.len(), &"32 bytes"));
+        }
+        PrivateKey::from_bytes(&bytes).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "KeyPair", deny_unknown_fields)]
+struct KeyPairFields<K, P> {
+    private_key: K,
+    public_key: P,
+}
+
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+impl Serialize for KeyPair {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        KeyPairFields { private_key: self.prk(), public_key: self.puk() }.serialize(serializer)
+    }
+}
+
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+impl<'de> Deserialize<'de> for KeyPair {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fields = KeyPairFields::<PrivateKey, PublicKey>::deserialize(deserializer)?;
+        let pair = KeyPair::new(fields.private_key, fields.public_key);
+        if !pair.verify_consistency() {
+            return Err(D::Error::custom("The private key does not match the public key."));
+        }
+        Ok(pair)
+    }
+}
+
+impl Serialize for Signature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&self.to_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Signature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = deserialize_bytes(deserializer, "SM2 signature")?;
+        Signature::from_bytes(&bytes).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(feature = "sm2-encrypt")]
+impl Serialize for Ciphertext {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&[&self.c1()[..], self.c3(), self.c2()].concat(), serializer)
+    }
+}
+
+#[cfg(feature = "sm2-encrypt")]
+impl<'de> Deserialize<'de> for Ciphertext {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = deserialize_bytes(deserializer, "SM2 cipher data")?;
+        Ciphertext::from_bytes(&bytes, Mode::C1C3C2).map_err(D::Error::custom)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use serde_test::{assert_de_tokens_error, assert_tokens, Compact, Configure, Readable, Token};
+
+    use crate::sm2::ecc::Crypto;
+    use crate::sm2::format::PublicKeyFormat;
+    use crate::sm2::key::HexKey;
+
+    use super::*;
+
+    const PRIVATE_KEY: &str = "6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e";
+    const PUBLIC_KEY: &str = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+
+    /// 密钥与签名不实现PartialEq，按JSON编码比较
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(transparent)]
+    struct Encoded<T>(T);
+
+    impl<T: Serialize> PartialEq for Encoded<T> {
+        fn eq(&self, other: &Self) -> bool {
+            serde_json::to_value(&self.0).unwrap() == serde_json::to_value(&other.0).unwrap()
+        }
+    }
+
+    /// serde_test的Token只接受'static数据
+    fn leak(bytes: Vec<u8>) -> &'static [u8] {
+        Box::leak(bytes.into_boxed_slice())
+    }
+
+    #[test]
+    fn keys() {
+        let puk = PublicKey::decode(PUBLIC_KEY);
+        let prk = PrivateKey::decode(PRIVATE_KEY);
+        let puk_bytes = leak(hex::decode(PUBLIC_KEY).unwrap());
+        let prk_bytes = leak(hex::decode(PRIVATE_KEY).unwrap());
+        assert_tokens(&Encoded(puk.clone()).readable(), &[Token::Str(PUBLIC_KEY)]);
+        assert_tokens(&Encoded(puk.clone()).compact(), &[Token::Bytes(puk_bytes)]);
+        assert_tokens(&Encoded(prk.clone()).readable(), &[Token::Str(PRIVATE_KEY)]);
+        assert_tokens(&Encoded(prk.clone()).compact(), &[Token::Bytes(prk_bytes)]);
+
+        // 压缩格式与u8序列同样可以解析
+        let compressed = puk.to_text(PublicKeyFormat::HexCompressed);
+        assert_eq!(serde_json::from_str::<PublicKey>(&format!("{:?}", compressed)).unwrap().encode(), PUBLIC_KEY);
+        assert_eq!(serde_json::from_value::<PrivateKey>(serde_json::json!(prk_bytes)).unwrap().encode(), PRIVATE_KEY);
+
+        let json = serde_json::to_string(&KeyPair::new(prk, puk)).unwrap();
+        assert_eq!(json, format!(r#"{{"private_key":"{}","public_key":"{}"}}"#, PRIVATE_KEY, PUBLIC_KEY));
+        let pair: KeyPair = serde_json::from_str(&json).unwrap();
+        assert_eq!((pair.prk().encode(), pair.puk().encode()), (PRIVATE_KEY.to_string(), PUBLIC_KEY.to_string()));
+
+        let mismatched = json.replace(&PRIVATE_KEY[..8], "00000000");
+        assert!(serde_json::from_str::<KeyPair>(&mismatched).unwrap_err().to_string().contains("does not match"));
+        assert!(serde_json::from_str::<PublicKey>(r#""04a8""#).is_err());
+        assert_de_tokens_error::<Compact<PrivateKey>>(&[Token::Bytes(&prk_bytes[..31])], "invalid length 31, expected 32 bytes");
+        assert_de_tokens_error::<Readable<PublicKey>>(
+            &[Token::Str("zz")],
+            "The SM2 public key is not valid hex. Invalid character 'z' at position 0",
+        );
+    }
+
+    #[test]
+    fn signature_and_ciphertext() {
+        let prk = PrivateKey::decode(PRIVATE_KEY);
+        let puk = PublicKey::decode(PUBLIC_KEY);
+        let crypto = Crypto::default();
+
+        let signature = crypto.signer(KeyPair::new(prk.clone(), puk.clone())).sign_bytes(b"serde");
+        let raw = leak(signature.to_bytes().to_vec());
+        let text = Box::leak(hex::encode(raw).into_boxed_str());
+        assert_tokens(&Encoded(signature.clone()).readable(), &[Token::Str(text)]);
+        assert_tokens(&Encoded(signature).compact(), &[Token::Bytes(raw)]);
+
+        let legacy = Crypto::new(Mode::C1C2C3).encryptor(puk).encrypt_bytes(b"serde");
+        let ciphertext = Ciphertext::from_bytes(&legacy, Mode::C1C2C3).unwrap();
+        let standard = ciphertext.clone().convert_mode(Mode::C1C3C2);
+        assert_tokens(&standard.clone().compact(), &[Token::Bytes(leak(standard.to_bytes()))]);
+        let json = serde_json::to_string(&ciphertext).unwrap();
+        assert_eq!(json, format!("{:?}", hex::encode(standard.to_bytes())));
+        let parsed: Ciphertext = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, standard);
+        assert_eq!(crypto.decryptor(prk).decrypt_bytes(&parsed.to_bytes()), b"serde");
+    }
+}
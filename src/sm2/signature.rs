@@ -0,0 +1,389 @@
+//! SM2数字签名算法（GB/T 32918.2、GM/T 0003.2）
+//!
+//! 签名者以私钥d对消息M签名：先计算用户杂凑值ZA，再计算 e = SM3(ZA ∥ M)，
+//! 最后由随机数k求得签名(r, s)。验证者以公钥及相同的用户ID验证。
+
+use crate::env;
+use crate::sm2::ecc::za;
+use crate::sm2::hazmat::ScalarBytesBe;
+use crate::sm2::key::PublicKey;
+use crate::sm2::p256::P256Elliptic;
+use crate::sm2::p256::params::{EC_N, EC_P, EC_P_MINUS_N, P256A, P256B};
+use crate::sm2::p256::payload::Payload;
+use crate::sm2::p256::point::{P256AffinePoint, P256BasePoint, P256JacobianPoint};
+use crate::sm2::p256::scalar::Scalar;
+use crate::sm3;
+
+pub use crate::sm2::ecc::{Signature, Verifier, DEFAULT_USER_ID};
+pub use crate::sm2::error::VerifyError;
+#[cfg(feature = "sm2-sign")]
+pub use crate::sm2::ecc::Signer;
+
+/// 待签名消息的摘要 e = SM3(ZA ∥ M)
+pub fn digest(id: &[u8], public_key: &PublicKey, message: &[u8]) -> [u8; 32] {
+    let za = za(&P256Elliptic::init().ec, id, public_key);
+    let mut hasher = sm3::Hasher::new();
+    hasher.update(&za).update(message);
+    hasher.finalize()
+}
+
+/// 不分配内存的签名验证，供堆尚未初始化的引导程序校验固件签名
+///
+/// 公钥为未压缩编码 04 ∥ x ∥ y，摘要为 e = SM3(ZA ∥ M)（见[`digest`]），签名为 r ∥ s。
+/// 运算只使用栈上的定长数组与limb算术，任何输入都不会panic。
+pub fn verify_no_alloc(public_key: &[u8; 65], digest: &[u8; 32], signature: &[u8; 64]) -> Result<(), VerifyError> {
+    let (mut r, mut s) = ([0u8; 32], [0u8; 32]);
+    r.copy_from_slice(&signature[..32]);
+    s.copy_from_slice(&signature[32..]);
+
+    // r, s ∈ [1, n-1]
+    if r == [0u8; 32] || r >= EC_N {
+        return Err(VerifyError::ROutOfRange);
+    }
+    if s == [0u8; 32] || s >= EC_N {
+        return Err(VerifyError::SOutOfRange);
+    }
+
+    let point = public_point(public_key).ok_or(VerifyError::InvalidPublicKey)?;
+
+    // t = (r + s) mod n, t ≠ 0
+    let r = Scalar::from_bytes_be(&r);
+    let t = r.add(&Scalar::from_bytes_be(&s)).to_bytes_be();
+    if t == [0u8; 32] {
+        return Err(VerifyError::ZeroSum);
+    }
+
+    // (x1, y1) = sG + tP ≠ O
+    let point = P256AffinePoint::double_multiply_vartime(&s, &point, &t);
+    if point.is_infinity() {
+        return Err(VerifyError::PointAtInfinity);
+    }
+
+    // (e + x1) mod n = r 即 x1 ≡ r - e (mod n)，x1 < p，候选值为v及不超过p时的v + n
+    let v = r.subtract(&Scalar::from_bytes_be(digest)).to_bytes_be();
+    let candidate = Payload::from_bytes(&v);
+    if point.x_equals(&candidate) {
+        return Ok(());
+    }
+    if v < EC_P_MINUS_N && point.x_equals(&candidate.add(&Payload::from_bytes(&EC_N))) {
+        return Ok(());
+    }
+    Err(VerifyError::Mismatch)
+}
+
+/// 未压缩编码的公钥 04 ∥ x ∥ y，不是曲线上的点时为None
+fn public_point(public_key: &[u8; 65]) -> Option<P256AffinePoint> {
+    let (mut x, mut y) = ([0u8; 32], [0u8; 32]);
+    x.copy_from_slice(&public_key[1..33]);
+    y.copy_from_slice(&public_key[33..]);
+    if public_key[0] != 0x04 || x >= EC_P || y >= EC_P {
+        return None;
+    }
+    let (x, y) = (Payload::from_bytes(&x), Payload::from_bytes(&y));
+    (y.square().to_bytes() == curve_rhs(&x).to_bytes()).then(|| P256AffinePoint::new(x, y))
+}
+
+/// x^3 + ax + b
+fn curve_rhs(x: &Payload) -> Payload {
+    x.square().multiply(x).add(&Payload::new(P256A).multiply(x)).add(&Payload::new(P256B))
+}
+
+/// 每批线性组合的签名数，符号搜索需遍历2^CHUNK种组合
+const CHUNK: usize = 8;
+
+/// 批量验证（默认用户ID），全部有效时返回true
+///
+/// 验证方程 sG + tP = R 只给出R的横坐标 x1 = (r - e) mod n，纵坐标的符号未知。
+/// 每批以随机的128位系数z_i做线性组合：Σ(z_i·s_i)G + Σ(z_i·t_i)P_i 以一次基点乘法与一次多标量乘法求出，
+/// 再搜索符号ε_i使其等于Σ ε_i·z_i·R_i。各签名共用倍点运算，签名越多、公钥越少重复，节省越明显；
+/// 由于仍需逐项计算z_i·R_i，加速不及携带完整R的签名方案。
+///
+/// 任何一项无效时返回false，不指出是哪一项，需要时可逐项调用[`Verifier::check_bytes`]。
+pub fn verify_batch(items: &[(&[u8], &Signature, &PublicKey)]) -> bool {
+    items.chunks(CHUNK).all(verify_chunk)
+}
+
+fn verify_chunk(items: &[(&[u8], &Signature, &PublicKey)]) -> bool {
+    let mut g = Scalar::from_bytes_be(&[0u8; 32]);
+    let mut terms = Vec::with_capacity(items.len());
+    let mut lifted = Vec::with_capacity(items.len());
+
+    for (message, signature, key) in items {
+        let Ok(public_key) = <[u8; 65]>::try_from(key.to_bytes()) else {
+            return false;
+        };
+        let Some(point) = public_point(&public_key) else {
+            return false;
+        };
+        if signature.r().bits() > 256 || signature.s().bits() > 256 {
+            return false;
+        }
+        let raw = signature.to_bytes();
+        let e = digest(DEFAULT_USER_ID, key, message);
+
+        let (mut r, mut s) = ([0u8; 32], [0u8; 32]);
+        r.copy_from_slice(&raw[..32]);
+        s.copy_from_slice(&raw[32..]);
+        if r == [0u8; 32] || r >= EC_N || s == [0u8; 32] || s >= EC_N {
+            return false;
+        }
+        let (r, s) = (Scalar::from_bytes_be(&r), Scalar::from_bytes_be(&s));
+        let t = r.add(&s);
+        if t.to_bytes_be() == [0u8; 32] {
+            return false;
+        }
+
+        // x1 ≡ r - e (mod n)；x1还可能为v + n时（概率约2^-128）单独验证
+        let v = r.subtract(&Scalar::from_bytes_be(&e)).to_bytes_be();
+        if v < EC_P_MINUS_N {
+            if verify_no_alloc(&public_key, &e, &raw).is_err() {
+                return false;
+            }
+            continue;
+        }
+        let x = Payload::from_bytes(&v);
+        let Some(y) = curve_rhs(&x).sqrt() else {
+            return false;
+        };
+
+        let mut bytes = [0u8; 32];
+        env::fill_bytes(&mut bytes[16..]);
+        bytes[31] |= 1;
+        let z = Scalar::from_bytes_be(&bytes);
+
+        g = g.add(&z.multiply(&s));
+        terms.push((point, z.multiply(&t).to_bytes_be()));
+        lifted.push(P256AffinePoint::new(x, y).multiply_vartime(&bytes));
+    }
+
+    // d = Σ z_i·(s_i·G + t_i·P_i) - Σ ε_i·z_i·R_i，初始 ε_i = +1
+    let mut d = P256BasePoint::comb(&ScalarBytesBe(g.to_bytes_be()).to_le())
+        .add_vartime(&P256AffinePoint::multi_multiply_vartime(&terms));
+    for a in &lifted {
+        d = d.subtract_vartime(a);
+    }
+    let doubled: Vec<P256JacobianPoint> = lifted.iter().map(|a| a.add_vartime(a)).collect();
+
+    // 按格雷码每次翻转一个符号，d随之加减2·z_j·R_j
+    let mut positive = vec![true; lifted.len()];
+    for step in 1..(1usize << lifted.len()) {
+        if d.is_infinity() {
+            return true;
+        }
+        let j = step.trailing_zeros() as usize;
+        d = if positive[j] { d.add_vartime(&doubled[j]) } else { d.subtract_vartime(&doubled[j]) };
+        positive[j] = !positive[j];
+    }
+    d.is_infinity()
+}
+
+
+#[cfg(all(test, feature = "sm2-sign", feature = "sm2-encrypt"))]
+mod tests {
+    use num_bigint::BigUint;
+
+    use crate::sm2::{HexKey, KeyPair, PrivateKey};
+
+    use super::*;
+
+    const PRK: &str = "6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e";
+    const PUK: &str = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+
+    #[test]
+    fn main() {
+        let keypair = KeyPair::new(PrivateKey::decode(PRK), PublicKey::decode(PUK));
+        let signer = Signer::new(keypair);
+        let verifier = Verifier::new(PublicKey::decode(PUK));
+
+        let signature = signer.sign_bytes(b"message digest");
+        assert!(verifier.verify_bytes(b"message digest", &signature));
+        assert_eq!(verifier.check_bytes(b"message", &signature), Err(VerifyError::Mismatch));
+
+        // OpenSSL 3.0 `pkeyutl -sign -digest sm3 -pkeyopt distid:1234567812345678` 生成的签名
+        let openssl = hex::decode(concat!(
+            "30460221009001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb",
+            "022100e229a134165d20694ece0e4d47bcee2b06444b6f5c2a06b74a9b6e5bc0e64cbd",
+        )).unwrap();
+        let signature = Signature::parse(&openssl).unwrap();
+        assert!(verifier.verify_bytes(b"message digest", &signature));
+        assert_eq!(hex::encode(signature.r().to_bytes_be()), "9001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb");
+    }
+
+    #[test]
+    fn formats() {
+        let der = hex::decode(concat!(
+            "30460221009001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb",
+            "022100e229a134165d20694ece0e4d47bcee2b06444b6f5c2a06b74a9b6e5bc0e64cbd",
+        )).unwrap();
+        let signature = Signature::from_der(&der).unwrap();
+        assert_eq!(signature.to_der(), der);
+
+        let raw = signature.to_bytes();
+        assert_eq!(hex::encode(raw), concat!(
+            "9001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb",
+            "e229a134165d20694ece0e4d47bcee2b06444b6f5c2a06b74a9b6e5bc0e64cbd",
+        ));
+        assert_eq!(Signature::from_bytes(&raw).unwrap().to_der(), der);
+
+        // 较短的整数在原始格式中补齐32字节，DER中去掉前导零
+        let mut raw = [0u8; 64];
+        (raw[31], raw[63]) = (1, 0x80);
+        let signature = Signature::from_bytes(&raw).unwrap();
+        assert_eq!(hex::encode(signature.to_der()), "300702010102020080");
+        assert_eq!(Signature::from_der(&signature.to_der()).unwrap().to_bytes(), raw);
+
+        let e = Signature::from_bytes(&raw[..63]).unwrap_err();
+        assert_eq!((e.reason, e.expected, e.actual), ("unexpected length", Some(64), 63));
+        assert!(Signature::from_der(&raw).is_err());
+    }
+
+    #[test]
+    fn base64url() {
+        let der = hex::decode(concat!(
+            "30460221009001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb",
+            "022100e229a134165d20694ece0e4d47bcee2b06444b6f5c2a06b74a9b6e5bc0e64cbd",
+        )).unwrap();
+        let signature = Signature::from_der(&der).unwrap();
+        let text = signature.to_base64url();
+        assert_eq!(text, "kAHJ03I8_xgbR7CcN_B5fs37hJnTH2DfpJgE0h9K0eviKaE0Fl0gaU7ODk1HvO4rBkRLb1wqBrdKm25bwOZMvQ");
+        assert_eq!(Signature::from_base64url(&text).unwrap().to_der(), der);
+
+        // 标准字符集、填充与长度错误
+        let e = Signature::from_base64url(&text.replace('_', "/")).unwrap_err();
+        assert_eq!((e.reason, e.offset), ("invalid base64url", 8));
+        assert_eq!(Signature::from_base64url(&format!("{}==", text)).unwrap_err().reason, "invalid base64url");
+        assert_eq!(Signature::from_base64url(&text[..84]).unwrap_err().reason, "unexpected length");
+    }
+
+    #[test]
+    fn user_id() {
+        let keypair = KeyPair::new(PrivateKey::decode(PRK), PublicKey::decode(PUK));
+        let id = b"ALICE123@YAHOO.COM";
+        let signature = Signer::with_user_id(keypair.clone(), id).sign_bytes(b"message digest");
+
+        assert!(Verifier::with_user_id(PublicKey::decode(PUK), id).verify_bytes(b"message digest", &signature));
+        assert!(!Verifier::new(PublicKey::decode(PUK)).verify_bytes(b"message digest", &signature));
+
+        // 默认用户ID与未指定时一致
+        let signature = Signer::with_user_id(keypair, DEFAULT_USER_ID).sign_bytes(b"message digest");
+        assert!(Verifier::new(PublicKey::decode(PUK)).verify_bytes(b"message digest", &signature));
+
+        // OpenSSL 3.0 `pkeyutl -sign -digest sm3 -pkeyopt distid:ALICE123@YAHOO.COM` 生成的签名
+        let openssl = hex::decode(concat!(
+            "3044022024e41a4ccfab6ff1b85de5270e3199000b7a487f84ae18b9048cbeb61633abcf",
+            "0220673107a97ef8d74d542cd4e5d60a6eefd98c3daff71b71f19911ca247080e8a1",
+        )).unwrap();
+        let signature = Signature::from_der(&openssl).unwrap();
+        assert!(Verifier::with_user_id(PublicKey::decode(PUK), id).verify_bytes(b"message digest", &signature));
+    }
+
+    #[test]
+    #[should_panic(expected = "UID is too large.")]
+    fn user_id_too_large() {
+        Verifier::with_user_id(PublicKey::decode(PUK), &[0x31; 8192]);
+    }
+
+    #[test]
+    fn digest() {
+        let puk = PublicKey::decode(PUK);
+        let za = crate::sm2::compute_za(b"1234567812345678", &puk);
+        let e = super::digest(b"1234567812345678", &puk, b"abc");
+        assert_eq!(e, sm3::hash(&[za.as_slice(), b"abc"].concat()));
+    }
+
+    #[test]
+    fn batch() {
+        let keys: Vec<KeyPair> = (1u8..=3).map(|i| {
+            let d = PrivateKey::decode(&hex::encode([i; 32]));
+            KeyPair::new(d.clone(), d.derive_public(P256Elliptic::init()))
+        }).collect();
+        let messages: Vec<Vec<u8>> = (0..11u8).map(|i| vec![i; i as usize * 7]).collect();
+        let signed: Vec<(Signature, PublicKey)> = messages.iter().enumerate().map(|(i, message)| {
+            let keypair = &keys[i % keys.len()];
+            (Signer::new(keypair.clone()).sign_bytes(message), keypair.puk().clone())
+        }).collect();
+        let items: Vec<(&[u8], &Signature, &PublicKey)> = messages.iter().zip(&signed)
+            .map(|(message, (signature, key))| (message.as_slice(), signature, key))
+            .collect();
+
+        // 11项分为两批，且含有纵坐标为奇、偶的各种组合
+        assert!(verify_batch(&items));
+        assert!(verify_batch(&items[..1]));
+        assert!(verify_batch(&[]));
+
+        // OpenSSL生成的签名
+        let puk = PublicKey::decode(PUK);
+        let openssl = Signature::from_der(&hex::decode(concat!(
+            "30460221009001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb",
+            "022100e229a134165d20694ece0e4d47bcee2b06444b6f5c2a06b74a9b6e5bc0e64cbd",
+        )).unwrap()).unwrap();
+        let mut with_openssl = items.clone();
+        with_openssl.push((b"message digest", &openssl, &puk));
+        assert!(verify_batch(&with_openssl));
+
+        // 任意一项的消息、公钥或签名不符都使整批失败
+        for i in [0, 5, 10] {
+            let mut bad = items.clone();
+            bad[i].0 = b"tampered";
+            assert!(!verify_batch(&bad));
+
+            let mut bad = items.clone();
+            bad[i].2 = &puk;
+            assert!(!verify_batch(&bad));
+        }
+        let swapped = Signature::new(openssl.s().clone(), openssl.r().clone());
+        let mut bad = items.clone();
+        bad[3].1 = &swapped;
+        assert!(!verify_batch(&bad));
+    }
+
+    #[test]
+    fn no_alloc() {
+        let puk: [u8; 65] = hex::decode(PUK).unwrap().try_into().unwrap();
+        let key = PublicKey::decode(PUK);
+        let e = super::digest(DEFAULT_USER_ID, &key, b"message digest");
+
+        let openssl = hex::decode(concat!(
+            "30460221009001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb",
+            "022100e229a134165d20694ece0e4d47bcee2b06444b6f5c2a06b74a9b6e5bc0e64cbd",
+        )).unwrap();
+        let signature = Signature::from_der(&openssl).unwrap().to_bytes();
+        assert_eq!(verify_no_alloc(&puk, &e, &signature), Ok(()));
+
+        let signer = Signer::new(KeyPair::new(PrivateKey::decode(PRK), key.clone()));
+        let verifier = Verifier::new(key.clone());
+        for message in [&b""[..], b"abc", &[0x5a; 1000]] {
+            let e = super::digest(DEFAULT_USER_ID, &key, message);
+            let signature = signer.sign_bytes(message);
+            assert_eq!(verify_no_alloc(&puk, &e, &signature.to_bytes()), verifier.check_bytes(message, &signature));
+        }
+
+        let mut digest = e;
+        digest[0] ^= 1;
+        assert_eq!(verify_no_alloc(&puk, &digest, &signature), Err(VerifyError::Mismatch));
+
+        let n = hex::decode("FFFFFFFEFFFFFFFFFFFFFFFFFFFFFFFF7203DF6B21C6052B53BBF40939D54123").unwrap();
+        let mut bad = signature;
+        bad[..32].fill(0);
+        assert_eq!(verify_no_alloc(&puk, &e, &bad), Err(VerifyError::ROutOfRange));
+        bad[..32].copy_from_slice(&n);
+        assert_eq!(verify_no_alloc(&puk, &e, &bad), Err(VerifyError::ROutOfRange));
+        let mut bad = signature;
+        bad[32..].copy_from_slice(&n);
+        assert_eq!(verify_no_alloc(&puk, &e, &bad), Err(VerifyError::SOutOfRange));
+
+        // r = n - s
+        let s = BigUint::from_bytes_be(&signature[32..]);
+        let r = BigUint::from_bytes_be(&n) - s;
+        let mut bad = signature;
+        bad[..32].copy_from_slice(&crate::sm2::key::to_32_bytes(r.to_bytes_be()));
+        assert_eq!(verify_no_alloc(&puk, &e, &bad), Err(VerifyError::ZeroSum));
+
+        let mut bad = puk;
+        bad[64] ^= 1;
+        assert_eq!(verify_no_alloc(&bad, &e, &signature), Err(VerifyError::InvalidPublicKey));
+        let mut bad = puk;
+        bad[0] = 0x02;
+        assert_eq!(verify_no_alloc(&bad, &e, &signature), Err(VerifyError::InvalidPublicKey));
+    }
+}
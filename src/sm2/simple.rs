@@ -0,0 +1,100 @@
+use crate::sm2::ecc::{Crypto, Signature};
+#[cfg(feature = "sm2-encrypt")]
+use crate::sm2::ecc::DecryptError;
+use crate::sm2::error::VerifyError;
+use crate::sm2::key::{HexKey, PublicKey};
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+use crate::sm2::key::{KeyError, PrivateKey};
+#[cfg(feature = "sm2-sign")]
+use crate::sm2::key::{KeyGenerator, KeyPair};
+#[cfg(feature = "sm2-sign")]
+use crate::sm2::p256::P256Elliptic;
+
+/// 生成秘钥对，返回16进制编码的(私钥, 公钥)
+#[cfg(any(feature = "sm2-sign", feature = "sm2-encrypt"))]
+pub fn generate_keypair() -> (String, String) {
+    crate::sm2::generate_keypair()
+}
+
+/// 加密，密文为C1C3C2顺序的原始字节，公钥格式不正确时返回错误
+#[cfg(feature = "sm2-encrypt")]
+pub fn encrypt(public_key: &str, plain: &[u8]) -> Result<Vec<u8>, KeyError> {
+    Ok(Crypto::default().encryptor(PublicKey::try_decode(public_key)?).encrypt_bytes(plain))
+}
+
+/// 解密C1C3C2顺序的原始字节密文，私钥格式不正确或密文无效时返回错误
+#[cfg(feature = "sm2-encrypt")]
+pub fn decrypt(private_key: &str, cipher: &[u8]) -> Result<Vec<u8>, DecryptError> {
+    let private_key = PrivateKey::try_decode(private_key).map_err(DecryptError::InvalidKey)?;
+    Crypto::default().decryptor(private_key).try_decrypt_bytes(cipher)
+}
+
+/// 签名，公钥由私钥推导，返回DER编码的签名，私钥格式不正确时返回错误
+#[cfg(feature = "sm2-sign")]
+pub fn sign(private_key: &str, data: &[u8]) -> Result<Vec<u8>, KeyError> {
+    let private_key = PrivateKey::try_decode(private_key)?;
+    let public_key = KeyGenerator::init(Box::new(P256Elliptic::init())).gen_public_key(&private_key);
+    let keypair = KeyPair::new(private_key, public_key);
+    Ok(Crypto::default().signer(keypair).sign_bytes(data).encode())
+}
+
+/// 验证DER编码的签名，公钥或签名格式不正确时返回false
+pub fn verify(public_key: &str, data: &[u8], signature: &[u8]) -> bool {
+    check(public_key, data, signature).is_ok()
+}
+
+/// 验证DER编码的签名，失败时返回具体原因，公钥格式不正确时为[`VerifyError::InvalidPublicKey`]
+pub fn check(public_key: &str, data: &[u8], signature: &[u8]) -> Result<(), VerifyError> {
+    let public_key = PublicKey::try_decode(public_key).map_err(|_| VerifyError::InvalidPublicKey)?;
+    let signature = Signature::parse(signature).map_err(VerifyError::Malformed)?;
+    Crypto::default().verifier(public_key).check_bytes(data, &signature)
+}
+
+
+#[cfg(all(test, feature = "sm2-sign", feature = "sm2-encrypt"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn main() {
+        let (prk, puk) = generate_keypair();
+
+        let plain = b"\x00\xffbinary payload";
+        let cipher = encrypt(&puk, plain).unwrap();
+        assert_eq!(cipher.len(), 1 + 64 + 32 + plain.len());
+        assert_eq!(decrypt(&prk, &cipher).unwrap(), plain);
+
+        let signature = sign(&prk, plain).unwrap();
+        assert!(verify(&puk, plain, &signature));
+        assert!(!verify(&puk, b"tampered", &signature));
+        assert!(!verify(&puk, plain, &signature[1..]));
+        match check(&puk, plain, &signature[1..]) {
+            Err(VerifyError::Malformed(e)) => assert_eq!((e.offset, e.reason), (0, "unexpected DER tag")),
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert_eq!(check(&puk, b"tampered", &signature), Err(VerifyError::Mismatch));
+    }
+
+    #[test]
+    fn malformed_input() {
+        let (prk, puk) = generate_keypair();
+        let signature = sign(&prk, b"data").unwrap();
+
+        // 公钥不是16进制、缺少前缀或不在曲线上
+        for bad in ["zz", &puk[2..], &puk[..puk.len() - 2]] {
+            assert!(!verify(bad, b"data", &signature), "{}", bad);
+            assert_eq!(check(bad, b"data", &signature), Err(VerifyError::InvalidPublicKey));
+            assert!(encrypt(bad, b"data").is_err());
+        }
+        // 签名不是DER编码
+        assert!(!verify(&puk, b"data", b"zz"));
+        assert!(!verify(&puk, b"data", &hex::encode(&signature).into_bytes()));
+
+        let cipher = encrypt(&puk, b"data").unwrap();
+        for bad in ["zz", &prk[2..], "00".repeat(32).as_str()] {
+            assert!(matches!(decrypt(bad, &cipher), Err(DecryptError::InvalidKey(_))), "{}", bad);
+            assert!(sign(bad, b"data").is_err());
+        }
+        assert!(matches!(decrypt(&prk, &cipher[1..]), Err(DecryptError::Invalid(_))));
+    }
+}
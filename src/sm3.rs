@@ -1,13 +1,97 @@
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::codec::encode_hex_fixed;
+
 mod core;
+mod hmac;
+mod pbkdf2;
+mod prf;
+#[cfg(all(target_arch = "riscv64", target_feature = "zksh"))]
+mod zksh;
+
+pub use crate::sm3::core::{Hasher, IV};
+pub use crate::sm3::hmac::Hmac;
+pub use crate::sm3::pbkdf2::pbkdf2;
+pub use crate::sm3::prf::{p_sm3, prf};
 
 /// 计算摘要信息：Hash值编码为Hex字符串
 pub fn digest(data: &str) -> String {
-    hex::encode(hash(data.as_bytes()))
+    encode_hex_fixed(hash(data.as_bytes())).to_string()
 }
 
 /// 计算Hash值
 pub fn hash(data: &[u8]) -> [u8; 32] {
-    core::Crypto::new(data).hash()
+    let mut hasher = Hasher::new();
+    hasher.update(data);
+    hasher.finalize()
 }
 
+/// 计算HMAC-SM3消息认证码
+pub fn hmac(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut hmac = Hmac::new(key);
+    hmac.update(data);
+    hmac.finalize()
+}
+
+/// [`hash_many`]默认每个任务处理的输入个数
+pub const DEFAULT_CHUNK_SIZE: usize = 256;
+
+/// 批量计算Hash值，结果与输入一一对应
+///
+/// 启用`rayon`特性时按[`DEFAULT_CHUNK_SIZE`]分块，在rayon线程池中并行计算；否则逐个计算。
+///
+/// ```
+/// use yarism::sm3;
+///
+/// let records = ["abc", "abcd", ""];
+/// let digests = sm3::hash_many(&records);
+/// assert_eq!(digests[0], sm3::hash(b"abc"));
+/// assert_eq!(digests.len(), 3);
+/// ```
+pub fn hash_many<T: AsRef<[u8]> + Sync>(inputs: &[T]) -> Vec<[u8; 32]> {
+    hash_many_with_chunk_size(inputs, DEFAULT_CHUNK_SIZE)
+}
 
+/// 同[`hash_many`]，每个任务处理`chunk_size`个输入
+///
+/// 输入很短时加大分块可减少调度开销，输入长短悬殊时减小分块可使各线程负载均衡。未启用`rayon`特性时分块不起作用。
+pub fn hash_many_with_chunk_size<T: AsRef<[u8]> + Sync>(inputs: &[T], chunk_size: usize) -> Vec<[u8; 32]> {
+    if chunk_size == 0 {
+        fail!("The chunk size must be positive.");
+    }
+    let mut digests = vec![[0u8; 32]; inputs.len()];
+    let hash_chunk = |(out, chunk): (&mut [[u8; 32]], &[T])| {
+        for (digest, input) in out.iter_mut().zip(chunk) {
+            *digest = hash(input.as_ref());
+        }
+    };
+    #[cfg(feature = "rayon")]
+    digests.par_chunks_mut(chunk_size).zip(inputs.par_chunks(chunk_size)).for_each(hash_chunk);
+    #[cfg(not(feature = "rayon"))]
+    digests.chunks_mut(chunk_size).zip(inputs.chunks(chunk_size)).for_each(hash_chunk);
+    digests
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_many_matches_hash() {
+        let records = (0..1000u32).map(|i| i.to_string().repeat(i as usize % 40)).collect::<Vec<_>>();
+        let expected = records.iter().map(|r| hash(r.as_bytes())).collect::<Vec<_>>();
+        assert_eq!(hash_many(&records), expected);
+        for chunk_size in [1, 7, 1000, 4096] {
+            assert_eq!(hash_many_with_chunk_size(&records, chunk_size), expected);
+        }
+        assert!(hash_many::<&[u8]>(&[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "The chunk size must be positive.")]
+    fn zero_chunk_size() {
+        hash_many_with_chunk_size(&[b"abc"], 0);
+    }
+}
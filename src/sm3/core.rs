@@ -1,9 +1,25 @@
-// 初始值，用于确定压缩函数寄存器的初态
-const IV: [u32; 8] = [0x7380_166f, 0x4914_b2b9, 0x1724_42d7, 0xda8a_0600, 0xa96f_30bc, 0x1631_38aa, 0xe38d_ee4d, 0xb0fb_0e4e];
+use crate::endian;
+
+/// 初始值，用于确定压缩函数寄存器的初态
+pub const IV: [u32; 8] = [0x7380_166f, 0x4914_b2b9, 0x1724_42d7, 0xda8a_0600, 0xa96f_30bc, 0x1631_38aa, 0xe38d_ee4d, 0xb0fb_0e4e];
 
 const T0: u32 = 0x79cc_4519;
 const T1: u32 = 0x7a87_9d8a;
 
+/// 压缩函数第j轮使用的常量 Tj <<< (j mod 32)
+const TJ: [u32; 64] = rotated_constants();
+
+const fn rotated_constants() -> [u32; 64] {
+    let mut table = [0u32; 64];
+    let mut j = 0;
+    while j < 64 {
+        let t = if j < 16 { T0 } else { T1 };
+        table[j] = t.rotate_left(j as u32 % 32);
+        j += 1;
+    }
+    table
+}
+
 fn ff0(x: u32, y: u32, z: u32) -> u32 {
     x ^ y ^ z
 }
@@ -21,226 +37,253 @@ fn gg1(x: u32, y: u32, z: u32) -> u32 {
 }
 
 /// 压缩函数中的置换函数
+#[cfg(not(all(target_arch = "riscv64", target_feature = "zksh")))]
 fn p0(x: u32) -> u32 {
     x ^ x.rotate_left(9) ^ x.rotate_left(17)
 }
 
 /// 消息扩展中的置换函数
+#[cfg(not(all(target_arch = "riscv64", target_feature = "zksh")))]
 fn p1(x: u32) -> u32 {
     x ^ x.rotate_left(15) ^ x.rotate_left(23)
 }
 
+#[cfg(all(target_arch = "riscv64", target_feature = "zksh"))]
+use crate::sm3::zksh::{p0, p1};
+
 
-#[derive(Debug)]
-pub struct Crypto {
-    data: Vec<u8>,
-    blocks: Vec<[u8; 64]>,
+/// 流式计算杂凑值
+///
+/// 状态仅包含8个字寄存器与一个64字节的分组缓冲，不分配堆内存、不递归，调用栈占用为常量：
+/// x86_64 release构建下`update`、`finalize`的栈占用约1KiB，debug构建约3KiB。
+#[derive(Clone)]
+#[cfg_attr(any(test, not(feature = "no-fmt")), derive(Debug))]
+pub struct Hasher {
     registers: [u32; 8],
+    buffer: [u8; 64],
+    /// 缓冲区中尚未压缩的字节数
+    pending: usize,
+    /// 已输入消息的字节数
+    length: u64,
 }
 
-impl Crypto {
-    pub fn new(data: &[u8]) -> Self {
-        Crypto {
-            data: data.iter().map(|e| *e).collect(),
-            blocks: Vec::new(),
-            registers: IV,
-        }
+impl Default for Hasher {
+    fn default() -> Self {
+        Self::new()
     }
+}
 
-    pub fn hash(&mut self) -> [u8; 32] {
-        self.pad().block().iterate().output()
+impl Hasher {
+    pub fn new() -> Self {
+        Hasher { registers: IV, buffer: [0; 64], pending: 0, length: 0 }
     }
 
+    /// 输入消息，可多次调用
+    pub fn update(&mut self, data: &[u8]) -> &mut Self {
+        self.length = self.length.wrapping_add(data.len() as u64);
+        let mut data = data;
+
+        if self.pending > 0 {
+            let n = (64 - self.pending).min(data.len());
+            self.buffer[self.pending..self.pending + n].copy_from_slice(&data[..n]);
+            self.pending += n;
+            data = &data[n..];
+            if self.pending < 64 {
+                return self;
+            }
+            compress(&mut self.registers, &self.buffer);
+            self.pending = 0;
+        }
+
+        // 分组： 将消息按512比特进行分组，不足一个分组的部分留待后续输入或填充
+        let mut blocks = data.chunks_exact(64);
+        for block in &mut blocks {
+            compress(&mut self.registers, block.try_into().unwrap());
+        }
+        let rest = blocks.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.pending = rest.len();
+        self
+    }
+
+    /// 填充并输出256比特的哈希值
+    ///
     /// 假设消息m的长度为l 比特。首先将比特“1”添加到消息的末尾，再添加k 个“0”，
     /// k是满足l + 1 + k ≡ 448mod512 的最小的非负整数。然后再添加一个64位比特串，该比特串是长度l的二进 制表示。
     /// 填充后的消息m′的比特长度为512的倍数。
     /// 例如:对消息01100001 01100010 01100011，其长度l=24，经填充得到比特串:
     /// 01100001 01100010 01100011 1 {00 · · · 00}(423比特) {00 · · · 011000}(64比特，l的二进制表示)
-    fn pad(&mut self) -> &mut Self {
+    pub fn finalize(mut self) -> [u8; 32] {
         // 计算原始数据的长度
-        let l = (self.data.len() << 3) as u64;
-        // 将'10000000'添加到数据的末尾
-        self.data.push(0x80);
-        // 循环n次填充0x00, l + 8 + k = 448 mod 512,  k mod 8 = n
-        while self.data.len() % 64 != 56 {
-            self.data.push(0x00);
-        }
+        let l = self.length.wrapping_mul(8);
+        // 将'10000000'添加到数据的末尾，再填充0x00直至 l + 8 + k = 448 mod 512
+        let mut pad = [0u8; 64];
+        pad[0] = 0x80;
+        let n = if self.pending < 56 { 56 - self.pending } else { 120 - self.pending };
+        self.update(&pad[..n]);
         // 填充l的二进制表示，长度64位；填充后的数据总长度为512 * N位。
-        self.data.push((l >> 56 & 0xff) as u8);
-        self.data.push((l >> 48 & 0xff) as u8);
-        self.data.push((l >> 40 & 0xff) as u8);
-        self.data.push((l >> 32 & 0xff) as u8);
-        self.data.push((l >> 24 & 0xff) as u8);
-        self.data.push((l >> 16 & 0xff) as u8);
-        self.data.push((l >> 8 & 0xff) as u8);
-        self.data.push((l & 0xff) as u8);
-        self
-    }
-
+        self.update(&l.to_be_bytes());
 
-    /// 分组： 将填充后的消息m′按512比特进行分组:m′ = B(0)B(1) · · · B(n−1), 其中n=(l+k+65)/512。
-    fn block(&mut self) -> &mut Self {
-        let length = self.data.len();
-        let mut c = 0;
-        while c * 64 != length {
-            let mut block = [0; 64];
-            for i in (c * 64)..((c + 1) * 64) {
-                block[i - c * 64] = self.data[i];
-            }
-            self.blocks.push(block);
-            c += 1;
-        }
-        self
+        // 大端模式：[u32; 8] -> [u8; 32]
+        let mut hash: [u8; 32] = [0; 32];
+        endian::store_be(&self.registers, &mut hash);
+        hash
     }
+}
 
-    /// 迭代压缩
-    /// 1. 扩展
-    ///     将消息分组B(i)按以下方法扩展生成132个字W0, W1, · · · , W67, W0′, W1′, · · · , W63′，
-    ///     用于压缩函数CF:
-    ///         a)将消息分组B(i)划分为16个字W0, W1, · · · , W15。
-    ///         b)FOR j=16 TO 67
-    ///             Wj ← P1(Wj−16 ⊕Wj−9 ⊕(Wj−3 ≪ 15))⊕(Wj−13 ≪ 7)⊕Wj−6
-    ///         c)FOR j=0 TO 63
-    ///              Wj′ =Wj ⊕Wj+4
-    /// 2.压缩
-    ///     令A,B,C,D,E,F,G,H为字寄存器,SS1,SS2,TT1,TT2为中间变量,压缩函数V i+1 i ≤ n−1。
-    ///     计算过程描述如下:
-    ///     ABCDEFGH ← V (i)
-    ///     FOR j=0 TO 63
-    ///         SS1←((A≪12)+E+(Tj ≪j))≪7 SS2 ← SS1⊕(A ≪ 12)
-    ///         TT1 ← FFj (A, B, C) + D + SS2 + Wj′
-    ///         TT2 ← GGj (E, F, G) + H + SS1 + Wj
-    ///         D←C
-    ///         C←B≪9
-    ///         B←A
-    ///         A←TT1
-    ///         H←G
-    ///         G ← F ≪ 19
-    ///         F←E
-    ///         E ← P0(TT2)
-    ///     V(i+1) ← ABCDEFGH⊕V(i)
-    fn iterate(&mut self) -> &mut Self {
-        self.blocks.iter().for_each(|b| {
-            // 扩展
-            // 每个分组扩展生成132个字W0, W1, · · · , W67, W0′, W1′, · · · , W63′
-            let mut w1: [u32; 68] = [0; 68];
-            let mut w2: [u32; 64] = [0; 64];
-            // 将消息分组B(i)划分为16个字 W0, W1, · · · , W15
-            for i in 0..16 {
-                w1[i] = u32::from(b[i * 4]) << 24
-                    | u32::from(b[i * 4 + 1]) << 16
-                    | u32::from(b[i * 4 + 2]) << 8
-                    | u32::from(b[i * 4 + 3]);
-            }
-            // 计算 W16, ..., W67;  Wj ← P1(Wj−16 ⊕ Wj−9 ⊕ (Wj−3 ≪ 15)) ⊕ (Wj−13 ≪ 7) ⊕ Wj−6
-            for i in 16..68 {
-                w1[i] = p1(w1[i - 16] ^ w1[i - 9] ^ w1[i - 3].rotate_left(15))
-                    ^ w1[i - 13].rotate_left(7)
-                    ^ w1[i - 6];
-            }
-            // 计算 W': W'0, W'1, ... W'63;   Wj′ = Wj ⊕ Wj+4
-            for i in 0..64 {
-                w2[i] = w1[i] ^ w1[i + 4];
-            }
-            // 压缩
-            // ABCDEFGH ← V (i)
-            let mut ra = self.registers[0];
-            let mut rb = self.registers[1];
-            let mut rc = self.registers[2];
-            let mut rd = self.registers[3];
-            let mut re = self.registers[4];
-            let mut rf = self.registers[5];
-            let mut rg = self.registers[6];
-            let mut rh = self.registers[7];
-
-            let mut ss1: u32;
-            let mut ss2: u32;
-            let mut tt1: u32;
-            let mut tt2: u32;
-            for i in 0..16 {
-                ss1 = ra.rotate_left(12)
-                    .wrapping_add(re)
-                    .wrapping_add(T0.rotate_left(i as u32))
-                    .rotate_left(7);
-                ss2 = ss1 ^ ra.rotate_left(12);
-                tt1 = ff0(ra, rb, rc)
-                    .wrapping_add(rd)
-                    .wrapping_add(ss2)
-                    .wrapping_add(w2[i]);
-                tt2 = gg0(re, rf, rg)
-                    .wrapping_add(rh)
-                    .wrapping_add(ss1)
-                    .wrapping_add(w1[i]);
-                rd = rc;
-                rc = rb.rotate_left(9);
-                rb = ra;
-                ra = tt1;
-                rh = rg;
-                rg = rf.rotate_left(19);
-                rf = re;
-                re = p0(tt2);
-            }
-            for i in 16..64 {
-                ss1 = ra.rotate_left(12)
-                    .wrapping_add(re)
-                    .wrapping_add(T1.rotate_left(i as u32))
-                    .rotate_left(7);
-                ss2 = ss1 ^ ra.rotate_left(12);
-                tt1 = ff1(ra, rb, rc)
-                    .wrapping_add(rd)
-                    .wrapping_add(ss2)
-                    .wrapping_add(w2[i]);
-                tt2 = gg1(re, rf, rg)
-                    .wrapping_add(rh)
-                    .wrapping_add(ss1)
-                    .wrapping_add(w1[i]);
-                rd = rc;
-                rc = rb.rotate_left(9);
-                rb = ra;
-                ra = tt1;
-                rh = rg;
-                rg = rf.rotate_left(19);
-                rf = re;
-                re = p0(tt2);
-            }
-            // V(i+1) ← ABCDEFGH⊕V(i)
-            self.registers[0] ^= ra;
-            self.registers[1] ^= rb;
-            self.registers[2] ^= rc;
-            self.registers[3] ^= rd;
-            self.registers[4] ^= re;
-            self.registers[5] ^= rf;
-            self.registers[6] ^= rg;
-            self.registers[7] ^= rh;
-        });
-        self
+/// 迭代压缩
+/// ```text
+/// 1. 扩展
+///     将消息分组B(i)按以下方法扩展生成132个字W0, W1, · · · , W67, W0′, W1′, · · · , W63′，
+///     用于压缩函数CF:
+///         a)将消息分组B(i)划分为16个字W0, W1, · · · , W15。
+///         b)FOR j=16 TO 67
+///             Wj ← P1(Wj−16 ⊕Wj−9 ⊕(Wj−3 ≪ 15))⊕(Wj−13 ≪ 7)⊕Wj−6
+///         c)FOR j=0 TO 63
+///              Wj′ =Wj ⊕Wj+4
+/// 2.压缩
+///     令A,B,C,D,E,F,G,H为字寄存器,SS1,SS2,TT1,TT2为中间变量,压缩函数V i+1 i ≤ n−1。
+///     计算过程描述如下:
+///     ABCDEFGH ← V (i)
+///     FOR j=0 TO 63
+///         SS1←((A≪12)+E+(Tj ≪j))≪7 SS2 ← SS1⊕(A ≪ 12)
+///         TT1 ← FFj (A, B, C) + D + SS2 + Wj′
+///         TT2 ← GGj (E, F, G) + H + SS1 + Wj
+///         D←C
+///         C←B≪9
+///         B←A
+///         A←TT1
+///         H←G
+///         G ← F ≪ 19
+///         F←E
+///         E ← P0(TT2)
+///     V(i+1) ← ABCDEFGH⊕V(i)
+/// ```
+fn compress(registers: &mut [u32; 8], b: &[u8; 64]) {
+    // 扩展
+    // 每个分组扩展生成132个字W0, W1, · · · , W67, W0′, W1′, · · · , W63′
+    let mut w1: [u32; 68] = [0; 68];
+    let mut w2: [u32; 64] = [0; 64];
+    // 将消息分组B(i)划分为16个字 W0, W1, · · · , W15
+    w1[..16].copy_from_slice(&endian::load_be::<16>(b));
+    // 计算 W16, ..., W67;  Wj ← P1(Wj−16 ⊕ Wj−9 ⊕ (Wj−3 ≪ 15)) ⊕ (Wj−13 ≪ 7) ⊕ Wj−6
+    for i in 16..68 {
+        w1[i] = p1(w1[i - 16] ^ w1[i - 9] ^ w1[i - 3].rotate_left(15))
+            ^ w1[i - 13].rotate_left(7)
+            ^ w1[i - 6];
+    }
+    // 计算 W': W'0, W'1, ... W'63;   Wj′ = Wj ⊕ Wj+4
+    for i in 0..64 {
+        w2[i] = w1[i] ^ w1[i + 4];
     }
+    // 压缩
+    // ABCDEFGH ← V (i)
+    let mut ra = registers[0];
+    let mut rb = registers[1];
+    let mut rc = registers[2];
+    let mut rd = registers[3];
+    let mut re = registers[4];
+    let mut rf = registers[5];
+    let mut rg = registers[6];
+    let mut rh = registers[7];
 
-    /// 输出256比特的哈希值
-    fn output(&self) -> [u8; 32] {
-        // 大端模式：[u32; 8] -> [u8; 32]
-        let mut hash: [u8; 32] = [0; 32];
-        for (i, e) in self.registers.iter().enumerate() {
-            hash[i * 4] = (*e >> 24) as u8;
-            hash[i * 4 + 1] = (*e >> 16) as u8;
-            hash[i * 4 + 2] = (*e >> 8) as u8;
-            hash[i * 4 + 3] = *e as u8;
-        }
-        hash
+    let mut ss1: u32;
+    let mut ss2: u32;
+    let mut tt1: u32;
+    let mut tt2: u32;
+    for i in 0..16 {
+        ss1 = ra.rotate_left(12)
+            .wrapping_add(re)
+            .wrapping_add(TJ[i])
+            .rotate_left(7);
+        ss2 = ss1 ^ ra.rotate_left(12);
+        tt1 = ff0(ra, rb, rc)
+            .wrapping_add(rd)
+            .wrapping_add(ss2)
+            .wrapping_add(w2[i]);
+        tt2 = gg0(re, rf, rg)
+            .wrapping_add(rh)
+            .wrapping_add(ss1)
+            .wrapping_add(w1[i]);
+        rd = rc;
+        rc = rb.rotate_left(9);
+        rb = ra;
+        ra = tt1;
+        rh = rg;
+        rg = rf.rotate_left(19);
+        rf = re;
+        re = p0(tt2);
+    }
+    for i in 16..64 {
+        ss1 = ra.rotate_left(12)
+            .wrapping_add(re)
+            .wrapping_add(TJ[i])
+            .rotate_left(7);
+        ss2 = ss1 ^ ra.rotate_left(12);
+        tt1 = ff1(ra, rb, rc)
+            .wrapping_add(rd)
+            .wrapping_add(ss2)
+            .wrapping_add(w2[i]);
+        tt2 = gg1(re, rf, rg)
+            .wrapping_add(rh)
+            .wrapping_add(ss1)
+            .wrapping_add(w1[i]);
+        rd = rc;
+        rc = rb.rotate_left(9);
+        rb = ra;
+        ra = tt1;
+        rh = rg;
+        rg = rf.rotate_left(19);
+        rf = re;
+        re = p0(tt2);
     }
+    // V(i+1) ← ABCDEFGH⊕V(i)
+    registers[0] ^= ra;
+    registers[1] ^= rb;
+    registers[2] ^= rc;
+    registers[3] ^= rd;
+    registers[4] ^= re;
+    registers[5] ^= rf;
+    registers[6] ^= rg;
+    registers[7] ^= rh;
 }
 
 
 #[cfg(test)]
 mod tests {
-    use crate::sm3::core::Crypto;
+    use crate::sm3::core::{Hasher, T0, T1, TJ};
+
+    #[test]
+    fn rotated_constants() {
+        assert_eq!(TJ[0], T0);
+        assert_eq!(TJ[15], T0.rotate_left(15));
+        assert_eq!(TJ[16], T1.rotate_left(16));
+        assert_eq!(TJ[33], T1.rotate_left(1));
+        assert_eq!(TJ[63], 0x3d43cec5);
+    }
 
     #[test]
     fn main() {
         let plain = String::from("abc");
         let data = plain.as_bytes();
-        let hash = hex::encode(Crypto::new(data).hash());
+        let hash = hex::encode(Hasher::new().update(data).clone().finalize());
         assert_eq!(hash, "66c7f0f462eeedd9d1f2d46bdc10e4e24167c4875cf2f7a2297da02b8f4ba8e0");
+
+        // GB/T 32905 附录A示例2：512比特消息
+        let data = "abcd".repeat(16);
+        let hash = hex::encode(Hasher::new().update(data.as_bytes()).clone().finalize());
+        assert_eq!(hash, "debe9ff92275b8a138604889c18e5a4d6fdb70e5387e5765293dcba39c0c5732");
+
+        // 任意切分输入，结果一致
+        let data: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+        let mut expected = Hasher::new();
+        expected.update(&data);
+        let expected = expected.finalize();
+        for step in [1, 3, 55, 56, 63, 64, 65, 200] {
+            let mut hasher = Hasher::new();
+            data.chunks(step).for_each(|e| { hasher.update(e); });
+            assert_eq!(hasher.finalize(), expected);
+        }
     }
 }
 
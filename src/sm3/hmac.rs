@@ -0,0 +1,64 @@
+use crate::sm3::core::Hasher;
+
+const BLOCK_SIZE: usize = 64;
+
+/// HMAC-SM3（GB/T 15852.2、RFC 2104）
+///
+/// 构造时完成密钥的内外层填充，`clone`后可对多条消息复用。
+#[derive(Clone)]
+#[cfg_attr(any(test, not(feature = "no-fmt")), derive(Debug))]
+pub struct Hmac {
+    inner: Hasher,
+    outer: Hasher,
+}
+
+impl Hmac {
+    pub fn new(key: &[u8]) -> Self {
+        // 超过分组长度的密钥先做杂凑
+        let mut block = [0u8; BLOCK_SIZE];
+        if key.len() > BLOCK_SIZE {
+            block[..32].copy_from_slice(&crate::sm3::hash(key));
+        } else {
+            block[..key.len()].copy_from_slice(key);
+        }
+
+        let (mut inner, mut outer) = (Hasher::new(), Hasher::new());
+        inner.update(&block.map(|b| b ^ 0x36));
+        outer.update(&block.map(|b| b ^ 0x5c));
+        Hmac { inner, outer }
+    }
+
+    pub fn update(&mut self, data: &[u8]) -> &mut Self {
+        self.inner.update(data);
+        self
+    }
+
+    pub fn finalize(self) -> [u8; 32] {
+        let mut outer = self.outer;
+        outer.update(&self.inner.finalize());
+        outer.finalize()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn main() {
+        let mac = |key: &[u8], data: &[u8]| hex::encode(crate::sm3::hmac(key, data));
+        assert_eq!(mac(b"", b""), "0d23f72ba15e9c189a879aefc70996b06091de6e64d31b7a84004356dd915261");
+        assert_eq!(mac(b"key", b"The quick brown fox jumps over the lazy dog"), "bd4a34077888162b210645b8ebf74b9af357303789357a27c7fc457244ebd398");
+        assert_eq!(
+            mac(&(1..=32).collect::<Vec<u8>>(), b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            "be190a66f210be0df56c243d5c1a53e9301fd3f0313244a412f6ae97ab7fe407",
+        );
+        // 长于分组的密钥
+        assert_eq!(mac(&[0xaa; 80], b"abc"), "da7b7a52ef9391112a811e9f1cf2d72c5bfaaefe4f71bf2a7ace5f1a4fa37a1c");
+
+        let mut hmac = Hmac::new(b"key");
+        hmac.update(b"The quick brown ").update(b"fox jumps over the lazy dog");
+        assert_eq!(hex::encode(hmac.finalize()), "bd4a34077888162b210645b8ebf74b9af357303789357a27c7fc457244ebd398");
+    }
+}
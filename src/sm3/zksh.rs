@@ -0,0 +1,23 @@
+//! RISC-V Zksh标量密码扩展：sm3p0/sm3p1指令分别计算置换函数P0、P1
+
+use std::arch::asm;
+
+/// 压缩函数中的置换函数
+#[inline(always)]
+pub(crate) fn p0(x: u32) -> u32 {
+    let y: u32;
+    unsafe {
+        asm!("sm3p0 {y}, {x}", y = lateout(reg) y, x = in(reg) x, options(pure, nomem, nostack));
+    }
+    y
+}
+
+/// 消息扩展中的置换函数
+#[inline(always)]
+pub(crate) fn p1(x: u32) -> u32 {
+    let y: u32;
+    unsafe {
+        asm!("sm3p1 {y}, {x}", y = lateout(reg) y, x = in(reg) x, options(pure, nomem, nostack));
+    }
+    y
+}
@@ -1,15 +1,39 @@
+use crate::codec::{decode_hex_fixed, encode_hex_fixed, SizeLimitError, DEFAULT_MAX_LEN};
+use crate::env;
+
 mod core;
 mod ecb;
-mod cbc;
+pub(crate) mod cbc;
 mod cfb;
 mod ofb;
 mod ctr;
+mod cts;
+#[cfg(feature = "sm3")]
+mod essiv;
+mod gcm;
+mod iv;
+mod key;
+mod mac;
+#[cfg(all(target_arch = "riscv64", target_feature = "zksed"))]
+mod zksed;
+#[cfg(feature = "rustcrypto")]
+mod rustcrypto;
+
+pub use crate::sm4::cbc::CbcError;
+pub use crate::sm4::core::{CK, FK, SBOX};
+#[cfg(feature = "sm3")]
+pub use crate::sm4::essiv::{Essiv, DEFAULT_SECTOR_SIZE};
+pub use crate::sm4::gcm::{allow_nonce_reuse, GcmDecryptor, GcmEncryptor, GcmError, Sm4Gcm};
+pub use crate::sm4::iv::{Iv16, Nonce12};
+pub use crate::sm4::key::Sm4Key;
+pub use crate::sm4::mac::MacPadding;
+#[cfg(feature = "rustcrypto")]
+pub use crate::sm4::rustcrypto::Sm4;
 
 
 /// 随机生成秘钥，返回由16进制字符组成的长度为32的字符串
 pub fn generate_key() -> String {
-    let key = uuid::Uuid::new_v4();
-    key.to_simple().to_string()
+    encode_hex_fixed(env::random::<16>()).to_string()
 }
 
 /// 随机初始化向量，返回由16进制字符组成的长度为32的字符串，用于CBC、CFB、OFB、CTR分组模式
@@ -17,6 +41,19 @@ pub fn generate_iv() -> String {
     generate_key()
 }
 
+/// 加密单个分组
+///
+/// 轮秘钥与中间状态均在栈上，不分配堆内存、不递归，适用于栈空间受限的引导程序：
+/// x86_64 release构建下栈占用不超过512字节，debug构建约1.5KiB。
+pub fn encrypt_block(key: &[u8; 16], block: &mut [u8; 16]) {
+    *block = core::Crypto::init(key).encrypt(block);
+}
+
+/// 解密单个分组，栈占用同[`encrypt_block`]
+pub fn decrypt_block(key: &[u8; 16], block: &mut [u8; 16]) {
+    *block = core::Crypto::init(key).decrypt(block);
+}
+
 pub fn encrypt_ecb(key: String, plain: String) -> String {
     let mode = Mode::ECB { key };
     let crypto = CryptoFactory::new(mode);
@@ -90,21 +127,106 @@ pub trait Cryptographer {
 
     fn decrypt_bytes(&self, cipher: &[u8]) -> Vec<u8>;
 
+    /// 单次加解密输入的长度上限，默认为[`DEFAULT_MAX_LEN`]，可由[`CryptoFactory::with_max_len`]调整
+    fn max_len(&self) -> usize {
+        DEFAULT_MAX_LEN
+    }
+
+    /// 明文超过[`Cryptographer::max_len`]时返回错误
+    fn try_encrypt_bytes(&self, plain: &[u8]) -> Result<Vec<u8>, SizeLimitError> {
+        SizeLimitError::check("SM4 plain data", plain.len(), self.max_len())?;
+        Ok(self.encrypt_bytes(plain))
+    }
+
+    /// 密文超过[`Cryptographer::max_len`]时返回错误
+    fn try_decrypt_bytes(&self, cipher: &[u8]) -> Result<Vec<u8>, SizeLimitError> {
+        SizeLimitError::check("SM4 cipher data", cipher.len(), self.max_len())?;
+        Ok(self.decrypt_bytes(cipher))
+    }
+
     fn encrypt(&self, data: String) -> String {
         let cipher = self.encrypt_bytes(data.as_bytes());
         hex::encode(cipher)
     }
 
+    /// 16进制密文长度超过[`Cryptographer::max_len`]的两倍时在解码之前panic
     fn decrypt(&self, data: String) -> String {
-        let plain = self.decrypt_bytes(&hex::decode(data).unwrap());
+        if let Err(e) = SizeLimitError::check("SM4 hex cipher data", data.len(), self.max_len().saturating_mul(2)) {
+            fail!("{}", e)
+        }
+        let cipher = match hex::decode(data) {
+            Ok(cipher) => cipher,
+            Err(e) => fail!("The cipher must be composed of hex chars. {}", e),
+        };
+        let plain = self.decrypt_bytes(&cipher);
         String::from_utf8_lossy(plain.as_ref()).to_string()
     }
 }
 
+/// GCM加密，返回 C ‖ T，T为16字节认证标签
+pub fn encrypt_gcm(key: &[u8; 16], nonce: &Nonce12, aad: &[u8], plain: &[u8]) -> Vec<u8> {
+    Sm4Gcm::new(key).encrypt(nonce, aad, plain)
+}
+
+/// GCM解密 C ‖ T，认证标签校验失败时不返回任何明文
+pub fn decrypt_gcm(key: &[u8; 16], nonce: &Nonce12, aad: &[u8], cipher: &[u8]) -> Result<Vec<u8>, GcmError> {
+    Sm4Gcm::new(key).decrypt(nonce, aad, cipher)
+}
+
+/// GCM原地加密，返回分离的16字节认证标签，适用于直接处理网络收发缓冲区
+pub fn encrypt_gcm_in_place_detached(key: &[u8; 16], nonce: &Nonce12, aad: &[u8], buffer: &mut [u8]) -> [u8; 16] {
+    let mut encryptor = GcmEncryptor::new(key, nonce);
+    encryptor.update_aad(aad).update_in_place(buffer);
+    encryptor.finalize_tag()
+}
+
+/// GCM原地解密，认证标签校验失败时缓冲区保持为密文
+pub fn decrypt_gcm_in_place_detached(key: &[u8; 16], nonce: &Nonce12, aad: &[u8], buffer: &mut [u8], tag: &[u8]) -> Result<(), GcmError> {
+    let mut decryptor = GcmDecryptor::new(key, nonce);
+    decryptor.update_aad(aad);
+    decryptor.decrypt_in_place_detached(buffer, tag)
+}
+
+/// CBC原地解密并校验PKCS#7填充，返回去除填充后的明文切片
+pub fn decrypt_cbc_in_place<'a>(key: &[u8; 16], iv: &Iv16, buffer: &'a mut [u8]) -> Result<&'a mut [u8], CbcError> {
+    cbc::decrypt_in_place(key, iv.as_bytes(), buffer)
+}
+
+/// CBC-CS3密文窃取加密，密文与明文等长，适用于须保持原长度的场景（如数据库列的原地加密）
+///
+/// 数据至少16字节。
+pub fn encrypt_cbc_cs3_in_place(key: &[u8; 16], iv: &Iv16, buffer: &mut [u8]) -> Result<(), CbcError> {
+    cts::encrypt_in_place(key, iv.as_bytes(), buffer)
+}
+
+/// CBC-CS3密文窃取解密
+pub fn decrypt_cbc_cs3_in_place(key: &[u8; 16], iv: &Iv16, buffer: &mut [u8]) -> Result<(), CbcError> {
+    cts::decrypt_in_place(key, iv.as_bytes(), buffer)
+}
+
+/// 旧式CBC-MAC：零IV的CBC加密，取最后一个密文分组作为消息认证码
+///
+/// **警告**：仅用于对接仍要求CBC-MAC的旧银行规范。对变长消息可伪造（已知M的MAC即可构造M ‖ M'的MAC），
+/// 且无密钥派生与长度绑定，新设计应使用HMAC-SM3或GCM。
+pub fn legacy_cbc_mac(key: &[u8; 16], data: &[u8], padding: MacPadding) -> [u8; 16] {
+    mac::cbc_mac(key, data, padding)
+}
+
+/// 初始化向量的传递方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IvPolicy {
+    /// 初始化向量置于密文之前：IV ‖ C，常见于Java等实现
+    Prefixed,
+    /// 初始化向量与密文分开传递
+    #[default]
+    Separate,
+}
+
 pub struct CryptoFactory;
 
 
 impl CryptoFactory {
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(mode: Mode) -> Box<dyn Cryptographer> {
         match mode {
             Mode::ECB { key } => {
@@ -124,6 +246,88 @@ impl CryptoFactory {
             }
         }
     }
+
+    /// 按指定的初始化向量传递方式构造分组模式
+    ///
+    /// `IvPolicy::Prefixed`时，加密使用`mode`中的初始化向量并将其置于密文之前，解密从密文前16字节读取初始化向量。
+    pub fn with_iv_policy(mode: Mode, policy: IvPolicy) -> Box<dyn Cryptographer> {
+        if policy == IvPolicy::Separate {
+            return CryptoFactory::new(mode);
+        }
+        let (key, iv, build): (_, _, ModeBuilder) = match mode {
+            Mode::ECB { .. } => fail!("The ECB mode does not use an IV."),
+            Mode::CBC { key, iv } => (key, iv, |k, v| Box::new(cbc::CryptoMode::new(k, v))),
+            Mode::CFB { key, iv } => (key, iv, |k, v| Box::new(cfb::CryptoMode::new(k, v))),
+            Mode::OFB { key, iv } => (key, iv, |k, v| Box::new(ofb::CryptoMode::new(k, v))),
+            Mode::CTR { key, iv } => (key, iv, |k, v| Box::new(ctr::CryptoMode::new(k, v))),
+        };
+        Box::new(PrefixedIv { key: hex_decode_of_key(&key), iv: hex_decode_of_iv(&iv), build })
+    }
+
+    /// 为分组模式设置单次输入的长度上限，超出时各加解密方法在处理之前panic，`try_`方法返回错误
+    pub fn with_max_len(crypto: Box<dyn Cryptographer>, max_len: usize) -> Box<dyn Cryptographer> {
+        Box::new(Limited { crypto, max_len })
+    }
+}
+
+type ModeBuilder = fn(&[u8], &[u8]) -> Box<dyn Cryptographer>;
+
+/// 密文格式为 IV ‖ C 的分组模式
+struct PrefixedIv {
+    key: [u8; 16],
+    iv: [u8; 16],
+    build: ModeBuilder,
+}
+
+impl Cryptographer for PrefixedIv {
+    fn encrypt_bytes(&self, plain: &[u8]) -> Vec<u8> {
+        let cipher = (self.build)(&self.key, &self.iv).encrypt_bytes(plain);
+        [&self.iv[..], &cipher].concat()
+    }
+
+    fn decrypt_bytes(&self, cipher: &[u8]) -> Vec<u8> {
+        if cipher.len() < 16 {
+            fail!("The cipher must start with a 16 bytes IV.");
+        }
+        let (iv, cipher) = cipher.split_at(16);
+        (self.build)(&self.key, iv).decrypt_bytes(cipher)
+    }
+}
+
+/// 限制输入长度的分组模式
+struct Limited {
+    crypto: Box<dyn Cryptographer>,
+    max_len: usize,
+}
+
+impl Cryptographer for Limited {
+    fn encrypt_bytes(&self, plain: &[u8]) -> Vec<u8> {
+        match self.try_encrypt_bytes(plain) {
+            Ok(cipher) => cipher,
+            Err(e) => fail!("{}", e),
+        }
+    }
+
+    fn decrypt_bytes(&self, cipher: &[u8]) -> Vec<u8> {
+        match self.try_decrypt_bytes(cipher) {
+            Ok(plain) => plain,
+            Err(e) => fail!("{}", e),
+        }
+    }
+
+    fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    fn try_encrypt_bytes(&self, plain: &[u8]) -> Result<Vec<u8>, SizeLimitError> {
+        SizeLimitError::check("SM4 plain data", plain.len(), self.max_len)?;
+        Ok(self.crypto.encrypt_bytes(plain))
+    }
+
+    fn try_decrypt_bytes(&self, cipher: &[u8]) -> Result<Vec<u8>, SizeLimitError> {
+        SizeLimitError::check("SM4 cipher data", cipher.len(), self.max_len)?;
+        Ok(self.crypto.decrypt_bytes(cipher))
+    }
 }
 
 
@@ -135,19 +339,71 @@ fn xor(a: &[u8], b: &[u8]) -> [u8; 16] {
     out
 }
 
-fn hex_decode_of_key(key: &str) -> Vec<u8> {
-    match hex::decode(key) {
+fn hex_decode_of_key(key: &str) -> [u8; 16] {
+    match decode_hex_fixed::<16>(key) {
         Ok(data) => data,
-        Err(_) => panic!("The Key must be composed of hex chars with a length of 32.")
+        Err(e) => fail!("The Key must be composed of hex chars with a length of 32. {}", e)
     }
 }
 
-fn hex_decode_of_iv(iv: &str) -> Vec<u8> {
-    match hex::decode(iv) {
+fn hex_decode_of_iv(iv: &str) -> [u8; 16] {
+    match decode_hex_fixed::<16>(iv) {
         Ok(data) => data,
-        Err(_) => panic!("The IV must be composed of hex chars with a length of 32.")
+        Err(e) => fail!("The IV must be composed of hex chars with a length of 32. {}", e)
     }
 }
 
 
 
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iv_policy() {
+        let key = "0123456789abcdeffedcba9876543210".to_string();
+        let iv = "00112233445566778899aabbccddeeff".to_string();
+        let plain = b"Hello World, \xe5\x93\x88\xe5\x96\xbd";
+
+        let modes = || [
+            Mode::CBC { key: key.clone(), iv: iv.clone() },
+            Mode::CFB { key: key.clone(), iv: iv.clone() },
+            Mode::OFB { key: key.clone(), iv: iv.clone() },
+            Mode::CTR { key: key.clone(), iv: iv.clone() },
+        ];
+        for (separate, prefixed) in modes().into_iter().zip(modes()) {
+            let separate = CryptoFactory::with_iv_policy(separate, IvPolicy::Separate);
+            let prefixed = CryptoFactory::with_iv_policy(prefixed, IvPolicy::Prefixed);
+
+            let cipher = prefixed.encrypt_bytes(plain);
+            assert_eq!(hex::encode(&cipher[..16]), iv);
+            assert_eq!(cipher[16..], separate.encrypt_bytes(plain));
+            assert_eq!(prefixed.decrypt_bytes(&cipher), plain);
+        }
+
+        // 解密时以密文携带的初始化向量为准
+        let other = "ffeeddccbbaa99887766554433221100".to_string();
+        let sender = CryptoFactory::with_iv_policy(Mode::CBC { key: key.clone(), iv: other }, IvPolicy::Prefixed);
+        let receiver = CryptoFactory::with_iv_policy(Mode::CBC { key, iv }, IvPolicy::Prefixed);
+        assert_eq!(receiver.decrypt_bytes(&sender.encrypt_bytes(plain)), plain);
+    }
+    #[test]
+    fn max_len() {
+        let key = "0123456789abcdeffedcba9876543210".to_string();
+        let iv = "00112233445566778899aabbccddeeff".to_string();
+        let cbc = CryptoFactory::with_max_len(CryptoFactory::new(Mode::CBC { key: key.clone(), iv: iv.clone() }), 32);
+        let prefixed = CryptoFactory::with_iv_policy(Mode::CTR { key, iv }, IvPolicy::Prefixed);
+        let ctr = CryptoFactory::with_max_len(prefixed, 32);
+
+        let cipher = cbc.try_encrypt_bytes(&[1; 31]).unwrap();
+        assert_eq!(cbc.try_decrypt_bytes(&cipher).unwrap(), [1; 31]);
+        assert_eq!(cbc.try_encrypt_bytes(&[1; 33]), Err(SizeLimitError { what: "SM4 plain data", len: 33, max: 32 }));
+        assert_eq!(ctr.try_decrypt_bytes(&[0; 48]).unwrap_err().len, 48);
+        assert_eq!(ctr.decrypt_bytes(&ctr.encrypt_bytes(&[2; 16])), [2; 16]);
+
+        assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| cbc.decrypt("00".repeat(33)))).is_err());
+        assert_eq!(CryptoFactory::new(Mode::ECB { key: "0123456789abcdeffedcba9876543210".to_string() }).max_len(), DEFAULT_MAX_LEN);
+    }
+}
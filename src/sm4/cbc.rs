@@ -1,5 +1,49 @@
+use std::fmt::{Display, Formatter};
+
 use crate::sm4::core::Crypto;
 use crate::sm4::{Cryptographer, xor};
+use crate::error_code::{Code, ErrorCode};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CbcError {
+    /// 密文长度不是16的倍数或为空
+    InvalidLength,
+    /// PKCS#7填充不正确
+    InvalidPadding,
+    /// 密文窃取模式的数据不足16字节
+    TooShort,
+}
+
+#[cfg(not(feature = "no-fmt"))]
+impl Display for CbcError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CbcError::InvalidLength => write!(f, "The cipher's length must be a non-zero multiple of 16 bytes."),
+            CbcError::InvalidPadding => write!(f, "The padding of the decrypted data is invalid."),
+            CbcError::TooShort => write!(f, "The data must be at least 16 bytes for ciphertext stealing."),
+        }
+    }
+}
+
+/// 不带错误消息，以[`ErrorCode`]区分错误
+#[cfg(feature = "no-fmt")]
+impl Display for CbcError {
+    fn fmt(&self, _: &mut Formatter<'_>) -> std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl std::error::Error for CbcError {}
+
+impl ErrorCode for CbcError {
+    fn code(&self) -> Code {
+        match self {
+            CbcError::InvalidLength => Code::INVALID_CIPHERTEXT,
+            CbcError::InvalidPadding => Code::INVALID_PADDING,
+            CbcError::TooShort => Code::INVALID_LENGTH,
+        }
+    }
+}
 
 /// CBC: Cipher Block Chaining
 ///
@@ -65,7 +109,7 @@ impl Cryptographer for CryptoMode {
     fn decrypt_bytes(&self, cipher: &[u8]) -> Vec<u8> {
         let (quotients, remainder) = (cipher.len() / 16, cipher.len() % 16);
         if remainder != 0 {
-            panic!("The cipher‘s length must be a multiple of 16 bytes.");
+            fail!("The cipher‘s length must be a multiple of 16 bytes.");
         }
 
         let mut out: Vec<u8> = Vec::new();
@@ -86,10 +130,41 @@ impl Cryptographer for CryptoMode {
     }
 }
 
+/// 原地解密，逐分组覆盖缓冲区，返回去除填充后的明文切片
+///
+/// 填充校验遍历最后一个分组的全部字节，不因填充长度提前返回。
+pub(crate) fn decrypt_in_place<'a>(key: &[u8; 16], iv: &[u8; 16], buffer: &'a mut [u8]) -> Result<&'a mut [u8], CbcError> {
+    if buffer.is_empty() || buffer.len() % 16 != 0 {
+        return Err(CbcError::InvalidLength);
+    }
+
+    let crypto = Crypto::init(key);
+    let mut previous = *iv;
+    for block in buffer.chunks_exact_mut(16) {
+        let cipher: [u8; 16] = (*block).try_into().unwrap();
+        let plain = xor(&previous, &crypto.decrypt(&cipher));
+        block.copy_from_slice(&plain);
+        previous = cipher;
+    }
+
+    let len = buffer.len();
+    let pad = buffer[len - 1];
+    let mut bad = ((pad == 0) | (pad > 16)) as u8;
+    for (i, b) in buffer[len - 16..].iter().enumerate() {
+        // 位于填充区内的字节必须等于填充长度
+        let inside = (16 - i) as u8 <= pad;
+        bad |= (inside & (*b != pad)) as u8;
+    }
+    if bad != 0 {
+        return Err(CbcError::InvalidPadding);
+    }
+    Ok(&mut buffer[..len - pad as usize])
+}
+
 
 #[cfg(test)]
 mod tests {
-    use crate::sm4::cbc::CryptoMode;
+    use crate::sm4::cbc::{decrypt_in_place, CbcError, CryptoMode};
     use crate::sm4::Cryptographer;
 
     #[test]
@@ -105,4 +180,28 @@ mod tests {
 
         assert_eq!(plain, text);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn in_place() {
+        let key = [0x01; 16];
+        let iv = [0x02; 16];
+        for len in [0, 1, 15, 16, 17, 100] {
+            let plain = vec![0x5A; len];
+            let mut buffer = CryptoMode::new(&key, &iv).encrypt_bytes(&plain);
+            assert_eq!(decrypt_in_place(&key, &iv, &mut buffer).unwrap(), plain.as_slice());
+        }
+
+        // 篡改IV最后一个字节使填充长度变为0x29
+        let mut buffer = CryptoMode::new(&key, &iv).encrypt_bytes(b"padding");
+        let mut tampered = iv;
+        tampered[15] ^= 0x20;
+        assert_eq!(decrypt_in_place(&key, &tampered, &mut buffer).unwrap_err(), CbcError::InvalidPadding);
+        // 填充长度合法但填充字节不一致
+        let mut buffer = CryptoMode::new(&key, &iv).encrypt_bytes(b"padding");
+        tampered = iv;
+        tampered[8] ^= 0x01;
+        assert_eq!(decrypt_in_place(&key, &tampered, &mut buffer).unwrap_err(), CbcError::InvalidPadding);
+        assert_eq!(decrypt_in_place(&key, &iv, &mut [0u8; 15]).unwrap_err(), CbcError::InvalidLength);
+        assert_eq!(decrypt_in_place(&key, &iv, &mut []).unwrap_err(), CbcError::InvalidLength);
+    }
+}
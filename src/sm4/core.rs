@@ -1,16 +1,26 @@
-const FK: [u32; 4] = [0xa3b1bac6, 0x56aa3350, 0x677d9197, 0xb27022dc];
-const CK: [u32; 32] = [
-    0x00070e15, 0x1c232a31, 0x383f464d, 0x545b6269,
-    0x70777e85, 0x8c939aa1, 0xa8afb6bd, 0xc4cbd2d9,
-    0xe0e7eef5, 0xfc030a11, 0x181f262d, 0x343b4249,
-    0x50575e65, 0x6c737a81, 0x888f969d, 0xa4abb2b9,
-    0xc0c7ced5, 0xdce3eaf1, 0xf8ff060d, 0x141b2229,
-    0x30373e45, 0x4c535a61, 0x686f767d, 0x848b9299,
-    0xa0a7aeb5, 0xbcc3cad1, 0xd8dfe6ed, 0xf4fb0209,
-    0x10171e25, 0x2c333a41, 0x484f565d, 0x646b7279
-];
+use crate::endian;
+
+/// 系统参数FK
+pub const FK: [u32; 4] = [0xa3b1bac6, 0x56aa3350, 0x677d9197, 0xb27022dc];
+/// 固定参数CK，CKi的第j个字节为 (4i + j) × 7 mod 256
+pub const CK: [u32; 32] = fixed_parameters();
+
+const fn fixed_parameters() -> [u32; 32] {
+    let mut ck = [0u32; 32];
+    let mut i = 0;
+    while i < 32 {
+        let mut j = 0;
+        while j < 4 {
+            ck[i] = ck[i] << 8 | ((4 * i + j) * 7 % 256) as u32;
+            j += 1;
+        }
+        i += 1;
+    }
+    ck
+}
+
 /// s-box
-const BOX: [u8; 256] = [
+pub const SBOX: [u8; 256] = [
     0xd6, 0x90, 0xe9, 0xfe, 0xcc, 0xe1, 0x3d, 0xb7, 0x16, 0xb6, 0x14, 0xc2, 0x28, 0xfb, 0x2c, 0x05,
     0x2b, 0x67, 0x9a, 0x76, 0x2a, 0xbe, 0x04, 0xc3, 0xaa, 0x44, 0x13, 0x26, 0x49, 0x86, 0x06, 0x99,
     0x9c, 0x42, 0x50, 0xf4, 0x91, 0xef, 0x98, 0x7a, 0x33, 0x54, 0x0b, 0x43, 0xed, 0xcf, 0xac, 0x62,
@@ -31,71 +41,72 @@ const BOX: [u8; 256] = [
 
 /// 非线性变换
 /// 输入为A=(a0,a1,a2,a3),输出为B=(b0,b1,b2,b3),则(b0,b1,b2,b3)=(box(a0),box(a1),box(a2),box(a3))
+#[cfg_attr(all(target_arch = "riscv64", target_feature = "zksed"), allow(dead_code))]
 fn nonlinear_trans(a: u32) -> u32 {
     let _a: [u8; 4] = [(a >> 24) as u8, (a >> 16) as u8, (a >> 8) as u8, a as u8];
     let mut _b = [0; 4];
     for (i, v) in _b.iter_mut().enumerate() {
-        *v = BOX[_a[i] as usize]
+        *v = SBOX[_a[i] as usize]
     }
     (_b[0] as u32) << 24 | (_b[1] as u32) << 16 | (_b[2] as u32) << 8 | _b[3] as u32
 }
 
 /// 线性变换
 /// C = L(B) = B ⊕ (B <<< 2) ⊕ (B <<< 10) ⊕ (B <<< 18) ⊕ (B <<< 24)
+#[cfg_attr(all(target_arch = "riscv64", target_feature = "zksed"), allow(dead_code))]
 fn linear_trans(b: u32) -> u32 {
     b ^ b.rotate_left(2) ^ b.rotate_left(10) ^ b.rotate_left(18) ^ b.rotate_left(24)
 }
 
 /// 线性变换，用于密钥扩展算法计算轮密钥
+#[cfg_attr(all(target_arch = "riscv64", target_feature = "zksed"), allow(dead_code))]
 fn linear_trans_ex(b: u32) -> u32 {
     b ^ b.rotate_left(13) ^ b.rotate_left(23)
 }
 
+/// 合成置换T = L(τ(·))，用于轮函数
+#[cfg(not(all(target_arch = "riscv64", target_feature = "zksed")))]
+#[inline(always)]
+fn t(x: u32) -> u32 {
+    linear_trans(nonlinear_trans(x))
+}
+
+/// 合成置换T' = L'(τ(·))，用于密钥扩展
+#[cfg(not(all(target_arch = "riscv64", target_feature = "zksed")))]
+#[inline(always)]
+fn t_ex(x: u32) -> u32 {
+    linear_trans_ex(nonlinear_trans(x))
+}
+
+#[cfg(all(target_arch = "riscv64", target_feature = "zksed"))]
+use crate::sm4::zksed::{t, t_ex};
+
 /// 分组长度和密钥长度均为128比特。将16个字节`[u8;16]`转换为四个字`[u32; 4]`
 fn bytes_to_words(input: &[u8]) -> [u32; 4] {
     if input.len() != 16 {
-        panic!("The block size and Key size must be 16 bytes!");
-    }
-    let mut out: [u32; 4] = [0; 4];
-    for (i, v) in out.iter_mut().enumerate() {
-        *v = bytes_to_word(&input[i * 4..(i + 1) * 4]);
+        fail!("The block size and Key size must be 16 bytes!");
     }
-    out
-}
-
-/// 四个字节转换为一个字
-#[inline(always)]
-fn bytes_to_word(input: &[u8]) -> u32 {
-    (input[0] as u32) << 24 | (input[1] as u32) << 16 | (input[2] as u32) << 8 | (input[3] as u32)
+    endian::load_be(input)
 }
 
 /// 将四个字转换为16个字节
-fn words_to_bytes(input: &[u32]) -> [u8; 16] {
+fn words_to_bytes(input: &[u32; 4]) -> [u8; 16] {
     let mut out: [u8; 16] = [0; 16];
-    for i in 0..4 {
-        let bytes = word_to_bytes(input[i]);
-        for j in 0..4 {
-            out[i * 4 + j] = bytes[j];
-        }
-    }
+    endian::store_be(input, &mut out);
     out
 }
 
-/// 将一个字转换为4个字节
-#[inline(always)]
-fn word_to_bytes(input: u32) -> [u8; 4] {
-    [(input >> 24) as u8, (input >> 16) as u8, (input >> 8) as u8, input as u8]
-}
-
 
-#[derive(Debug)]
+/// 轮秘钥保存在定长数组中，加解密过程不分配堆内存
+#[derive(Clone)]
+#[cfg_attr(any(test, not(feature = "no-fmt")), derive(Debug))]
 pub struct Crypto {
-    rk: Vec<u32>,
+    rk: [u32; 32],
 }
 
 impl Crypto {
     pub fn init(key: &[u8]) -> Self {
-        let mut crypto = Crypto { rk: Vec::new() };
+        let mut crypto = Crypto { rk: [0; 32] };
         // 秘钥：MK=(MK0, MK1, MK2, MK3)
         let mut k: [u32; 4] = bytes_to_words(key);
         // 计算轮秘钥rk
@@ -105,15 +116,12 @@ impl Crypto {
         }
         // rki =Ki+4 =Ki ⊕ T′(Ki+1 ⊕ Ki+2 ⊕ Ki+3 ⊕ CKi)
         for i in 0..8 {
-            k[0] ^= linear_trans_ex(nonlinear_trans(k[1] ^ k[2] ^ k[3] ^ CK[i * 4]));
-            k[1] ^= linear_trans_ex(nonlinear_trans(k[2] ^ k[3] ^ k[0] ^ CK[i * 4 + 1]));
-            k[2] ^= linear_trans_ex(nonlinear_trans(k[3] ^ k[0] ^ k[1] ^ CK[i * 4 + 2]));
-            k[3] ^= linear_trans_ex(nonlinear_trans(k[0] ^ k[1] ^ k[2] ^ CK[i * 4 + 3]));
-
-            crypto.rk.push(k[0]);
-            crypto.rk.push(k[1]);
-            crypto.rk.push(k[2]);
-            crypto.rk.push(k[3]);
+            k[0] ^= t_ex(k[1] ^ k[2] ^ k[3] ^ CK[i * 4]);
+            k[1] ^= t_ex(k[2] ^ k[3] ^ k[0] ^ CK[i * 4 + 1]);
+            k[2] ^= t_ex(k[3] ^ k[0] ^ k[1] ^ CK[i * 4 + 2]);
+            k[3] ^= t_ex(k[0] ^ k[1] ^ k[2] ^ CK[i * 4 + 3]);
+
+            crypto.rk[i * 4..(i + 1) * 4].copy_from_slice(&k);
         }
         crypto
     }
@@ -124,10 +132,10 @@ impl Crypto {
     pub fn encrypt(&self, plain: &[u8]) -> [u8; 16] {
         let mut x: [u32; 4] = bytes_to_words(plain);
         for i in 0..8 {
-            x[0] ^= linear_trans(nonlinear_trans(x[1] ^ x[2] ^ x[3] ^ self.rk[i * 4]));
-            x[1] ^= linear_trans(nonlinear_trans(x[2] ^ x[3] ^ x[0] ^ self.rk[i * 4 + 1]));
-            x[2] ^= linear_trans(nonlinear_trans(x[3] ^ x[0] ^ x[1] ^ self.rk[i * 4 + 2]));
-            x[3] ^= linear_trans(nonlinear_trans(x[0] ^ x[1] ^ x[2] ^ self.rk[i * 4 + 3]));
+            x[0] ^= t(x[1] ^ x[2] ^ x[3] ^ self.rk[i * 4]);
+            x[1] ^= t(x[2] ^ x[3] ^ x[0] ^ self.rk[i * 4 + 1]);
+            x[2] ^= t(x[3] ^ x[0] ^ x[1] ^ self.rk[i * 4 + 2]);
+            x[3] ^= t(x[0] ^ x[1] ^ x[2] ^ self.rk[i * 4 + 3]);
         }
         words_to_bytes(&[x[3], x[2], x[1], x[0]])
     }
@@ -136,10 +144,10 @@ impl Crypto {
     pub fn decrypt(&self, cipher: &[u8]) -> [u8; 16] {
         let mut x: [u32; 4] = bytes_to_words(cipher);
         for i in 0..8 {
-            x[0] ^= linear_trans(nonlinear_trans(x[1] ^ x[2] ^ x[3] ^ self.rk[31 - i * 4]));
-            x[1] ^= linear_trans(nonlinear_trans(x[2] ^ x[3] ^ x[0] ^ self.rk[30 - i * 4]));
-            x[2] ^= linear_trans(nonlinear_trans(x[3] ^ x[0] ^ x[1] ^ self.rk[29 - i * 4]));
-            x[3] ^= linear_trans(nonlinear_trans(x[0] ^ x[1] ^ x[2] ^ self.rk[28 - i * 4]));
+            x[0] ^= t(x[1] ^ x[2] ^ x[3] ^ self.rk[31 - i * 4]);
+            x[1] ^= t(x[2] ^ x[3] ^ x[0] ^ self.rk[30 - i * 4]);
+            x[2] ^= t(x[3] ^ x[0] ^ x[1] ^ self.rk[29 - i * 4]);
+            x[3] ^= t(x[0] ^ x[1] ^ x[2] ^ self.rk[28 - i * 4]);
         }
         words_to_bytes(&[x[3], x[2], x[1], x[0]])
     }
@@ -148,7 +156,15 @@ impl Crypto {
 
 #[cfg(test)]
 mod tests {
-    use crate::sm4::core::Crypto;
+    use crate::sm4::core::{Crypto, CK};
+
+    /// GB/T 32907 给出的CK取值
+    #[test]
+    fn fixed_parameters() {
+        assert_eq!(&CK[..4], [0x00070e15, 0x1c232a31, 0x383f464d, 0x545b6269]);
+        assert_eq!(&CK[28..], [0x10171e25, 0x2c333a41, 0x484f565d, 0x646b7279]);
+        assert_eq!(CK[13], 0x6c737a81);
+    }
 
     #[test]
     fn main() {
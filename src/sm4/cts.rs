@@ -0,0 +1,110 @@
+use crate::sm4::cbc::CbcError;
+use crate::sm4::core::Crypto;
+use crate::sm4::xor;
+
+/// CBC-CS3: 密文窃取（NIST SP 800-38A Addendum）
+///
+/// 不填充，密文与明文等长，数据至少16字节。最后一个不完整分组补0后按CBC加密，
+/// 再将倒数第二个密文分组截断到与之等长并与最后一个密文分组交换位置；
+/// 长度为16的倍数时同样交换最后两个分组，仅有一个分组时与CBC相同。
+pub(crate) fn encrypt_in_place(key: &[u8; 16], iv: &[u8; 16], buffer: &mut [u8]) -> Result<(), CbcError> {
+    let (n, d) = blocks(buffer.len())?;
+    let crypto = Crypto::init(key);
+
+    let mut previous = *iv;
+    for block in buffer[..(n - 1).max(1) * 16].chunks_exact_mut(16) {
+        previous = crypto.encrypt(&xor(&previous, block));
+        block.copy_from_slice(&previous);
+    }
+    if n == 1 {
+        return Ok(());
+    }
+
+    // Cn = E((Pn* ∥ 0) ⊕ Cn-1)，输出 … ∥ Cn ∥ MSB_d(Cn-1)
+    let mut last = [0u8; 16];
+    last[..d].copy_from_slice(&buffer[(n - 1) * 16..]);
+    let cn = crypto.encrypt(&xor(&previous, &last));
+    buffer[(n - 2) * 16..(n - 1) * 16].copy_from_slice(&cn);
+    buffer[(n - 1) * 16..].copy_from_slice(&previous[..d]);
+    Ok(())
+}
+
+pub(crate) fn decrypt_in_place(key: &[u8; 16], iv: &[u8; 16], buffer: &mut [u8]) -> Result<(), CbcError> {
+    let (n, d) = blocks(buffer.len())?;
+    let crypto = Crypto::init(key);
+
+    if n > 1 {
+        // Z = D(Cn) = (Pn* ∥ 0) ⊕ Cn-1，由此恢复完整的Cn-1与Pn*
+        let tail = (n - 2) * 16;
+        let z = crypto.decrypt(&buffer[tail..tail + 16]);
+        let mut previous = [0u8; 16];
+        previous[..d].copy_from_slice(&buffer[tail + 16..]);
+        previous[d..].copy_from_slice(&z[d..]);
+        let plain = xor(&z, &previous);
+        buffer[tail..tail + 16].copy_from_slice(&previous);
+        buffer[tail + 16..].copy_from_slice(&plain[..d]);
+    }
+
+    let mut previous = *iv;
+    for block in buffer[..(n - 1).max(1) * 16].chunks_exact_mut(16) {
+        let cipher: [u8; 16] = (*block).try_into().unwrap();
+        block.copy_from_slice(&xor(&previous, &crypto.decrypt(&cipher)));
+        previous = cipher;
+    }
+    Ok(())
+}
+
+/// 分组数n（最后一组可不完整）及最后一组的字节数d ∈ [1, 16]
+fn blocks(len: usize) -> Result<(usize, usize), CbcError> {
+    if len < 16 {
+        return Err(CbcError::TooShort);
+    }
+    let n = (len + 15) / 16;
+    Ok((n, len - (n - 1) * 16))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; 16] = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54, 0x32, 0x10];
+    const IV: [u8; 16] = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f];
+
+    /// 期望值由OpenSSL的SM4-CBC（无填充）加密补0的明文后，按CS3重排得到
+    #[test]
+    fn known_answer() {
+        let cases = [
+            (16, "2677f46b09c122cc975533105bd4a22a"),
+            (17, "0b5ce3606bfd16071a4a7ea0d597f74b26"),
+            (32, "d9ee98830e69745c9827f934a19621f82677f46b09c122cc975533105bd4a22a"),
+            (40, "2677f46b09c122cc975533105bd4a22a10e8328fa036b2b651a073f4dba7def8d9ee98830e69745c"),
+            (71, concat!(
+                "2677f46b09c122cc975533105bd4a22ad9ee98830e69745c9827f934a19621f8db45a48645909eefda6bae89a72e659b",
+                "ea55718beeea705b575731359ff6abe2a6394a4e05bd7c",
+            )),
+        ];
+        for (len, cipher) in cases {
+            let plain: Vec<u8> = (0..len as u8).collect();
+            let mut buffer = plain.clone();
+            encrypt_in_place(&KEY, &IV, &mut buffer).unwrap();
+            assert_eq!(hex::encode(&buffer), cipher, "len = {}", len);
+            decrypt_in_place(&KEY, &IV, &mut buffer).unwrap();
+            assert_eq!(buffer, plain, "len = {}", len);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for len in 16..100 {
+            let plain: Vec<u8> = (0..len).map(|i| (i * 7) as u8).collect();
+            let mut buffer = plain.clone();
+            encrypt_in_place(&KEY, &IV, &mut buffer).unwrap();
+            assert_ne!(buffer, plain);
+            decrypt_in_place(&KEY, &IV, &mut buffer).unwrap();
+            assert_eq!(buffer, plain, "len = {}", len);
+        }
+        assert_eq!(encrypt_in_place(&KEY, &IV, &mut [0u8; 15]).unwrap_err(), CbcError::TooShort);
+        assert_eq!(decrypt_in_place(&KEY, &IV, &mut []).unwrap_err(), CbcError::TooShort);
+    }
+}
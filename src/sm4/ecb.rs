@@ -57,7 +57,7 @@ impl Cryptographer for CryptoMode {
     fn decrypt_bytes(&self, cipher: &[u8]) -> Vec<u8> {
         let (quotients, remainder) = (cipher.len() / 16, cipher.len() % 16);
         if remainder != 0 {
-            panic!("The cipher‘s length must be a multiple of 16 bytes.");
+            fail!("The cipher‘s length must be a multiple of 16 bytes.");
         }
 
         let mut out: Vec<u8> = Vec::new();
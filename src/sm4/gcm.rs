@@ -0,0 +1,515 @@
+use std::fmt::{Display, Formatter};
+
+use crate::ct::ct_eq;
+use crate::codec::{SizeLimitError, DEFAULT_MAX_LEN};
+use crate::sm4::core::Crypto;
+use crate::sm4::Nonce12;
+use crate::error_code::{Code, ErrorCode};
+
+/// GCM: Galois/Counter Mode
+///
+/// 带关联数据的认证加密模式，CTR模式加密并以GHASH计算认证标签。
+///
+/// 关联数据可分多次输入，但须在任何明文、密文之前输入；明文、密文同样可分多次输入，
+/// 分片边界不必与16字节分组对齐。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GcmError {
+    /// 认证标签校验失败
+    TagMismatch,
+    /// 明文或密文超过长度上限，见[`Sm4Gcm::with_max_len`]
+    TooLarge(SizeLimitError),
+}
+
+#[cfg(not(feature = "no-fmt"))]
+impl Display for GcmError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GcmError::TagMismatch => write!(f, "The GCM authentication tag does not match."),
+            GcmError::TooLarge(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// 不带错误消息，以[`ErrorCode`]区分错误
+#[cfg(feature = "no-fmt")]
+impl Display for GcmError {
+    fn fmt(&self, _: &mut Formatter<'_>) -> std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl std::error::Error for GcmError {}
+
+impl ErrorCode for GcmError {
+    fn code(&self) -> Code {
+        match self {
+            GcmError::TagMismatch => Code::MAC_MISMATCH,
+            GcmError::TooLarge(e) => e.code(),
+        }
+    }
+}
+
+/// 加解密共用的状态：CTR计数器与GHASH累加器
+#[derive(Clone)]
+struct Gcm {
+    crypto: Crypto,
+    h: u128,
+    /// E(K, J0)，用于生成认证标签
+    ej0: [u8; 16],
+    counter: [u8; 16],
+    keystream: [u8; 16],
+    /// 当前密钥流分组中已使用的字节数
+    used: usize,
+    ghash: u128,
+    buffer: [u8; 16],
+    pending: usize,
+    aad_len: u64,
+    data_len: u64,
+}
+
+impl Gcm {
+    fn new(key: &[u8; 16], nonce: &[u8]) -> Self {
+        let crypto = Crypto::init(key);
+        let h = u128::from_be_bytes(crypto.encrypt(&[0u8; 16]));
+        let mut gcm = Gcm {
+            crypto,
+            h,
+            ej0: [0; 16],
+            counter: [0; 16],
+            keystream: [0; 16],
+            used: 16,
+            ghash: 0,
+            buffer: [0; 16],
+            pending: 0,
+            aad_len: 0,
+            data_len: 0,
+        };
+
+        // 96比特的IV：J0 = IV ‖ 0^31 ‖ 1；其他长度：J0 = GHASH(IV ‖ 0^s ‖ 0^64 ‖ len(IV))
+        let j0 = if nonce.len() == 12 {
+            let mut j0 = [0u8; 16];
+            j0[..12].copy_from_slice(nonce);
+            j0[15] = 1;
+            j0
+        } else {
+            gcm.absorb(nonce);
+            gcm.flush();
+            gcm.absorb(&((nonce.len() as u128) * 8).to_be_bytes());
+            let j0 = gcm.ghash.to_be_bytes();
+            gcm.ghash = 0;
+            j0
+        };
+        gcm.ej0 = gcm.crypto.encrypt(&j0);
+        gcm.counter = j0;
+        gcm
+    }
+
+    fn update_aad(&mut self, aad: &[u8]) {
+        if self.data_len > 0 {
+            fail!("The AAD must be fed before any data.")
+        }
+        self.aad_len += aad.len() as u64;
+        self.absorb(aad);
+    }
+
+    /// 将密文计入GHASH
+    fn absorb_data(&mut self, cipher: &[u8]) {
+        if self.data_len == 0 {
+            // 关联数据结束，补齐最后一个分组
+            self.flush();
+        }
+        self.data_len += cipher.len() as u64;
+        self.absorb(cipher);
+    }
+
+    /// CTR模式加解密，密钥流分组从inc32(J0)开始
+    fn apply_keystream(&mut self, data: &mut [u8]) {
+        for b in data.iter_mut() {
+            if self.used == 16 {
+                let n = u32::from_be_bytes(self.counter[12..].try_into().unwrap()).wrapping_add(1);
+                self.counter[12..].copy_from_slice(&n.to_be_bytes());
+                self.keystream = self.crypto.encrypt(&self.counter);
+                self.used = 0;
+            }
+            *b ^= self.keystream[self.used];
+            self.used += 1;
+        }
+    }
+
+    fn tag(mut self) -> [u8; 16] {
+        self.flush();
+        let lengths = ((self.aad_len as u128 * 8) << 64) | (self.data_len as u128 * 8);
+        self.absorb(&lengths.to_be_bytes());
+        let mut tag = self.ghash.to_be_bytes();
+        tag.iter_mut().zip(self.ej0.iter()).for_each(|(t, e)| *t ^= e);
+        tag
+    }
+
+    /// GHASH输入，不足一个分组的部分暂存
+    fn absorb(&mut self, data: &[u8]) {
+        for b in data {
+            self.buffer[self.pending] = *b;
+            self.pending += 1;
+            if self.pending == 16 {
+                self.ghash = gf_mul(self.ghash ^ u128::from_be_bytes(self.buffer), self.h);
+                self.pending = 0;
+            }
+        }
+    }
+
+    /// 以0补齐暂存的不完整分组
+    fn flush(&mut self) {
+        if self.pending > 0 {
+            self.buffer[self.pending..].fill(0);
+            self.ghash = gf_mul(self.ghash ^ u128::from_be_bytes(self.buffer), self.h);
+            self.pending = 0;
+        }
+    }
+}
+
+/// GF(2^128)乘法，约简多项式 x^128 + x^7 + x^2 + x + 1，按位处理且不含数据相关分支
+fn gf_mul(x: u128, y: u128) -> u128 {
+    let mut z = 0u128;
+    let mut v = y;
+    for i in 0..128 {
+        let bit = (x >> (127 - i)) & 1;
+        z ^= v & 0u128.wrapping_sub(bit);
+        let lsb = v & 1;
+        v = (v >> 1) ^ ((0xE1u128 << 120) & 0u128.wrapping_sub(lsb));
+    }
+    z
+}
+
+/// 调试构建中的nonce重用检测
+///
+/// 每个线程记录最近加密所用的 (密钥, nonce) 及其认证标签。同一对再次用于加密时，标签不同说明数据或关联数据不同，
+/// 即发生了可泄露明文异或值、伪造标签的nonce重用；完全相同的数据重复加密不泄露信息，不视为重用。
+/// 以 (E(K, 0^128), E(K, J0)) 的带随机密钥的摘要代表 (密钥, nonce)，不保存密钥。发布构建中不存在。
+#[cfg(debug_assertions)]
+mod misuse {
+    use std::cell::{Cell, RefCell};
+    use std::collections::{HashMap, VecDeque};
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hash, Hasher};
+
+    /// 每个线程最多记录的 (密钥, nonce) 个数，超出时淘汰最早的
+    const CAPACITY: usize = 4096;
+
+    thread_local! {
+        pub(super) static ALLOWED: Cell<bool> = const { Cell::new(false) };
+        static SEEN: RefCell<Seen> = RefCell::default();
+    }
+
+    #[derive(Default)]
+    struct Seen {
+        state: RandomState,
+        tags: HashMap<u64, [u8; 16]>,
+        order: VecDeque<u64>,
+    }
+
+    pub(super) fn check(id: (u128, [u8; 16]), tag: &[u8; 16]) {
+        if ALLOWED.with(|allowed| allowed.get()) {
+            return;
+        }
+        let reused = SEEN.with(|seen| {
+            let mut seen = seen.borrow_mut();
+            let mut hasher = seen.state.build_hasher();
+            id.hash(&mut hasher);
+            let id = hasher.finish();
+            match seen.tags.get(&id) {
+                Some(previous) => previous != tag,
+                None => {
+                    if seen.order.len() == CAPACITY {
+                        let oldest = seen.order.pop_front().unwrap();
+                        seen.tags.remove(&oldest);
+                    }
+                    seen.tags.insert(id, *tag);
+                    seen.order.push_back(id);
+                    false
+                }
+            }
+        });
+        if reused {
+            fail!("The GCM nonce has been reused with the same key for different data.")
+        }
+    }
+}
+
+/// 在当前线程执行`f`，期间不检测nonce重用
+///
+/// 用于以同一密钥、nonce校验多组已知答案的测试；发布构建中直接执行`f`。
+pub fn allow_nonce_reuse<R>(f: impl FnOnce() -> R) -> R {
+    #[cfg(debug_assertions)]
+    {
+        struct Restore(bool);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                misuse::ALLOWED.with(|allowed| allowed.set(self.0));
+            }
+        }
+        let _restore = Restore(misuse::ALLOWED.with(|allowed| allowed.replace(true)));
+        f()
+    }
+    #[cfg(not(debug_assertions))]
+    f()
+}
+
+/// 流式GCM加密
+pub struct GcmEncryptor(Gcm);
+
+impl GcmEncryptor {
+    pub fn new(key: &[u8; 16], nonce: &Nonce12) -> Self {
+        GcmEncryptor(Gcm::new(key, nonce.as_bytes()))
+    }
+
+    /// 使用任意长度的nonce，仅用于与采用非96比特nonce的系统互通
+    pub fn with_nonce_bytes(key: &[u8; 16], nonce: &[u8]) -> Self {
+        GcmEncryptor(Gcm::new(key, nonce))
+    }
+
+    /// 输入关联数据，须在[`GcmEncryptor::update`]之前调用
+    pub fn update_aad(&mut self, aad: &[u8]) -> &mut Self {
+        self.0.update_aad(aad);
+        self
+    }
+
+    /// 加密一段明文，返回等长密文
+    pub fn update(&mut self, plain: &[u8]) -> Vec<u8> {
+        let mut out = plain.to_vec();
+        self.update_in_place(&mut out);
+        out
+    }
+
+    /// 在原缓冲区中将明文加密为密文
+    pub fn update_in_place(&mut self, buffer: &mut [u8]) {
+        self.0.apply_keystream(buffer);
+        self.0.absorb_data(buffer);
+    }
+
+    /// 结束加密，返回16字节认证标签
+    ///
+    /// 调试构建中，同一密钥与nonce此前已用于加密不同的数据时panic，见[`misuse`]。
+    pub fn finalize_tag(self) -> [u8; 16] {
+        #[cfg(debug_assertions)]
+        let id = (self.0.h, self.0.ej0);
+        let tag = self.0.tag();
+        #[cfg(debug_assertions)]
+        misuse::check(id, &tag);
+        tag
+    }
+}
+
+/// 流式GCM解密
+///
+/// 注意：[`GcmDecryptor::update`]返回的明文在[`GcmDecryptor::verify`]成功之前未经认证，不应被使用。
+pub struct GcmDecryptor(Gcm);
+
+impl GcmDecryptor {
+    pub fn new(key: &[u8; 16], nonce: &Nonce12) -> Self {
+        GcmDecryptor(Gcm::new(key, nonce.as_bytes()))
+    }
+
+    /// 使用任意长度的nonce，仅用于与采用非96比特nonce的系统互通
+    pub fn with_nonce_bytes(key: &[u8; 16], nonce: &[u8]) -> Self {
+        GcmDecryptor(Gcm::new(key, nonce))
+    }
+
+    /// 输入关联数据，须在[`GcmDecryptor::update`]之前调用
+    pub fn update_aad(&mut self, aad: &[u8]) -> &mut Self {
+        self.0.update_aad(aad);
+        self
+    }
+
+    /// 解密一段密文，返回等长明文
+    pub fn update(&mut self, cipher: &[u8]) -> Vec<u8> {
+        let mut out = cipher.to_vec();
+        self.update_in_place(&mut out);
+        out
+    }
+
+    /// 在原缓冲区中将密文解密为明文
+    pub fn update_in_place(&mut self, buffer: &mut [u8]) {
+        self.0.absorb_data(buffer);
+        self.0.apply_keystream(buffer);
+    }
+
+    /// 以`buffer`作为最后一段密文：先校验认证标签，通过后才在原缓冲区中解密，校验失败时缓冲区保持为密文
+    pub fn decrypt_in_place_detached(mut self, buffer: &mut [u8], tag: &[u8]) -> Result<(), GcmError> {
+        self.0.absorb_data(buffer);
+        GcmDecryptor(self.0.clone()).verify(tag)?;
+        self.0.apply_keystream(buffer);
+        Ok(())
+    }
+
+    /// 结束解密，返回根据已输入数据计算的认证标签
+    pub fn finalize_tag(self) -> [u8; 16] {
+        self.0.tag()
+    }
+
+    /// 结束解密并校验认证标签
+    pub fn verify(self, tag: &[u8]) -> Result<(), GcmError> {
+        let expected = self.finalize_tag();
+        if !ct_eq(&expected, tag) {
+            return Err(GcmError::TagMismatch);
+        }
+        Ok(())
+    }
+}
+
+/// 绑定密钥的一次性GCM加解密，密文格式为 C ‖ T，T为16字节认证标签
+#[derive(Clone)]
+pub struct Sm4Gcm {
+    key: [u8; 16],
+    max_len: usize,
+}
+
+impl Sm4Gcm {
+    pub fn new(key: &[u8; 16]) -> Self {
+        Sm4Gcm { key: *key, max_len: DEFAULT_MAX_LEN }
+    }
+
+    /// 设置明文（不含认证标签的密文）的长度上限，默认为[`DEFAULT_MAX_LEN`]
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// 明文超过长度上限时panic，见[`Sm4Gcm::try_encrypt`]
+    pub fn encrypt(&self, nonce: &Nonce12, aad: &[u8], plain: &[u8]) -> Vec<u8> {
+        match self.try_encrypt(nonce, aad, plain) {
+            Ok(cipher) => cipher,
+            Err(e) => fail!("{}", e),
+        }
+    }
+
+    /// 明文超过长度上限时返回[`GcmError::TooLarge`]
+    pub fn try_encrypt(&self, nonce: &Nonce12, aad: &[u8], plain: &[u8]) -> Result<Vec<u8>, GcmError> {
+        SizeLimitError::check("SM4-GCM plain data", plain.len(), self.max_len).map_err(GcmError::TooLarge)?;
+        let mut encryptor = GcmEncryptor::new(&self.key, nonce);
+        let mut out = encryptor.update_aad(aad).update(plain);
+        out.extend_from_slice(&encryptor.finalize_tag());
+        Ok(out)
+    }
+
+    /// 认证标签校验失败时不返回任何明文
+    pub fn decrypt(&self, nonce: &Nonce12, aad: &[u8], cipher: &[u8]) -> Result<Vec<u8>, GcmError> {
+        if cipher.len() < 16 {
+            return Err(GcmError::TagMismatch);
+        }
+        SizeLimitError::check("SM4-GCM cipher data", cipher.len() - 16, self.max_len).map_err(GcmError::TooLarge)?;
+        let (cipher, tag) = cipher.split_at(cipher.len() - 16);
+        let mut decryptor = GcmDecryptor::new(&self.key, nonce);
+        let plain = decryptor.update_aad(aad).update(cipher);
+        decryptor.verify(tag).map(|_| plain)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 8998 附录A.1
+    const KEY: &str = "0123456789abcdeffedcba9876543210";
+    const NONCE: &str = "00001234567800000000abcd";
+    const AAD: &str = "feedfacedeadbeeffeedfacedeadbeefabaddad2";
+    const PLAIN: &str = concat!(
+        "aaaaaaaaaaaaaaaabbbbbbbbbbbbbbbbccccccccccccccccdddddddddddddddd",
+        "eeeeeeeeeeeeeeeeffffffffffffffffeeeeeeeeeeeeeeeeaaaaaaaaaaaaaaaa",
+    );
+    const CIPHER: &str = concat!(
+        "17f399f08c67d5ee19d0dc9969c4bb7d5fd46fd3756489069157b282bb200735",
+        "d82710ca5c22f0ccfa7cbf93d496ac15a56834cbcf98c397b4024a2691233b8d",
+    );
+    const TAG: &str = "83de3541e4c2b58177e065a9bf7b62ec";
+
+    #[test]
+    fn main() {
+        let key: [u8; 16] = hex::decode(KEY).unwrap().try_into().unwrap();
+        let nonce = Nonce12::from_slice(&hex::decode(NONCE).unwrap()).unwrap();
+        let (aad, plain) = (hex::decode(AAD).unwrap(), hex::decode(PLAIN).unwrap());
+
+        let mut encryptor = GcmEncryptor::new(&key, &nonce);
+        let cipher = encryptor.update_aad(&aad).update(&plain);
+        assert_eq!(hex::encode(&cipher), CIPHER);
+        assert_eq!(hex::encode(encryptor.finalize_tag()), TAG);
+
+        // 关联数据与明文任意分片
+        for step in [1, 5, 16, 17, 33] {
+            let mut encryptor = GcmEncryptor::new(&key, &nonce);
+            aad.chunks(step).for_each(|e| { encryptor.update_aad(e); });
+            let cipher: Vec<u8> = plain.chunks(step).flat_map(|e| encryptor.update(e)).collect();
+            assert_eq!(hex::encode(&cipher), CIPHER);
+            assert_eq!(hex::encode(encryptor.finalize_tag()), TAG);
+
+            let mut decryptor = GcmDecryptor::new(&key, &nonce);
+            aad.chunks(step).for_each(|e| { decryptor.update_aad(e); });
+            let text: Vec<u8> = cipher.chunks(step).flat_map(|e| decryptor.update(e)).collect();
+            assert_eq!(text, plain);
+            assert_eq!(decryptor.verify(&hex::decode(TAG).unwrap()), Ok(()));
+        }
+
+        let mut decryptor = GcmDecryptor::new(&key, &nonce);
+        decryptor.update_aad(&aad[1..]).update(&cipher);
+        assert_eq!(decryptor.verify(&hex::decode(TAG).unwrap()), Err(GcmError::TagMismatch));
+    }
+
+    #[test]
+    fn gf() {
+        // x · x^127 = x^128 ≡ x^7 + x^2 + x + 1，最高位表示x^0
+        let x = 1u128 << 126;
+        assert_eq!(gf_mul(x, 1), 0xE1u128 << 120);
+        assert_eq!(gf_mul(1u128 << 127, 0x1234), 0x1234);
+    }
+
+    #[test]
+    fn in_place() {
+        let key: [u8; 16] = hex::decode(KEY).unwrap().try_into().unwrap();
+        let nonce = Nonce12::from_slice(&hex::decode(NONCE).unwrap()).unwrap();
+        let (aad, plain) = (hex::decode(AAD).unwrap(), hex::decode(PLAIN).unwrap());
+
+        let mut buffer = plain.clone();
+        let tag = crate::sm4::encrypt_gcm_in_place_detached(&key, &nonce, &aad, &mut buffer);
+        assert_eq!((hex::encode(&buffer), hex::encode(tag)), (CIPHER.to_string(), TAG.to_string()));
+
+        // 标签错误时缓冲区不被解密
+        let mut forged = tag;
+        forged[0] ^= 1;
+        assert_eq!(crate::sm4::decrypt_gcm_in_place_detached(&key, &nonce, &aad, &mut buffer, &forged), Err(GcmError::TagMismatch));
+        assert_eq!(hex::encode(&buffer), CIPHER);
+
+        assert_eq!(crate::sm4::decrypt_gcm_in_place_detached(&key, &nonce, &aad, &mut buffer, &tag), Ok(()));
+        assert_eq!(buffer, plain);
+    }
+    #[test]
+    #[cfg(debug_assertions)]
+    fn nonce_reuse() {
+        let gcm = Sm4Gcm::new(&crate::env::random());
+        let nonce = Nonce12::random();
+        let cipher = gcm.encrypt(&nonce, b"aad", b"first");
+        // 重复加密相同数据、解密均不视为重用
+        assert_eq!(gcm.encrypt(&nonce, b"aad", b"first"), cipher);
+        assert_eq!(gcm.decrypt(&nonce, b"aad", &cipher).unwrap(), b"first");
+        assert_eq!(gcm.encrypt(&Nonce12::random(), b"aad", b"second").len(), 22);
+
+        let reused = std::panic::catch_unwind(|| gcm.encrypt(&nonce, b"aad", b"second"));
+        assert!(reused.is_err());
+        let reused = std::panic::catch_unwind(|| gcm.encrypt(&nonce, b"other", b"first"));
+        assert!(reused.is_err());
+        allow_nonce_reuse(|| gcm.encrypt(&nonce, b"aad", b"third"));
+        assert!(std::panic::catch_unwind(|| gcm.encrypt(&nonce, b"aad", b"third")).is_err());
+    }
+    #[test]
+    fn max_len() {
+        let gcm = Sm4Gcm::new(&[0x42; 16]).with_max_len(4);
+        let nonce = Nonce12::from_counter(1);
+        let cipher = gcm.try_encrypt(&nonce, b"", b"four").unwrap();
+        assert_eq!(gcm.decrypt(&nonce, b"", &cipher).unwrap(), b"four");
+
+        let e = gcm.try_encrypt(&nonce, b"", b"fives").unwrap_err();
+        assert_eq!(e, GcmError::TooLarge(SizeLimitError { what: "SM4-GCM plain data", len: 5, max: 4 }));
+        assert_eq!(e.code(), Code::INVALID_LENGTH);
+        let long = Sm4Gcm::new(&[0x42; 16]).encrypt(&Nonce12::from_counter(2), b"", b"fives");
+        assert!(matches!(gcm.decrypt(&Nonce12::from_counter(2), b"", &long), Err(GcmError::TooLarge(_))));
+    }
+}
@@ -0,0 +1,112 @@
+use crate::env;
+
+/// 16字节初始化向量，用于CBC等分组模式
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(any(test, not(feature = "no-fmt")), derive(Debug))]
+pub struct Iv16([u8; 16]);
+
+impl Iv16 {
+    pub fn new(iv: [u8; 16]) -> Self {
+        Iv16(iv)
+    }
+
+    /// 随机生成
+    pub fn random() -> Self {
+        Iv16(env::random())
+    }
+
+    /// 由计数器生成：高8字节为0，低8字节为大端计数值
+    pub fn from_counter(counter: u64) -> Self {
+        let mut iv = [0u8; 16];
+        iv[8..].copy_from_slice(&counter.to_be_bytes());
+        Iv16(iv)
+    }
+
+    /// 长度不是16字节时返回None
+    pub fn from_slice(iv: &[u8]) -> Option<Self> {
+        iv.try_into().ok().map(Iv16)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+}
+
+impl From<[u8; 16]> for Iv16 {
+    fn from(iv: [u8; 16]) -> Self {
+        Iv16(iv)
+    }
+}
+
+impl AsRef<[u8]> for Iv16 {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// 12字节随机数（nonce），用于GCM模式
+///
+/// 同一密钥下nonce不得重复：随机生成时单个密钥的加密次数应远小于2^32，
+/// 否则应使用[`Nonce12::from_counter`]按消息序号生成。
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(any(test, not(feature = "no-fmt")), derive(Debug))]
+pub struct Nonce12([u8; 12]);
+
+impl Nonce12 {
+    pub fn new(nonce: [u8; 12]) -> Self {
+        Nonce12(nonce)
+    }
+
+    /// 随机生成
+    pub fn random() -> Self {
+        Nonce12(env::random())
+    }
+
+    /// 由计数器生成：高4字节为0，低8字节为大端计数值
+    pub fn from_counter(counter: u64) -> Self {
+        let mut nonce = [0u8; 12];
+        nonce[4..].copy_from_slice(&counter.to_be_bytes());
+        Nonce12(nonce)
+    }
+
+    /// 长度不是12字节时返回None
+    pub fn from_slice(nonce: &[u8]) -> Option<Self> {
+        nonce.try_into().ok().map(Nonce12)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 12] {
+        &self.0
+    }
+}
+
+impl From<[u8; 12]> for Nonce12 {
+    fn from(nonce: [u8; 12]) -> Self {
+        Nonce12(nonce)
+    }
+}
+
+impl AsRef<[u8]> for Nonce12 {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn main() {
+        assert_eq!(hex::encode(Iv16::from_counter(0x0102).as_bytes()), "00000000000000000000000000000102");
+        assert_eq!(hex::encode(Nonce12::from_counter(u64::MAX).as_bytes()), "00000000ffffffffffffffff");
+
+        assert_eq!(Iv16::from_slice(&[7; 16]), Some(Iv16::new([7; 16])));
+        assert_eq!(Iv16::from_slice(&[7; 12]), None);
+        assert_eq!(Nonce12::from_slice(&[7; 12]), Some(Nonce12::from([7; 12])));
+        assert_eq!(Nonce12::from_slice(&[7; 16]), None);
+
+        assert_ne!(Iv16::random(), Iv16::random());
+        assert_ne!(Nonce12::random(), Nonce12::random());
+    }
+}
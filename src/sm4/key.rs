@@ -0,0 +1,103 @@
+#[cfg(any(test, not(feature = "no-fmt")))]
+use std::fmt::{Debug, Formatter};
+
+use crate::ct::ct_eq;
+use crate::env;
+use crate::sm4::core::Crypto;
+
+/// 16字节SM4密钥
+///
+/// `Debug`只输出校验值（KCV），不输出密钥本身。
+#[derive(Clone, PartialEq, Eq)]
+pub struct Sm4Key([u8; 16]);
+
+impl Sm4Key {
+    pub fn new(key: [u8; 16]) -> Self {
+        Sm4Key(key)
+    }
+
+    /// 随机生成
+    pub fn random() -> Self {
+        Sm4Key(env::random())
+    }
+
+    /// 长度不是16字节时返回None
+    pub fn from_slice(key: &[u8]) -> Option<Self> {
+        key.try_into().ok().map(Sm4Key)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    /// 密钥校验值：用该密钥加密16个0字节，取密文前3字节
+    ///
+    /// 密钥分发、终端灌装时随密钥成分一同登记，用于核对密钥是否录入正确，不泄露密钥本身。
+    pub fn kcv(&self) -> [u8; 3] {
+        let block = self.check_block();
+        [block[0], block[1], block[2]]
+    }
+
+    /// 核对校验值，长度可为1到16字节，常见的有2、3、4字节，比较时间不依赖内容
+    pub fn verify_kcv(&self, kcv: &[u8]) -> bool {
+        if kcv.is_empty() || kcv.len() > 16 {
+            return false;
+        }
+        ct_eq(&self.check_block()[..kcv.len()], kcv)
+    }
+
+    /// 核对16进制的校验值，不区分大小写，如`"2677F4"`
+    pub fn verify_kcv_hex(&self, kcv: &str) -> bool {
+        hex::decode(kcv).map(|kcv| self.verify_kcv(&kcv)).unwrap_or(false)
+    }
+
+    fn check_block(&self) -> [u8; 16] {
+        Crypto::init(&self.0).encrypt(&[0u8; 16])
+    }
+}
+
+impl From<[u8; 16]> for Sm4Key {
+    fn from(key: [u8; 16]) -> Self {
+        Sm4Key(key)
+    }
+}
+
+impl AsRef<[u8]> for Sm4Key {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(any(test, not(feature = "no-fmt")))]
+impl Debug for Sm4Key {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sm4Key").field("kcv", &hex::encode(self.kcv())).finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 期望值由OpenSSL的SM4-ECB加密16个0字节得到
+    #[test]
+    fn kcv() {
+        let key = Sm4Key::new([0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54, 0x32, 0x10]);
+        assert_eq!(hex::encode(key.kcv()), "2677f4");
+        assert_eq!(hex::encode(Sm4Key::from([0; 16]).kcv()), "9f1f7b");
+        assert_eq!(format!("{:?}", key), "Sm4Key { kcv: \"2677f4\" }");
+
+        assert!(key.verify_kcv(&[0x26, 0x77]));
+        assert!(key.verify_kcv(&hex::decode("2677f46b09c122cc975533105bd4a22a").unwrap()));
+        assert!(key.verify_kcv_hex("2677F46B"));
+        assert!(!key.verify_kcv_hex("2677f5"));
+        assert!(!key.verify_kcv_hex("2677f"));
+        assert!(!key.verify_kcv(&[]));
+        assert!(!key.verify_kcv(&[0; 17]));
+
+        assert_eq!(Sm4Key::from_slice(key.as_bytes()), Some(key));
+        assert_eq!(Sm4Key::from_slice(&[0; 15]), None);
+        assert_ne!(Sm4Key::random(), Sm4Key::random());
+    }
+}
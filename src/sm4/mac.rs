@@ -0,0 +1,55 @@
+use crate::sm4::core::Crypto;
+
+/// CBC-MAC的消息填充方式（ISO/IEC 9797-1）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MacPadding {
+    /// 填充方法1：补0x00至分组长度整数倍，已对齐时不填充，空消息填充为一个全零分组
+    Zero,
+    /// 填充方法2：先补0x80，再补0x00至分组长度整数倍
+    Iso9797M2,
+}
+
+/// 零IV的CBC-MAC，取最后一个密文分组
+pub(crate) fn cbc_mac(key: &[u8; 16], data: &[u8], padding: MacPadding) -> [u8; 16] {
+    let crypto = Crypto::init(key);
+    let mut padded = data.to_vec();
+    match padding {
+        MacPadding::Zero if !data.is_empty() => {}
+        MacPadding::Zero => padded.push(0x00),
+        MacPadding::Iso9797M2 => padded.push(0x80),
+    }
+    padded.resize((padded.len() + 15) / 16 * 16, 0x00);
+
+    let mut state = [0u8; 16];
+    for block in padded.chunks_exact(16) {
+        for (s, b) in state.iter_mut().zip(block) {
+            *s ^= b;
+        }
+        state = crypto.encrypt(&state);
+    }
+    state
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn main() {
+        // 与OpenSSL `enc -sm4-cbc -nopad`零IV加密结果的最后一个分组一致
+        let key: [u8; 16] = hex::decode("0123456789abcdeffedcba9876543210").unwrap().try_into().unwrap();
+        let fox = b"The quick brown fox jumps over the lazy dog";
+        let mac = |data: &[u8], padding| hex::encode(cbc_mac(&key, data, padding));
+
+        assert_eq!(mac(&key, MacPadding::Zero), "681edf34d206965e86b3e94f536e4246");
+        assert_eq!(mac(fox, MacPadding::Zero), "d104e5c08198a5a4b88801aab435cd85");
+        assert_eq!(mac(fox, MacPadding::Iso9797M2), "4be4fe8cb829a00236c48d9f00279ba9");
+        assert_eq!(mac(b"", MacPadding::Zero), "2677f46b09c122cc975533105bd4a22a");
+        assert_eq!(mac(b"", MacPadding::Iso9797M2), "8c338e5a27e349beae39214feda97099");
+
+        // 填充方法1无法区分末尾的0x00
+        assert_eq!(mac(&[fox.as_slice(), &[0x00]].concat(), MacPadding::Zero), mac(fox, MacPadding::Zero));
+        assert_ne!(mac(&[fox.as_slice(), &[0x00]].concat(), MacPadding::Iso9797M2), mac(fox, MacPadding::Iso9797M2));
+    }
+}
@@ -0,0 +1,102 @@
+use cipher::consts::U16;
+use cipher::{AlgorithmName, BlockCipher, Key, KeyInit, KeySizeUser};
+
+use crate::sm4::core::Crypto;
+
+/// SM4分组密码，实现RustCrypto的`cipher`特征，可直接套用`gcm`、`ccm`、`ctr`等通用模式实现
+#[derive(Clone)]
+#[cfg_attr(any(test, not(feature = "no-fmt")), derive(Debug))]
+pub struct Sm4 {
+    crypto: Crypto,
+}
+
+impl KeySizeUser for Sm4 {
+    type KeySize = U16;
+}
+
+impl KeyInit for Sm4 {
+    fn new(key: &Key<Self>) -> Self {
+        Sm4 { crypto: Crypto::init(key) }
+    }
+}
+
+impl BlockCipher for Sm4 {}
+
+impl AlgorithmName for Sm4 {
+    fn write_alg_name(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Sm4")
+    }
+}
+
+cipher::impl_simple_block_encdec!(
+    Sm4, U16, sm4, block,
+    encrypt: {
+        let out = sm4.crypto.encrypt(block.get_in());
+        block.get_out().copy_from_slice(&out);
+    }
+    decrypt: {
+        let out = sm4.crypto.decrypt(block.get_in());
+        block.get_out().copy_from_slice(&out);
+    }
+);
+
+
+#[cfg(test)]
+mod tests {
+    use aes_gcm::aead::{Aead, Payload};
+    use aes_gcm::AesGcm;
+    use cipher::consts::{U12, U16};
+    use cipher::{BlockDecrypt, BlockEncrypt};
+
+    use super::*;
+
+    #[test]
+    fn main() {
+        let key = hex::decode("0123456789abcdeffedcba9876543210").unwrap();
+        let sm4 = Sm4::new_from_slice(&key).unwrap();
+
+        let mut block = cipher::Block::<Sm4>::clone_from_slice(&key);
+        sm4.encrypt_block(&mut block);
+        assert_eq!(hex::encode(block), "681edf34d206965e86b3e94f536e4246");
+        sm4.decrypt_block(&mut block);
+        assert_eq!(&block[..], &key[..]);
+    }
+
+    #[test]
+    fn gcm() {
+        // RFC 8998 附录A.1
+        let key = hex::decode("0123456789abcdeffedcba9876543210").unwrap();
+        let nonce = hex::decode("00001234567800000000abcd").unwrap();
+        let aad = hex::decode("feedfacedeadbeeffeedfacedeadbeefabaddad2").unwrap();
+        let plain = hex::decode(concat!(
+            "aaaaaaaaaaaaaaaabbbbbbbbbbbbbbbbccccccccccccccccdddddddddddddddd",
+            "eeeeeeeeeeeeeeeeffffffffffffffffeeeeeeeeeeeeeeeeaaaaaaaaaaaaaaaa",
+        )).unwrap();
+
+        let gcm = AesGcm::<Sm4, U12>::new_from_slice(&key).unwrap();
+        let sealed = gcm.encrypt(nonce.as_slice().into(), Payload { msg: &plain, aad: &aad }).unwrap();
+        assert_eq!(hex::encode(&sealed), concat!(
+            "17f399f08c67d5ee19d0dc9969c4bb7d5fd46fd3756489069157b282bb200735",
+            "d82710ca5c22f0ccfa7cbf93d496ac15a56834cbcf98c397b4024a2691233b8d",
+            "83de3541e4c2b58177e065a9bf7b62ec",
+        ));
+
+        let opened = gcm.decrypt(nonce.as_slice().into(), Payload { msg: &sealed, aad: &aad }).unwrap();
+        assert_eq!(opened, plain);
+
+        // 与原生GCM实现一致
+        let key: [u8; 16] = key.try_into().unwrap();
+        let nonce12 = crate::sm4::Nonce12::from_slice(&nonce).unwrap();
+        assert_eq!(crate::sm4::encrypt_gcm(&key, &nonce12, &aad, &plain), sealed);
+        assert_eq!(crate::sm4::decrypt_gcm(&key, &nonce12, &aad, &sealed), Ok(plain.clone()));
+
+        // 非96比特的nonce
+        let nonce = [0x5A; 16];
+        let gcm = AesGcm::<Sm4, U16>::new_from_slice(&key).unwrap();
+        let sealed = gcm.encrypt(nonce.as_slice().into(), Payload { msg: &plain, aad: &aad }).unwrap();
+        let mut encryptor = crate::sm4::GcmEncryptor::with_nonce_bytes(&key, &nonce);
+        let mut cipher = encryptor.update_aad(&aad).update(&plain);
+        cipher.extend_from_slice(&encryptor.finalize_tag());
+        assert_eq!(cipher, sealed);
+    }
+}
@@ -0,0 +1,44 @@
+//! RISC-V Zksed标量密码扩展
+//!
+//! sm4ed/sm4ks每条指令对源寄存器中的一个字节完成S盒替换及线性变换，4条指令构成一次合成置换。
+//! 指令按小端序装载的字计算，本实现的字为大端序，因此前后各做一次字节序反转。
+
+use std::arch::asm;
+
+/// 合成置换T = L(τ(·))
+#[inline(always)]
+pub(crate) fn t(x: u32) -> u32 {
+    let x = x.swap_bytes();
+    let mut y = 0u32;
+    unsafe {
+        asm!(
+            "sm4ed {y}, {y}, {x}, 0",
+            "sm4ed {y}, {y}, {x}, 1",
+            "sm4ed {y}, {y}, {x}, 2",
+            "sm4ed {y}, {y}, {x}, 3",
+            y = inout(reg) y,
+            x = in(reg) x,
+            options(pure, nomem, nostack),
+        );
+    }
+    y.swap_bytes()
+}
+
+/// 密钥扩展中的合成置换T' = L'(τ(·))
+#[inline(always)]
+pub(crate) fn t_ex(x: u32) -> u32 {
+    let x = x.swap_bytes();
+    let mut y = 0u32;
+    unsafe {
+        asm!(
+            "sm4ks {y}, {y}, {x}, 0",
+            "sm4ks {y}, {y}, {x}, 1",
+            "sm4ks {y}, {y}, {x}, 2",
+            "sm4ks {y}, {y}, {x}, 3",
+            y = inout(reg) y,
+            x = in(reg) x,
+            options(pure, nomem, nostack),
+        );
+    }
+    y.swap_bytes()
+}
@@ -0,0 +1,214 @@
+//! 国密与国际算法混合部署时的算法协商
+//!
+//! 按策略与对端能力选定对称算法和签名算法；选中国密算法时返回本库的构造器，国际算法由调用方自行实现。
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::error_code::{Code, ErrorCode};
+#[cfg(feature = "sm2-sign")]
+use crate::sm2::{KeyPair, Signer};
+use crate::sm2::{Crypto, PublicKey, Verifier};
+use crate::sm4::{GcmDecryptor, GcmEncryptor, Nonce12};
+
+/// 协商策略，允许的算法范围依次扩大
+///
+/// - RequireSm（`require-sm`）：对称算法与签名算法均须为国密算法
+/// - AllowAesFallback（`allow-aes-fallback`）：签名须为SM2，对端不支持SM4时可退回AES
+/// - PreferSm（`prefer-sm`）：两类算法均优先国密，对端不支持时各自退回国际算法
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Policy {
+    RequireSm,
+    AllowAesFallback,
+    PreferSm,
+}
+
+impl FromStr for Policy {
+    type Err = SuiteError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "require-sm" => Ok(Policy::RequireSm),
+            "allow-aes-fallback" => Ok(Policy::AllowAesFallback),
+            "prefer-sm" => Ok(Policy::PreferSm),
+            _ => Err(SuiteError::UnknownPolicy(s.to_string())),
+        }
+    }
+}
+
+/// 对称算法
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Cipher {
+    Sm4Gcm,
+    Aes128Gcm,
+    Aes256Gcm,
+}
+
+impl Cipher {
+    pub fn is_sm(&self) -> bool {
+        matches!(self, Cipher::Sm4Gcm)
+    }
+}
+
+/// 签名算法
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SignatureAlgorithm {
+    Sm2WithSm3,
+    EcdsaP256WithSha256,
+}
+
+impl SignatureAlgorithm {
+    pub fn is_sm(&self) -> bool {
+        matches!(self, SignatureAlgorithm::Sm2WithSm3)
+    }
+}
+
+/// 对端支持的算法，各自按对端的偏好排序
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    pub ciphers: Vec<Cipher>,
+    pub signatures: Vec<SignatureAlgorithm>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SuiteError {
+    /// 策略名称不是`require-sm`、`allow-aes-fallback`或`prefer-sm`
+    UnknownPolicy(String),
+    /// 对端没有策略允许的对称算法
+    NoCommonCipher,
+    /// 对端没有策略允许的签名算法
+    NoCommonSignature,
+}
+
+impl Display for SuiteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SuiteError::UnknownPolicy(name) => write!(f, "The suite policy {} is unknown.", name),
+            SuiteError::NoCommonCipher => write!(f, "The peer supports no cipher allowed by the policy."),
+            SuiteError::NoCommonSignature => write!(f, "The peer supports no signature algorithm allowed by the policy."),
+        }
+    }
+}
+
+impl std::error::Error for SuiteError {}
+
+impl ErrorCode for SuiteError {
+    fn code(&self) -> Code {
+        match self {
+            SuiteError::UnknownPolicy(_) => Code::INVALID_DATA,
+            SuiteError::NoCommonCipher | SuiteError::NoCommonSignature => Code::NOT_SUPPORTED,
+        }
+    }
+}
+
+/// 协商结果
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Suite {
+    pub cipher: Cipher,
+    pub signature: SignatureAlgorithm,
+}
+
+impl Suite {
+    /// 选中SM4-GCM时返回加密器，否则为None
+    pub fn gcm_encryptor(&self, key: &[u8; 16], nonce: &Nonce12) -> Option<GcmEncryptor> {
+        self.cipher.is_sm().then(|| GcmEncryptor::new(key, nonce))
+    }
+
+    /// 选中SM4-GCM时返回解密器，否则为None
+    pub fn gcm_decryptor(&self, key: &[u8; 16], nonce: &Nonce12) -> Option<GcmDecryptor> {
+        self.cipher.is_sm().then(|| GcmDecryptor::new(key, nonce))
+    }
+
+    /// 选中SM2时以`crypto`的用户ID等设置创建签名者，否则为None
+    #[cfg(feature = "sm2-sign")]
+    pub fn signer(&self, crypto: &Crypto, keypair: KeyPair) -> Option<Signer> {
+        self.signature.is_sm().then(|| crypto.signer(keypair))
+    }
+
+    /// 选中SM2时以`crypto`的用户ID等设置创建验证者，否则为None
+    pub fn verifier(&self, crypto: &Crypto, key: PublicKey) -> Option<Verifier> {
+        self.signature.is_sm().then(|| crypto.verifier(key))
+    }
+}
+
+/// 按策略从对端能力中选定算法
+///
+/// 国密算法总是优先；退回国际算法时按对端的偏好顺序选择。
+pub fn negotiate(policy: Policy, peer: &Capabilities) -> Result<Suite, SuiteError> {
+    let (cipher_fallback, signature_fallback) = match policy {
+        Policy::RequireSm => (false, false),
+        Policy::AllowAesFallback => (true, false),
+        Policy::PreferSm => (true, true),
+    };
+
+    let cipher = select(&peer.ciphers, Cipher::is_sm, cipher_fallback).ok_or(SuiteError::NoCommonCipher)?;
+    let signature = select(&peer.signatures, SignatureAlgorithm::is_sm, signature_fallback)
+        .ok_or(SuiteError::NoCommonSignature)?;
+    Ok(Suite { cipher, signature })
+}
+
+fn select<T: Copy>(offered: &[T], is_sm: fn(&T) -> bool, fallback: bool) -> Option<T> {
+    offered.iter().find(|a| is_sm(a)).or_else(|| offered.first().filter(|_| fallback)).copied()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sm2::HexKey;
+
+    fn peer(ciphers: &[Cipher], signatures: &[SignatureAlgorithm]) -> Capabilities {
+        Capabilities { ciphers: ciphers.to_vec(), signatures: signatures.to_vec() }
+    }
+
+    #[test]
+    fn policy() {
+        assert_eq!("allow-aes-fallback".parse(), Ok(Policy::AllowAesFallback));
+        assert_eq!("sm-only".parse::<Policy>(), Err(SuiteError::UnknownPolicy("sm-only".to_string())));
+    }
+
+    #[test]
+    fn negotiate_policies() {
+        use Cipher::*;
+        use SignatureAlgorithm::*;
+
+        // 对端偏好AES，但同时支持国密时仍选国密
+        let mixed = peer(&[Aes256Gcm, Sm4Gcm], &[EcdsaP256WithSha256, Sm2WithSm3]);
+        for policy in [Policy::RequireSm, Policy::AllowAesFallback, Policy::PreferSm] {
+            assert_eq!(negotiate(policy, &mixed), Ok(Suite { cipher: Sm4Gcm, signature: Sm2WithSm3 }));
+        }
+
+        let aes = peer(&[Aes128Gcm, Aes256Gcm], &[Sm2WithSm3]);
+        assert_eq!(negotiate(Policy::RequireSm, &aes), Err(SuiteError::NoCommonCipher));
+        assert_eq!(negotiate(Policy::AllowAesFallback, &aes), Ok(Suite { cipher: Aes128Gcm, signature: Sm2WithSm3 }));
+
+        let international = peer(&[Aes256Gcm], &[EcdsaP256WithSha256]);
+        assert_eq!(negotiate(Policy::AllowAesFallback, &international), Err(SuiteError::NoCommonSignature));
+        assert_eq!(
+            negotiate(Policy::PreferSm, &international),
+            Ok(Suite { cipher: Aes256Gcm, signature: EcdsaP256WithSha256 })
+        );
+
+        assert_eq!(negotiate(Policy::PreferSm, &Capabilities::default()), Err(SuiteError::NoCommonCipher));
+    }
+
+    #[test]
+    fn constructors() {
+        let key = [0x42; 16];
+        let nonce = Nonce12::new([7; 12]);
+        let sm = Suite { cipher: Cipher::Sm4Gcm, signature: SignatureAlgorithm::Sm2WithSm3 };
+        let aes = Suite { cipher: Cipher::Aes128Gcm, signature: SignatureAlgorithm::EcdsaP256WithSha256 };
+
+        let mut encryptor = sm.gcm_encryptor(&key, &nonce).unwrap();
+        let cipher = encryptor.update(b"bridge");
+        let tag = encryptor.finalize_tag();
+        let mut decryptor = sm.gcm_decryptor(&key, &nonce).unwrap();
+        assert_eq!(decryptor.update(&cipher), b"bridge");
+        assert_eq!(decryptor.verify(&tag), Ok(()));
+        assert!(aes.gcm_encryptor(&key, &nonce).is_none());
+
+        let puk = PublicKey::decode("04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e");
+        assert!(sm.verifier(&Crypto::default(), puk.clone()).is_some());
+        assert!(aes.verifier(&Crypto::default(), puk).is_none());
+    }
+}
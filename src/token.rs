@@ -0,0 +1,203 @@
+use std::fmt::{Display, Formatter};
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+
+use crate::ct::ct_eq;
+use crate::clock::Clock;
+use crate::env::EnvClock;
+use crate::sm3;
+use crate::error_code::{Code, ErrorCode};
+
+/// 令牌签名密钥
+///
+/// 轮换时将新密钥置于密钥集首位用于签发，旧密钥保留在其后，直至以其签发的令牌全部过期。
+#[derive(Clone)]
+pub struct Key {
+    id: String,
+    secret: Vec<u8>,
+}
+
+impl Key {
+    /// `id`随令牌传递，用于验证时定位密钥，须非空且仅含字母、数字、`-`和`_`，否则panic
+    pub fn new(id: &str, secret: &[u8]) -> Self {
+        if id.is_empty() || !id.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_') {
+            fail!("The key id must be non-empty and contain only [A-Za-z0-9_-].");
+        }
+        Key { id: id.to_string(), secret: secret.to_vec() }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn mac(&self, message: &[u8]) -> [u8; 32] {
+        sm3::hmac(&self.secret, message)
+    }
+}
+
+impl std::fmt::Debug for Key {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Key").field("id", &self.id).finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenError {
+    /// 令牌格式错误
+    Malformed,
+    /// 密钥集中没有令牌指定的密钥
+    UnknownKey(String),
+    /// 消息认证码不匹配
+    BadSignature,
+    /// 令牌已于该时间戳过期
+    Expired(i64),
+    /// 签发时密钥集为空
+    EmptyKeySet,
+}
+
+impl Display for TokenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenError::Malformed => write!(f, "The token is malformed."),
+            TokenError::UnknownKey(id) => write!(f, "The token key {} is not in the key set.", id),
+            TokenError::BadSignature => write!(f, "The token signature validation failed."),
+            TokenError::Expired(t) => write!(f, "The token expired at {}.", t),
+            TokenError::EmptyKeySet => write!(f, "The key set must not be empty."),
+        }
+    }
+}
+
+impl std::error::Error for TokenError {}
+
+impl ErrorCode for TokenError {
+    fn code(&self) -> Code {
+        match self {
+            TokenError::Malformed => Code::INVALID_DATA,
+            TokenError::UnknownKey(_) | TokenError::EmptyKeySet => Code::KEY_NOT_FOUND,
+            TokenError::BadSignature => Code::MAC_MISMATCH,
+            TokenError::Expired(_) => Code::VERIFY_FAILED,
+        }
+    }
+}
+
+/// 以密钥集首位的密钥签发令牌，`expires_at`为过期时间的UNIX时间戳（秒）
+///
+/// 令牌格式：`key id.expires_at.base64url(payload).base64url(HMAC-SM3)`，
+/// 消息认证码覆盖最后一个`.`之前的全部内容。密钥集为空时返回[`TokenError::EmptyKeySet`]。
+pub fn sign_token(keys: &[Key], payload: &[u8], expires_at: i64) -> Result<String, TokenError> {
+    let key = keys.first().ok_or(TokenError::EmptyKeySet)?;
+    let message = format!("{}.{}.{}", key.id, expires_at, URL_SAFE_NO_PAD.encode(payload));
+    let mac = key.mac(message.as_bytes());
+    Ok(format!("{}.{}", message, URL_SAFE_NO_PAD.encode(mac)))
+}
+
+/// 验证令牌并返回载荷，密钥集中任一密钥签发且未过期的令牌均有效
+pub fn verify_token(keys: &[Key], token: &str) -> Result<Vec<u8>, TokenError> {
+    verify_token_with_clock(keys, token, &EnvClock)
+}
+
+/// 同[`verify_token`]，以`clock`的当前时间判断是否过期
+pub fn verify_token_with_clock(keys: &[Key], token: &str, clock: &dyn Clock) -> Result<Vec<u8>, TokenError> {
+    let (message, mac) = token.rsplit_once('.').ok_or(TokenError::Malformed)?;
+    let mut parts = message.splitn(3, '.');
+    let (id, expires_at, payload) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(id), Some(expires_at), Some(payload)) => (id, expires_at, payload),
+        _ => return Err(TokenError::Malformed),
+    };
+    let expires_at: i64 = expires_at.parse().map_err(|_| TokenError::Malformed)?;
+    let mac = URL_SAFE_NO_PAD.decode(mac).map_err(|_| TokenError::Malformed)?;
+    let payload = URL_SAFE_NO_PAD.decode(payload).map_err(|_| TokenError::Malformed)?;
+
+    let key = keys.iter().find(|key| key.id == id).ok_or_else(|| TokenError::UnknownKey(id.to_string()))?;
+    let expected = key.mac(message.as_bytes());
+    if !ct_eq(&expected, &mac) {
+        return Err(TokenError::BadSignature);
+    }
+
+    // 过期时间经认证后才可信
+    if clock.now() >= expires_at {
+        return Err(TokenError::Expired(expires_at));
+    }
+    Ok(payload)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::clock::FixedClock;
+    use crate::env::{with_env, Deterministic};
+
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    #[test]
+    fn main() {
+        let keys = [Key::new("k2", b"current secret"), Key::new("k1", b"previous secret")];
+        let token = sign_token(&keys, b"{\"uid\":42}", NOW + 600).unwrap();
+        assert!(token.starts_with("k2.1700000600.eyJ1aWQiOjQyfQ."));
+
+        with_env(Deterministic::new(0, NOW), || {
+            assert_eq!(verify_token(&keys, &token), Ok(b"{\"uid\":42}".to_vec()));
+
+            // 轮换窗口内旧密钥签发的令牌仍有效，移除旧密钥后失效
+            let old = sign_token(&keys[1..], b"csrf", NOW + 60).unwrap();
+            assert_eq!(verify_token(&keys, &old), Ok(b"csrf".to_vec()));
+            assert_eq!(verify_token(&keys[..1], &old), Err(TokenError::UnknownKey("k1".to_string())));
+        });
+
+        with_env(Deterministic::new(0, NOW + 600), || {
+            assert_eq!(verify_token(&keys, &token), Err(TokenError::Expired(NOW + 600)));
+            assert_eq!(TokenError::Expired(NOW + 600).to_string(), "The token expired at 1700000600.");
+        });
+    }
+
+    #[test]
+    fn clock() {
+        let keys = [Key::new("k1", b"secret")];
+        let token = sign_token(&keys, b"payload", NOW + 600).unwrap();
+        let clock = FixedClock::new(NOW + 599);
+        assert_eq!(verify_token_with_clock(&keys, &token, &clock), Ok(b"payload".to_vec()));
+        clock.advance(1);
+        assert_eq!(verify_token_with_clock(&keys, &token, &clock), Err(TokenError::Expired(NOW + 600)));
+    }
+
+    #[test]
+    fn tampered() {
+        let keys = [Key::new("k1", b"secret")];
+        let token = sign_token(&keys, b"payload", NOW + 600).unwrap();
+        let (message, mac) = token.rsplit_once('.').unwrap();
+
+        with_env(Deterministic::new(0, NOW), || {
+            // 延长过期时间、替换载荷、截断或篡改认证码
+            let extended = token.replacen("1700000600", "1800000600", 1);
+            assert_eq!(verify_token(&keys, &extended), Err(TokenError::BadSignature));
+            let replaced = token.replacen(&URL_SAFE_NO_PAD.encode(b"payload"), &URL_SAFE_NO_PAD.encode(b"admin"), 1);
+            assert_eq!(verify_token(&keys, &replaced), Err(TokenError::BadSignature));
+            assert_eq!(verify_token(&keys, &token[..token.len() - 3]), Err(TokenError::BadSignature));
+            let flipped = format!("{}.{}{}", message, if mac.starts_with('A') { 'B' } else { 'A' }, &mac[1..]);
+            assert_eq!(verify_token(&keys, &flipped), Err(TokenError::BadSignature));
+
+            // 其他密钥签发的同名令牌
+            let forged = sign_token(&[Key::new("k1", b"guess")], b"payload", NOW + 600).unwrap();
+            assert_eq!(verify_token(&keys, &forged), Err(TokenError::BadSignature));
+
+            for malformed in ["", "k1", "k1.1700000600.cGF5bG9hZA", "k1.soon.cGF5bG9hZA.AAAA", "k1.1700000600.*.AAAA"] {
+                assert_eq!(verify_token(&keys, malformed), Err(TokenError::Malformed), "{}", malformed);
+            }
+        });
+    }
+
+    #[test]
+    fn empty_keys() {
+        let e = sign_token(&[], b"payload", NOW).unwrap_err();
+        assert_eq!((e.clone(), e.code()), (TokenError::EmptyKeySet, Code::KEY_NOT_FOUND));
+    }
+
+    #[test]
+    #[should_panic(expected = "The key id must be non-empty and contain only [A-Za-z0-9_-].")]
+    fn invalid_key_id() {
+        Key::new("k1.v2", b"secret");
+    }
+}
@@ -0,0 +1,462 @@
+use std::fmt::{Display, Formatter};
+
+use num_bigint::BigUint;
+use yasna::models::ObjectIdentifier;
+use yasna::{BERReader, DERWriter, Tag};
+
+use crate::cert::{encodable, oid, read_time, write_generalized_time, Certificate};
+use crate::clock::Clock;
+use crate::pkcs7::{Pkcs7Error, SignedData};
+use crate::{env, sm3};
+use crate::error_code::{Code, ErrorCode};
+
+/// 验证时间戳响应，成功时返回时间戳信息
+///
+/// 要求响应状态为已授权，时间戳令牌由`tsa`证书签名，消息印记与随机数与请求一致。
+pub fn verify_response(request: &TimeStampReq, response: &[u8], tsa: &Certificate) -> Result<TstInfo, TspError> {
+    let response = TimeStampResp::decode(response)?;
+    response.verify(request, tsa)
+}
+
+/// 同[`verify_response`]，并以`clock`检查生成时间：不得晚于当前时间，且须在TSA证书有效期内
+pub fn verify_response_with_clock(request: &TimeStampReq, response: &[u8], tsa: &Certificate, clock: &dyn Clock) -> Result<TstInfo, TspError> {
+    let info = verify_response(request, response, tsa)?;
+    if info.gen_time > clock.now() || !tsa.validity().contains(info.gen_time) {
+        return Err(TspError::InvalidTime(info.gen_time));
+    }
+    Ok(info)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TspError {
+    /// 不是合法的DER编码或缺少必要字段
+    Malformed,
+    /// 消息印记的摘要算法不是SM3
+    UnsupportedAlgorithm,
+    /// 时间戳服务拒绝了请求，附带状态码
+    Rejected(u8),
+    /// 响应中没有时间戳令牌
+    MissingToken,
+    /// 时间戳令牌的内容类型不是TSTInfo
+    NotTstInfo,
+    /// 时间戳令牌签名验证失败
+    Pkcs7(Pkcs7Error),
+    /// 消息印记与请求不一致
+    ImprintMismatch,
+    /// 随机数与请求不一致
+    NonceMismatch,
+    /// 生成时间晚于当前时间、不在TSA证书有效期内，或编码时不在0000年至9999年内
+    InvalidTime(i64),
+}
+
+impl Display for TspError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TspError::Malformed => write!(f, "The timestamp data is malformed."),
+            TspError::UnsupportedAlgorithm => write!(f, "The message imprint algorithm is not SM3."),
+            TspError::Rejected(status) => write!(f, "The timestamp request was rejected with status {}.", status),
+            TspError::MissingToken => write!(f, "The timestamp response has no token."),
+            TspError::NotTstInfo => write!(f, "The timestamp token does not contain TSTInfo."),
+            TspError::Pkcs7(e) => write!(f, "{}", e),
+            TspError::ImprintMismatch => write!(f, "The message imprint does not match the request."),
+            TspError::NonceMismatch => write!(f, "The nonce does not match the request."),
+            TspError::InvalidTime(t) => write!(f, "The timestamp generation time {} is not acceptable.", t),
+        }
+    }
+}
+
+impl std::error::Error for TspError {}
+
+impl ErrorCode for TspError {
+    fn code(&self) -> Code {
+        match self {
+            TspError::Malformed | TspError::MissingToken | TspError::NotTstInfo => Code::INVALID_DATA,
+            TspError::UnsupportedAlgorithm => Code::NOT_SUPPORTED,
+            TspError::Rejected(_) => Code::FAIL,
+            TspError::Pkcs7(e) => e.code(),
+            TspError::ImprintMismatch => Code::DIGEST_MISMATCH,
+            TspError::NonceMismatch | TspError::InvalidTime(_) => Code::VERIFY_FAILED,
+        }
+    }
+}
+
+impl From<Pkcs7Error> for TspError {
+    fn from(e: Pkcs7Error) -> Self {
+        TspError::Pkcs7(e)
+    }
+}
+
+/// 时间戳请求
+///
+/// ```text
+/// TimeStampReq ::= SEQUENCE {
+///      version         INTEGER { v1(1) },
+///      messageImprint  MessageImprint,    -- SEQUENCE { hashAlgorithm sm3, hashedMessage OCTET STRING }
+///      reqPolicy       OBJECT IDENTIFIER OPTIONAL,
+///      nonce           INTEGER OPTIONAL,
+///      certReq         BOOLEAN DEFAULT FALSE }
+/// ```
+///
+/// 随机数是任意长度的非负整数，TSA常用64至160位。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeStampReq {
+    imprint: [u8; 32],
+    policy: Option<Vec<u64>>,
+    nonce: Option<BigUint>,
+    cert_req: bool,
+}
+
+impl TimeStampReq {
+    /// 对数据申请时间戳，携带随机数并要求返回TSA证书
+    pub fn new(data: &[u8]) -> Self {
+        Self::from_digest(&sm3::hash(data))
+    }
+
+    /// 使用预先计算的SM3摘要申请时间戳，随机数为64位
+    pub fn from_digest(digest: &[u8; 32]) -> Self {
+        let nonce = BigUint::from_bytes_be(&env::random::<8>());
+        TimeStampReq { imprint: *digest, policy: None, nonce: Some(nonce), cert_req: true }
+    }
+
+    pub fn with_policy(mut self, policy: &[u64]) -> Self {
+        self.policy = Some(policy.to_vec());
+        self
+    }
+
+    pub fn with_nonce(mut self, nonce: Option<BigUint>) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    pub fn with_cert_req(mut self, cert_req: bool) -> Self {
+        self.cert_req = cert_req;
+        self
+    }
+
+    pub fn imprint(&self) -> &[u8; 32] {
+        &self.imprint
+    }
+
+    pub fn nonce(&self) -> Option<&BigUint> {
+        self.nonce.as_ref()
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        yasna::construct_der(|writer| {
+            writer.write_sequence(|writer| {
+                writer.next().write_u8(1);
+                write_imprint(writer.next(), &self.imprint);
+                if let Some(policy) = &self.policy {
+                    writer.next().write_oid(&ObjectIdentifier::from_slice(policy));
+                }
+                if let Some(nonce) = &self.nonce {
+                    writer.next().write_biguint(nonce);
+                }
+                if self.cert_req {
+                    writer.next().write_bool(true);
+                }
+            })
+        })
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self, TspError> {
+        let (imprint, policy, nonce, cert_req) = yasna::parse_der(data, |reader| {
+            reader.read_sequence(|reader| {
+                reader.next().read_u8()?;
+                let imprint = read_imprint(reader.next())?;
+                let policy = reader.read_optional(|reader| reader.read_oid())?;
+                let nonce = reader.read_optional(|reader| reader.read_biguint())?;
+                let cert_req = reader.read_default(false, |reader| reader.read_bool())?;
+                reader.read_optional(|reader| reader.read_tagged_implicit(Tag::context(0), |reader| reader.read_der()))?;
+                Ok((imprint, policy, nonce, cert_req))
+            })
+        }).map_err(|_| TspError::Malformed)?;
+
+        Ok(TimeStampReq {
+            imprint: imprint?,
+            policy: policy.map(|e| e.components().to_vec()),
+            nonce,
+            cert_req,
+        })
+    }
+}
+
+/// 时间戳响应
+///
+/// ```text
+/// TimeStampResp ::= SEQUENCE {
+///      status          PKIStatusInfo,     -- SEQUENCE { status INTEGER, statusString SEQUENCE OF UTF8String OPTIONAL, ... }
+///      timeStampToken  ContentInfo OPTIONAL }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimeStampResp {
+    status: u8,
+    status_string: Vec<String>,
+    token: Option<Vec<u8>>,
+}
+
+impl TimeStampResp {
+    /// 状态码：0 granted，1 grantedWithMods，2 rejection，3 waiting，4 revocationWarning，5 revocationNotification
+    pub fn status(&self) -> u8 {
+        self.status
+    }
+
+    pub fn status_string(&self) -> &[String] {
+        &self.status_string
+    }
+
+    /// 时间戳令牌（DER），可直接嵌入电子签章或PDF签名
+    pub fn token(&self) -> Option<&[u8]> {
+        self.token.as_deref()
+    }
+
+    pub fn verify(&self, request: &TimeStampReq, tsa: &Certificate) -> Result<TstInfo, TspError> {
+        if self.status > 1 {
+            return Err(TspError::Rejected(self.status));
+        }
+        let token = self.token.as_deref().ok_or(TspError::MissingToken)?;
+        let info = verify_token(token, &request.imprint, tsa)?;
+        if request.nonce.is_some() && info.nonce != request.nonce {
+            return Err(TspError::NonceMismatch);
+        }
+        Ok(info)
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        yasna::construct_der(|writer| {
+            writer.write_sequence(|writer| {
+                writer.next().write_sequence(|writer| {
+                    writer.next().write_u8(self.status);
+                    if !self.status_string.is_empty() {
+                        writer.next().write_sequence_of(|writer| {
+                            self.status_string.iter().for_each(|s| writer.next().write_utf8_string(s));
+                        });
+                    }
+                });
+                if let Some(token) = &self.token {
+                    writer.next().write_der(token);
+                }
+            })
+        })
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self, TspError> {
+        yasna::parse_der(data, |reader| {
+            reader.read_sequence(|reader| {
+                let (status, status_string) = reader.next().read_sequence(|reader| {
+                    let status = reader.next().read_u8()?;
+                    let mut status_string = vec![];
+                    reader.read_optional(|reader| {
+                        reader.read_sequence_of(|reader| {
+                            status_string.push(reader.read_utf8string()?);
+                            Ok(())
+                        })
+                    })?;
+                    reader.read_optional(|reader| reader.read_bitvec_bytes())?;
+                    Ok((status, status_string))
+                })?;
+                let token = reader.read_optional(|reader| reader.read_der())?;
+                Ok(TimeStampResp { status, status_string, token })
+            })
+        }).map_err(|_| TspError::Malformed)
+    }
+}
+
+/// 时间戳信息
+///
+/// ```text
+/// TSTInfo ::= SEQUENCE {
+///      version         INTEGER { v1(1) },
+///      policy          OBJECT IDENTIFIER,
+///      messageImprint  MessageImprint,
+///      serialNumber    INTEGER,
+///      genTime         GeneralizedTime,
+///      accuracy        Accuracy OPTIONAL,
+///      ordering        BOOLEAN DEFAULT FALSE,
+///      nonce           INTEGER OPTIONAL,
+///      tsa         \[0] GeneralName OPTIONAL,
+///      extensions  \[1] IMPLICIT Extensions OPTIONAL }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TstInfo {
+    pub policy: Vec<u64>,
+    pub imprint: [u8; 32],
+    pub serial: BigUint,
+    /// 时间戳时间，UNIX时间戳（秒）
+    pub gen_time: i64,
+    pub nonce: Option<BigUint>,
+}
+
+impl TstInfo {
+    pub fn decode(data: &[u8]) -> Result<Self, TspError> {
+        let (policy, imprint, serial, gen_time, nonce) = yasna::parse_der(data, |reader| {
+            reader.read_sequence(|reader| {
+                reader.next().read_u8()?;
+                let policy = reader.next().read_oid()?;
+                let imprint = read_imprint(reader.next())?;
+                let serial = reader.next().read_biguint()?;
+                let gen_time = read_time(reader.next())?;
+                reader.read_optional(|reader| reader.read_sequence(|reader| reader.next().read_der()))?;
+                reader.read_default(false, |reader| reader.read_bool())?;
+                let nonce = reader.read_optional(|reader| reader.read_biguint())?;
+                reader.read_optional(|reader| reader.read_tagged(Tag::context(0), |reader| reader.read_der()))?;
+                reader.read_optional(|reader| reader.read_tagged_implicit(Tag::context(1), |reader| reader.read_der()))?;
+                Ok((policy, imprint, serial, gen_time, nonce))
+            })
+        }).map_err(|_| TspError::Malformed)?;
+
+        Ok(TstInfo { policy: policy.components().to_vec(), imprint: imprint?, serial, gen_time, nonce })
+    }
+
+    /// 生成时间编码为GeneralizedTime，不在0000年至9999年内时返回[`TspError::InvalidTime`]
+    pub fn encode(&self) -> Result<Vec<u8>, TspError> {
+        if !encodable(self.gen_time) {
+            return Err(TspError::InvalidTime(self.gen_time));
+        }
+        Ok(yasna::construct_der(|writer| {
+            writer.write_sequence(|writer| {
+                writer.next().write_u8(1);
+                writer.next().write_oid(&ObjectIdentifier::from_slice(&self.policy));
+                write_imprint(writer.next(), &self.imprint);
+                writer.next().write_biguint(&self.serial);
+                write_generalized_time(writer.next(), self.gen_time);
+                if let Some(nonce) = &self.nonce {
+                    writer.next().write_biguint(nonce);
+                }
+            })
+        }))
+    }
+}
+
+/// 验证时间戳令牌，`digest`为被盖时间戳数据的SM3摘要
+///
+/// 令牌中不携带TSA证书时同样可以验证。
+pub fn verify_token(token: &[u8], digest: &[u8; 32], tsa: &Certificate) -> Result<TstInfo, TspError> {
+    let signed_data = SignedData::decode(token)?;
+    if signed_data.content_type() != &oid::of(oid::TST_INFO) {
+        return Err(TspError::NotTstInfo);
+    }
+    signed_data.verify_with_certificate(tsa)?;
+
+    let info = TstInfo::decode(signed_data.content().ok_or(TspError::MissingToken)?)?;
+    if &info.imprint != digest {
+        return Err(TspError::ImprintMismatch);
+    }
+    Ok(info)
+}
+
+/// MessageImprint ::= SEQUENCE { hashAlgorithm AlgorithmIdentifier, hashedMessage OCTET STRING }
+fn write_imprint(writer: DERWriter, imprint: &[u8; 32]) {
+    writer.write_sequence(|writer| {
+        writer.next().write_sequence(|writer| writer.next().write_oid(&oid::of(oid::SM3)));
+        writer.next().write_bytes(imprint);
+    })
+}
+
+/// 摘要算法不是SM3或长度不是32字节时，内层返回错误，以便与编码错误区分
+fn read_imprint(reader: BERReader) -> yasna::ASN1Result<Result<[u8; 32], TspError>> {
+    reader.read_sequence(|reader| {
+        let algorithm = reader.next().read_sequence(|reader| {
+            let algorithm = reader.next().read_oid()?;
+            reader.read_optional(|reader| reader.read_null())?;
+            Ok(algorithm)
+        })?;
+        let hashed = reader.next().read_bytes()?;
+        if algorithm != oid::of(oid::SM3) {
+            return Ok(Err(TspError::UnsupportedAlgorithm));
+        }
+        Ok(hashed.try_into().map_err(|_| TspError::Malformed))
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::cert::{generate_self_signed, Subject, Validity};
+    use crate::clock::FixedClock;
+    use crate::sm2::{HexKey, KeyPair, PrivateKey, PublicKey};
+
+    use super::*;
+
+    const PRK: &str = "6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e";
+    const PUK: &str = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+
+    /// 模拟TSA签发时间戳响应
+    fn grant(request: &TimeStampReq, keypair: &KeyPair, cert: &Certificate) -> Vec<u8> {
+        let info = TstInfo {
+            policy: vec![1, 2, 156, 1, 1],
+            imprint: *request.imprint(),
+            serial: BigUint::from(42u8),
+            gen_time: 1700000000,
+            nonce: request.nonce().cloned(),
+        };
+        let content = info.encode().unwrap();
+        let token = SignedData::sign_content(keypair, cert, oid::TST_INFO, Some(&content), &sm3::hash(&content));
+        TimeStampResp { status: 0, status_string: vec![], token: Some(token.encode()) }.encode()
+    }
+
+    #[test]
+    fn main() {
+        let keypair = KeyPair::new(PrivateKey::decode(PRK), PublicKey::decode(PUK));
+        let tsa = generate_self_signed(&keypair, &Subject::new("tsa"), &Validity::days(365));
+
+        let request = TimeStampReq::new(b"archive");
+        assert_eq!(TimeStampReq::decode(&request.encode()).unwrap(), request);
+
+        let response = grant(&request, &keypair, &tsa);
+        let info = verify_response(&request, &response, &tsa).unwrap();
+        assert_eq!(info.gen_time, 1700000000);
+        assert_eq!(info.serial, BigUint::from(42u8));
+
+        let other = TimeStampReq::new(b"other");
+        assert_eq!(verify_response(&other, &response, &tsa).unwrap_err(), TspError::ImprintMismatch);
+        let replay = request.clone().with_nonce(Some(BigUint::from(7u8)));
+        assert_eq!(verify_response(&replay, &response, &tsa).unwrap_err(), TspError::NonceMismatch);
+
+        let rejected = TimeStampResp { status: 2, status_string: vec!["badAlg".to_string()], token: None }.encode();
+        assert_eq!(verify_response(&request, &rejected, &tsa).unwrap_err(), TspError::Rejected(2));
+    }
+
+    #[test]
+    fn generation_time() {
+        let keypair = KeyPair::new(PrivateKey::decode(PRK), PublicKey::decode(PUK));
+        let clock = FixedClock::new(1_699_000_000);
+        let tsa = generate_self_signed(&keypair, &Subject::new("tsa"), &Validity::days_with_clock(365, &clock));
+        let request = TimeStampReq::new(b"archive");
+        let response = grant(&request, &keypair, &tsa);
+
+        // 令牌生成于1700000000，TSA证书此后过期不影响已签发的时间戳
+        clock.set(1_800_000_000);
+        assert_eq!(verify_response_with_clock(&request, &response, &tsa, &clock).unwrap().gen_time, 1_700_000_000);
+        clock.set(1_699_999_999);
+        assert_eq!(verify_response_with_clock(&request, &response, &tsa, &clock).unwrap_err(), TspError::InvalidTime(1_700_000_000));
+
+        let expired = generate_self_signed(&keypair, &Subject::new("tsa"), &Validity::days_with_clock(1, &FixedClock::new(1_600_000_000)));
+        let response = grant(&request, &keypair, &expired);
+        assert_eq!(verify_response_with_clock(&request, &response, &expired, &clock).unwrap_err(), TspError::InvalidTime(1_700_000_000));
+    }
+
+    #[test]
+    fn long_nonce() {
+        // TSA常用的160位随机数，超出u64
+        let keypair = KeyPair::new(PrivateKey::decode(PRK), PublicKey::decode(PUK));
+        let tsa = generate_self_signed(&keypair, &Subject::new("tsa"), &Validity::days(365));
+        let nonce = BigUint::from_bytes_be(&[0xa5; 20]);
+        let request = TimeStampReq::new(b"archive").with_nonce(Some(nonce.clone()));
+        assert_eq!(TimeStampReq::decode(&request.encode()).unwrap().nonce(), Some(&nonce));
+
+        let info = verify_response(&request, &grant(&request, &keypair, &tsa), &tsa).unwrap();
+        assert_eq!(info.nonce, Some(nonce));
+        assert!(TimeStampReq::new(b"archive").nonce().unwrap().bits() <= 64);
+    }
+
+    #[test]
+    fn gen_time_range() {
+        let mut info = TstInfo { policy: vec![1, 2, 156, 1, 1], imprint: [0; 32], serial: BigUint::from(1u8), gen_time: 253402300799, nonce: None };
+        assert_eq!(TstInfo::decode(&info.encode().unwrap()).unwrap().gen_time, 253402300799);
+        for gen_time in [253402300800, -62167219201, i64::MAX, i64::MIN] {
+            info.gen_time = gen_time;
+            assert_eq!(info.encode().unwrap_err(), TspError::InvalidTime(gen_time));
+        }
+    }
+}
@@ -0,0 +1,431 @@
+//! 参照Wycheproof组织的边界用例
+//!
+//! Wycheproof未收录SM2与SM4-GCM，以下用例按其结构（tcId、说明、输入、期望结果）整理：
+//! 有效用例取自OpenSSL 3.0或RustCrypto `aes-gcm`的输出，无效用例覆盖越界的(r, s)、
+//! 可延展签名、非DER编码、非法公钥、不在曲线上的密文C1以及截断或篡改的认证标签。
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Expect {
+    Valid,
+    Invalid,
+}
+
+use Expect::{Invalid, Valid};
+
+const PUK: &str = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+
+/// SM2签名验证用例，消息与签名均为16进制，用户ID为默认值
+struct SignatureCase {
+    tc_id: u32,
+    comment: &'static str,
+    msg: &'static str,
+    sig: &'static str,
+    result: Expect,
+}
+
+/// 消息"message digest"
+const MSG: &str = "6d65737361676520646967657374";
+
+static SIGNATURE_CASES: &[SignatureCase] = &[
+    SignatureCase {
+        tc_id: 1,
+        comment: "OpenSSL signature",
+        msg: MSG,
+        sig: "30460221009001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb022100e229a134165d20694ece0e4d47bcee2b06444b6f5c2a06b74a9b6e5bc0e64cbd",
+        result: Valid,
+    },
+    SignatureCase {
+        tc_id: 2,
+        comment: "OpenSSL signature of the empty message",
+        msg: "",
+        sig: "3046022100a2aa95a3fa470f4a4272aa0a63baff58a258ca21542f436ce4160b26f641447a022100b5ee732e2ece4d208dc131b90cbc28e94ca1a7d55ff9a74043eeed7b9644bad3",
+        result: Valid,
+    },
+    SignatureCase {
+        tc_id: 3,
+        comment: "signature of another message",
+        msg: "",
+        sig: "30460221009001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb022100e229a134165d20694ece0e4d47bcee2b06444b6f5c2a06b74a9b6e5bc0e64cbd",
+        result: Invalid,
+    },
+    SignatureCase {
+        tc_id: 4,
+        comment: "r + n",
+        msg: MSG,
+        sig: "30460221019001c9d2723cff181b47b09c37f0797e3fff6404f4e5660af853f8db5920130e022100e229a134165d20694ece0e4d47bcee2b06444b6f5c2a06b74a9b6e5bc0e64cbd",
+        result: Invalid,
+    },
+    SignatureCase {
+        tc_id: 5,
+        comment: "s + n",
+        msg: MSG,
+        sig: "30460221009001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb022101e229a133165d20694ece0e4d47bcee2a78482ada7df00be29e576264fabb8de0",
+        result: Invalid,
+    },
+    SignatureCase {
+        tc_id: 6,
+        comment: "r = 0",
+        msg: MSG,
+        sig: "3026020100022100e229a134165d20694ece0e4d47bcee2b06444b6f5c2a06b74a9b6e5bc0e64cbd",
+        result: Invalid,
+    },
+    SignatureCase {
+        tc_id: 7,
+        comment: "s = 0",
+        msg: MSG,
+        sig: "30260221009001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb020100",
+        result: Invalid,
+    },
+    SignatureCase {
+        tc_id: 8,
+        comment: "r = n",
+        msg: MSG,
+        sig: "3046022100fffffffeffffffffffffffffffffffff7203df6b21c6052b53bbf40939d54123022100e229a134165d20694ece0e4d47bcee2b06444b6f5c2a06b74a9b6e5bc0e64cbd",
+        result: Invalid,
+    },
+    SignatureCase {
+        tc_id: 9,
+        comment: "s = n",
+        msg: MSG,
+        sig: "30460221009001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb022100fffffffeffffffffffffffffffffffff7203df6b21c6052b53bbf40939d54123",
+        result: Invalid,
+    },
+    SignatureCase {
+        tc_id: 10,
+        comment: "malleated s: n - s",
+        msg: MSG,
+        sig: "30450221009001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb02201dd65ecae9a2df96b131f1b2b84311d46bbf93fbc59bfe74092085ad78eef466",
+        result: Invalid,
+    },
+    SignatureCase {
+        tc_id: 11,
+        comment: "r + s = n",
+        msg: MSG,
+        sig: "3026020101022100fffffffeffffffffffffffffffffffff7203df6b21c6052b53bbf40939d54122",
+        result: Invalid,
+    },
+    SignatureCase {
+        tc_id: 12,
+        comment: "r = s = 1",
+        msg: MSG,
+        sig: "3006020101020101",
+        result: Invalid,
+    },
+    SignatureCase {
+        tc_id: 13,
+        comment: "r and s swapped",
+        msg: MSG,
+        sig: "3046022100e229a134165d20694ece0e4d47bcee2b06444b6f5c2a06b74a9b6e5bc0e64cbd0221009001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb",
+        result: Invalid,
+    },
+    SignatureCase {
+        tc_id: 14,
+        comment: "negative r: missing 00 before a set high bit",
+        msg: MSG,
+        sig: "304502209001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb022100e229a134165d20694ece0e4d47bcee2b06444b6f5c2a06b74a9b6e5bc0e64cbd",
+        result: Invalid,
+    },
+    SignatureCase {
+        tc_id: 15,
+        comment: "non-minimal INTEGER: extra leading zero",
+        msg: MSG,
+        sig: "3047022200009001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb022100e229a134165d20694ece0e4d47bcee2b06444b6f5c2a06b74a9b6e5bc0e64cbd",
+        result: Invalid,
+    },
+    SignatureCase {
+        tc_id: 16,
+        comment: "BER long-form length of SEQUENCE",
+        msg: MSG,
+        sig: "3081460221009001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb022100e229a134165d20694ece0e4d47bcee2b06444b6f5c2a06b74a9b6e5bc0e64cbd",
+        result: Invalid,
+    },
+    SignatureCase {
+        tc_id: 17,
+        comment: "BER indefinite length",
+        msg: MSG,
+        sig: "30800221009001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb022100e229a134165d20694ece0e4d47bcee2b06444b6f5c2a06b74a9b6e5bc0e64cbd0000",
+        result: Invalid,
+    },
+    SignatureCase {
+        tc_id: 18,
+        comment: "trailing byte after SEQUENCE",
+        msg: MSG,
+        sig: "30460221009001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb022100e229a134165d20694ece0e4d47bcee2b06444b6f5c2a06b74a9b6e5bc0e64cbd00",
+        result: Invalid,
+    },
+    SignatureCase {
+        tc_id: 19,
+        comment: "truncated signature",
+        msg: MSG,
+        sig: "30460221009001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb022100e229a134165d20694ece0e4d47bcee2b06444b6f5c2a06b74a9b6e5bc0e64c",
+        result: Invalid,
+    },
+    SignatureCase {
+        tc_id: 20,
+        comment: "SEQUENCE with r only",
+        msg: MSG,
+        sig: "30230221009001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb",
+        result: Invalid,
+    },
+    SignatureCase {
+        tc_id: 21,
+        comment: "s tagged as BIT STRING",
+        msg: MSG,
+        sig: "30460221009001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb032100e229a134165d20694ece0e4d47bcee2b06444b6f5c2a06b74a9b6e5bc0e64cbd",
+        result: Invalid,
+    },
+    SignatureCase {
+        tc_id: 22,
+        comment: "empty signature",
+        msg: MSG,
+        sig: "",
+        result: Invalid,
+    },
+];
+
+/// 以有效签名（tcId 1）验证不同的公钥编码
+struct PublicKeyCase {
+    tc_id: u32,
+    comment: &'static str,
+    puk: &'static str,
+    result: Expect,
+}
+
+static PUBLIC_KEY_CASES: &[PublicKeyCase] = &[
+    PublicKeyCase { tc_id: 1, comment: "uncompressed public key", puk: PUK, result: Valid },
+    PublicKeyCase {
+        tc_id: 2,
+        comment: "point not on the curve: y + 1",
+        puk: "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6f",
+        result: Invalid,
+    },
+    PublicKeyCase {
+        tc_id: 3,
+        comment: "compressed prefix on an uncompressed point",
+        puk: "03a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e",
+        result: Invalid,
+    },
+    PublicKeyCase {
+        tc_id: 4,
+        comment: "missing prefix",
+        puk: "a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e",
+        result: Invalid,
+    },
+    PublicKeyCase {
+        tc_id: 5,
+        comment: "x replaced by p",
+        puk: "04fffffffeffffffffffffffffffffffffffffffff00000000ffffffffffffffff30ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e",
+        result: Invalid,
+    },
+    PublicKeyCase {
+        tc_id: 6,
+        comment: "point at infinity",
+        puk: "0400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        result: Invalid,
+    },
+];
+
+/// SM2解密用例，`c1`为不含04前缀的x1 ‖ y1，与`c3`、`c2`一并按两种分量顺序拼接，均为16进制
+#[cfg(feature = "sm2-encrypt")]
+struct DecryptionCase {
+    tc_id: u32,
+    comment: &'static str,
+    c1: &'static str,
+    result: Expect,
+}
+
+/// 以PUK加密"wycheproof"所得密文的C3与C2
+#[cfg(feature = "sm2-encrypt")]
+const DEC_C3: &str = "2e4bc9dcd86f70012ebee8b3ec7db5999aef28e7f383f34a638506b6f084cf6e";
+#[cfg(feature = "sm2-encrypt")]
+const DEC_C2: &str = "3a5e82f9ee302dc8c20b";
+
+#[cfg(feature = "sm2-encrypt")]
+static DECRYPTION_CASES: &[DecryptionCase] = &[
+    DecryptionCase {
+        tc_id: 1,
+        comment: "valid ciphertext",
+        c1: "0498e0a1eb9f1e40227f9bf55e41e3539aabc73c9d4e74b7ef1dfe45681b9148fc8bebb7fe661412b5818934eb66ead3618c5d205455ebe510c0e48efee2a431",
+        result: Valid,
+    },
+    DecryptionCase {
+        tc_id: 2,
+        comment: "C1 = (1, 1)",
+        c1: "00000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000001",
+        result: Invalid,
+    },
+    DecryptionCase {
+        tc_id: 3,
+        comment: "C1 is the point at infinity",
+        c1: "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        result: Invalid,
+    },
+    DecryptionCase {
+        tc_id: 4,
+        comment: "C1 coordinates all 0xff",
+        c1: "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        result: Invalid,
+    },
+    DecryptionCase {
+        tc_id: 5,
+        comment: "x1 replaced by p",
+        c1: "fffffffeffffffffffffffffffffffffffffffff00000000fffffffffffffffffc8bebb7fe661412b5818934eb66ead3618c5d205455ebe510c0e48efee2a431",
+        result: Invalid,
+    },
+    DecryptionCase {
+        tc_id: 6,
+        comment: "flipped last bit of y1",
+        c1: "0498e0a1eb9f1e40227f9bf55e41e3539aabc73c9d4e74b7ef1dfe45681b9148fc8bebb7fe661412b5818934eb66ead3618c5d205455ebe510c0e48efee2a430",
+        result: Invalid,
+    },
+];
+
+/// SM4-GCM解密用例，`ct`为密文，`tag`为认证标签，均为16进制
+struct GcmCase {
+    tc_id: u32,
+    comment: &'static str,
+    nonce: &'static str,
+    aad: &'static str,
+    ct: &'static str,
+    tag: &'static str,
+    pt: &'static str,
+    result: Expect,
+}
+
+/// RFC 8998 附录A.1的密钥
+const GCM_KEY: &str = "0123456789abcdeffedcba9876543210";
+const GCM_NONCE: &str = "00001234567800000000abcd";
+const RFC_AAD: &str = "feedfacedeadbeeffeedfacedeadbeefabaddad2";
+const RFC_CT: &str = "17f399f08c67d5ee19d0dc9969c4bb7d5fd46fd3756489069157b282bb200735d82710ca5c22f0ccfa7cbf93d496ac15a56834cbcf98c397b4024a2691233b8d";
+const RFC_PT: &str = "aaaaaaaaaaaaaaaabbbbbbbbbbbbbbbbccccccccccccccccddddddddddddddddeeeeeeeeeeeeeeeeffffffffffffffffeeeeeeeeeeeeeeeeaaaaaaaaaaaaaaaa";
+
+static GCM_CASES: &[GcmCase] = &[
+    GcmCase { tc_id: 1, comment: "RFC 8998 A.1", nonce: GCM_NONCE, aad: RFC_AAD, ct: RFC_CT, tag: "83de3541e4c2b58177e065a9bf7b62ec", pt: RFC_PT, result: Valid },
+    GcmCase { tc_id: 2, comment: "empty plaintext and AAD", nonce: GCM_NONCE, aad: "", ct: "", tag: "54f157af32744bb83bbe8aa6f1578b71", pt: "", result: Valid },
+    GcmCase { tc_id: 3, comment: "AAD only", nonce: GCM_NONCE, aad: "686561646572", ct: "", tag: "6a400aaa0e23f1816c791ced1f8918ac", pt: "", result: Valid },
+    GcmCase { tc_id: 4, comment: "one byte plaintext", nonce: GCM_NONCE, aad: "", ct: "bd", tag: "4cff308466921c24c7dfedd9cea69106", pt: "00", result: Valid },
+    GcmCase {
+        tc_id: 5,
+        comment: "17 byte plaintext and AAD",
+        nonce: GCM_NONCE,
+        aad: "6262626262626262626262626262626262",
+        ct: "dc38523b47ac1e25c30a0643b31e61a7f2",
+        tag: "8952e03a4927a5b7b94cafa8927789c7",
+        pt: "6161616161616161616161616161616161",
+        result: Valid,
+    },
+    GcmCase { tc_id: 6, comment: "128-bit nonce", nonce: "5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a", aad: "", ct: "6287b4cca0", tag: "67493da405b20d1f0f79b15b9bc9c532", pt: "6e6f6e6365", result: Valid },
+    GcmCase { tc_id: 7, comment: "64-bit nonce", nonce: "a5a5a5a5a5a5a5a5", aad: "", ct: "02c54825ea", tag: "4d6f64fee9b78c29b11e2b3d3c0a4666", pt: "6e6f6e6365", result: Valid },
+    GcmCase { tc_id: 8, comment: "flipped first bit of tag", nonce: GCM_NONCE, aad: RFC_AAD, ct: RFC_CT, tag: "03de3541e4c2b58177e065a9bf7b62ec", pt: RFC_PT, result: Invalid },
+    GcmCase { tc_id: 9, comment: "flipped last bit of tag", nonce: GCM_NONCE, aad: RFC_AAD, ct: RFC_CT, tag: "83de3541e4c2b58177e065a9bf7b62ed", pt: RFC_PT, result: Invalid },
+    GcmCase { tc_id: 10, comment: "tag truncated to 15 bytes", nonce: GCM_NONCE, aad: RFC_AAD, ct: RFC_CT, tag: "83de3541e4c2b58177e065a9bf7b62", pt: RFC_PT, result: Invalid },
+    GcmCase { tc_id: 11, comment: "tag truncated to 4 bytes", nonce: GCM_NONCE, aad: RFC_AAD, ct: RFC_CT, tag: "83de3541", pt: RFC_PT, result: Invalid },
+    GcmCase { tc_id: 12, comment: "empty tag", nonce: GCM_NONCE, aad: "", ct: "", tag: "", pt: "", result: Invalid },
+    GcmCase { tc_id: 13, comment: "all-zero tag", nonce: GCM_NONCE, aad: "", ct: "", tag: "00000000000000000000000000000000", pt: "", result: Invalid },
+    GcmCase { tc_id: 14, comment: "flipped bit in ciphertext", nonce: GCM_NONCE, aad: RFC_AAD, ct: "17f399f08c67d5ee19d0dc9969c4bb7d5fd46fd3756489069157b282bb200735d82710ca5c22f0ccfa7cbf93d496ac15a56834cbcf98c397b4024a2691233b8c", tag: "83de3541e4c2b58177e065a9bf7b62ec", pt: RFC_PT, result: Invalid },
+    GcmCase { tc_id: 15, comment: "truncated ciphertext", nonce: GCM_NONCE, aad: RFC_AAD, ct: "17f399f08c67d5ee19d0dc9969c4bb7d5fd46fd3756489069157b282bb200735d82710ca5c22f0ccfa7cbf93d496ac15a56834cbcf98c397b4024a2691233b", tag: "83de3541e4c2b58177e065a9bf7b62ec", pt: RFC_PT, result: Invalid },
+    GcmCase { tc_id: 16, comment: "modified AAD", nonce: GCM_NONCE, aad: "feedfacedeadbeeffeedfacedeadbeefabaddad3", ct: RFC_CT, tag: "83de3541e4c2b58177e065a9bf7b62ec", pt: RFC_PT, result: Invalid },
+    GcmCase { tc_id: 17, comment: "AAD moved into ciphertext", nonce: GCM_NONCE, aad: "", ct: "686561646572", tag: "6a400aaa0e23f1816c791ced1f8918ac", pt: "", result: Invalid },
+    GcmCase { tc_id: 18, comment: "wrong nonce", nonce: "00001234567800000000abce", aad: RFC_AAD, ct: RFC_CT, tag: "83de3541e4c2b58177e065a9bf7b62ec", pt: RFC_PT, result: Invalid },
+];
+
+
+#[cfg(test)]
+mod tests {
+    use crate::sm2::signature::{Signature, Verifier};
+    use crate::sm2::{HexKey, PublicKey};
+    #[cfg(feature = "sm2-encrypt")]
+    use crate::sm2::{DecryptError, Mode, PrivateKey};
+    use crate::sm4::{allow_nonce_reuse, GcmDecryptor, GcmEncryptor};
+
+    use super::*;
+
+    fn verify(puk: &str, msg: &str, sig: &str) -> Expect {
+        let key = match PublicKey::try_decode(puk) {
+            Ok(key) => key,
+            Err(_) => return Invalid,
+        };
+        let signature = match Signature::parse(&hex::decode(sig).unwrap()) {
+            Ok(signature) => signature,
+            Err(_) => return Invalid,
+        };
+        match Verifier::new(key).verify_bytes(&hex::decode(msg).unwrap(), &signature) {
+            true => Valid,
+            false => Invalid,
+        }
+    }
+
+    #[test]
+    fn sm2_signature() {
+        for case in SIGNATURE_CASES {
+            assert_eq!(verify(PUK, case.msg, case.sig), case.result, "tcId {}: {}", case.tc_id, case.comment);
+        }
+    }
+
+    #[test]
+    fn sm2_public_key() {
+        let sig = SIGNATURE_CASES[0].sig;
+        for case in PUBLIC_KEY_CASES {
+            assert_eq!(verify(case.puk, MSG, sig), case.result, "tcId {}: {}", case.tc_id, case.comment);
+        }
+    }
+
+    #[cfg(feature = "sm2-encrypt")]
+    #[test]
+    fn sm2_decryption() {
+        let prk = PrivateKey::decode("6aea1ccf610488aaa7fddba3dd6d76d3bdfd50f957d847be3d453defb695f28e");
+        for mode in [Mode::C1C3C2, Mode::C1C2C3] {
+            let decryptor = crate::sm2::Crypto::new(mode).decryptor(prk.clone());
+            for case in DECRYPTION_CASES {
+                let (c1, c2, c3) = (hex::decode(case.c1).unwrap(), hex::decode(DEC_C2).unwrap(), hex::decode(DEC_C3).unwrap());
+                let cipher = match mode {
+                    Mode::C1C3C2 => [&[0x04], c1.as_slice(), &c3, &c2].concat(),
+                    Mode::C1C2C3 => [&[0x04], c1.as_slice(), &c2, &c3].concat(),
+                };
+                match decryptor.try_decrypt_bytes(&cipher) {
+                    Ok(plain) => {
+                        assert_eq!(case.result, Valid, "{:?} tcId {}: {}", mode, case.tc_id, case.comment);
+                        assert_eq!(plain, b"wycheproof", "{:?} tcId {}", mode, case.tc_id);
+                    }
+                    // 无效的C1须在与私钥相乘之前被拒绝，而不是到C3校验时才失败
+                    Err(DecryptError::Invalid(reason)) => {
+                        assert_eq!(case.result, Invalid, "{:?} tcId {}: {}", mode, case.tc_id, case.comment);
+                        assert!(reason.contains("C1"), "{:?} tcId {}: {}", mode, case.tc_id, reason);
+                    }
+                    Err(e) => panic!("{:?} tcId {}: {}", mode, case.tc_id, e),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sm4_gcm() {
+        let key: [u8; 16] = hex::decode(GCM_KEY).unwrap().try_into().unwrap();
+        for case in GCM_CASES {
+            let (nonce, aad) = (hex::decode(case.nonce).unwrap(), hex::decode(case.aad).unwrap());
+            let (ct, tag) = (hex::decode(case.ct).unwrap(), hex::decode(case.tag).unwrap());
+            let mut buffer = ct.clone();
+
+            let mut decryptor = GcmDecryptor::with_nonce_bytes(&key, &nonce);
+            decryptor.update_aad(&aad);
+            let result = match decryptor.decrypt_in_place_detached(&mut buffer, &tag) {
+                Ok(()) => Valid,
+                Err(_) => Invalid,
+            };
+            assert_eq!(result, case.result, "tcId {}: {}", case.tc_id, case.comment);
+
+            match case.result {
+                Valid => {
+                    assert_eq!(hex::encode(&buffer), case.pt, "tcId {}", case.tc_id);
+                    // 有效用例同时校验加密方向，各用例共用同一密钥与nonce
+                    let mut encryptor = GcmEncryptor::with_nonce_bytes(&key, &nonce);
+                    let cipher = encryptor.update_aad(&aad).update(&hex::decode(case.pt).unwrap());
+                    let actual = allow_nonce_reuse(|| encryptor.finalize_tag().to_vec());
+                    assert_eq!((cipher, actual), (ct, tag), "tcId {}", case.tc_id);
+                }
+                // 校验失败时不释放明文
+                Invalid => assert_eq!(buffer, ct, "tcId {}", case.tc_id),
+            }
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//! 最低支持的Rust版本（MSRV）与常量表
+//!
+//! MSRV以`Cargo.toml`的`rust-version`为准，CI的`msrv`任务以该版本工具链构建并运行测试，README同步说明。
+
+#![cfg(all(feature = "sm2-verify", feature = "sm4"))]
+
+use yarism::{sm2, sm3, sm4};
+
+// 下游可在常量上下文中使用的参数表
+const SBOX_0: u8 = sm4::SBOX[0];
+const CK_31: u32 = sm4::CK[31];
+const FK_0: u32 = sm4::FK[0];
+const SM3_IV_0: u32 = sm3::IV[0];
+const SM2_N_TOP: u8 = sm2::EC_N[0];
+
+#[test]
+fn const_tables() {
+    assert_eq!(SBOX_0, 0xd6);
+    assert_eq!(CK_31, 0x646b7279);
+    assert_eq!(FK_0, 0xa3b1bac6);
+    assert_eq!(SM3_IV_0, 0x7380166f);
+    assert_eq!(SM2_N_TOP, 0xff);
+}
+
+#[test]
+fn declared_msrv() {
+    let msrv = env!("CARGO_PKG_RUST_VERSION");
+    assert!(!msrv.is_empty(), "The rust-version in Cargo.toml is missing.");
+
+    let ci = include_str!("../.github/workflows/ci.yml");
+    assert!(ci.contains(&format!("dtolnay/rust-toolchain@{}", msrv)), "The CI msrv job does not use Rust {}.", msrv);
+    let readme = include_str!("../README.md");
+    assert!(readme.contains(&format!("MSRV is Rust {}", msrv)), "The README does not state Rust {} as the MSRV.", msrv);
+}
@@ -0,0 +1,57 @@
+//! 以计数分配器确认`verify_no_alloc`不分配内存
+//!
+//! 全局分配器作用于整个测试程序，因此单独放在集成测试中。
+
+#![cfg(feature = "sm2-verify")]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use yarism::sm2::signature::{digest, verify_no_alloc, VerifyError, DEFAULT_USER_ID};
+use yarism::sm2::{HexKey, PublicKey};
+
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+const PUK: &str = "04a8af64e38eea41c254df769b5b41fbaa2d77b226b301a2636d463c52b46c777230ad1714e686dd641b9e04596530b38f6a64215b0ed3b081f8641724c5443a6e";
+
+#[test]
+fn verify_without_allocation() {
+    let public_key: [u8; 65] = hex::decode(PUK).unwrap().try_into().unwrap();
+    let e = digest(DEFAULT_USER_ID, &PublicKey::decode(PUK), b"message digest");
+    let signature: [u8; 64] = hex::decode(concat!(
+        "9001c9d3723cff181b47b09c37f0797ecdfb8499d31f60dfa49804d21f4ad1eb",
+        "e229a134165d20694ece0e4d47bcee2b06444b6f5c2a06b74a9b6e5bc0e64cbd",
+    )).unwrap().try_into().unwrap();
+    let mut tampered = e;
+    tampered[31] ^= 1;
+
+    let before = ALLOCATIONS.with(Cell::get);
+    let results = [
+        verify_no_alloc(&public_key, &e, &signature),
+        verify_no_alloc(&public_key, &tampered, &signature),
+    ];
+    let after = ALLOCATIONS.with(Cell::get);
+
+    assert_eq!(results, [Ok(()), Err(VerifyError::Mismatch)]);
+    // 准备输入时的分配已被计入，说明计数器生效
+    assert!(before > 0);
+    assert_eq!(before, after);
+}